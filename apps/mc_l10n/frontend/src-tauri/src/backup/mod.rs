@@ -0,0 +1,389 @@
+pub mod s3;
+pub mod webdav;
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::Manager;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::config::AppConfig;
+use crate::database;
+use crate::keychain;
+use crate::worker_pool::WorkerPool;
+use s3::S3Client;
+use webdav::WebDavClient;
+
+// 备份文件名为 <前缀><UTC 时间>.zip，按名称排序即按时间排序
+const BACKUP_PREFIX: &str = "th-suite-backup-";
+const BACKUP_EXTENSION: &str = ".zip";
+
+// 备份归档中的数据库快照
+const DATABASE_ENTRY: &str = "database.db";
+
+// 数据目录中随数据库一起备份的文件；扫描快照、缓存和导入目录可以重新生成，不备份
+const DATA_FILES: &[&str] = &[
+    "project_settings.json",
+    "workspaces.json",
+    "glossary.json",
+    "remote_servers.json",
+    "migration_audit.json",
+];
+
+// 密码或 S3 私有访问密钥在系统钥匙串中的账户名
+const KEYCHAIN_ACCOUNT: &str = "backup";
+
+// 定时备份检查间隔
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+// 备份目标；密码和私有访问密钥保存在系统钥匙串中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackupTarget {
+    WebDav {
+        // 存放备份的目录地址
+        url: String,
+        username: String,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        #[serde(default)]
+        prefix: String,
+        access_key_id: String,
+    },
+}
+
+// 备份设置，保存在数据目录下的 backup.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub target: Option<BackupTarget>,
+    // 自动备份间隔（小时），0 表示只手动备份
+    #[serde(default)]
+    pub interval_hours: u32,
+    // 备份后只保留最近的这么多份，0 表示不清理
+    #[serde(default)]
+    pub keep_last: u32,
+    #[serde(default)]
+    pub last_backup_at: Option<String>,
+    // 最近一次备份失败的原因，成功后清空
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl BackupSettings {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let settings_path = Self::get_settings_path()?;
+
+        if settings_path.exists() {
+            let content = fs::read_to_string(&settings_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let settings_path = Self::get_settings_path()?;
+
+        if let Some(parent) = settings_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&settings_path, content)?;
+        Ok(())
+    }
+
+    fn get_settings_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("backup.json"))
+    }
+
+    // 开启了定时备份且距上次备份已超过间隔
+    fn is_due(&self) -> bool {
+        if self.target.is_none() || self.interval_hours == 0 {
+            return false;
+        }
+        let Some(last) = self.last_backup_at.as_deref().and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok()) else {
+            return true;
+        };
+        chrono::Utc::now().signed_duration_since(last) >= chrono::Duration::hours(self.interval_hours as i64)
+    }
+}
+
+// 备份目标中的一份备份
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteBackup {
+    pub name: String,
+    pub size: Option<u64>,
+}
+
+enum BackupClient {
+    WebDav(WebDavClient),
+    S3(S3Client),
+}
+
+impl BackupClient {
+    fn from_settings(settings: &BackupSettings) -> Result<Self, String> {
+        let target = settings.target.as_ref().ok_or("No backup target configured")?;
+        let secret = keychain::load_secret(KEYCHAIN_ACCOUNT)?;
+        Ok(match target {
+            BackupTarget::WebDav { url, username } => BackupClient::WebDav(WebDavClient::new(url, username, secret)),
+            BackupTarget::S3 { endpoint, bucket, region, prefix, access_key_id } => {
+                let secret = secret.ok_or("No secret access key stored for the backup target")?;
+                BackupClient::S3(S3Client::new(endpoint, bucket, region, prefix, access_key_id, secret))
+            }
+        })
+    }
+
+    async fn put(&self, name: &str, bytes: Vec<u8>) -> Result<(), String> {
+        match self {
+            BackupClient::WebDav(client) => client.put(name, bytes).await,
+            BackupClient::S3(client) => client.put(name, bytes).await,
+        }
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        match self {
+            BackupClient::WebDav(client) => client.get(name).await,
+            BackupClient::S3(client) => client.get(name).await,
+        }
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), String> {
+        match self {
+            BackupClient::WebDav(client) => client.delete(name).await,
+            BackupClient::S3(client) => client.delete(name).await,
+        }
+    }
+
+    // 只返回本应用创建的备份，最新的在前
+    async fn list(&self) -> Result<Vec<RemoteBackup>, String> {
+        let mut backups = match self {
+            BackupClient::WebDav(client) => client.list().await?,
+            BackupClient::S3(client) => client.list().await?,
+        };
+        backups.retain(|backup| is_backup_name(&backup.name));
+        backups.sort_by(|a, b| b.name.cmp(&a.name));
+        Ok(backups)
+    }
+}
+
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX) && name.ends_with(BACKUP_EXTENSION) && !name.contains('/')
+}
+
+pub fn store_secret(secret: &str) -> Result<(), String> {
+    keychain::store_secret(KEYCHAIN_ACCOUNT, secret)
+}
+
+pub fn delete_secret() -> Result<(), String> {
+    keychain::delete_secret(KEYCHAIN_ACCOUNT)
+}
+
+// 生成快照并上传，然后按 keep_last 清理旧备份；结果记录到备份设置中
+pub async fn backup_now(workers: &WorkerPool) -> Result<RemoteBackup, String> {
+    let mut settings = BackupSettings::load().map_err(|e| e.to_string())?;
+    let result = upload_snapshot(&settings, workers).await;
+
+    match &result {
+        Ok(_) => {
+            settings.last_backup_at = Some(chrono::Utc::now().to_rfc3339());
+            settings.last_error = None;
+        }
+        Err(e) => settings.last_error = Some(e.clone()),
+    }
+    settings.save().map_err(|e| e.to_string())?;
+    result
+}
+
+async fn upload_snapshot(settings: &BackupSettings, workers: &WorkerPool) -> Result<RemoteBackup, String> {
+    let client = BackupClient::from_settings(settings)?;
+    let snapshot = workers.submit(create_snapshot)?;
+    let bytes = snapshot.await.map_err(|_| "Backup task failed".to_string())??;
+
+    let name = format!("{}{}{}", BACKUP_PREFIX, chrono::Utc::now().format("%Y%m%dT%H%M%SZ"), BACKUP_EXTENSION);
+    let size = bytes.len() as u64;
+    client.put(&name, bytes).await?;
+
+    if settings.keep_last > 0 {
+        if let Err(e) = prune(&client, settings.keep_last).await {
+            eprintln!("Failed to remove old backups: {}", e);
+        }
+    }
+    Ok(RemoteBackup { name, size: Some(size) })
+}
+
+pub async fn list_backups() -> Result<Vec<RemoteBackup>, String> {
+    let settings = BackupSettings::load().map_err(|e| e.to_string())?;
+    BackupClient::from_settings(&settings)?.list().await
+}
+
+// 删除 keep_last 份之外的旧备份，返回删除的备份名
+pub async fn prune_backups(keep_last: u32) -> Result<Vec<String>, String> {
+    let settings = BackupSettings::load().map_err(|e| e.to_string())?;
+    prune(&BackupClient::from_settings(&settings)?, keep_last).await
+}
+
+async fn prune(client: &BackupClient, keep_last: u32) -> Result<Vec<String>, String> {
+    let mut removed = Vec::new();
+    for backup in client.list().await?.into_iter().skip(keep_last.max(1) as usize) {
+        client.delete(&backup.name).await?;
+        removed.push(backup.name);
+    }
+    Ok(removed)
+}
+
+// 下载备份并替换本地数据库和数据文件，返回恢复的文件；原数据库保留为 .before-restore
+pub async fn restore_backup(name: &str, workers: &WorkerPool) -> Result<Vec<String>, String> {
+    if !is_backup_name(name) {
+        return Err(format!("Not a backup: {}", name));
+    }
+    let settings = BackupSettings::load().map_err(|e| e.to_string())?;
+    let bytes = BackupClient::from_settings(&settings)?.get(name).await?;
+
+    let result = workers.submit(move || restore_snapshot(bytes))?;
+    result.await.map_err(|_| "Restore task failed".to_string())?
+}
+
+// 定时备份：定期检查是否到期，失败原因记录在备份设置中
+pub fn start_scheduler(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+            if !BackupSettings::load().is_ok_and(|settings| settings.is_due()) {
+                continue;
+            }
+            if let Err(e) = backup_now(&app.state::<WorkerPool>()).await {
+                eprintln!("Scheduled backup failed: {}", e);
+            }
+        }
+    });
+}
+
+// 用 VACUUM INTO 生成一致的数据库快照，和数据文件一起打包
+fn create_snapshot() -> Result<Vec<u8>, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let data_dir = config.get_data_dir();
+    let database_path = config.get_database_path();
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    if database_path.exists() {
+        let snapshot_path = data_dir.join("backup_snapshot.db");
+        let _ = fs::remove_file(&snapshot_path);
+        let conn = database::open(&database_path)?;
+        conn.execute("VACUUM INTO ?1", [snapshot_path.to_string_lossy()])
+            .map_err(|e| format!("Failed to snapshot database: {}", e))?;
+        let bytes = fs::read(&snapshot_path).map_err(|e| e.to_string());
+        let _ = fs::remove_file(&snapshot_path);
+        add_entry(&mut writer, DATABASE_ENTRY, &bytes?, options)?;
+    }
+
+    for name in DATA_FILES {
+        if let Ok(bytes) = fs::read(data_dir.join(name)) {
+            add_entry(&mut writer, name, &bytes, options)?;
+        }
+    }
+
+    let cursor = writer.finish().map_err(|e| format!("Failed to write backup: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+fn add_entry(writer: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, bytes: &[u8], options: SimpleFileOptions) -> Result<(), String> {
+    writer.start_file(name, options).map_err(|e| format!("Failed to write backup: {}", e))?;
+    writer.write_all(bytes).map_err(|e| format!("Failed to write backup: {}", e))
+}
+
+fn restore_snapshot(bytes: Vec<u8>) -> Result<Vec<String>, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let data_dir = config.get_data_dir();
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Invalid backup: {}", e))?;
+    let mut restored = Vec::new();
+
+    if let Some(database) = read_entry(&mut archive, DATABASE_ENTRY)? {
+        restore_database(&config.get_database_path(), &database)?;
+        restored.push(DATABASE_ENTRY.to_string());
+    }
+
+    for name in DATA_FILES {
+        if let Some(content) = read_entry(&mut archive, name)? {
+            fs::write(data_dir.join(name), content).map_err(|e| format!("Failed to restore {}: {}", name, e))?;
+            restored.push(name.to_string());
+        }
+    }
+    Ok(restored)
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let Ok(mut entry) = archive.by_name(name) else {
+        return Ok(None);
+    };
+    let mut buffer = Vec::new();
+    entry.read_to_end(&mut buffer).map_err(|e| format!("Failed to read {} from backup: {}", name, e))?;
+    Ok(Some(buffer))
+}
+
+// 先写到临时文件并校验，通过后再替换；旧的 WAL 文件属于原数据库，一并移走
+fn restore_database(database_path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = database_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let staged = database::sibling(database_path, ".restore");
+    fs::write(&staged, bytes).map_err(|e| format!("Failed to write {}: {}", staged.display(), e))?;
+
+    // 加密数据库的备份需要用钥匙串中同一个密钥打开
+    let check: Result<String, _> = database::open(&staged)
+        .and_then(|conn| conn.query_row("PRAGMA quick_check", [], |row| row.get(0)).map_err(|e| e.to_string()));
+    if check.as_deref() != Ok("ok") {
+        let _ = fs::remove_file(&staged);
+        return Err("The database in this backup is damaged".to_string());
+    }
+
+    if database_path.exists() {
+        fs::rename(database_path, database::sibling(database_path, ".before-restore"))
+            .map_err(|e| format!("Failed to keep the current database: {}", e))?;
+    }
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(database::sibling(database_path, suffix));
+    }
+    fs::rename(&staged, database_path).map_err(|e| format!("Failed to restore database: {}", e))
+}
+
+// 按本地名（忽略命名空间前缀）查找元素，返回各元素的内容
+fn xml_elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut elements = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let tag_end = rest.find(|c: char| c == '>' || c == '/' || c.is_whitespace()).unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        if tag.rsplit(':').next() != Some(name) {
+            continue;
+        }
+        let Some(open_end) = rest.find('>') else { break };
+        if rest[..open_end].ends_with('/') {
+            elements.push("");
+            rest = &rest[open_end + 1..];
+            continue;
+        }
+
+        let content = &rest[open_end + 1..];
+        let closing = format!("</{}>", tag);
+        let Some(close) = content.find(&closing) else { break };
+        elements.push(&content[..close]);
+        rest = &content[close + closing.len()..];
+    }
+    elements
+}
+
+fn xml_text<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    xml_elements(xml, name).into_iter().next()
+}
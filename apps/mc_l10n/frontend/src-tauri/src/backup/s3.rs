@@ -0,0 +1,181 @@
+use reqwest::{Client, Method};
+use sha2::{Digest, Sha256};
+
+use super::{xml_elements, xml_text, RemoteBackup};
+
+// HMAC-SHA256 的分组大小
+const HMAC_BLOCK_SIZE: usize = 64;
+
+// S3 兼容存储（AWS、MinIO、R2 等），使用路径风格地址和 SigV4 签名
+pub struct S3Client {
+    client: Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    prefix: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Client {
+    pub fn new(endpoint: &str, bucket: &str, region: &str, prefix: &str, access_key_id: &str, secret_access_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            access_key_id: access_key_id.to_string(),
+            secret_access_key,
+        }
+    }
+
+    fn object_key(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.prefix, name)
+        }
+    }
+
+    pub async fn put(&self, name: &str, bytes: Vec<u8>) -> Result<(), String> {
+        let key = self.object_key(name);
+        self.send(Method::PUT, Some(&key), &[], bytes).await.map(|_| ())
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        let key = self.object_key(name);
+        self.send(Method::GET, Some(&key), &[], Vec::new()).await
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<(), String> {
+        let key = self.object_key(name);
+        self.send(Method::DELETE, Some(&key), &[], Vec::new()).await.map(|_| ())
+    }
+
+    // ListObjectsV2，按 continuation-token 翻页
+    pub async fn list(&self) -> Result<Vec<RemoteBackup>, String> {
+        let prefix = if self.prefix.is_empty() { String::new() } else { format!("{}/", self.prefix) };
+        let mut backups = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("list-type", "2".to_string()), ("prefix", prefix.clone())];
+            if let Some(token) = &token {
+                query.push(("continuation-token", token.clone()));
+            }
+            let body = self.send(Method::GET, None, &query, Vec::new()).await?;
+            let body = String::from_utf8_lossy(&body);
+
+            for contents in xml_elements(&body, "Contents") {
+                let Some(key) = xml_text(contents, "Key") else { continue };
+                let Some(name) = key.strip_prefix(&prefix) else { continue };
+                if name.is_empty() || name.contains('/') {
+                    continue;
+                }
+                backups.push(RemoteBackup {
+                    name: name.to_string(),
+                    size: xml_text(contents, "Size").and_then(|size| size.trim().parse().ok()),
+                });
+            }
+
+            token = xml_text(&body, "NextContinuationToken").map(str::to_string);
+            if xml_text(&body, "IsTruncated") != Some("true") || token.is_none() {
+                break;
+            }
+        }
+        Ok(backups)
+    }
+
+    async fn send(&self, method: Method, key: Option<&str>, query: &[(&str, String)], body: Vec<u8>) -> Result<Vec<u8>, String> {
+        let url = reqwest::Url::parse(&self.endpoint).map_err(|e| format!("Invalid endpoint {}: {}", self.endpoint, e))?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+
+        let mut path = format!("/{}", uri_encode(&self.bucket));
+        if let Some(key) = key {
+            for segment in key.split('/') {
+                path.push('/');
+                path.push_str(&uri_encode(segment));
+            }
+        }
+        let mut query: Vec<(String, String)> = query.iter().map(|(k, v)| (uri_encode(k), uri_encode(v))).collect();
+        query.sort();
+        let query = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method, path, query, host, payload_hash, amz_date, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut signing_key = hmac_sha256(format!("AWS4{}", self.secret_access_key).as_bytes(), date.as_bytes());
+        for part in [self.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part.as_bytes());
+        }
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            self.access_key_id, scope, signature
+        );
+
+        let mut request_url = format!("{}{}", self.endpoint, path);
+        if !query.is_empty() {
+            request_url = format!("{}?{}", request_url, query);
+        }
+        let response = self
+            .client
+            .request(method, &request_url)
+            .header("x-amz-date", &amz_date)
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("Authorization", authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+        if !status.is_success() {
+            let message = xml_text(&String::from_utf8_lossy(&bytes), "Message").unwrap_or_default().to_string();
+            return Err(format!("{} returned {} {}", request_url, status, message).trim_end().to_string());
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+// SigV4 要求的 URI 编码：只保留 RFC 3986 的非保留字符
+fn uri_encode(value: &str) -> String {
+    urlencoding::encode(value).into_owned()
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner_pad: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+    let outer_pad: Vec<u8> = block.iter().map(|b| b ^ 0x5c).collect();
+    let inner = Sha256::new().chain_update(&inner_pad).chain_update(message).finalize();
+    Sha256::new().chain_update(&outer_pad).chain_update(inner).finalize().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,89 @@
+use reqwest::{Client, Method};
+
+use super::{xml_elements, xml_text, RemoteBackup};
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<propfind xmlns="DAV:"><prop><getcontentlength/></prop></propfind>"#;
+
+// WebDAV 目录（如 Nextcloud 的 remote.php/dav/files/<用户>/<目录>）
+pub struct WebDavClient {
+    client: Client,
+    base_url: String,
+    username: String,
+    password: Option<String>,
+}
+
+impl WebDavClient {
+    pub fn new(url: &str, username: &str, password: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: url.trim_end_matches('/').to_string(),
+            username: username.to_string(),
+            password,
+        }
+    }
+
+    fn request(&self, method: Method, name: Option<&str>) -> reqwest::RequestBuilder {
+        let url = match name {
+            Some(name) => format!("{}/{}", self.base_url, urlencoding::encode(name)),
+            None => format!("{}/", self.base_url),
+        };
+        self.client
+            .request(method, url)
+            .basic_auth(&self.username, self.password.as_deref())
+    }
+
+    pub async fn put(&self, name: &str, bytes: Vec<u8>) -> Result<(), String> {
+        let response = self.request(Method::PUT, Some(name)).body(bytes).send().await.map_err(|e| e.to_string())?;
+        check_status(response).await.map(|_| ())
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        let response = self.request(Method::GET, Some(name)).send().await.map_err(|e| e.to_string())?;
+        let bytes = check_status(response).await?.bytes().await.map_err(|e| e.to_string())?;
+        Ok(bytes.to_vec())
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<(), String> {
+        let response = self.request(Method::DELETE, Some(name)).send().await.map_err(|e| e.to_string())?;
+        check_status(response).await.map(|_| ())
+    }
+
+    // PROPFIND Depth: 1 列出目录中的文件
+    pub async fn list(&self) -> Result<Vec<RemoteBackup>, String> {
+        let method = Method::from_bytes(b"PROPFIND").map_err(|e| e.to_string())?;
+        let response = self
+            .request(method, None)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml")
+            .body(PROPFIND_BODY)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let body = check_status(response).await?.text().await.map_err(|e| e.to_string())?;
+
+        let backups = xml_elements(&body, "response")
+            .into_iter()
+            .filter_map(|response| {
+                let href = xml_text(response, "href")?;
+                let name = href.trim_end_matches('/').rsplit('/').next()?;
+                let name = urlencoding::decode(name).ok()?.into_owned();
+                Some(RemoteBackup {
+                    name,
+                    size: xml_text(response, "getcontentlength").and_then(|size| size.trim().parse().ok()),
+                })
+            })
+            .collect();
+        Ok(backups)
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, String> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        let status = response.status();
+        let url = response.url().to_string();
+        Err(format!("{} returned {}", url, status))
+    }
+}
@@ -0,0 +1,167 @@
+use encoding_rs::{Encoding, BIG5, EUC_KR, GBK, SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::io_retry;
+
+// 不是 UTF-8 且没有 BOM 时依次尝试的旧版编码，得分相同时靠前的优先
+const LEGACY_ENCODINGS: [&Encoding; 4] = [GBK, SHIFT_JIS, BIG5, EUC_KR];
+
+// 解码后的文本及其原始编码，写回时据此保持编码
+pub struct DecodedText {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+impl DecodedText {
+    pub fn is_utf8(&self) -> bool {
+        self.encoding == UTF_8
+    }
+}
+
+pub fn read_file(path: &Path) -> io::Result<DecodedText> {
+    io_retry::retry_io(|| fs::read(path)).map(|bytes| decode(&bytes))
+}
+
+// 先按 BOM 判断，其次是合法的 UTF-8；都不是时选择能完整解码且得分最高的旧版编码（如 GBK、Shift-JIS），
+// 仍无法判断时按 UTF-8 有损解码
+pub fn decode(bytes: &[u8]) -> DecodedText {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+        return DecodedText { text: text.into_owned(), encoding, bom: true };
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return DecodedText { text: text.to_string(), encoding: UTF_8, bom: false };
+    }
+
+    let mut best: Option<(i64, &'static Encoding, String)> = None;
+    for encoding in LEGACY_ENCODINGS {
+        let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+        if had_errors {
+            continue;
+        }
+        let score = plausibility(&text);
+        if best.as_ref().map_or(true, |(best_score, _, _)| score > *best_score) {
+            best = Some((score, encoding, text.into_owned()));
+        }
+    }
+
+    match best {
+        Some((_, encoding, text)) => DecodedText { text, encoding, bom: false },
+        None => DecodedText { text: String::from_utf8_lossy(bytes).into_owned(), encoding: UTF_8, bom: false },
+    }
+}
+
+// 按原编码编码文本；原编码无法表示其中的字符时返回 None
+pub fn encode(text: &str, encoding: &'static Encoding, bom: bool) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() + 3);
+    // encoding_rs 不支持编码为 UTF-16，单独处理
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let little_endian = encoding == UTF_16LE;
+        if bom {
+            bytes.extend_from_slice(if little_endian { &[0xFF, 0xFE] } else { &[0xFE, 0xFF] });
+        }
+        for unit in text.encode_utf16() {
+            bytes.extend_from_slice(&if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() });
+        }
+        return Some(bytes);
+    }
+    if encoding == UTF_8 {
+        if bom {
+            bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+        }
+        bytes.extend_from_slice(text.as_bytes());
+        return Some(bytes);
+    }
+
+    let (encoded, _, unmappable) = encoding.encode(text);
+    (!unmappable).then(|| encoded.into_owned())
+}
+
+// 解码结果像正常文本的程度：假名、汉字、谚文和全角标点加分；半角片假名（GBK 文本被当作 Shift-JIS 时大量出现）、
+// 私用区字符和 C1 控制字符减分
+fn plausibility(text: &str) -> i64 {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x3040..=0x30FF => 3,
+            0x4E00..=0x9FFF | 0xAC00..=0xD7AF => 2,
+            0x3000..=0x303F | 0xFF01..=0xFF60 => 1,
+            0xFF61..=0xFF9F => -3,
+            0xE000..=0xF8FF | 0x80..=0x9F => -5,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_detects_bom() {
+        let utf8 = decode(b"\xEF\xBB\xBFa=\xE4\xB8\xAD");
+        assert_eq!((utf8.text.as_str(), utf8.encoding, utf8.bom), ("a=中", UTF_8, true));
+
+        let utf16le = decode(&[0xFF, 0xFE, b'a', 0, b'=', 0, 0x2D, 0x4E]);
+        assert_eq!((utf16le.text.as_str(), utf16le.encoding, utf16le.bom), ("a=中", UTF_16LE, true));
+
+        let utf16be = decode(&[0xFE, 0xFF, 0, b'a', 0, b'=', 0x4E, 0x2D]);
+        assert_eq!((utf16be.text.as_str(), utf16be.encoding, utf16be.bom), ("a=中", UTF_16BE, true));
+    }
+
+    #[test]
+    fn decode_prefers_utf8_without_bom() {
+        let decoded = decode("item.sword=钻石剑".as_bytes());
+        assert!(decoded.is_utf8());
+        assert!(!decoded.bom);
+        assert_eq!(decoded.text, "item.sword=钻石剑");
+    }
+
+    #[test]
+    fn decode_detects_gbk() {
+        // “中文名称” 的 GBK 编码
+        let decoded = decode(b"name=\xD6\xD0\xCE\xC4\xC3\xFB\xB3\xC6");
+        assert_eq!(decoded.encoding, GBK);
+        assert!(!decoded.bom);
+        assert_eq!(decoded.text, "name=中文名称");
+
+        let (bytes, _, _) = GBK.encode("item.diamond_sword.name=钻石剑\ntile.stone.name=石头\n");
+        let decoded = decode(&bytes);
+        assert_eq!(decoded.encoding, GBK);
+        assert_eq!(decoded.text, "item.diamond_sword.name=钻石剑\ntile.stone.name=石头\n");
+    }
+
+    #[test]
+    fn decode_detects_shift_jis() {
+        let (bytes, _, _) = SHIFT_JIS.encode("item.sword.name=ダイヤモンドの剣\n");
+        let decoded = decode(&bytes);
+        assert_eq!(decoded.encoding, SHIFT_JIS);
+        assert_eq!(decoded.text, "item.sword.name=ダイヤモンドの剣\n");
+    }
+
+    #[test]
+    fn decode_falls_back_to_lossy_utf8() {
+        // 0xFF 在所有候选编码中都是无效的字节
+        let decoded = decode(b"a=\xFF\xFF");
+        assert!(decoded.is_utf8());
+        assert_eq!(decoded.text, "a=\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn encode_round_trips_original_encoding() {
+        let text = "name=中文名称";
+        for (encoding, bom) in [(UTF_8, false), (UTF_8, true), (UTF_16LE, true), (UTF_16BE, true), (GBK, false)] {
+            let bytes = encode(text, encoding, bom).unwrap();
+            let decoded = decode(&bytes);
+            assert_eq!((decoded.text.as_str(), decoded.encoding, decoded.bom), (text, encoding, bom));
+        }
+    }
+
+    #[test]
+    fn encode_rejects_unmappable_characters() {
+        assert!(encode("name=😀", GBK, false).is_none());
+        assert!(encode("name=😀", UTF_8, false).is_some());
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::jar::{ArchiveDiagnostics, JarReader};
+use crate::metadata_cache::MetadataCache;
+use crate::mod_metadata::extract_mod_metadata;
+use crate::profiling::ModTiming;
+
+const CLASS_MAGIC: u32 = 0xCAFE_BABE;
+
+// 类名中出现这些片段时认为该类负责界面显示
+const UI_CLASS_HINTS: &[&str] = &["gui", "screen", "widget", "menu", "hud", "overlay", "tooltip", "button"];
+
+// 硬编码字符串的处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingStatus {
+    // 无法通过语言文件翻译，需要向 MOD 作者反馈改用翻译键
+    ReportUpstream,
+}
+
+// 在类常量池中找到的疑似面向用户的字符串
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardcodedString {
+    pub class_name: String,
+    pub text: String,
+    // gui、tooltip 或 formatted（带格式代码的文本）
+    pub context: String,
+    pub status: FindingStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModHardcodedStrings {
+    pub mod_id: String,
+    pub mod_name: String,
+    pub jar_path: String,
+    pub strings: Vec<HardcodedString>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardcodedStringReport {
+    pub scanned_jars: u32,
+    pub total_strings: u32,
+    pub mods: Vec<ModHardcodedStrings>,
+}
+
+// 高级扫描：读取 JAR 中所有类的常量池，找出硬编码在代码里的界面文本
+pub fn scan_hardcoded_strings(
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+    diagnostics: &ArchiveDiagnostics,
+) -> HardcodedStringReport {
+    let scanned_jars = jar_paths.len() as u32;
+    let mut mods: Vec<ModHardcodedStrings> = limits
+        .map(jar_paths, |limits, path| {
+            let metadata = extract_mod_metadata(&path, limits, cache, &mut ModTiming::default())?;
+            let strings = {
+                let _io = limits.io.acquire();
+                scan_jar(&path, diagnostics).ok()?
+            };
+            if strings.is_empty() {
+                return None;
+            }
+            Some(ModHardcodedStrings {
+                mod_id: metadata.mod_id,
+                mod_name: metadata.display_name,
+                jar_path: path.to_string_lossy().to_string(),
+                strings,
+            })
+        })
+        .into_iter()
+        .flatten()
+        .collect();
+    mods.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+
+    HardcodedStringReport {
+        scanned_jars,
+        total_strings: mods.iter().map(|m| m.strings.len() as u32).sum(),
+        mods,
+    }
+}
+
+fn scan_jar(path: &Path, diagnostics: &ArchiveDiagnostics) -> Result<Vec<HardcodedString>, String> {
+    let mut jar = JarReader::open_with(path, diagnostics)?;
+    let mut seen = HashSet::new();
+    let mut strings = Vec::new();
+
+    for name in jar.entry_names() {
+        // 多版本 JAR 中的重复类和模块描述不参与扫描
+        if !name.ends_with(".class") || name.starts_with("META-INF/") || name.ends_with("module-info.class") {
+            continue;
+        }
+        let Some(bytes) = jar.read_entry(&name) else { continue };
+        let Ok(constants) = read_string_constants(&bytes) else { continue };
+
+        let class_name = name.trim_end_matches(".class").replace('/', ".");
+        for constant in constants {
+            // 字符串拼接配方中的 \u{1} 是参数占位符
+            let text = constant.replace('\u{1}', "%s");
+            let Some(context) = classify(&class_name, &text) else { continue };
+            // 同一文本只报告第一次出现的位置
+            if seen.insert(text.clone()) {
+                strings.push(HardcodedString {
+                    class_name: class_name.clone(),
+                    text,
+                    context: context.to_string(),
+                    status: FindingStatus::ReportUpstream,
+                });
+            }
+        }
+    }
+
+    Ok(strings)
+}
+
+// 解析类文件常量池，返回 CONSTANT_String 引用的字符串字面量
+pub fn read_string_constants(bytes: &[u8]) -> Result<Vec<String>, String> {
+    let mut reader = ByteReader { bytes, pos: 0 };
+    if reader.u32()? != CLASS_MAGIC {
+        return Err("Not a class file".to_string());
+    }
+    reader.skip(4)?; // minor_version, major_version
+
+    let count = reader.u16()? as usize;
+    let mut utf8: Vec<Option<String>> = vec![None; count];
+    let mut string_refs = Vec::new();
+
+    let mut index = 1;
+    while index < count {
+        let tag = reader.u8()?;
+        match tag {
+            1 => {
+                let len = reader.u16()? as usize;
+                utf8[index] = Some(decode_modified_utf8(reader.take(len)?));
+            }
+            8 => string_refs.push(reader.u16()? as usize),
+            7 | 16 | 19 | 20 => reader.skip(2)?,
+            15 => reader.skip(3)?,
+            3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => reader.skip(4)?,
+            5 | 6 => {
+                // long 和 double 占用两个常量池槽位
+                reader.skip(8)?;
+                index += 1;
+            }
+            _ => return Err(format!("Unknown constant pool tag {}", tag)),
+        }
+        index += 1;
+    }
+
+    Ok(string_refs
+        .into_iter()
+        .filter_map(|i| utf8.get(i).cloned().flatten())
+        .collect())
+}
+
+// 判断字面量是否像面向用户的文本，返回其所在的界面上下文
+fn classify(class_name: &str, text: &str) -> Option<&'static str> {
+    let text = text.trim();
+    let char_count = text.chars().count();
+    if !(3..=500).contains(&char_count) || !text.chars().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+    if looks_technical(text) {
+        return None;
+    }
+
+    let lower_class = class_name.to_lowercase();
+    let formatted = text.contains('§');
+    let context = if lower_class.contains("tooltip") {
+        "tooltip"
+    } else if UI_CLASS_HINTS.iter().any(|hint| lower_class.contains(hint)) {
+        "gui"
+    } else if formatted {
+        "formatted"
+    } else {
+        return None;
+    };
+
+    let words = text.split_whitespace().count();
+    if words >= 2 || formatted || !text.is_ascii() {
+        return Some(context);
+    }
+
+    // 单个单词只接受首字母大写的普通单词，如 "Settings"
+    let mut chars = text.chars();
+    let capitalized = chars.next().is_some_and(|c| c.is_uppercase())
+        && chars.all(|c| c.is_lowercase() || matches!(c, '!' | '?' | ':' | '.'));
+    capitalized.then_some(context)
+}
+
+// 翻译键、资源路径、类型描述符、日志模板等不会直接显示给用户
+fn looks_technical(text: &str) -> bool {
+    if text.contains("://") || text.contains("{}") || text.starts_with('(') {
+        return true;
+    }
+    if text.starts_with('L') && text.ends_with(';') {
+        return true;
+    }
+    if text.contains(char::is_whitespace) {
+        return false;
+    }
+
+    text.contains(['.', '_', '/', ':', '$', '#', '=', '<', '>', '[', '{'])
+        || !text.chars().any(|c| c.is_lowercase()) && text.chars().any(|c| c.is_uppercase())
+        || text.chars().skip(1).any(|c| c.is_uppercase())
+}
+
+// 类文件使用修改版 UTF-8：NUL 编码为两个字节，补充平面字符编码为代理对
+fn decode_modified_utf8(bytes: &[u8]) -> String {
+    let mut units: Vec<u16> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i] as u16;
+        if b & 0x80 == 0 {
+            units.push(b);
+            i += 1;
+        } else if b & 0xE0 == 0xC0 && i + 1 < bytes.len() {
+            units.push(((b & 0x1F) << 6) | (bytes[i + 1] as u16 & 0x3F));
+            i += 2;
+        } else if b & 0xF0 == 0xE0 && i + 2 < bytes.len() {
+            units.push(((b & 0x0F) << 12) | ((bytes[i + 1] as u16 & 0x3F) << 6) | (bytes[i + 2] as u16 & 0x3F));
+            i += 3;
+        } else {
+            units.push(0xFFFD);
+            i += 1;
+        }
+    }
+    String::from_utf16_lossy(&units)
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or("Truncated class file")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), String> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
@@ -0,0 +1,411 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+
+// 持有 IO 许可超过该时间视为一次慢读取（网络驱动器延迟高或带宽被并发读取占满）
+const SLOW_HOLD: Duration = Duration::from_millis(1500);
+
+// 连续慢读取达到该次数时并发数减半
+const SLOW_STREAK: u32 = 3;
+
+// 降低并发后连续快速读取达到该次数时并发数加一，直到恢复初始值
+const FAST_STREAK: u32 = 20;
+
+// 计数信号量，限制同时进行的同类任务数量。自适应的信号量根据许可持有时间调整并发数
+pub struct Limiter {
+    state: Mutex<LimiterState>,
+    released: Condvar,
+    max: usize,
+    adaptive: bool,
+}
+
+struct LimiterState {
+    in_use: usize,
+    limit: usize,
+    slow_streak: u32,
+    fast_streak: u32,
+}
+
+pub struct LimiterPermit<'a> {
+    limiter: &'a Limiter,
+    acquired_at: Instant,
+}
+
+impl Limiter {
+    pub fn new(permits: usize) -> Self {
+        let max = permits.max(1);
+        Self {
+            state: Mutex::new(LimiterState { in_use: 0, limit: max, slow_streak: 0, fast_streak: 0 }),
+            released: Condvar::new(),
+            max,
+            adaptive: false,
+        }
+    }
+
+    pub fn adaptive(permits: usize) -> Self {
+        Self { adaptive: true, ..Self::new(permits) }
+    }
+
+    pub fn acquire(&self) -> LimiterPermit<'_> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        while state.in_use >= state.limit {
+            state = self.released.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        state.in_use += 1;
+        LimiterPermit { limiter: self, acquired_at: Instant::now() }
+    }
+
+    // 当前允许的并发数
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).limit
+    }
+
+    // 初始并发数
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    fn record_hold(&self, state: &mut LimiterState, held: Duration) {
+        if held >= SLOW_HOLD {
+            state.fast_streak = 0;
+            state.slow_streak += 1;
+            if state.slow_streak >= SLOW_STREAK && state.limit > 1 {
+                state.limit /= 2;
+                state.slow_streak = 0;
+                eprintln!("Slow IO detected, reducing IO concurrency to {}", state.limit);
+            }
+        } else {
+            state.slow_streak = 0;
+            state.fast_streak += 1;
+            if state.fast_streak >= FAST_STREAK && state.limit < self.max {
+                state.limit += 1;
+                state.fast_streak = 0;
+                self.released.notify_one();
+            }
+        }
+    }
+}
+
+impl Drop for LimiterPermit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.in_use -= 1;
+        if self.limiter.adaptive {
+            self.limiter.record_hold(&mut state, self.acquired_at.elapsed());
+        }
+        self.limiter.released.notify_one();
+    }
+}
+
+// 未配置时同时读入内存的归档数据上限
+pub const DEFAULT_MEMORY_BUDGET_MB: u64 = 512;
+
+thread_local! {
+    // 当前线程持有的内存预留数量
+    static HELD_RESERVATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// 一次扫描中读入内存的归档数据（压缩存储的嵌套 JAR、整合包内的 MOD 等）的总字节数上限。
+// 已持有预留的线程再次预留时不等待（嵌套归档逐层预留），避免线程之间互相等待
+#[derive(Clone)]
+pub struct MemoryBudget {
+    inner: Arc<BudgetState>,
+}
+
+struct BudgetState {
+    used: Mutex<u64>,
+    released: Condvar,
+    limit: u64,
+}
+
+pub struct MemoryReservation {
+    inner: Arc<BudgetState>,
+    bytes: u64,
+}
+
+impl MemoryBudget {
+    pub fn new(limit_bytes: u64) -> Self {
+        Self {
+            inner: Arc::new(BudgetState { used: Mutex::new(0), released: Condvar::new(), limit: limit_bytes.max(1) }),
+        }
+    }
+
+    // 超过上限的单个请求在没有其他预留时仍然放行
+    pub fn reserve(&self, bytes: u64) -> MemoryReservation {
+        let state = &self.inner;
+        let mut used = state.used.lock().unwrap_or_else(|e| e.into_inner());
+        if HELD_RESERVATIONS.with(|held| held.get()) == 0 {
+            while *used > 0 && *used + bytes > state.limit {
+                used = state.released.wait(used).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+        *used += bytes;
+        HELD_RESERVATIONS.with(|held| held.set(held.get() + 1));
+        MemoryReservation { inner: Arc::clone(state), bytes }
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        let mut used = self.inner.used.lock().unwrap_or_else(|e| e.into_inner());
+        *used = used.saturating_sub(self.bytes);
+        HELD_RESERVATIONS.with(|held| held.set(held.get().saturating_sub(1)));
+        self.inner.released.notify_all();
+    }
+}
+
+// 扫描的取消和暂停控制，可以在其他线程上设置；克隆共享同一状态
+#[derive(Clone, Default)]
+pub struct ScanControl {
+    inner: Arc<ControlState>,
+}
+
+#[derive(Default)]
+struct ControlState {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+}
+
+impl ScanControl {
+    // 同时唤醒暂停中的扫描，使其尽快结束
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        self.inner.resumed.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn pause(&self) {
+        *self.inner.paused.lock().unwrap_or_else(|e| e.into_inner()) = true;
+    }
+
+    pub fn resume(&self) {
+        *self.inner.paused.lock().unwrap_or_else(|e| e.into_inner()) = false;
+        self.inner.resumed.notify_all();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.inner.paused.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 扫描的检查点：暂停时阻塞到恢复或取消，已经完成的工作保留，恢复后从这里继续；返回 false 表示已取消
+    pub fn checkpoint(&self) -> bool {
+        let mut paused = self.inner.paused.lock().unwrap_or_else(|e| e.into_inner());
+        while *paused && !self.is_cancelled() {
+            paused = self.inner.resumed.wait(paused).unwrap_or_else(|e| e.into_inner());
+        }
+        !self.is_cancelled()
+    }
+}
+
+// 磁盘 IO（ZIP 读取）与 CPU（哈希、解析）任务分别限流；IO 读取变慢时（如网络驱动器）自动降低 IO 并发
+pub struct WorkLimits {
+    pub io: Limiter,
+    pub cpu: Limiter,
+    pub memory: MemoryBudget,
+    // 取消后 map 不再开始新的任务，暂停时在开始下一个任务前等待
+    pub control: ScanControl,
+    workers: usize,
+}
+
+impl WorkLimits {
+    pub fn new(io_concurrency: usize, cpu_concurrency: usize) -> Self {
+        Self {
+            io: Limiter::adaptive(io_concurrency),
+            cpu: Limiter::new(cpu_concurrency),
+            memory: MemoryBudget::new(DEFAULT_MEMORY_BUDGET_MB * 1024 * 1024),
+            control: ScanControl::default(),
+            workers: io_concurrency.max(cpu_concurrency).max(1),
+        }
+    }
+
+    pub fn with_control(self, control: ScanControl) -> Self {
+        Self { control, ..self }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            memory: MemoryBudget::new(config.scan_memory_budget_mb * 1024 * 1024),
+            ..Self::new(config.effective_io_concurrency(), config.effective_cpu_concurrency())
+        }
+    }
+
+    // 在工作线程上并行处理，结果保持输入顺序；任务内部通过 io/cpu 许可控制各阶段并发。
+    // 暂停时各线程在开始下一个条目前等待；取消后剩余的条目不再处理，结果中只有已完成的部分
+    pub fn map<T, R, F>(&self, items: Vec<T>, work: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(&Self, T) -> R + Sync,
+    {
+        let total = items.len();
+        let workers = self.workers.min(total);
+        if workers <= 1 {
+            return items.into_iter().map_while(|item| self.control.checkpoint().then(|| work(self, item))).collect();
+        }
+
+        let queue: Vec<Mutex<Option<T>>> = items.into_iter().map(|item| Mutex::new(Some(item))).collect();
+        let results: Vec<Mutex<Option<R>>> = (0..total).map(|_| Mutex::new(None)).collect();
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= total || !self.control.checkpoint() {
+                        break;
+                    }
+
+                    let item = queue[index].lock().unwrap_or_else(|e| e.into_inner()).take();
+                    if let Some(item) = item {
+                        let result = work(self, item);
+                        *results[index].lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                    }
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .filter_map(|slot| slot.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect()
+    }
+}
+
+pub fn available_cores() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    // 等待足够长的时间，确认另一线程仍在阻塞
+    const BLOCKED_WAIT: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn limiter_blocks_until_a_permit_is_released() {
+        let limiter = Limiter::new(1);
+        let permit = limiter.acquire();
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let _permit = limiter.acquire();
+                sender.send(()).unwrap();
+            });
+            assert!(receiver.recv_timeout(BLOCKED_WAIT).is_err());
+            drop(permit);
+            assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+        });
+    }
+
+    #[test]
+    fn adaptive_limiter_halves_on_slow_reads_and_recovers() {
+        let limiter = Limiter::adaptive(4);
+        let hold = |held| limiter.record_hold(&mut limiter.state.lock().unwrap(), held);
+        for _ in 0..SLOW_STREAK {
+            hold(SLOW_HOLD);
+        }
+        assert_eq!(limiter.limit(), 2);
+        for _ in 0..FAST_STREAK * 2 {
+            hold(Duration::ZERO);
+        }
+        assert_eq!(limiter.limit(), limiter.max());
+    }
+
+    #[test]
+    fn memory_budget_blocks_until_enough_is_released() {
+        let budget = &MemoryBudget::new(100);
+        let (sender, receiver) = mpsc::channel();
+        let (release, released) = mpsc::channel::<()>();
+        thread::scope(|scope| {
+            // 预留在另一线程上持有，本线程的预留不享受重入豁免
+            let holder = sender.clone();
+            scope.spawn(move || {
+                let _reservation = budget.reserve(80);
+                holder.send(()).unwrap();
+                released.recv().unwrap();
+            });
+            receiver.recv().unwrap();
+
+            let waiting = scope.spawn(move || {
+                let _reservation = budget.reserve(40);
+                sender.send(()).unwrap();
+            });
+            assert!(receiver.recv_timeout(BLOCKED_WAIT).is_err());
+            release.send(()).unwrap();
+            assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+            waiting.join().unwrap();
+        });
+        assert_eq!(*budget.inner.used.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn oversized_reservation_proceeds_when_nothing_is_held() {
+        let budget = MemoryBudget::new(100);
+        let reservation = budget.reserve(1000);
+        assert_eq!(*budget.inner.used.lock().unwrap(), 1000);
+        drop(reservation);
+        assert_eq!(*budget.inner.used.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn thread_holding_a_reservation_is_not_blocked_again() {
+        let budget = MemoryBudget::new(100);
+        // 嵌套归档逐层预留：外层已持有预留时，内层超出上限也不等待
+        let outer = budget.reserve(80);
+        let inner = budget.reserve(40);
+        assert_eq!(*budget.inner.used.lock().unwrap(), 120);
+        drop(inner);
+        drop(outer);
+        // 全部释放后不再豁免
+        assert_eq!(HELD_RESERVATIONS.with(|held| held.get()), 0);
+    }
+
+    #[test]
+    fn map_keeps_input_order_across_workers() {
+        let limits = WorkLimits::new(4, 4);
+        let results = limits.map((0..100).collect(), |_, item: u32| {
+            thread::sleep(Duration::from_micros(u64::from(100 - item)));
+            item * 2
+        });
+        assert_eq!(results, (0..100).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn map_stops_after_cancel() {
+        let limits = WorkLimits::new(1, 1);
+        let processed = AtomicUsize::new(0);
+        let results = limits.map((0..10).collect(), |limits, item: u32| {
+            if processed.fetch_add(1, Ordering::SeqCst) == 2 {
+                limits.control.cancel();
+            }
+            item
+        });
+        assert_eq!(results, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn map_waits_while_paused() {
+        let limits = WorkLimits::new(2, 2);
+        let control = limits.control.clone();
+        control.pause();
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                sender.send(limits.map(vec![1, 2, 3], |_, item: u32| item)).unwrap();
+            });
+            assert!(receiver.recv_timeout(BLOCKED_WAIT).is_err());
+            control.resume();
+            assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), vec![1, 2, 3]);
+        });
+    }
+}
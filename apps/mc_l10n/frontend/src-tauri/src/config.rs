@@ -2,13 +2,101 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::fs;
 
+use crate::concurrency;
+use crate::metrics::MetricsFormat;
+use crate::priority::PriorityRules;
+use crate::scanners::custom_rules::ExtractionRule;
+use crate::scanners::mod_jars::DEFAULT_MODS_DIR_DEPTH;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub database_path: String,
     pub data_dir: String,
+    // light、dark 或 system（跟随系统）
     pub theme: String,
     pub language: String,
     pub auto_save: bool,
+    // 磁盘 IO 任务（ZIP 读取）并发数，0 表示根据 CPU 核心数自动决定
+    #[serde(default)]
+    pub io_concurrency: usize,
+    // CPU 任务（哈希、解析）并发数，0 表示使用全部核心
+    #[serde(default)]
+    pub cpu_concurrency: usize,
+    // 被用户关闭的内容扫描器 id
+    #[serde(default)]
+    pub disabled_scanners: Vec<String>,
+    // 语言资源合并优先级规则
+    #[serde(default)]
+    pub priority_rules: PriorityRules,
+    // 用于解析 CurseForge 导出包中的 MOD 文件名，未配置时只列出项目和文件 id
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+    // 导入 .mrpack 时在这些目录中按文件名和 SHA-1 查找引用的 MOD 文件（如启动器的 MOD 缓存）
+    #[serde(default)]
+    pub mod_search_dirs: Vec<String>,
+    // 本地找不到引用的 MOD 文件时从整合包列出的地址下载
+    #[serde(default)]
+    pub download_pack_mods: bool,
+    // 直接在归档内扫描 .mrpack 和 CurseForge 导出包，不解压到数据目录；此时不下载引用的 MOD 文件
+    #[serde(default)]
+    pub scan_archives_in_place: bool,
+    // 被启动器禁用的 MOD（.jar.disabled/.jar.old）的语言文件计入扫描结果和键数统计
+    #[serde(default)]
+    pub include_disabled_mods: bool,
+    // 解析带注释或尾随逗号的非标准 JSON 语言文件，并在扫描结果中给出警告
+    #[serde(default)]
+    pub lenient_lang_json: bool,
+    // mods 目录下递归查找 JAR 的子目录层数（Prism/MultiMC 等会按版本或端分子目录）
+    #[serde(default = "default_mods_dir_depth")]
+    pub mods_dir_depth: usize,
+    // 一次扫描中同时读入内存的归档数据上限（MB）；未压缩存储的嵌套 JAR 直接在文件上读取，不计入
+    #[serde(default = "default_scan_memory_budget_mb")]
+    pub scan_memory_budget_mb: u64,
+    // 从网址扫描整合包时允许下载的最大大小（MB）
+    #[serde(default = "default_max_pack_download_mb")]
+    pub max_pack_download_mb: u64,
+    // 匿名使用统计，默认关闭
+    #[serde(default)]
+    pub telemetry_enabled: bool,
+    // 统计上报地址，未配置时只在本地累计
+    #[serde(default)]
+    pub telemetry_endpoint: Option<String>,
+    // 用户允许运行的扩展 id；扩展目录中未启用的扩展不会被执行
+    #[serde(default)]
+    pub enabled_extensions: Vec<String>,
+    // 本机指标服务的监听地址，如 127.0.0.1:9464；只接受回环地址，未配置时不启动
+    #[serde(default)]
+    pub metrics_listen: Option<String>,
+    // 指标文件输出路径，每次指标变化后覆盖写入
+    #[serde(default)]
+    pub metrics_file: Option<String>,
+    #[serde(default)]
+    pub metrics_format: MetricsFormat,
+    // 同时运行的扫描数，其余扫描在队列中等待
+    #[serde(default = "default_max_concurrent_scans")]
+    pub max_concurrent_scans: usize,
+    // 用户完成或跳过首次启动引导后为 true
+    #[serde(default)]
+    pub onboarding_completed: bool,
+    // 用户定义的额外提取规则，每条规则作为一个扫描器运行
+    #[serde(default)]
+    pub extraction_rules: Vec<ExtractionRule>,
+}
+
+fn default_max_pack_download_mb() -> u64 {
+    2048
+}
+
+fn default_mods_dir_depth() -> usize {
+    DEFAULT_MODS_DIR_DEPTH
+}
+
+fn default_scan_memory_budget_mb() -> u64 {
+    concurrency::DEFAULT_MEMORY_BUDGET_MB
+}
+
+fn default_max_concurrent_scans() -> usize {
+    1
 }
 
 impl Default for AppConfig {
@@ -19,6 +107,28 @@ impl Default for AppConfig {
             theme: "light".to_string(),
             language: "zh-CN".to_string(),
             auto_save: true,
+            io_concurrency: 0,
+            cpu_concurrency: 0,
+            disabled_scanners: Vec::new(),
+            priority_rules: PriorityRules::default(),
+            curseforge_api_key: None,
+            mod_search_dirs: Vec::new(),
+            download_pack_mods: false,
+            scan_archives_in_place: false,
+            include_disabled_mods: false,
+            lenient_lang_json: false,
+            mods_dir_depth: default_mods_dir_depth(),
+            scan_memory_budget_mb: default_scan_memory_budget_mb(),
+            max_pack_download_mb: default_max_pack_download_mb(),
+            telemetry_enabled: false,
+            telemetry_endpoint: None,
+            enabled_extensions: Vec::new(),
+            metrics_listen: None,
+            metrics_file: None,
+            metrics_format: MetricsFormat::default(),
+            max_concurrent_scans: default_max_concurrent_scans(),
+            onboarding_completed: false,
+            extraction_rules: Vec::new(),
         }
     }
 }
@@ -89,6 +199,23 @@ impl AppConfig {
         }
     }
     
+    pub fn effective_io_concurrency(&self) -> usize {
+        if self.io_concurrency > 0 {
+            self.io_concurrency
+        } else {
+            // 机械硬盘随机读过多会严重抖动，默认最多 4 路
+            concurrency::available_cores().min(4)
+        }
+    }
+
+    pub fn effective_cpu_concurrency(&self) -> usize {
+        if self.cpu_concurrency > 0 {
+            self.cpu_concurrency
+        } else {
+            concurrency::available_cores()
+        }
+    }
+
     pub fn ensure_directories(&self) -> Result<(), Box<dyn std::error::Error>> {
         let data_dir = self.get_data_dir();
         fs::create_dir_all(&data_dir)?;
@@ -0,0 +1,175 @@
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::keychain;
+
+// 数据库密钥在系统钥匙串中的账户名
+const KEYCHAIN_ACCOUNT: &str = "database-key";
+
+// 未加密 SQLite 文件的文件头
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+// 钥匙串中读到的密钥，避免每次打开数据库都访问钥匙串（部分系统会弹出授权提示）
+static KEY_CACHE: Mutex<Option<Option<String>>> = Mutex::new(None);
+
+// 本地数据库的加密状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionStatus {
+    // 以 encryption 特性（SQLCipher）构建时为 true
+    pub supported: bool,
+    pub encrypted: bool,
+    pub key_stored: bool,
+}
+
+// 打开本地数据库；启用了加密时用钥匙串中的密钥解锁
+pub fn open(database_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(database_path).map_err(|e| format!("Failed to open local database: {}", e))?;
+    unlock(&conn)?;
+    Ok(conn)
+}
+
+pub fn open_read_only(database_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open_with_flags(database_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open local database: {}", e))?;
+    unlock(&conn)?;
+    Ok(conn)
+}
+
+// PRAGMA key 必须在第一次读取之前执行；普通 SQLite 会忽略该指令
+fn unlock(conn: &Connection) -> Result<(), String> {
+    if let Some(key) = database_key()? {
+        conn.pragma_update(None, "key", &key)
+            .map_err(|e| format!("Failed to unlock local database: {}", e))?;
+    }
+    Ok(())
+}
+
+fn database_key() -> Result<Option<String>, String> {
+    let mut cache = KEY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(key) = cache.as_ref() {
+        return Ok(key.clone());
+    }
+    let key = keychain::load_secret(KEYCHAIN_ACCOUNT)?;
+    *cache = Some(key.clone());
+    Ok(key)
+}
+
+fn set_database_key(key: Option<&str>) -> Result<(), String> {
+    match key {
+        Some(key) => keychain::store_secret(KEYCHAIN_ACCOUNT, key)?,
+        None => keychain::delete_secret(KEYCHAIN_ACCOUNT)?,
+    }
+    *KEY_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(key.map(str::to_string));
+    Ok(())
+}
+
+pub fn encryption_status(database_path: &Path) -> Result<EncryptionStatus, String> {
+    Ok(EncryptionStatus {
+        supported: cfg!(feature = "encryption"),
+        encrypted: is_encrypted(database_path),
+        key_stored: database_key()?.is_some(),
+    })
+}
+
+// 已存在且文件头不是明文 SQLite 文件头
+fn is_encrypted(database_path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    fs::File::open(database_path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|_| header != SQLITE_HEADER)
+}
+
+// 生成随机密钥保存到钥匙串，并把明文数据库迁移为加密数据库
+pub fn enable_encryption(database_path: &Path) -> Result<EncryptionStatus, String> {
+    ensure_supported()?;
+    if is_encrypted(database_path) {
+        return Err("The local database is already encrypted".to_string());
+    }
+
+    // 先保存密钥，避免数据库已加密而密钥没有保存下来
+    let key = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+    set_database_key(Some(&key))?;
+    if database_path.exists() {
+        let migrated = Connection::open(database_path)
+            .map_err(|e| format!("Failed to open local database: {}", e))
+            .and_then(|plain| migrate(plain, database_path, &key, Some(&key)));
+        if let Err(e) = migrated {
+            let _ = set_database_key(None);
+            return Err(e);
+        }
+    }
+    encryption_status(database_path)
+}
+
+// 把加密数据库导出为明文数据库，并从钥匙串删除密钥
+pub fn disable_encryption(database_path: &Path) -> Result<EncryptionStatus, String> {
+    ensure_supported()?;
+    if database_path.exists() && is_encrypted(database_path) {
+        let encrypted = open(database_path)?;
+        migrate(encrypted, database_path, "", None)?;
+    }
+    set_database_key(None)?;
+    encryption_status(database_path)
+}
+
+fn ensure_supported() -> Result<(), String> {
+    if cfg!(feature = "encryption") {
+        Ok(())
+    } else {
+        Err("This build does not include database encryption support".to_string())
+    }
+}
+
+// 用 sqlcipher_export 把 source 整库导出到以 target_key 加密（空字符串为明文）的新文件，
+// 用 verify_key 打开校验后替换原文件；原文件只在替换成功后删除
+fn migrate(source: Connection, database_path: &Path, target_key: &str, verify_key: Option<&str>) -> Result<(), String> {
+    let staged = sibling(database_path, ".migrating");
+    let _ = fs::remove_file(&staged);
+
+    let user_version: i64 = source
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read local database: {}", e))?;
+    source
+        .execute("ATTACH DATABASE ?1 AS migrated KEY ?2", [staged.to_string_lossy().as_ref(), target_key])
+        .and_then(|_| source.query_row("SELECT sqlcipher_export('migrated')", [], |_| Ok(())))
+        .and_then(|_| source.execute_batch(&format!("PRAGMA migrated.user_version = {}", user_version)))
+        .and_then(|_| source.execute_batch("DETACH DATABASE migrated"))
+        .map_err(|e| {
+            let _ = fs::remove_file(&staged);
+            format!("Failed to migrate local database: {}", e)
+        })?;
+    drop(source);
+
+    let check: Result<String, _> = Connection::open(&staged).and_then(|conn| {
+        if let Some(key) = verify_key {
+            conn.pragma_update(None, "key", key)?;
+        }
+        conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+    });
+    if check.as_deref() != Ok("ok") {
+        let _ = fs::remove_file(&staged);
+        return Err("The migrated database failed verification; the original was left unchanged".to_string());
+    }
+
+    let previous = sibling(database_path, ".previous");
+    fs::rename(database_path, &previous).map_err(|e| format!("Failed to replace local database: {}", e))?;
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(sibling(database_path, suffix));
+    }
+    if let Err(e) = fs::rename(&staged, database_path) {
+        let _ = fs::rename(&previous, database_path);
+        return Err(format!("Failed to replace local database: {}", e));
+    }
+    let _ = fs::remove_file(&previous);
+    Ok(())
+}
+
+pub fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::AppConfig;
+use crate::quick_preview;
+use crate::scanners::mod_jars::collect_mod_jar_paths;
+
+// 每个启动器最多列出的实例数
+const MAX_INSTANCES_PER_LAUNCHER: usize = 50;
+
+// 首次启动引导使用的环境报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReport {
+    pub first_run: bool,
+    pub os: String,
+    pub minecraft_dirs: Vec<String>,
+    pub launchers: Vec<LauncherInfo>,
+    pub java: Vec<JavaInstallation>,
+    // 按 MOD 数量排序的项目建议，可以直接加入工作区
+    pub suggestions: Vec<InstanceSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherInfo {
+    pub name: String,
+    pub path: String,
+    pub instance_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceSuggestion {
+    pub name: String,
+    pub path: String,
+    pub launcher: String,
+    pub mod_count: u32,
+    pub loader: Option<String>,
+    pub minecraft_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaInstallation {
+    pub path: String,
+    // java -version 输出的版本号
+    pub version: Option<String>,
+}
+
+// 启动器名称、数据目录、实例目录相对数据目录的路径
+struct LauncherLocation {
+    name: &'static str,
+    root: PathBuf,
+    instances: &'static str,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from)
+}
+
+// 各系统下的应用数据目录（Windows 的 %APPDATA%、macOS 的 Application Support、Linux 的 ~/.local/share）
+fn app_data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(appdata) = std::env::var_os("APPDATA") {
+        dirs.push(PathBuf::from(appdata));
+    }
+    if let Some(home) = home_dir() {
+        if cfg!(target_os = "macos") {
+            dirs.push(home.join("Library").join("Application Support"));
+        } else {
+            let data_home = std::env::var_os("XDG_DATA_HOME").map(PathBuf::from).unwrap_or_else(|| home.join(".local").join("share"));
+            dirs.push(data_home);
+        }
+    }
+    dirs
+}
+
+fn default_minecraft_dirs() -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = app_data_dirs()
+        .into_iter()
+        .flat_map(|dir| [dir.join(".minecraft"), dir.join("minecraft")])
+        .collect();
+    if let Some(home) = home_dir() {
+        candidates.push(home.join(".minecraft"));
+        candidates.push(home.join(".var/app/com.mojang.Minecraft/.minecraft"));
+    }
+    dedup_existing(candidates)
+}
+
+fn launcher_locations() -> Vec<LauncherLocation> {
+    let mut locations = Vec::new();
+    for dir in app_data_dirs() {
+        for (name, relative, instances) in [
+            ("Prism Launcher", "PrismLauncher", "instances"),
+            ("MultiMC", "multimc", "instances"),
+            ("Modrinth App", "ModrinthApp", "profiles"),
+            ("Modrinth App", "com.modrinth.theseus", "profiles"),
+            ("ATLauncher", "ATLauncher", "instances"),
+            ("GDLauncher", "gdlauncher_carbon/data", "instances"),
+        ] {
+            locations.push(LauncherLocation {
+                name,
+                root: dir.join(relative),
+                instances,
+            });
+        }
+    }
+    if let Some(home) = home_dir() {
+        locations.push(LauncherLocation {
+            name: "CurseForge",
+            root: home.join("curseforge").join("minecraft"),
+            instances: "Instances",
+        });
+        locations.push(LauncherLocation {
+            name: "Prism Launcher",
+            root: home.join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher"),
+            instances: "instances",
+        });
+    }
+    locations
+}
+
+// 探测常见的 .minecraft 目录、启动器实例和 Java；只读取目录，不修改任何文件
+pub fn detect_environment(config: &AppConfig) -> EnvironmentReport {
+    let minecraft_dirs = default_minecraft_dirs();
+    let mut launchers = Vec::new();
+    let mut suggestions = Vec::new();
+
+    for dir in &minecraft_dirs {
+        if let Some(suggestion) = suggest_instance(dir, "Minecraft Launcher", config) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    for location in launcher_locations() {
+        let instances_dir = location.root.join(location.instances);
+        if !instances_dir.is_dir() || !seen.insert(instances_dir.clone()) {
+            continue;
+        }
+        let instances = list_instances(&instances_dir);
+        launchers.push(LauncherInfo {
+            name: location.name.to_string(),
+            path: location.root.to_string_lossy().to_string(),
+            instance_count: instances.len() as u32,
+        });
+        suggestions.extend(
+            instances
+                .iter()
+                .take(MAX_INSTANCES_PER_LAUNCHER)
+                .filter_map(|instance| suggest_instance(instance, location.name, config)),
+        );
+    }
+
+    suggestions.sort_by(|a, b| b.mod_count.cmp(&a.mod_count).then_with(|| a.name.cmp(&b.name)));
+
+    EnvironmentReport {
+        first_run: !config.onboarding_completed,
+        os: std::env::consts::OS.to_string(),
+        minecraft_dirs: minecraft_dirs.iter().map(|dir| dir.to_string_lossy().to_string()).collect(),
+        launchers,
+        java: detect_java(),
+        suggestions,
+    }
+}
+
+fn list_instances(instances_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(instances_dir) else {
+        return Vec::new();
+    };
+    let mut instances: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+    instances.sort();
+    instances
+}
+
+// Prism/MultiMC 的游戏目录在实例下的 .minecraft 或 minecraft 中
+fn game_dir(instance: &Path) -> PathBuf {
+    [".minecraft", "minecraft"]
+        .iter()
+        .map(|name| instance.join(name))
+        .find(|dir| dir.join("mods").is_dir())
+        .unwrap_or_else(|| instance.to_path_buf())
+}
+
+// 只建议含有 MOD 的目录；加载器和版本从实例清单中读取
+fn suggest_instance(instance: &Path, launcher: &str, config: &AppConfig) -> Option<InstanceSuggestion> {
+    let game_dir = game_dir(instance);
+    let mod_count = collect_mod_jar_paths(&game_dir, config).len() as u32;
+    if mod_count == 0 {
+        return None;
+    }
+    let (loader, minecraft_version) = quick_preview::instance_manifest(instance);
+
+    Some(InstanceSuggestion {
+        name: instance.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+        path: game_dir.to_string_lossy().to_string(),
+        launcher: launcher.to_string(),
+        mod_count,
+        loader,
+        minecraft_version,
+    })
+}
+
+// PATH 和 JAVA_HOME 中的 java
+fn detect_java() -> Vec<JavaInstallation> {
+    let executable = if cfg!(target_os = "windows") { "java.exe" } else { "java" };
+    let mut candidates = Vec::new();
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        candidates.push(PathBuf::from(java_home).join("bin").join(executable));
+    }
+    if let Some(path) = std::env::var_os("PATH") {
+        candidates.extend(std::env::split_paths(&path).map(|dir| dir.join(executable)));
+    }
+
+    dedup_existing(candidates)
+        .into_iter()
+        .map(|path| JavaInstallation {
+            version: java_version(&path),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
+// java -version 输出到 stderr，形如 openjdk version "17.0.8" 2023-07-18
+fn java_version(java: &Path) -> Option<String> {
+    let output = Command::new(java).arg("-version").output().ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let first_line = stderr.lines().next()?;
+    let start = first_line.find('"')? + 1;
+    let end = start + first_line[start..].find('"')?;
+    Some(first_line[start..end].to_string())
+}
+
+// 去掉不存在的路径，并按规范化路径去重
+fn dedup_existing(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = BTreeSet::new();
+    paths
+        .into_iter()
+        .filter(|path| path.exists())
+        .filter(|path| seen.insert(fs::canonicalize(path).unwrap_or_else(|_| path.clone())))
+        .collect()
+}
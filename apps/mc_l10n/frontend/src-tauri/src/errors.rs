@@ -0,0 +1,292 @@
+use serde::Serialize;
+use std::fmt;
+
+// 稳定的错误代码。代码一经发布不再改变含义，新增错误时同时加入 ALL，前端通过 list_error_codes 获取说明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unknown,
+    TaskFailed,
+    InvalidInput,
+    NotFound,
+    ScanNotFound,
+    ScanAlreadyStarted,
+    ScanCancelled,
+    NoLanguageFiles,
+    CorruptJar,
+    InvalidPack,
+    PathNotFound,
+    PermissionDenied,
+    DiskFull,
+    FileInUse,
+    AlreadyExists,
+    InstanceRunning,
+    IntegrityCheckFailed,
+    UnsupportedVersion,
+    DatabaseLocked,
+    DatabaseError,
+    BackendUnavailable,
+    DownloadFailed,
+    RemoteAuthFailed,
+    SyncSkipped,
+    InvalidBundle,
+    KeychainError,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 26] = [
+        ErrorCode::Unknown,
+        ErrorCode::TaskFailed,
+        ErrorCode::InvalidInput,
+        ErrorCode::NotFound,
+        ErrorCode::ScanNotFound,
+        ErrorCode::ScanAlreadyStarted,
+        ErrorCode::ScanCancelled,
+        ErrorCode::NoLanguageFiles,
+        ErrorCode::CorruptJar,
+        ErrorCode::InvalidPack,
+        ErrorCode::PathNotFound,
+        ErrorCode::PermissionDenied,
+        ErrorCode::DiskFull,
+        ErrorCode::FileInUse,
+        ErrorCode::AlreadyExists,
+        ErrorCode::InstanceRunning,
+        ErrorCode::IntegrityCheckFailed,
+        ErrorCode::UnsupportedVersion,
+        ErrorCode::DatabaseLocked,
+        ErrorCode::DatabaseError,
+        ErrorCode::BackendUnavailable,
+        ErrorCode::DownloadFailed,
+        ErrorCode::RemoteAuthFailed,
+        ErrorCode::SyncSkipped,
+        ErrorCode::InvalidBundle,
+        ErrorCode::KeychainError,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorCode::Unknown => "THS-GEN-000",
+            ErrorCode::TaskFailed => "THS-GEN-001",
+            ErrorCode::InvalidInput => "THS-GEN-002",
+            ErrorCode::NotFound => "THS-GEN-003",
+            ErrorCode::ScanNotFound => "THS-SCAN-001",
+            ErrorCode::ScanAlreadyStarted => "THS-SCAN-002",
+            ErrorCode::NoLanguageFiles => "THS-SCAN-003",
+            ErrorCode::CorruptJar => "THS-SCAN-004",
+            ErrorCode::InvalidPack => "THS-SCAN-005",
+            ErrorCode::ScanCancelled => "THS-SCAN-006",
+            ErrorCode::PathNotFound => "THS-FS-001",
+            ErrorCode::PermissionDenied => "THS-FS-002",
+            ErrorCode::DiskFull => "THS-FS-003",
+            ErrorCode::FileInUse => "THS-FS-004",
+            ErrorCode::AlreadyExists => "THS-FS-005",
+            ErrorCode::InstanceRunning => "THS-INST-001",
+            ErrorCode::IntegrityCheckFailed => "THS-EXPORT-001",
+            ErrorCode::UnsupportedVersion => "THS-EXPORT-002",
+            ErrorCode::DatabaseLocked => "THS-DB-001",
+            ErrorCode::DatabaseError => "THS-DB-002",
+            ErrorCode::BackendUnavailable => "THS-NET-001",
+            ErrorCode::DownloadFailed => "THS-NET-002",
+            ErrorCode::RemoteAuthFailed => "THS-SYNC-001",
+            ErrorCode::SyncSkipped => "THS-SYNC-002",
+            ErrorCode::InvalidBundle => "THS-BUNDLE-001",
+            ErrorCode::KeychainError => "THS-KEY-001",
+        }
+    }
+
+    // 前端按此决定提供的操作按钮，取值稳定
+    pub fn action(self) -> &'static str {
+        match self {
+            ErrorCode::Unknown | ErrorCode::IntegrityCheckFailed => "report_issue",
+            ErrorCode::TaskFailed | ErrorCode::DatabaseLocked | ErrorCode::ScanCancelled => "retry",
+            ErrorCode::InvalidInput | ErrorCode::UnsupportedVersion => "fix_input",
+            ErrorCode::ScanNotFound | ErrorCode::NotFound => "refresh",
+            ErrorCode::ScanAlreadyStarted | ErrorCode::SyncSkipped => "wait",
+            ErrorCode::NoLanguageFiles => "check_locale",
+            ErrorCode::CorruptJar => "redownload_mod",
+            ErrorCode::InvalidPack | ErrorCode::InvalidBundle => "choose_other_file",
+            ErrorCode::PathNotFound => "check_path",
+            ErrorCode::PermissionDenied => "check_permissions",
+            ErrorCode::DiskFull => "free_disk_space",
+            ErrorCode::FileInUse => "close_other_programs",
+            ErrorCode::AlreadyExists => "choose_other_path",
+            ErrorCode::InstanceRunning => "close_game",
+            ErrorCode::DatabaseError => "restore_backup",
+            ErrorCode::BackendUnavailable => "check_connection",
+            ErrorCode::DownloadFailed => "retry_download",
+            ErrorCode::RemoteAuthFailed | ErrorCode::KeychainError => "check_credentials",
+        }
+    }
+
+    pub fn hint(self) -> &'static str {
+        match self {
+            ErrorCode::Unknown => "Unexpected error. Please report it together with the error code and log.",
+            ErrorCode::TaskFailed => "A background task stopped unexpectedly. Try the operation again.",
+            ErrorCode::InvalidInput => "A parameter is invalid or not supported. Check the value and try again.",
+            ErrorCode::NotFound => "The requested item no longer exists. Refresh the list and try again.",
+            ErrorCode::ScanNotFound => "The scan result is no longer available. Run the scan again.",
+            ErrorCode::ScanAlreadyStarted => "The scan has already started and can no longer be changed.",
+            ErrorCode::ScanCancelled => "The scan was cancelled and no result was saved. Start the scan again when needed.",
+            ErrorCode::NoLanguageFiles => "No language files were found for this locale. Check the locale and the project folder.",
+            ErrorCode::CorruptJar => "A JAR or zip archive is damaged or incomplete. Re-download the mod or pack.",
+            ErrorCode::InvalidPack => "The file is not a valid resource pack. Check that pack.mcmeta exists and is valid JSON.",
+            ErrorCode::PathNotFound => "The file or folder does not exist. Check the path or choose it again.",
+            ErrorCode::PermissionDenied => "No permission to write here. Choose another folder or adjust its permissions.",
+            ErrorCode::DiskFull => "Not enough disk space. Free some space or choose another drive.",
+            ErrorCode::FileInUse => "A file is in use by another program. Close that program and try again.",
+            ErrorCode::AlreadyExists => "The target already exists. Choose another path or remove the existing one.",
+            ErrorCode::InstanceRunning => "Minecraft is running from this instance. Close the game, or force the operation.",
+            ErrorCode::IntegrityCheckFailed => "The written pack did not match what was planned. Please report this issue.",
+            ErrorCode::UnsupportedVersion => "This Minecraft version is not supported. Choose another target version.",
+            ErrorCode::DatabaseLocked => "The database is busy with another operation. Try again in a moment.",
+            ErrorCode::DatabaseError => "The local database could not be read or written. Restore it from a backup if the problem persists.",
+            ErrorCode::BackendUnavailable => "The server could not be reached or returned an error. Check the network and server settings.",
+            ErrorCode::DownloadFailed => "A download failed or was incomplete. Try downloading again.",
+            ErrorCode::RemoteAuthFailed => "Could not log in to the remote server. Check the stored credentials and host key.",
+            ErrorCode::SyncSkipped => "Sync is paused by the schedule (quiet hours or metered network). Force a sync to run it now.",
+            ErrorCode::InvalidBundle => "The file is not a valid project bundle or backup. Choose another file.",
+            ErrorCode::KeychainError => "The system keychain could not be accessed. Unlock it and store the credentials again.",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<ErrorCode> {
+        ErrorCode::ALL.into_iter().find(|candidate| candidate.code() == code)
+    }
+}
+
+// 未打标记的错误信息按片段归类，靠前的优先；匹配不区分大小写
+const RULES: &[(&str, ErrorCode)] = &[
+    ("appears to be running", ErrorCode::InstanceRunning),
+    ("failed the integrity check", ErrorCode::IntegrityCheckFailed),
+    ("task failed", ErrorCode::TaskFailed),
+    ("scan result not found", ErrorCode::ScanNotFound),
+    ("scan not found in queue", ErrorCode::ScanNotFound),
+    ("has already started", ErrorCode::ScanAlreadyStarted),
+    ("sync skipped", ErrorCode::SyncSkipped),
+    ("failed to read archive", ErrorCode::CorruptJar),
+    ("pack.mcmeta", ErrorCode::InvalidPack),
+    ("project bundle", ErrorCode::InvalidBundle),
+    ("not a backup", ErrorCode::InvalidBundle),
+    ("invalid backup", ErrorCode::InvalidBundle),
+    ("unsupported minecraft version", ErrorCode::UnsupportedVersion),
+    ("database is locked", ErrorCode::DatabaseLocked),
+    ("database", ErrorCode::DatabaseError),
+    ("metadata cache", ErrorCode::DatabaseError),
+    ("keychain", ErrorCode::KeychainError),
+    ("no password stored", ErrorCode::RemoteAuthFailed),
+    ("ssh handshake failed", ErrorCode::RemoteAuthFailed),
+    ("host key", ErrorCode::RemoteAuthFailed),
+    ("authentication", ErrorCode::RemoteAuthFailed),
+    ("backend api", ErrorCode::BackendUnavailable),
+    ("failed to download", ErrorCode::DownloadFailed),
+    ("rejected ", ErrorCode::DownloadFailed),
+    ("invalid checksum", ErrorCode::DownloadFailed),
+    ("not enough disk space", ErrorCode::DiskFull),
+    ("no space left", ErrorCode::DiskFull),
+    ("os error 28)", ErrorCode::DiskFull),
+    ("os error 112)", ErrorCode::DiskFull),
+    ("in use by another program", ErrorCode::FileInUse),
+    ("os error 32)", ErrorCode::FileInUse),
+    ("os error 33)", ErrorCode::FileInUse),
+    ("os error 13)", ErrorCode::PermissionDenied),
+    ("os error 5)", ErrorCode::PermissionDenied),
+    ("no write permission", ErrorCode::PermissionDenied),
+    ("permission denied", ErrorCode::PermissionDenied),
+    ("access is denied", ErrorCode::PermissionDenied),
+    ("already exists", ErrorCode::AlreadyExists),
+    ("language files found", ErrorCode::NoLanguageFiles),
+    ("translations found", ErrorCode::NoLanguageFiles),
+    ("language file for namespace", ErrorCode::NoLanguageFiles),
+    ("path not found", ErrorCode::PathNotFound),
+    ("does not exist", ErrorCode::PathNotFound),
+    ("no such file", ErrorCode::PathNotFound),
+    ("cannot find the", ErrorCode::PathNotFound),
+    ("os error 2)", ErrorCode::PathNotFound),
+    ("not found", ErrorCode::NotFound),
+    ("invalid ", ErrorCode::InvalidInput),
+    ("unknown ", ErrorCode::InvalidInput),
+    ("unsupported ", ErrorCode::InvalidInput),
+];
+
+// 在错误来源处明确指定代码，避免依赖信息文本归类
+pub fn tagged(code: ErrorCode, message: impl fmt::Display) -> String {
+    format!("[{}] {}", code.code(), message)
+}
+
+// 归类错误信息，返回代码和去掉标记后的信息
+pub fn classify(message: &str) -> (ErrorCode, &str) {
+    let explicit = message
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .and_then(|(code, rest)| ErrorCode::from_code(code).map(|code| (code, rest)));
+    if let Some(explicit) = explicit {
+        return explicit;
+    }
+
+    let lowercase = message.to_lowercase();
+    let code = RULES
+        .iter()
+        .find(|(fragment, _)| lowercase.contains(fragment))
+        .map_or(ErrorCode::Unknown, |(_, code)| *code);
+    (code, message)
+}
+
+// 命令返回给前端的错误：稳定代码、原始信息和可供程序判断的处理建议
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandError {
+    pub code: &'static str,
+    pub message: String,
+    pub hint: &'static str,
+    pub action: &'static str,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        let error = Self {
+            code: code.code(),
+            message: message.into(),
+            hint: code.hint(),
+            action: code.action(),
+        };
+        // 日志与界面显示相同的代码，便于按截图和日志对照
+        eprintln!("{}", error);
+        error
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        let (code, message) = classify(&message);
+        CommandError::new(code, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::from(message.to_string())
+    }
+}
+
+// 错误代码说明，供前端和支持人员查阅
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorCodeInfo {
+    pub code: &'static str,
+    pub hint: &'static str,
+    pub action: &'static str,
+}
+
+pub fn error_codes() -> Vec<ErrorCodeInfo> {
+    ErrorCode::ALL
+        .into_iter()
+        .map(|code| ErrorCodeInfo {
+            code: code.code(),
+            hint: code.hint(),
+            action: code.action(),
+        })
+        .collect()
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::pack_import::download::DownloadProgress;
+use crate::progress::ScanProgress;
+use crate::scan_delta::ScanDelta;
+use crate::scan_queue::ScanQueueState;
+use crate::sync_scheduler::SyncRunReport;
+use crate::theme::ThemeState;
+use crate::ui_catalog::UiCatalogChanged;
+use crate::workspace::WorkspaceEvent;
+
+// 后端向前端发送的全部事件。新增事件时同时加入 ALL，前端通过 list_event_types 生成绑定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    ScanProgress,
+    ScanDelta,
+    WorkspaceChanged,
+    PackDownloadProgress,
+    UiCatalogChanged,
+    ThemeChanged,
+    SyncCompleted,
+    ScanQueueChanged,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 8] = [
+        EventKind::ScanProgress,
+        EventKind::ScanDelta,
+        EventKind::WorkspaceChanged,
+        EventKind::PackDownloadProgress,
+        EventKind::UiCatalogChanged,
+        EventKind::ThemeChanged,
+        EventKind::SyncCompleted,
+        EventKind::ScanQueueChanged,
+    ];
+
+    // 前端 listen 使用的事件名
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::ScanProgress => "scan-progress",
+            EventKind::ScanDelta => "scan-delta",
+            EventKind::WorkspaceChanged => "workspace-changed",
+            EventKind::PackDownloadProgress => "pack-download-progress",
+            EventKind::UiCatalogChanged => "ui-catalog-changed",
+            EventKind::ThemeChanged => "theme-changed",
+            EventKind::SyncCompleted => "sync-completed",
+            EventKind::ScanQueueChanged => "scan-queue-changed",
+        }
+    }
+
+    // 负载结构的版本；删除字段或改变字段含义时加一，只新增字段不需要
+    pub fn version(self) -> u32 {
+        match self {
+            EventKind::ScanProgress => 1,
+            EventKind::ScanDelta => 1,
+            EventKind::WorkspaceChanged => 1,
+            EventKind::PackDownloadProgress => 1,
+            EventKind::UiCatalogChanged => 1,
+            EventKind::ThemeChanged => 1,
+            EventKind::SyncCompleted => 1,
+            EventKind::ScanQueueChanged => 1,
+        }
+    }
+
+    fn payload(self) -> &'static str {
+        match self {
+            EventKind::ScanProgress => "ScanProgress",
+            EventKind::ScanDelta => "ScanDelta",
+            EventKind::WorkspaceChanged => "WorkspaceEvent",
+            EventKind::PackDownloadProgress => "DownloadProgress",
+            EventKind::UiCatalogChanged => "UiCatalogChanged",
+            EventKind::ThemeChanged => "ThemeState",
+            EventKind::SyncCompleted => "SyncRunReport",
+            EventKind::ScanQueueChanged => "ScanQueueState",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            EventKind::ScanProgress => "Progress of a running scan, throttled",
+            EventKind::ScanDelta => "Changes compared with the previous scan of the same path",
+            EventKind::WorkspaceChanged => "A workspace root was added, updated or removed",
+            EventKind::PackDownloadProgress => "Progress of a pack archive download",
+            EventKind::UiCatalogChanged => "UI string catalog files changed (development builds only)",
+            EventKind::ThemeChanged => "Configured, system or high-contrast theme changed",
+            EventKind::SyncCompleted => "A manual or scheduled sync run finished",
+            EventKind::ScanQueueChanged => "A scan was queued, started, finished, cancelled or reprioritized",
+        }
+    }
+}
+
+// 带类型的事件，负载与事件名一一对应
+pub enum AppEvent<'a> {
+    ScanProgress(&'a ScanProgress),
+    ScanDelta(&'a ScanDelta),
+    WorkspaceChanged(&'a WorkspaceEvent),
+    PackDownloadProgress(&'a DownloadProgress),
+    UiCatalogChanged(&'a UiCatalogChanged),
+    ThemeChanged(&'a ThemeState),
+    SyncCompleted(&'a SyncRunReport),
+    ScanQueueChanged(&'a ScanQueueState),
+}
+
+impl AppEvent<'_> {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            AppEvent::ScanProgress(_) => EventKind::ScanProgress,
+            AppEvent::ScanDelta(_) => EventKind::ScanDelta,
+            AppEvent::WorkspaceChanged(_) => EventKind::WorkspaceChanged,
+            AppEvent::PackDownloadProgress(_) => EventKind::PackDownloadProgress,
+            AppEvent::UiCatalogChanged(_) => EventKind::UiCatalogChanged,
+            AppEvent::ThemeChanged(_) => EventKind::ThemeChanged,
+            AppEvent::SyncCompleted(_) => EventKind::SyncCompleted,
+            AppEvent::ScanQueueChanged(_) => EventKind::ScanQueueChanged,
+        }
+    }
+}
+
+// 唯一的发送入口；前端未监听时发送失败可以忽略
+pub fn emit(app: &AppHandle, event: AppEvent<'_>) {
+    let name = event.kind().name();
+    let _ = match event {
+        AppEvent::ScanProgress(payload) => app.emit(name, payload),
+        AppEvent::ScanDelta(payload) => app.emit(name, payload),
+        AppEvent::WorkspaceChanged(payload) => app.emit(name, payload),
+        AppEvent::PackDownloadProgress(payload) => app.emit(name, payload),
+        AppEvent::UiCatalogChanged(payload) => app.emit(name, payload),
+        AppEvent::ThemeChanged(payload) => app.emit(name, payload),
+        AppEvent::SyncCompleted(payload) => app.emit(name, payload),
+        AppEvent::ScanQueueChanged(payload) => app.emit(name, payload),
+    };
+}
+
+// list_event_types 返回的事件说明
+#[derive(Debug, Clone, Serialize)]
+pub struct EventType {
+    pub name: String,
+    pub version: u32,
+    pub payload: String,
+    pub description: String,
+}
+
+pub fn event_types() -> Vec<EventType> {
+    EventKind::ALL
+        .iter()
+        .map(|kind| EventType {
+            name: kind.name().to_string(),
+            version: kind.version(),
+            payload: kind.payload().to_string(),
+            description: kind.description().to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::lang;
+use crate::metadata_cache::MetadataCache;
+use crate::mod_filter::{self, ModFilter};
+use crate::pack_check::{self, InstalledNamespace};
+use crate::pack_integrity::{self, PackPlan};
+use crate::pack_meta;
+use crate::preflight::{self, WriteOutcome};
+use crate::project_settings;
+use crate::script_hooks::{ScriptHooks, ON_BEFORE_EXPORT};
+
+// 资源包与数据包的默认 pack_format（1.20.1）
+const DEFAULT_RESOURCE_PACK_FORMAT: u32 = 15;
+const DEFAULT_DATA_PACK_FORMAT: u32 = 15;
+
+// 导出集合
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportSet {
+    // 客户端资源包：assets/<namespace>/lang/<locale>.json，包含客户端与通用 MOD
+    ClientResourcePack,
+    // 服务端语言注入：以数据包形式提供 data/<namespace>/lang/<locale>.json，只包含仅服务端 MOD，
+    // 供服务端翻译 API 类 MOD 加载
+    ServerLangInjection,
+}
+
+// 导出配置档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProfile {
+    pub name: String,
+    pub description: String,
+    pub sets: Vec<ExportSet>,
+}
+
+// 导出请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub scan_id: String,
+    pub project_path: String,
+    pub target_locale: String,
+    pub profile: String,
+    pub output_dir: String,
+    #[serde(default)]
+    pub force: bool,
+    // 只预检并返回将要写入的文件，不写入
+    #[serde(default)]
+    pub dry_run: bool,
+    // 按语言代码给出的包描述；客户端资源包通过 translate 组件按游戏语言显示
+    #[serde(default)]
+    pub descriptions: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportOutput {
+    pub set: ExportSet,
+    pub path: String,
+    pub namespaces: Vec<String>,
+    pub key_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub profile: String,
+    pub target_locale: String,
+    pub outputs: Vec<ExportOutput>,
+}
+
+// 内置的导出配置档
+pub fn builtin_profiles() -> Vec<ExportProfile> {
+    let profile = |name: &str, description: &str, sets: Vec<ExportSet>| ExportProfile {
+        name: name.to_string(),
+        description: description.to_string(),
+        sets,
+    };
+
+    vec![
+        profile(
+            "full",
+            "Client resource pack and server lang injection",
+            vec![ExportSet::ClientResourcePack, ExportSet::ServerLangInjection],
+        ),
+        profile(
+            "client",
+            "Client resource pack only",
+            vec![ExportSet::ClientResourcePack],
+        ),
+        profile(
+            "server",
+            "Server lang injection for server-only mods",
+            vec![ExportSet::ServerLangInjection],
+        ),
+    ]
+}
+
+pub fn find_profile(name: &str) -> Result<ExportProfile, String> {
+    builtin_profiles()
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown export profile: {}", name))
+}
+
+// 按 MOD 声明的运行环境把项目译文拆分到各导出集合并写入输出目录
+pub fn export_translations(
+    request: &ExportRequest,
+    profile: &ExportProfile,
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> Result<WriteOutcome<ExportResult>, String> {
+    let project_path = Path::new(&request.project_path);
+    let output_dir = Path::new(&request.output_dir);
+    let target_locale = request.target_locale.as_str();
+    let settings = project_settings::load_project_settings(&request.project_path);
+
+    // 被排除的 MOD 既不参与运行环境判断，其命名空间也不导出
+    let (jar_paths, excluded_jars) =
+        ModFilter::new(settings.excluded_mods).partition_jar_paths(jar_paths, limits, cache);
+    let excluded_namespaces = mod_filter::excluded_namespaces(&jar_paths, &excluded_jars, limits);
+    let mut translations = lang::load_locale_entries(project_path, target_locale);
+    // 译文原有的转义写法，未被脚本钩子修改的条目原样导出
+    let raw_entries = lang::load_locale_raw_entries(project_path, target_locale);
+    translations.retain(|namespace, _| !excluded_namespaces.contains(namespace));
+    if translations.is_empty() {
+        return Err(format!("No {} translations found in {}", target_locale, project_path.display()));
+    }
+    if let Some(hooks) = ScriptHooks::for_project(&request.project_path)? {
+        for (namespace, entries) in translations.iter_mut() {
+            hooks.apply(ON_BEFORE_EXPORT, entries, namespace, target_locale, &request.project_path)?;
+        }
+    }
+
+    let installed = pack_check::load_installed_namespaces(jar_paths, limits, cache, &settings.namespace_owners);
+    let mut outputs = Vec::new();
+    let mut planned = Vec::new();
+
+    for set in &profile.sets {
+        let selected: BTreeMap<&String, &HashMap<String, String>> = translations
+            .iter()
+            .filter(|(namespace, _)| belongs_to_set(*set, installed.get(*namespace)))
+            .collect();
+        if selected.is_empty() {
+            continue;
+        }
+
+        let (pack_name, root, pack_format, default_description) = match set {
+            ExportSet::ClientResourcePack => (
+                format!("client_resourcepack_{}", target_locale),
+                "assets",
+                DEFAULT_RESOURCE_PACK_FORMAT,
+                format!("{} translations (client)", target_locale),
+            ),
+            ExportSet::ServerLangInjection => (
+                format!("server_lang_{}", target_locale),
+                "data",
+                DEFAULT_DATA_PACK_FORMAT,
+                format!("{} translations (server)", target_locale),
+            ),
+        };
+        let pack_dir = output_dir.join(&pack_name);
+        let description = pack_meta::description_for_locale(&request.descriptions, target_locale)
+            .unwrap_or(&default_description);
+
+        // 先生成包内全部文件，试运行时只预检不写入
+        let mut files = Vec::new();
+
+        // 数据包描述在服务端显示，不会按客户端语言解析，直接使用目标语言的文本
+        let description_component = if *set == ExportSet::ClientResourcePack && !request.descriptions.is_empty() {
+            let key = pack_meta::description_key(&pack_name);
+            files.extend(description_translation_files(&pack_dir, &key, &request.descriptions)?);
+            pack_meta::localized_description(&key, description)
+        } else {
+            serde_json::Value::String(description.to_string())
+        };
+
+        files.push((pack_dir.join("pack.mcmeta"), pack_mcmeta_content(pack_format, &description_component)?));
+
+        let mut key_count = 0;
+        let mut plan = PackPlan {
+            pack_format,
+            lang_files: BTreeMap::new(),
+        };
+        for (namespace, entries) in &selected {
+            let content = lang::json_lang_content(entries, raw_entries.get(*namespace));
+            let lang_path = pack_dir.join(root).join(namespace).join("lang").join(format!("{}.json", target_locale));
+            files.push((lang_path, content));
+            key_count += entries.len() as u32;
+            plan.lang_files.insert(format!("{}/{}/lang/{}.json", root, namespace, target_locale), entries.len());
+        }
+
+        if request.dry_run {
+            planned.extend(files.into_iter().map(|(path, content)| (path, content.len() as u64)));
+        } else {
+            for (path, content) in &files {
+                write_file(path, content)?;
+            }
+
+            // 重新读取生成的包并与计划比对，避免发布损坏的包
+            let problems = pack_integrity::verify_pack(&pack_dir, &plan)?;
+            if !problems.is_empty() {
+                return Err(format!(
+                    "Exported pack {} failed the integrity check:\n{}",
+                    pack_dir.display(),
+                    problems.join("\n")
+                ));
+            }
+        }
+
+        outputs.push(ExportOutput {
+            set: *set,
+            path: pack_dir.to_string_lossy().to_string(),
+            namespaces: selected.keys().map(|ns| ns.to_string()).collect(),
+            key_count,
+        });
+    }
+
+    if request.dry_run {
+        let mut report = preflight::check(planned, false);
+        preflight::check_instance(&mut report, output_dir, request.force);
+        return Ok(WriteOutcome::DryRun(report));
+    }
+
+    Ok(WriteOutcome::Applied(ExportResult {
+        profile: profile.name.clone(),
+        target_locale: target_locale.to_string(),
+        outputs,
+    }))
+}
+
+// 未找到对应 MOD 的命名空间按通用处理，放入客户端资源包
+fn belongs_to_set(set: ExportSet, installed: Option<&InstalledNamespace>) -> bool {
+    let environment = installed.map_or("universal", |ns| ns.environment.as_str());
+    match set {
+        ExportSet::ClientResourcePack => environment != "server",
+        ExportSet::ServerLangInjection => environment == "server",
+    }
+}
+
+// 各语言的包描述文件：assets/<DESCRIPTION_NAMESPACE>/lang/<locale>.json
+fn description_translation_files(
+    pack_dir: &Path,
+    key: &str,
+    descriptions: &BTreeMap<String, String>,
+) -> Result<Vec<(PathBuf, String)>, String> {
+    let lang_dir = pack_dir.join("assets").join(pack_meta::DESCRIPTION_NAMESPACE).join("lang");
+    descriptions
+        .iter()
+        .map(|(locale, description)| {
+            let content = serde_json::to_string_pretty(&serde_json::json!({ key: description }))
+                .map_err(|e| e.to_string())?;
+            Ok((lang_dir.join(format!("{}.json", locale)), content))
+        })
+        .collect()
+}
+
+fn pack_mcmeta_content(pack_format: u32, description: &serde_json::Value) -> Result<String, String> {
+    let mcmeta = serde_json::json!({
+        "pack": {
+            "pack_format": pack_format,
+            "description": description,
+        }
+    });
+    serde_json::to_string_pretty(&mcmeta).map_err(|e| e.to_string())
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,323 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::pack_module::PackModuleResource;
+use crate::scanners::{ContentScanner, LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+
+// 每个扩展一个目录，目录中的 extension.json 描述扩展
+const MANIFEST_FILE: &str = "extension.json";
+
+// 扩展进程的最长运行时间
+const SCAN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+// 等待扩展进程退出时的轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// extension.json。扩展是独立的可执行程序或脚本：请求以一行 JSON 写入 stdin，
+// 结果以 JSON 从 stdout 读取，stderr 作为错误信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtensionManifest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    // 启动命令，如 ["python3", "main.py"]；相对路径相对于扩展目录
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub scanners: Vec<ExtensionScannerSpec>,
+    #[serde(default)]
+    pub commands: Vec<ExtensionCommandSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtensionScannerSpec {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub preset: ScanPreset,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtensionCommandSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+// 扩展信息，供前端展示和开关
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtensionInfo {
+    pub path: String,
+    pub enabled: bool,
+    pub manifest: Option<ExtensionManifest>,
+    // extension.json 无法读取时的错误
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExtensionRequest<'a> {
+    Scan {
+        scanner: &'a str,
+        project_path: &'a str,
+        preset: ScanPreset,
+    },
+    Command {
+        command: &'a str,
+        args: &'a Value,
+    },
+}
+
+// 扫描请求的返回内容
+#[derive(Debug, Default, Deserialize)]
+struct ExtensionScanResult {
+    #[serde(default)]
+    language_resources: Vec<ExtensionLangFile>,
+    #[serde(default)]
+    pack_modules: Vec<PackModuleResource>,
+    #[serde(default)]
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionLangFile {
+    namespace: String,
+    locale: String,
+    source_path: String,
+    key_count: u32,
+}
+
+pub fn extensions_dir(config: &AppConfig) -> PathBuf {
+    config.get_data_dir().join("extensions")
+}
+
+// 扩展目录下的全部扩展；只有用户启用的扩展才会被运行
+pub fn list_extensions(config: &AppConfig) -> Vec<ExtensionInfo> {
+    let Ok(entries) = fs::read_dir(extensions_dir(config)) else {
+        return Vec::new();
+    };
+
+    let mut extensions: Vec<ExtensionInfo> = entries
+        .flatten()
+        .filter(|entry| entry.path().join(MANIFEST_FILE).is_file())
+        .map(|entry| {
+            let path = entry.path();
+            let (manifest, error) = match read_manifest(&path) {
+                Ok(manifest) => (Some(manifest), None),
+                Err(e) => (None, Some(e)),
+            };
+            let enabled = manifest
+                .as_ref()
+                .is_some_and(|manifest| config.enabled_extensions.contains(&manifest.id));
+            ExtensionInfo {
+                path: path.to_string_lossy().to_string(),
+                enabled,
+                manifest,
+                error,
+            }
+        })
+        .collect();
+    extensions.sort_by(|a, b| a.path.cmp(&b.path));
+    extensions
+}
+
+fn read_manifest(dir: &Path) -> Result<ExtensionManifest, String> {
+    let content = fs::read_to_string(dir.join(MANIFEST_FILE)).map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))?;
+    let manifest: ExtensionManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+    if manifest.id.is_empty() || manifest.command.is_empty() {
+        return Err(format!("{} must declare an id and a command", MANIFEST_FILE));
+    }
+    Ok(manifest)
+}
+
+// 已启用的扩展及其目录
+fn enabled_extensions(config: &AppConfig) -> Vec<(PathBuf, ExtensionManifest)> {
+    list_extensions(config)
+        .into_iter()
+        .filter(|info| info.enabled)
+        .filter_map(|info| Some((PathBuf::from(info.path), info.manifest?)))
+        .collect()
+}
+
+pub fn find_extension(config: &AppConfig, id: &str) -> Result<ExtensionManifest, String> {
+    list_extensions(config)
+        .into_iter()
+        .filter_map(|info| info.manifest)
+        .find(|manifest| manifest.id == id)
+        .ok_or_else(|| format!("Extension not found: {}", id))
+}
+
+// 已启用扩展声明的内容扫描器；扫描器 id 为 "<扩展 id>.<扫描器 id>"
+pub fn scanners(config: &AppConfig) -> Vec<Box<dyn ContentScanner>> {
+    let mut scanners: Vec<Box<dyn ContentScanner>> = Vec::new();
+    for (dir, manifest) in enabled_extensions(config) {
+        for spec in &manifest.scanners {
+            scanners.push(Box::new(ExtensionScanner {
+                id: format!("{}.{}", manifest.id, spec.id),
+                name: spec.name.clone(),
+                phase: format!("scanning_extension_{}", spec.id),
+                scanner: spec.id.clone(),
+                preset: spec.preset,
+                dir: dir.clone(),
+                command: manifest.command.clone(),
+            }));
+        }
+    }
+    scanners
+}
+
+// 调用已启用扩展声明的命令，返回扩展输出的 JSON
+pub fn invoke_command(config: &AppConfig, extension_id: &str, command: &str, args: &Value) -> Result<Value, String> {
+    let (dir, manifest) = enabled_extensions(config)
+        .into_iter()
+        .find(|(_, manifest)| manifest.id == extension_id)
+        .ok_or_else(|| format!("Extension is not installed or not enabled: {}", extension_id))?;
+    if !manifest.commands.iter().any(|spec| spec.name == command) {
+        return Err(format!("Extension {} has no command {}", extension_id, command));
+    }
+
+    let output = run_extension(&dir, &manifest.command, &ExtensionRequest::Command { command, args }, COMMAND_TIMEOUT)?;
+    serde_json::from_slice(&output).map_err(|e| format!("Extension {} returned invalid JSON: {}", extension_id, e))
+}
+
+struct ExtensionScanner {
+    id: String,
+    name: String,
+    phase: String,
+    scanner: String,
+    preset: ScanPreset,
+    dir: PathBuf,
+    command: Vec<String>,
+}
+
+impl ContentScanner for ExtensionScanner {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn phase(&self) -> &str {
+        &self.phase
+    }
+
+    fn preset(&self) -> ScanPreset {
+        self.preset
+    }
+
+    // 扩展失败只记录为该扫描器的错误，不影响其他扫描器
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let request = ExtensionRequest::Scan {
+            scanner: &self.scanner,
+            project_path: &ctx.project_path.to_string_lossy(),
+            preset: ctx.preset,
+        };
+        let result = run_extension(&self.dir, &self.command, &request, SCAN_TIMEOUT).and_then(|stdout| {
+            serde_json::from_slice::<ExtensionScanResult>(&stdout).map_err(|e| format!("invalid JSON: {}", e))
+        });
+
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                output.errors.push(format!("Extension scanner {} failed: {}", self.id, e));
+                return;
+            }
+        };
+
+        let source_type = output.interner.intern(&self.id);
+        for file in result.language_resources {
+            ctx.progress.lang_file_done(&file.namespace, &file.source_path, file.key_count);
+            output.language_resources.push(LanguageResource {
+                namespace: output.interner.intern(&file.namespace),
+                locale: output.interner.intern(&file.locale),
+                source_path: file.source_path,
+                source_type: source_type.clone(),
+                key_count: file.key_count,
+                duplicate_keys: 0,
+                placeholders: BTreeMap::new(),
+                formatting: BTreeMap::new(),
+                key_categories: BTreeMap::new(),
+                parse_status: ParseStatus::Parsed,
+                parse_error: None,
+                // 扫描结束后按优先级规则计算
+                priority: 0,
+                bundled_pack: None,
+            });
+        }
+        output.pack_modules.extend(result.pack_modules);
+        output.warnings.extend(result.warnings.into_iter().map(|warning| format!("{}: {}", self.id, warning)));
+    }
+}
+
+// 在扩展目录中启动扩展进程，写入请求并读取 stdout；超时或非零退出码视为失败
+fn run_extension(dir: &Path, command: &[String], request: &ExtensionRequest<'_>, timeout: Duration) -> Result<Vec<u8>, String> {
+    let (program, args) = command.split_first().ok_or("Extension has no command")?;
+    let program = if program.contains('/') || program.contains('\\') {
+        dir.join(program).to_string_lossy().to_string()
+    } else {
+        program.clone()
+    };
+
+    let mut child = Command::new(&program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+
+    let mut line = serde_json::to_vec(request).map_err(|e| e.to_string())?;
+    line.push(b'\n');
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(&line);
+    }
+
+    // 在独立线程中读取输出，避免扩展写满管道缓冲区后阻塞
+    let mut stdout = child.stdout.take().ok_or("Extension stdout unavailable")?;
+    let mut stderr = child.stderr.take().ok_or("Extension stderr unavailable")?;
+    let stdout_reader = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stdout.read_to_end(&mut buffer);
+        buffer
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut buffer = String::new();
+        let _ = stderr.read_to_string(&mut buffer);
+        buffer
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) => break status,
+            None if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Extension timed out after {} s", timeout.as_secs()));
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    };
+
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(format!("Extension exited with {}: {}", status, stderr.trim()));
+    }
+    Ok(stdout)
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::scanners::LanguageResource;
+
+const SECTION: char = '§';
+
+// 颜色代码 0-9a-f、格式代码 k-o 和重置代码 r，不区分大小写
+const VALID_CODES: &str = "0123456789abcdefklmnor";
+
+// 一个键中的 § 格式代码
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyFormatting {
+    // 按出现顺序的代码（如 §6、§l、§x§f§f§0§0§0§0），翻译时需要保留
+    pub codes: Vec<String>,
+    // 格式错误的序列
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+}
+
+// 扫描结果中的格式代码统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormattingStats {
+    pub keys_with_codes: u32,
+    pub malformed_keys: u32,
+    // 每种代码（小写，RGB 颜色统一记为 §x）出现的次数
+    pub code_counts: BTreeMap<String, u32>,
+}
+
+// 分析文本中的 § 序列；不含 § 时返回 None。§x 之后须跟 6 组 §<十六进制>（1.16 起的 RGB 颜色写法）
+pub fn analyze(text: &str) -> Option<KeyFormatting> {
+    if !text.contains(SECTION) {
+        return None;
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut formatting = KeyFormatting { codes: Vec::new(), problems: Vec::new() };
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] != SECTION {
+            index += 1;
+            continue;
+        }
+        let Some(code) = chars.get(index + 1).map(|c| c.to_ascii_lowercase()) else {
+            formatting.problems.push("§ at the end of the text".to_string());
+            break;
+        };
+
+        if code == 'x' {
+            let hex_digits = (0..6)
+                .take_while(|n| {
+                    let at = index + 2 + n * 2;
+                    chars.get(at) == Some(&SECTION) && chars.get(at + 1).is_some_and(|c| c.is_ascii_hexdigit())
+                })
+                .count();
+            let length = 2 + hex_digits * 2;
+            let sequence: String = chars[index..index + length].iter().collect();
+            if hex_digits < 6 {
+                formatting.problems.push(format!("incomplete RGB colour {}", sequence));
+            }
+            formatting.codes.push(sequence);
+            index += length;
+        } else if VALID_CODES.contains(code) {
+            formatting.codes.push(format!("{}{}", SECTION, chars[index + 1]));
+            index += 2;
+        } else {
+            formatting.problems.push(format!("unknown code {}{}", SECTION, chars[index + 1]));
+            index += 2;
+        }
+    }
+
+    Some(formatting)
+}
+
+// 语言文件中含 § 的键及其格式代码
+pub fn inventory(entries: &HashMap<String, String>) -> BTreeMap<String, KeyFormatting> {
+    entries
+        .iter()
+        .filter_map(|(key, value)| analyze(value).map(|formatting| (key.clone(), formatting)))
+        .collect()
+}
+
+pub fn stats(resources: &[LanguageResource]) -> FormattingStats {
+    let mut stats = FormattingStats::default();
+    for formatting in resources.iter().flat_map(|resource| resource.formatting.values()) {
+        stats.keys_with_codes += 1;
+        if !formatting.problems.is_empty() {
+            stats.malformed_keys += 1;
+        }
+        for code in &formatting.codes {
+            let kind: String = code.chars().take(2).collect();
+            *stats.code_counts.entry(kind.to_lowercase()).or_default() += 1;
+        }
+    }
+    stats
+}
+
+// 每个含格式错误的语言文件一条警告
+pub fn malformed_warnings(resources: &[LanguageResource]) -> Vec<String> {
+    resources
+        .iter()
+        .filter_map(|resource| {
+            let mut malformed = resource.formatting.iter().filter(|(_, formatting)| !formatting.problems.is_empty());
+            let (first_key, first) = malformed.next()?;
+            let others = malformed.count();
+            let mut warning =
+                format!("{}: key {} has malformed formatting codes ({})", resource.source_path, first_key, first.problems.join(", "));
+            if others > 0 {
+                warning.push_str(&format!(" and {} more keys", others));
+            }
+            Some(warning)
+        })
+        .collect()
+}
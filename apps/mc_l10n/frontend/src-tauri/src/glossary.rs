@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::AppConfig;
+
+// 术语表条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub source: String,
+    pub target: String,
+    pub locale: String,
+    pub note: Option<String>,
+    pub updated_at: String,
+}
+
+// 本地术语表，保存在数据目录下的 glossary.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Glossary {
+    pub entries: Vec<GlossaryEntry>,
+}
+
+impl Glossary {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let glossary_path = Self::get_glossary_path()?;
+
+        if glossary_path.exists() {
+            let content = fs::read_to_string(&glossary_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let glossary_path = Self::get_glossary_path()?;
+
+        if let Some(parent) = glossary_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&glossary_path, content)?;
+        Ok(())
+    }
+
+    // 新增或更新同一原文与语言的术语
+    pub fn upsert(&mut self, source: &str, target: &str, locale: &str, note: Option<String>) {
+        let updated_at = chrono::Utc::now().to_rfc3339();
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.source == source && e.locale == locale)
+        {
+            entry.target = target.to_string();
+            entry.note = note.or(entry.note.take());
+            entry.updated_at = updated_at;
+            return;
+        }
+
+        self.entries.push(GlossaryEntry {
+            source: source.to_string(),
+            target: target.to_string(),
+            locale: locale.to_string(),
+            note,
+            updated_at,
+        });
+    }
+
+    fn get_glossary_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("glossary.json"))
+    }
+}
@@ -0,0 +1,72 @@
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::io_retry;
+
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+// 流式计算文件的 SHA-1，不把整个 JAR 读入内存；临时性 IO 错误时从头重新计算
+pub fn sha1_file(path: &Path) -> io::Result<String> {
+    io_retry::retry_io(|| sha1_file_once(path))
+}
+
+fn sha1_file_once(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha1::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// 文件的 SHA-1 和 SHA-256 指纹，用于跨实例去重和 Modrinth/CurseForge 按哈希查询
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub sha1: String,
+    pub sha256: String,
+}
+
+// 一次读取同时计算两种摘要
+pub fn fingerprint_file(path: &Path) -> io::Result<Fingerprint> {
+    io_retry::retry_io(|| fingerprint_file_once(path))
+}
+
+fn fingerprint_file_once(path: &Path) -> io::Result<Fingerprint> {
+    fingerprint_reader(&mut File::open(path)?)
+}
+
+// 流式计算任意数据源（如归档中的嵌套 JAR 条目）的指纹
+pub fn fingerprint_reader(reader: &mut impl Read) -> io::Result<Fingerprint> {
+    let mut sha1 = Sha1::new();
+    let mut sha256 = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        sha1.update(&buffer[..read]);
+        sha256.update(&buffer[..read]);
+    }
+
+    Ok(Fingerprint {
+        sha1: format!("{:x}", sha1.finalize()),
+        sha256: format!("{:x}", sha256.finalize()),
+    })
+}
+
+// 计算内存中数据的 SHA-1
+pub fn sha1_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(data))
+}
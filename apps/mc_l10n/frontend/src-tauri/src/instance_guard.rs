@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{self, ErrorCode};
+
+// 用于识别 Minecraft 实例根目录的文件和目录
+const INSTANCE_MARKERS: &[&str] = &["mods", "options.txt", "instance.cfg", "mmc-pack.json"];
+
+// 游戏运行时一直保持打开的文件，Windows 下可用独占打开检测
+#[cfg(windows)]
+const LOCKED_WHILE_RUNNING: &[&str] = &["logs/latest.log"];
+
+// 实例运行状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceStatus {
+    pub instance_path: Option<String>,
+    pub running: bool,
+    // 判断依据（匹配的进程或被占用的文件）
+    pub evidence: Vec<String>,
+}
+
+// 检测路径所属的实例是否正在运行；路径不在任何实例中时视为未运行
+pub fn detect_running_instance(path: &Path) -> InstanceStatus {
+    let Some(instance) = find_instance_root(path) else {
+        return InstanceStatus {
+            instance_path: None,
+            running: false,
+            evidence: Vec::new(),
+        };
+    };
+
+    let mut evidence = find_instance_processes(&instance);
+    evidence.extend(find_locked_files(&instance));
+
+    InstanceStatus {
+        instance_path: Some(instance.to_string_lossy().to_string()),
+        running: !evidence.is_empty(),
+        evidence,
+    }
+}
+
+// 写入实例前调用：实例正在运行时拒绝操作，除非调用方明确要求强制执行
+pub fn ensure_not_running(path: &Path, force: bool) -> Result<(), String> {
+    if force {
+        return Ok(());
+    }
+
+    let status = detect_running_instance(path);
+    if status.running {
+        return Err(errors::tagged(
+            ErrorCode::InstanceRunning,
+            format!(
+                "Minecraft instance {} appears to be running ({}). Close the game before writing to it.",
+                status.instance_path.unwrap_or_default(),
+                status.evidence.join("; ")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// 从路径向上查找包含实例特征文件的目录
+fn find_instance_root(path: &Path) -> Option<PathBuf> {
+    // 写入目标可能尚不存在，从最近的已存在目录开始查找
+    let existing = path.ancestors().find(|dir| dir.exists())?;
+    let path = fs::canonicalize(existing).ok()?;
+
+    path.ancestors()
+        .find(|dir| INSTANCE_MARKERS.iter().any(|marker| dir.join(marker).exists()))
+        .map(Path::to_path_buf)
+}
+
+// 查找工作目录或 --gameDir 参数指向该实例的 Java 进程
+#[cfg(target_os = "linux")]
+fn find_instance_processes(instance: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_string_lossy().to_string();
+            let cmdline = fs::read(entry.path().join("cmdline")).ok()?;
+            let args: Vec<String> = cmdline
+                .split(|b| *b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).to_string())
+                .collect();
+            let cwd = fs::read_link(entry.path().join("cwd")).ok();
+
+            is_game_process(&args, cwd.as_deref(), instance).then(|| format!("process {}", pid))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn find_instance_processes(instance: &Path) -> Vec<String> {
+    let Ok(output) = std::process::Command::new("ps").args(["-axww", "-o", "pid=,command="]).output() else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.trim().split_once(' ')?;
+            let args: Vec<String> = command.split_whitespace().map(|s| s.to_string()).collect();
+            is_game_process(&args, None, instance).then(|| format!("process {}", pid))
+        })
+        .collect()
+}
+
+// Windows 下没有不依赖额外库的进程命令行读取方式，依靠文件占用检测
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn find_instance_processes(_instance: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_game_process(args: &[String], cwd: Option<&Path>, instance: &Path) -> bool {
+    let is_java = args.first().is_some_and(|program| {
+        Path::new(program)
+            .file_stem()
+            .is_some_and(|name| name.to_string_lossy().to_lowercase().starts_with("java"))
+    });
+    if !is_java {
+        return false;
+    }
+
+    // 相对的 --gameDir 以进程工作目录为基准
+    let game_dir = args
+        .iter()
+        .position(|arg| arg == "--gameDir")
+        .and_then(|index| args.get(index + 1))
+        .map(|dir| cwd.map_or_else(|| PathBuf::from(dir), |cwd| cwd.join(dir)));
+    let candidates = [game_dir, cwd.map(Path::to_path_buf)];
+
+    candidates
+        .iter()
+        .flatten()
+        .filter_map(|dir| fs::canonicalize(dir).ok())
+        .any(|dir| dir.starts_with(instance))
+}
+
+// 游戏进程持有的文件在 Windows 下无法以独占方式打开
+#[cfg(windows)]
+fn find_locked_files(instance: &Path) -> Vec<String> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    LOCKED_WHILE_RUNNING
+        .iter()
+        .map(|relative| instance.join(relative))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .share_mode(0)
+                .open(path)
+                .is_err()
+        })
+        .map(|path| format!("{} is in use", path.display()))
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn find_locked_files(_instance: &Path) -> Vec<String> {
+    Vec::new()
+}
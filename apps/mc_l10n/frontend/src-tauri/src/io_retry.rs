@@ -0,0 +1,62 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+// 临时性 IO 错误的最大尝试次数（含第一次）
+const MAX_ATTEMPTS: u32 = 4;
+
+// 第一次重试前的等待时间，之后每次翻倍
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+// 网络驱动器（SMB/NAS）上连接抖动时常见的错误，重试通常可以恢复
+pub fn is_transient(error: &io::Error) -> bool {
+    if matches!(
+        error.kind(),
+        io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+    ) {
+        return true;
+    }
+
+    match error.raw_os_error() {
+        // ERROR_UNEXP_NET_ERR、ERROR_NETNAME_DELETED、ERROR_SEM_TIMEOUT、ERROR_NETWORK_BUSY
+        #[cfg(windows)]
+        Some(code) => matches!(code, 54 | 59 | 64 | 121),
+        // EIO、EHOSTDOWN、ESTALE
+        #[cfg(target_os = "linux")]
+        Some(code) => matches!(code, 5 | 112 | 116),
+        #[cfg(target_os = "macos")]
+        Some(code) => matches!(code, 5 | 64 | 70),
+        _ => false,
+    }
+}
+
+// 执行 op，遇到 transient 判定为临时性的错误时按指数退避重试
+pub fn retry<T, E>(mut op: impl FnMut() -> Result<T, E>, transient: impl Fn(&E) -> bool) -> Result<T, E> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < MAX_ATTEMPTS && transient(&e) => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+pub fn retry_io<T>(op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    retry(op, is_transient)
+}
+
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    retry_io(|| fs::read_to_string(path))
+}
@@ -0,0 +1,24 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use std::io::Write;
+use tauri::ipc::Response;
+
+// 超过该大小的 JSON 压缩后传输；较小的结果压缩收益不明显
+const COMPRESSION_THRESHOLD: usize = 256 * 1024;
+
+// 把结果编码为二进制 IPC 响应，绕过 Tauri 对大字符串的 JSON 转义和 webview 端解析。
+// 响应体为 UTF-8 JSON 或 gzip 压缩后的 JSON，前端按 gzip 魔数 (1f 8b) 区分
+pub fn encode_payload<T: Serialize>(value: &T) -> Result<Response, String> {
+    let json = serde_json::to_vec(value).map_err(|e| format!("Failed to serialize result: {}", e))?;
+    if json.len() < COMPRESSION_THRESHOLD {
+        return Ok(Response::new(json));
+    }
+
+    let mut encoder = GzEncoder::new(Vec::with_capacity(json.len() / 4), Compression::fast());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map(Response::new)
+        .map_err(|e| format!("Failed to compress result: {}", e))
+}
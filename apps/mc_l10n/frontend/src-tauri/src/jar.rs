@@ -0,0 +1,534 @@
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use zip::result::ZipError;
+use zip::{CompressionMethod, ZipArchive};
+
+use crate::charset::{self, DecodedText};
+use crate::concurrency::{MemoryBudget, MemoryReservation};
+use crate::errors::{self, ErrorCode};
+use crate::hashing::{self, Fingerprint};
+use crate::io_retry;
+
+// 超过该大小的 JAR 使用内存映射读取
+const MMAP_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+// 普通 JAR 的读缓冲区大小，避免中央目录解析时的大量小读取
+const BUFFER_CAPACITY: usize = 256 * 1024;
+
+// Fabric/Quilt 的 Jar-in-Jar 子 MOD 所在目录
+const NESTED_JAR_DIR: &str = "META-INF/jars/";
+
+// Forge/NeoForge jarjar 的清单，列出 META-INF/jarjar/ 中嵌入的 JAR
+const JARJAR_METADATA: &str = "META-INF/jarjar/metadata.json";
+
+// 嵌套 JAR 的最大递归层数，防止构造的归档无限嵌套
+pub const MAX_NESTED_DEPTH: usize = 4;
+
+// 单个条目解压后的大小上限，超过的条目（如 zip 炸弹）不读取
+const MAX_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+// 每次读取的块大小，读取过程中按块检查大小和超时
+const READ_CHUNK: usize = 64 * 1024;
+
+// 单个归档从打开起允许的读取时间，超时后不再读取其中的条目
+const ARCHIVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// 一次扫描中读取归档时触发的资源限制和读取失败。由扫描上下文持有，从中打开的归档（含嵌套归档）共享同一份，
+// 扫描器运行结束后并入 ScanOutput；扫描以外打开的归档各用一份，记录随归档丢弃
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveDiagnostics {
+    inner: Arc<Mutex<DiagnosticLog>>,
+}
+
+#[derive(Debug, Default)]
+struct DiagnosticLog {
+    violations: Vec<String>,
+    issues: Vec<ArchiveIssue>,
+}
+
+impl ArchiveDiagnostics {
+    fn log(&self) -> MutexGuard<'_, DiagnosticLog> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record_violation(&self, message: String) {
+        self.log().violations.push(message);
+    }
+
+    fn record_issue(&self, path: &str, entry: Option<&str>, error: &ZipError) {
+        self.log().issues.push(ArchiveIssue {
+            path: path.to_string(),
+            entry: entry.map(|entry| entry.to_string()),
+            kind: ArchiveErrorKind::from_zip_error(error),
+            message: error.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let log = self.log();
+        log.violations.is_empty() && log.issues.is_empty()
+    }
+
+    // 把已记录的内容移入 other
+    pub fn drain_into(&self, other: &ArchiveDiagnostics) {
+        let mut drained = std::mem::take(&mut *self.log());
+        let mut log = other.log();
+        log.violations.append(&mut drained.violations);
+        log.issues.append(&mut drained.issues);
+    }
+
+    // 取出并清空已记录的资源限制警告；同一归档被多个扫描器读取时只保留一条
+    pub fn take_violations(&self) -> Vec<String> {
+        let mut violations = std::mem::take(&mut self.log().violations);
+        let mut seen = HashSet::new();
+        violations.retain(|message| seen.insert(message.clone()));
+        violations
+    }
+
+    // 取出并清空已记录的读取失败；同一归档被多个扫描器读取时只保留一条
+    pub fn take_issues(&self) -> Vec<ArchiveIssue> {
+        let mut issues = std::mem::take(&mut self.log().issues);
+        let mut seen = HashSet::new();
+        issues.retain(|issue| seen.insert((issue.path.clone(), issue.entry.clone())));
+        issues
+    }
+}
+
+// 无法读取的归档或条目的错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveErrorKind {
+    // 归档结构或条目数据损坏（CRC 不符、压缩数据无效等）
+    Corrupt,
+    // 文件不完整，如下载中断的 JAR
+    Truncated,
+    PermissionDenied,
+    Io,
+}
+
+impl ArchiveErrorKind {
+    fn from_zip_error(error: &ZipError) -> Self {
+        match error {
+            ZipError::Io(e) if e.kind() == io::ErrorKind::PermissionDenied => ArchiveErrorKind::PermissionDenied,
+            ZipError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => ArchiveErrorKind::Truncated,
+            ZipError::Io(_) => ArchiveErrorKind::Io,
+            // 找不到中央目录结尾通常意味着文件被截断
+            ZipError::InvalidArchive(message) if message.contains("central directory") => ArchiveErrorKind::Truncated,
+            _ => ArchiveErrorKind::Corrupt,
+        }
+    }
+
+    // 其他 IO 错误不指定代码，由 errors::classify 按信息文本归类
+    fn tag(self, message: String) -> String {
+        match self {
+            ArchiveErrorKind::Corrupt | ArchiveErrorKind::Truncated => errors::tagged(ErrorCode::CorruptJar, message),
+            ArchiveErrorKind::PermissionDenied => errors::tagged(ErrorCode::PermissionDenied, message),
+            ArchiveErrorKind::Io => message,
+        }
+    }
+}
+
+// 扫描中无法读取的归档（entry 为空）或归档中的单个条目
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ArchiveIssue {
+    pub path: String,
+    #[serde(default)]
+    pub entry: Option<String>,
+    pub kind: ArchiveErrorKind,
+    pub message: String,
+}
+
+impl ArchiveIssue {
+    // 并入扫描结果 warnings/errors 的文本形式，带错误代码
+    pub fn describe(&self) -> String {
+        let target = match &self.entry {
+            Some(entry) => format!("{}!/{}", self.path, entry),
+            None => self.path.clone(),
+        };
+        self.kind.tag(format!("Could not read {}: {}", target, self.message))
+    }
+}
+
+// 条目读取被资源限制中止的原因
+enum LimitExceeded {
+    Size,
+    Timeout,
+}
+
+// stream_entry 交给调用方的数据流：超过大小上限或归档读取超时后以错误结束，并记下原因
+struct LimitedReader<R> {
+    inner: R,
+    read: u64,
+    deadline: Instant,
+    exceeded: Option<LimitExceeded>,
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.exceeded.is_none() {
+            if self.read > MAX_ENTRY_SIZE {
+                self.exceeded = Some(LimitExceeded::Size);
+            } else if Instant::now() > self.deadline {
+                self.exceeded = Some(LimitExceeded::Timeout);
+            }
+        }
+        if self.exceeded.is_some() {
+            return Err(io::Error::other("archive entry exceeds read limits"));
+        }
+        let read = self.inner.read(buf)?;
+        self.read += read as u64;
+        Ok(read)
+    }
+}
+
+// JAR 的底层读取方式
+pub enum JarSource {
+    Mapped(Cursor<Mmap>),
+    Buffered(BufReader<File>),
+    // 嵌套在其他归档中的 zip，整体解压到内存后读取
+    Memory(Cursor<Vec<u8>>),
+    // 未压缩存储的嵌套归档，直接读取外层文件中的对应区间
+    Window(FileWindow),
+}
+
+// 文件中从 start 开始、长度为 len 的区间，作为独立的数据流读取
+pub struct FileWindow {
+    reader: BufReader<File>,
+    start: u64,
+    len: u64,
+    pos: u64,
+}
+
+impl FileWindow {
+    fn open(path: &Path, start: u64, len: u64) -> io::Result<Self> {
+        let mut reader = BufReader::with_capacity(BUFFER_CAPACITY, File::open(path)?);
+        reader.seek(SeekFrom::Start(start))?;
+        Ok(Self { reader, start, len, pos: 0 })
+    }
+}
+
+impl Read for FileWindow {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.len.saturating_sub(self.pos);
+        let max = buf.len().min(remaining.min(usize::MAX as u64) as usize);
+        if max == 0 {
+            return Ok(0);
+        }
+        let read = self.reader.read(&mut buf[..max])?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for FileWindow {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        let target = target.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek before start of window"))?;
+        self.reader.seek(SeekFrom::Start(self.start + target))?;
+        self.pos = target;
+        Ok(target)
+    }
+}
+
+impl Read for JarSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            JarSource::Mapped(cursor) => cursor.read(buf),
+            JarSource::Buffered(reader) => reader.read(buf),
+            JarSource::Memory(cursor) => cursor.read(buf),
+            JarSource::Window(window) => window.read(buf),
+        }
+    }
+}
+
+impl Seek for JarSource {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            JarSource::Mapped(cursor) => cursor.seek(pos),
+            JarSource::Buffered(reader) => reader.seek(pos),
+            JarSource::Memory(cursor) => cursor.seek(pos),
+            JarSource::Window(window) => window.seek(pos),
+        }
+    }
+}
+
+// 按需读取条目的 JAR 归档：打开时只解析中央目录，条目内容在读取时才解压
+pub struct JarReader {
+    archive: ZipArchive<JarSource>,
+    // 用于资源限制警告
+    label: String,
+    deadline: Instant,
+    timed_out: bool,
+    // 归档位于磁盘文件中时为文件路径和归档在文件中的起始偏移，用于直接读取未压缩的嵌套归档
+    origin: Option<(PathBuf, u64)>,
+    // 读入内存的嵌套归档占用的内存预算，随归档释放
+    _reservation: Option<MemoryReservation>,
+    diagnostics: ArchiveDiagnostics,
+}
+
+impl JarReader {
+    // 扫描以外的读取，资源限制警告和读取失败不保留
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Self::open_with(path, &ArchiveDiagnostics::default())
+    }
+
+    // 扫描中的读取，资源限制警告和读取失败记入该次扫描的 diagnostics。
+    // 网络驱动器上的临时性 IO 错误会重试，重试时重新打开文件
+    pub fn open_with(path: &Path, diagnostics: &ArchiveDiagnostics) -> Result<Self, String> {
+        let archive = io_retry::retry(|| Self::open_once(path), is_transient).map_err(|e| {
+            let label = path.to_string_lossy();
+            diagnostics.record_issue(&label, None, &e);
+            let kind = ArchiveErrorKind::from_zip_error(&e);
+            match e {
+                ZipError::Io(e) => kind.tag(format!("Failed to open {}: {}", label, e)),
+                e => kind.tag(format!("Failed to read archive {}: {}", label, e)),
+            }
+        })?;
+        let mut reader = Self::new(archive, path.to_string_lossy().to_string(), diagnostics.clone());
+        reader.origin = Some((path.to_path_buf(), 0));
+        Ok(reader)
+    }
+
+    fn new(archive: ZipArchive<JarSource>, label: String, diagnostics: ArchiveDiagnostics) -> Self {
+        Self {
+            archive,
+            label,
+            deadline: Instant::now() + ARCHIVE_TIMEOUT,
+            timed_out: false,
+            origin: None,
+            _reservation: None,
+            diagnostics,
+        }
+    }
+
+    fn open_once(path: &Path) -> Result<ZipArchive<JarSource>, ZipError> {
+        let file = File::open(path)?;
+        let size = file.metadata()?.len();
+
+        let source = if size >= MMAP_THRESHOLD {
+            // 映射期间文件被外部修改会导致读取到不一致的数据，扫描期间 JAR 应保持只读
+            match unsafe { Mmap::map(&file) } {
+                Ok(mmap) => JarSource::Mapped(Cursor::new(mmap)),
+                Err(_) => JarSource::Buffered(BufReader::with_capacity(BUFFER_CAPACITY, file)),
+            }
+        } else {
+            JarSource::Buffered(BufReader::with_capacity(BUFFER_CAPACITY, file))
+        };
+
+        ZipArchive::new(source)
+    }
+
+    // 打开已读入内存的归档，如 JAR 内嵌的资源包 zip
+    pub fn from_bytes(bytes: Vec<u8>, label: &str, diagnostics: &ArchiveDiagnostics) -> Result<Self, String> {
+        let archive = ZipArchive::new(JarSource::Memory(Cursor::new(bytes))).map_err(|e| {
+            diagnostics.record_issue(label, None, &e);
+            errors::tagged(ErrorCode::CorruptJar, format!("Failed to read archive {}: {}", label, e))
+        })?;
+
+        Ok(Self::new(archive, label.to_string(), diagnostics.clone()))
+    }
+
+    // 嵌套层数超过 MAX_NESTED_DEPTH、其中的 JAR 不再展开时调用
+    pub fn record_nesting_limit(&self) {
+        self.diagnostics.record_violation(format!(
+            "Skipped jars nested in {}: nesting exceeds {} levels",
+            self.label, MAX_NESTED_DEPTH
+        ));
+    }
+
+    // 打开归档中嵌套的 JAR 或 zip。未压缩存储的条目直接在外层文件上读取；
+    // 压缩的条目解压到内存，按声明的大小占用内存预算，预算不足时等待其他归档释放
+    pub fn open_nested(&mut self, name: &str, budget: &MemoryBudget) -> Option<JarReader> {
+        let label = format!("{}!/{}", self.label, name);
+        let window = match (&self.origin, self.archive.by_name(name)) {
+            (Some((path, base)), Ok(entry)) if entry.compression() == CompressionMethod::Stored && !entry.encrypted() => {
+                Some((path.clone(), base + entry.data_start(), entry.size()))
+            }
+            _ => None,
+        };
+        if let Some((path, start, len)) = window {
+            let opened = FileWindow::open(&path, start, len)
+                .map_err(ZipError::Io)
+                .and_then(|window| ZipArchive::new(JarSource::Window(window)));
+            if let Ok(archive) = opened {
+                let mut nested = Self::new(archive, label, self.diagnostics.clone());
+                nested.origin = Some((path, start));
+                return Some(nested);
+            }
+        }
+
+        let declared = self.archive.by_name(name).map_or(0, |entry| entry.size());
+        let reservation = budget.reserve(declared.min(MAX_ENTRY_SIZE));
+        let bytes = self.read_entry(name)?;
+        let mut nested = Self::from_bytes(bytes, &label, &self.diagnostics).ok()?;
+        nested._reservation = Some(reservation);
+        Some(nested)
+    }
+
+    // 流式计算条目内容的指纹，不把条目读入内存
+    pub fn fingerprint_entry(&mut self, name: &str) -> Option<Fingerprint> {
+        let mut entry = self.archive.by_name(name).ok()?;
+        hashing::fingerprint_reader(&mut entry).ok()
+    }
+
+    // 条目在中央目录中声明的解压后大小，不可信，只用于选择读取方式
+    pub fn entry_size(&mut self, name: &str) -> Option<u64> {
+        self.archive.by_name(name).ok().map(|entry| entry.size())
+    }
+
+    // 以数据流交给 read 处理，不把条目读入内存。与 read_entry 相同受大小上限和归档读取超时约束，
+    // 超出时数据流以错误结束，记录资源限制警告并返回 None。数据流读到一半无法重试，其他读取错误由 read 自行处理
+    pub fn stream_entry<R>(&mut self, name: &str, read: impl FnOnce(&mut dyn Read) -> R) -> Option<R> {
+        if self.timed_out {
+            return None;
+        }
+        let deadline = self.deadline;
+        let (result, exceeded) = {
+            let entry = self.archive.by_name(name).ok()?;
+            if entry.size() > MAX_ENTRY_SIZE {
+                (None, Some(LimitExceeded::Size))
+            } else {
+                let mut limited = LimitedReader { inner: entry, read: 0, deadline, exceeded: None };
+                let result = read(&mut limited);
+                (Some(result), limited.exceeded)
+            }
+        };
+        match exceeded {
+            Some(limit) => {
+                self.record_limit(name, limit);
+                None
+            }
+            None => result,
+        }
+    }
+
+    pub fn entry_names(&self) -> Vec<String> {
+        self.archive.file_names().map(|name| name.to_string()).collect()
+    }
+
+    // 超过大小上限或归档读取超时的条目返回 None，并记录资源限制警告
+    pub fn read_entry(&mut self, name: &str) -> Option<Vec<u8>> {
+        if self.timed_out {
+            return None;
+        }
+
+        let deadline = self.deadline;
+        let result = io_retry::retry(
+            || {
+                let mut entry = self.archive.by_name(name)?;
+                if entry.size() > MAX_ENTRY_SIZE {
+                    return Ok(Err(LimitExceeded::Size));
+                }
+                // 声明的大小来自归档头部，不可信，只用于有限的预分配；实际大小在读取时检查
+                let mut buffer = Vec::with_capacity(entry.size().min(1024 * 1024) as usize);
+                let mut chunk = vec![0; READ_CHUNK];
+                loop {
+                    let read = entry.read(&mut chunk)?;
+                    if read == 0 {
+                        return Ok(Ok(buffer));
+                    }
+                    buffer.extend_from_slice(&chunk[..read]);
+                    if buffer.len() as u64 > MAX_ENTRY_SIZE {
+                        return Ok(Err(LimitExceeded::Size));
+                    }
+                    if Instant::now() > deadline {
+                        return Ok(Err(LimitExceeded::Timeout));
+                    }
+                }
+            },
+            is_transient,
+        );
+
+        // 条目不存在是正常情况（探测描述文件等），其余读取失败记录下来
+        let result = match result {
+            Ok(result) => result,
+            Err(ZipError::FileNotFound) => return None,
+            Err(e) => {
+                self.diagnostics.record_issue(&self.label, Some(name), &e);
+                return None;
+            }
+        };
+
+        match result {
+            Ok(buffer) => Some(buffer),
+            Err(limit) => {
+                self.record_limit(name, limit);
+                None
+            }
+        }
+    }
+
+    // 超时后该归档中其余的条目都不再读取
+    fn record_limit(&mut self, name: &str, limit: LimitExceeded) {
+        match limit {
+            LimitExceeded::Size => self.diagnostics.record_violation(format!(
+                "Skipped {}!/{}: decompressed size exceeds {} MB",
+                self.label,
+                name,
+                MAX_ENTRY_SIZE / (1024 * 1024)
+            )),
+            LimitExceeded::Timeout => {
+                self.timed_out = true;
+                self.diagnostics.record_violation(format!(
+                    "Stopped reading {} after {} seconds; remaining entries were skipped",
+                    self.label,
+                    ARCHIVE_TIMEOUT.as_secs()
+                ));
+            }
+        }
+    }
+
+    pub fn read_entry_string(&mut self, name: &str) -> Option<String> {
+        let bytes = self.read_entry(name)?;
+        let text = String::from_utf8_lossy(&bytes);
+        Some(text.trim_start_matches('\u{FEFF}').to_string())
+    }
+
+    // 读取文本条目并检测编码，用于可能不是 UTF-8 的语言文件
+    pub fn read_entry_text(&mut self, name: &str) -> Option<DecodedText> {
+        self.read_entry(name).map(|bytes| charset::decode(&bytes))
+    }
+
+    // 嵌入的子 MOD JAR：Fabric/Quilt 的 META-INF/jars/*.jar，以及 jarjar 清单中列出且存在于归档中的 JAR
+    pub fn nested_jar_entries(&mut self) -> Vec<String> {
+        let names = self.entry_names();
+        let mut entries: Vec<String> = names
+            .iter()
+            .filter(|name| {
+                name.strip_prefix(NESTED_JAR_DIR)
+                    .is_some_and(|file| file.ends_with(".jar") && !file.contains('/'))
+            })
+            .cloned()
+            .collect();
+
+        let jarjar: Option<serde_json::Value> =
+            self.read_entry_string(JARJAR_METADATA).and_then(|content| serde_json::from_str(&content).ok());
+        let listed = jarjar
+            .as_ref()
+            .and_then(|metadata| metadata.get("jars"))
+            .and_then(|jars| jars.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|jar| jar.get("path").and_then(|p| p.as_str()));
+        for path in listed {
+            if names.iter().any(|name| name == path) && !entries.iter().any(|entry| entry == path) {
+                entries.push(path.to_string());
+            }
+        }
+        entries
+    }
+}
+
+fn is_transient(error: &ZipError) -> bool {
+    matches!(error, ZipError::Io(e) if io_retry::is_transient(e))
+}
@@ -0,0 +1,79 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::pack_module::PackModuleResource;
+use crate::scanners::LanguageResource;
+
+// 无法归类的键
+pub const OTHER: &str = "other";
+
+// 键的第一段到内容类型的映射；1.12 的方块键以 tile. 开头，进度键有单复数两种写法
+const CATEGORIES: &[(&str, &str)] = &[
+    ("item", "item"),
+    ("block", "block"),
+    ("tile", "block"),
+    ("entity", "entity"),
+    ("gui", "gui"),
+    ("screen", "gui"),
+    ("container", "gui"),
+    ("advancement", "advancement"),
+    ("advancements", "advancement"),
+    ("enchantment", "enchantment"),
+    ("effect", "effect"),
+    ("potion", "effect"),
+    ("biome", "biome"),
+    ("itemGroup", "item_group"),
+    ("itemgroup", "item_group"),
+    ("death", "death_message"),
+    ("key", "key_binding"),
+    ("subtitles", "subtitle"),
+    ("tooltip", "tooltip"),
+    ("config", "config"),
+    ("fluid", "fluid"),
+    ("commands", "command"),
+];
+
+pub fn categorize(key: &str) -> &'static str {
+    let prefix = key.split('.').next().unwrap_or(key);
+    CATEGORIES
+        .iter()
+        .find(|(name, _)| *name == prefix)
+        .map_or(OTHER, |(_, category)| category)
+}
+
+// 语言文件中各内容类型的键数
+pub fn count(entries: &HashMap<String, String>) -> BTreeMap<String, u32> {
+    let mut counts = BTreeMap::new();
+    for key in entries.keys() {
+        *counts.entry(categorize(key).to_string()).or_default() += 1;
+    }
+    counts
+}
+
+// 按命名空间汇总各内容类型的键数，与 total_translatable_keys 的口径一致：
+// 没有分类明细的语言资源（如 Patchouli 页面、扩展提供的资源）计入 other，
+// 组合包模块以 module_type 作为命名空间、条目的 kind 作为类型
+pub fn summarize(
+    resources: &[LanguageResource],
+    pack_modules: &[PackModuleResource],
+) -> BTreeMap<String, BTreeMap<String, u32>> {
+    let mut summary: BTreeMap<String, BTreeMap<String, u32>> = BTreeMap::new();
+    for resource in resources {
+        let namespace = summary.entry(resource.namespace.to_string()).or_default();
+        let mut categorized = 0;
+        for (category, count) in &resource.key_categories {
+            *namespace.entry(category.clone()).or_default() += count;
+            categorized += count;
+        }
+        let uncategorized = resource.key_count.saturating_sub(categorized);
+        if uncategorized > 0 {
+            *namespace.entry(OTHER.to_string()).or_default() += uncategorized;
+        }
+    }
+    for module in pack_modules {
+        let namespace = summary.entry(module.module_type.clone()).or_default();
+        for entry in &module.entries {
+            *namespace.entry(entry.kind.clone()).or_default() += 1;
+        }
+    }
+    summary
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use crate::concurrency::WorkLimits;
+use crate::intern::Symbol;
+use crate::lang;
+use crate::locale;
+use crate::metadata_cache::MetadataCache;
+use crate::mod_metadata::extract_mod_metadata;
+use crate::priority::PriorityRules;
+use crate::profiling::ModTiming;
+use crate::scanners::{LanguageResource, ParseStatus};
+
+// 某个来源对冲突键给出的值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictingValue {
+    pub source_type: String,
+    pub source_path: String,
+    // 来源所属的 MOD（MOD 自身或内置资源包）
+    pub mod_id: Option<String>,
+    pub priority: u32,
+    pub value: String,
+}
+
+// 多个来源定义了同一个键且值不同
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyConflict {
+    pub namespace: String,
+    pub key: String,
+    // 按优先级合并后实际生效的值
+    pub winning_value: String,
+    pub winning_source: String,
+    // 按优先级从高到低排列
+    pub values: Vec<ConflictingValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyConflictReport {
+    pub locale: String,
+    pub sources_checked: u32,
+    pub conflicts: Vec<KeyConflict>,
+}
+
+// 一个语言来源及其全部键值
+struct LangSource {
+    resource: LanguageResource,
+    mod_id: Option<String>,
+    entries: HashMap<String, String>,
+}
+
+// 对比 MOD 自身语言文件和扫描到的语言资源，找出被不同来源以不同值定义的键
+pub fn detect_key_conflicts(
+    locale: &str,
+    jar_paths: Vec<PathBuf>,
+    resources: Vec<LanguageResource>,
+    rules: &PriorityRules,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> KeyConflictReport {
+    let locale = locale::canonical_locale(locale);
+
+    // MOD 自身的语言文件是合并的基础层
+    let mod_sources = limits.map(jar_paths, |limits, path| {
+        let metadata = extract_mod_metadata(&path, limits, cache, &mut ModTiming::default())?;
+        let entries = {
+            let _io = limits.io.acquire();
+            lang::load_jar_locale_entries(&path, &locale).ok()?
+        };
+        let jar_path = path.to_string_lossy().to_string();
+        Some(
+            entries
+                .into_iter()
+                .map(|(namespace, entries)| LangSource {
+                    resource: LanguageResource {
+                        // 只用于展示来源，不含扩展名
+                        source_path: format!("{}!/assets/{}/lang/{}", jar_path, namespace, locale),
+                        namespace: Symbol::from(namespace.as_str()),
+                        locale: Symbol::from(locale.as_str()),
+                        source_type: Symbol::from("mod_jar"),
+                        key_count: entries.len() as u32,
+                        duplicate_keys: 0,
+                        placeholders: BTreeMap::new(),
+                        formatting: BTreeMap::new(),
+                        key_categories: BTreeMap::new(),
+                        parse_status: ParseStatus::Parsed,
+                        parse_error: None,
+                        priority: 0,
+                        bundled_pack: None,
+                    },
+                    mod_id: Some(metadata.mod_id.clone()),
+                    entries,
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    let resource_sources = limits.map(
+        // mod_jar 资源即上面的基础层，不重复参与对比
+        resources
+            .into_iter()
+            .filter(|r| *r.locale == *locale && &*r.source_type != "mod_jar")
+            .collect(),
+        |limits, resource| {
+            let _io = limits.io.acquire();
+            let entries = lang::read_resource_entries(&resource.source_path)?;
+            let mod_id = resource.bundled_pack.as_ref().map(|origin| origin.mod_id.clone());
+            Some(LangSource { resource, mod_id, entries })
+        },
+    );
+
+    let mut sources: Vec<LangSource> = mod_sources.into_iter().flatten().flatten().collect();
+    sources.extend(resource_sources.into_iter().flatten());
+    for source in &mut sources {
+        source.resource.priority = rules.resolve(&source.resource);
+    }
+
+    // (命名空间, 键) -> 按来源顺序排列的 (来源下标, 值)
+    let mut providers: BTreeMap<(&str, &str), Vec<(usize, &str)>> = BTreeMap::new();
+    for (index, source) in sources.iter().enumerate() {
+        for (key, value) in &source.entries {
+            providers
+                .entry((&source.resource.namespace, key.as_str()))
+                .or_default()
+                .push((index, value.as_str()));
+        }
+    }
+
+    let conflicts = providers
+        .into_iter()
+        .filter(|(_, values)| values.iter().any(|(_, value)| *value != values[0].1))
+        .filter_map(|((namespace, key), values)| {
+            let mut values: Vec<ConflictingValue> = values
+                .into_iter()
+                .map(|(index, value)| {
+                    let source = &sources[index];
+                    let resource = &source.resource;
+                    ConflictingValue {
+                        source_type: resource.source_type.to_string(),
+                        source_path: resource.source_path.clone(),
+                        mod_id: source.mod_id.clone(),
+                        priority: resource.priority,
+                        value: value.to_string(),
+                    }
+                })
+                .collect();
+            // 稳定排序后同优先级保持来源顺序；后加载的来源覆盖先加载的，因此取同优先级中的最后一个
+            values.sort_by_key(|v| v.priority);
+            let winner = values.last().cloned()?;
+            values.reverse();
+
+            Some(KeyConflict {
+                namespace: namespace.to_string(),
+                key: key.to_string(),
+                winning_value: winner.value,
+                winning_source: winner.source_path,
+                values,
+            })
+        })
+        .collect();
+
+    KeyConflictReport {
+        locale,
+        sources_checked: sources.len() as u32,
+        conflicts,
+    }
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::hashing;
+use crate::lang;
+use crate::mod_diff;
+use crate::undo_journal::{self, OperationKind};
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// 一次键迁移：原文不变，只是键名在新版本中改变
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyMigration {
+    pub namespace: String,
+    pub old_key: String,
+    pub new_key: String,
+    pub source: String,
+    pub source_hash: String,
+    pub translation: String,
+}
+
+// 原文在新版本中对应多个新键，无法自动判断
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmbiguousRename {
+    pub namespace: String,
+    pub old_key: String,
+    pub source: String,
+    pub candidates: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationProposal {
+    pub mod_id: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub target_locale: String,
+    pub migrations: Vec<KeyMigration>,
+    pub ambiguous: Vec<AmbiguousRename>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationResult {
+    pub migrated_keys: u32,
+    pub skipped_keys: Vec<String>,
+    pub updated_files: Vec<String>,
+}
+
+// 比较 MOD 新旧版本的原文，按原文哈希匹配被删除与新增的键，
+// 为项目中已有译文的旧键提出迁移到新键的建议
+pub fn propose_key_migrations(
+    old_jar: &Path,
+    new_jar: &Path,
+    project_path: &Path,
+    target_locale: &str,
+) -> Result<MigrationProposal, String> {
+    let diff = mod_diff::diff_mod_jars(old_jar, new_jar, SOURCE_LOCALE)?;
+    let translations = lang::load_locale_entries(project_path, target_locale);
+
+    // (命名空间, 原文哈希) -> 新增的键
+    let mut added_by_hash: HashMap<(&str, String), Vec<&str>> = HashMap::new();
+    for change in &diff.added {
+        if let Some(source) = &change.new_value {
+            added_by_hash
+                .entry((change.namespace.as_str(), hashing::sha1_hex(source.as_bytes())))
+                .or_default()
+                .push(change.key.as_str());
+        }
+    }
+
+    let mut migrations = Vec::new();
+    let mut ambiguous = Vec::new();
+
+    for change in &diff.removed {
+        let Some(source) = &change.old_value else {
+            continue;
+        };
+        let namespace_translations = translations.get(&change.namespace);
+        let Some(translation) = namespace_translations.and_then(|entries| entries.get(&change.key)) else {
+            continue;
+        };
+
+        let source_hash = hashing::sha1_hex(source.as_bytes());
+        let candidates: Vec<&str> = added_by_hash
+            .get(&(change.namespace.as_str(), source_hash.clone()))
+            .map(|keys| {
+                keys.iter()
+                    .copied()
+                    .filter(|key| namespace_translations.map_or(true, |entries| !entries.contains_key(*key)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match candidates.as_slice() {
+            [] => {}
+            [new_key] => migrations.push(KeyMigration {
+                namespace: change.namespace.clone(),
+                old_key: change.key.clone(),
+                new_key: new_key.to_string(),
+                source: source.clone(),
+                source_hash,
+                translation: translation.clone(),
+            }),
+            _ => ambiguous.push(AmbiguousRename {
+                namespace: change.namespace.clone(),
+                old_key: change.key.clone(),
+                source: source.clone(),
+                candidates: candidates.iter().map(|key| key.to_string()).collect(),
+            }),
+        }
+    }
+
+    Ok(MigrationProposal {
+        mod_id: diff.mod_id,
+        old_version: diff.old_version,
+        new_version: diff.new_version,
+        target_locale: target_locale.to_string(),
+        migrations,
+        ambiguous,
+    })
+}
+
+// 应用迁移：在目标语言文件中把旧键改名为新键，并为每条成功的迁移写入审计记录
+pub fn apply_key_migrations(
+    project_path: &Path,
+    target_locale: &str,
+    mod_id: &str,
+    migrations: &[KeyMigration],
+) -> Result<MigrationResult, String> {
+    let lang_files: HashMap<String, _> = lang::find_locale_files(project_path, target_locale).into_iter().collect();
+    let mut by_namespace: HashMap<&str, Vec<&KeyMigration>> = HashMap::new();
+    for migration in migrations {
+        by_namespace.entry(migration.namespace.as_str()).or_default().push(migration);
+    }
+
+    let touched: Vec<&Path> = by_namespace
+        .keys()
+        .filter_map(|namespace| lang_files.get(*namespace).map(|path| path.as_path()))
+        .collect();
+    undo_journal::record(OperationKind::Patch, &format!("Migrate {} keys of {}", migrations.len(), mod_id), &touched)?;
+
+    let mut audit_log = MigrationAuditLog::load().map_err(|e| e.to_string())?;
+    let applied_at = chrono::Utc::now().to_rfc3339();
+    let mut result = MigrationResult {
+        migrated_keys: 0,
+        skipped_keys: Vec::new(),
+        updated_files: Vec::new(),
+    };
+
+    for (namespace, namespace_migrations) in by_namespace {
+        let Some(lang_path) = lang_files.get(namespace) else {
+            result
+                .skipped_keys
+                .extend(namespace_migrations.iter().map(|m| format!("{}:{}", namespace, m.old_key)));
+            continue;
+        };
+
+        let renames: HashMap<String, String> = namespace_migrations
+            .iter()
+            .map(|m| (m.old_key.clone(), m.new_key.clone()))
+            .collect();
+        let renamed = lang::rename_lang_keys(lang_path, &renames)?;
+
+        for migration in namespace_migrations {
+            if !renamed.contains(&migration.old_key) {
+                result.skipped_keys.push(format!("{}:{}", namespace, migration.old_key));
+                continue;
+            }
+
+            result.migrated_keys += 1;
+            audit_log.entries.push(MigrationAuditEntry {
+                project_path: project_path.to_string_lossy().to_string(),
+                mod_id: mod_id.to_string(),
+                target_locale: target_locale.to_string(),
+                namespace: namespace.to_string(),
+                old_key: migration.old_key.clone(),
+                new_key: migration.new_key.clone(),
+                source_hash: migration.source_hash.clone(),
+                translation: migration.translation.clone(),
+                applied_at: applied_at.clone(),
+            });
+        }
+
+        if !renamed.is_empty() {
+            result.updated_files.push(lang_path.to_string_lossy().to_string());
+        }
+    }
+
+    audit_log.save().map_err(|e| e.to_string())?;
+    result.skipped_keys.sort();
+    result.updated_files.sort();
+
+    Ok(result)
+}
+
+// 试运行：返回键迁移时实际会修改的语言文件及修改后的大小
+pub fn plan_key_migrations(
+    project_path: &Path,
+    target_locale: &str,
+    migrations: &[KeyMigration],
+) -> Result<Vec<(PathBuf, u64)>, String> {
+    let lang_files: HashMap<String, _> = lang::find_locale_files(project_path, target_locale).into_iter().collect();
+    let mut renames_by_namespace: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    for migration in migrations {
+        renames_by_namespace
+            .entry(migration.namespace.as_str())
+            .or_default()
+            .insert(migration.old_key.clone(), migration.new_key.clone());
+    }
+
+    let mut planned = Vec::new();
+    for (namespace, renames) in &renames_by_namespace {
+        let Some(lang_path) = lang_files.get(*namespace) else {
+            continue;
+        };
+        let (content, renamed) = lang::renamed_lang_content(lang_path, renames)?;
+        if !renamed.is_empty() {
+            planned.push((lang_path.clone(), content.len() as u64));
+        }
+    }
+
+    planned.sort();
+    Ok(planned)
+}
+
+// 键迁移审计记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationAuditEntry {
+    pub project_path: String,
+    pub mod_id: String,
+    pub target_locale: String,
+    pub namespace: String,
+    pub old_key: String,
+    pub new_key: String,
+    pub source_hash: String,
+    pub translation: String,
+    pub applied_at: String,
+}
+
+// 键迁移审计日志，保存在数据目录下的 migration_audit.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationAuditLog {
+    pub entries: Vec<MigrationAuditEntry>,
+}
+
+impl MigrationAuditLog {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let log_path = Self::get_log_path()?;
+
+        if log_path.exists() {
+            let content = fs::read_to_string(&log_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let log_path = Self::get_log_path()?;
+
+        if let Some(parent) = log_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&log_path, content)?;
+        Ok(())
+    }
+
+    fn get_log_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("migration_audit.json"))
+    }
+}
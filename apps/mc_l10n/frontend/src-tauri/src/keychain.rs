@@ -0,0 +1,27 @@
+// 系统钥匙串中的凭据；密码、私钥口令和访问密钥不写入配置文件
+const KEYRING_SERVICE: &str = "th-suite-mc-l10n";
+
+pub fn store_secret(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to store credentials in keychain: {}", e))
+}
+
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    match entry(account)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete credentials from keychain: {}", e)),
+    }
+}
+
+pub fn load_secret(account: &str) -> Result<Option<String>, String> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read credentials from keychain: {}", e)),
+    }
+}
+
+fn entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, account).map_err(|e| format!("Failed to open keychain: {}", e))
+}
@@ -0,0 +1,968 @@
+use indexmap::IndexMap;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::charset::{self, DecodedText};
+use crate::concurrency::{MemoryBudget, DEFAULT_MEMORY_BUDGET_MB};
+use crate::io_retry;
+use crate::jar::JarReader;
+use crate::lang_detect;
+use crate::locale::same_locale;
+use crate::scanners::patchouli;
+
+// 扫描时解析一个语言文件的结果
+pub struct ScannedLang {
+    pub entries: HashMap<String, String>,
+    // 出现多次的键数
+    pub duplicate_keys: u32,
+    // 宽松解析成功、文件不是 UTF-8 编码或有重复的键时的警告，由扫描器并入扫描结果
+    pub warnings: Vec<String>,
+}
+
+// 读取语言文件的全部键值（JSON 或 .lang 格式），按检测到的编码解码
+pub fn read_lang_entries(lang_path: &Path) -> Option<HashMap<String, String>> {
+    scan_lang_file(lang_path, false).ok().map(|scanned| scanned.entries)
+}
+
+// 与 read_lang_entries 相同，另返回重复的键数和警告；失败时返回原因
+pub fn scan_lang_file(lang_path: &Path, lenient: bool) -> Result<ScannedLang, String> {
+    let text = charset::read_file(lang_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let extension = lang_path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    scan_lang(&text, extension, &lang_path.to_string_lossy(), lenient)
+}
+
+// 读取扫描结果中的语言资源；source_path 可以是普通文件，也可以是 "<jar>!/<entry>" 形式的
+// 归档内路径（嵌套归档用多个 "!/" 分隔）
+pub fn read_resource_entries(source_path: &str) -> Option<HashMap<String, String>> {
+    let mut segments: Vec<&str> = source_path.split("!/").collect();
+    if segments.len() == 1 {
+        // Patchouli 手册页面按页面结构提取文本
+        if patchouli::is_book_path(source_path) {
+            let content = io_retry::read_to_string(Path::new(source_path)).ok()?;
+            return patchouli::page_entries(source_path, &content);
+        }
+        return read_lang_entries(Path::new(source_path));
+    }
+
+    let entry = segments.pop()?;
+    let mut reader = JarReader::open(Path::new(segments[0])).ok()?;
+    // 单个资源的读取不属于某次扫描，使用独立的默认预算
+    let budget = MemoryBudget::new(DEFAULT_MEMORY_BUDGET_MB * 1024 * 1024);
+    for nested in &segments[1..] {
+        reader = reader.open_nested(nested, &budget)?;
+    }
+
+    let text = reader.read_entry_text(entry)?;
+    if patchouli::is_book_path(source_path) {
+        return patchouli::page_entries(source_path, &text.text);
+    }
+    let (_, extension) = entry.rsplit_once('.')?;
+    parse_lang_entries(&text.text, extension)
+}
+
+// 按扩展名解析语言文件内容
+pub fn parse_lang_entries(content: &str, extension: &str) -> Option<HashMap<String, String>> {
+    parse_lang_entries_checked(content, extension, false).ok().map(|(entries, _)| entries)
+}
+
+// 保留原始写法的值：value 为解码后的文本，raw 为文件中的原样写法
+// （JSON 为含引号的字符串字面量，.lang 为 = 之后的部分）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue {
+    pub value: String,
+    pub raw: String,
+}
+
+// 保留原始写法的解析模式：写回时未修改的条目沿用 raw，\n、\"、\uXXXX 等转义逐字节保持不变。
+// 条目按在文件中第一次出现的顺序排列，重复的键以最后一次的值为准，JSON 中非字符串的值不返回
+pub fn parse_lang_entries_raw(content: &str, extension: &str) -> Option<IndexMap<String, RawValue>> {
+    match extension {
+        "json" => {
+            parse_lang_entries_checked(content, extension, false).ok()?;
+            let entries = json_top_level_members(content)
+                .into_iter()
+                .filter_map(|member| {
+                    let raw = &content[member.value?];
+                    let value = serde_json::from_str::<String>(raw).ok()?;
+                    Some((member.key, RawValue { value, raw: raw.to_string() }))
+                })
+                .collect();
+            Some(entries)
+        }
+        "lang" => Some(
+            legacy_lang_lines(content)
+                .into_iter()
+                .map(|entry| (entry.key, RawValue { value: entry.value, raw: entry.raw }))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+// 按命名空间加载某个语言 JSON 语言文件的原始写法，供导出时保持未修改条目的转义；.lang 的写法与 JSON 不通用，不返回
+pub fn load_locale_raw_entries(project_path: &Path, locale: &str) -> HashMap<String, IndexMap<String, RawValue>> {
+    find_locale_files(project_path, locale)
+        .into_iter()
+        .filter(|(_, lang_path)| lang_path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|(namespace, lang_path)| {
+            let text = charset::read_file(&lang_path).ok()?;
+            parse_lang_entries_raw(&text.text, "json").map(|entries| (namespace, entries))
+        })
+        .collect()
+}
+
+// 生成 JSON 语言文件，格式与 serde_json::to_string_pretty 相同。raw 中已有的键保持原文件中的顺序，
+// 新增的键按字母顺序排在后面；值与 raw 中记录的解码值相同的条目沿用原来的字符串字面量
+pub fn json_lang_content(entries: &HashMap<String, String>, raw: Option<&IndexMap<String, RawValue>>) -> String {
+    let mut ordered: Vec<(&String, &String)> = raw
+        .into_iter()
+        .flat_map(|raw| raw.keys())
+        .filter_map(|key| entries.get_key_value(key))
+        .collect();
+    let added: BTreeMap<&String, &String> =
+        entries.iter().filter(|(key, _)| raw.map_or(true, |raw| !raw.contains_key(*key))).collect();
+    ordered.extend(added);
+
+    let mut content = String::from("{");
+    for (index, (key, value)) in ordered.into_iter().enumerate() {
+        content.push_str(if index == 0 { "\n  " } else { ",\n  " });
+        content.push_str(&serde_json::Value::String(key.clone()).to_string());
+        content.push_str(": ");
+        match raw.and_then(|raw| raw.get(key)).filter(|raw| raw.value == *value) {
+            Some(raw) => content.push_str(&raw.raw),
+            None => content.push_str(&serde_json::Value::String(value.clone()).to_string()),
+        }
+    }
+    if !entries.is_empty() {
+        content.push('\n');
+    }
+    content.push('}');
+    content
+}
+
+// 解析已解码的语言文件内容，另返回重复的键数和 source_path 的警告；解析失败时返回原因。
+// lenient 见 parse_lang_entries_checked
+pub fn scan_lang(text: &DecodedText, extension: &str, source_path: &str, lenient: bool) -> Result<ScannedLang, String> {
+    let (entries, recovered) = parse_lang_entries_checked(&text.text, extension, lenient)?;
+    let mut warnings = Vec::new();
+    if recovered {
+        warnings.push(format!(
+            "{} is not standard JSON (comments or trailing commas were ignored)",
+            source_path
+        ));
+    }
+    if !text.is_utf8() {
+        warnings.push(format!(
+            "{} is encoded in {}; edits are written back in the same encoding",
+            source_path,
+            text.encoding.name()
+        ));
+    }
+
+    let duplicates = find_duplicate_keys(&text.text, extension);
+    warnings.extend(duplicate_key_warnings(source_path, &duplicates));
+    Ok(ScannedLang { entries, duplicate_keys: duplicates.len() as u32, warnings })
+}
+
+// 每个重复的键一条警告，lines 为其出现的行号
+pub fn duplicate_key_warnings(source_path: &str, duplicates: &[(String, Vec<usize>)]) -> Vec<String> {
+    duplicates
+        .iter()
+        .map(|(key, lines)| {
+            let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+            format!(
+                "{} defines key {} more than once (lines {}); the last value is used",
+                source_path,
+                key,
+                lines.join(", ")
+            )
+        })
+        .collect()
+}
+
+// 出现多次的键及其所在行号（从 1 开始），按第一次出现的顺序
+fn find_duplicate_keys(content: &str, extension: &str) -> Vec<(String, Vec<usize>)> {
+    let keys: Vec<(usize, String)> = match extension {
+        "json" => json_top_level_members(content).into_iter().map(|member| (member.line, member.key)).collect(),
+        "lang" => legacy_lang_lines(content).into_iter().map(|entry| (entry.line, entry.key)).collect(),
+        _ => return Vec::new(),
+    };
+
+    let mut lines: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut order = Vec::new();
+    for (line, key) in keys {
+        let key_lines = lines.entry(key.clone()).or_default();
+        if key_lines.is_empty() {
+            order.push(key);
+        }
+        key_lines.push(line);
+    }
+    order
+        .into_iter()
+        .filter_map(|key| {
+            let key_lines = lines.remove(&key)?;
+            (key_lines.len() > 1).then_some((key, key_lines))
+        })
+        .collect()
+}
+
+// JSON 语言文件顶层对象的一个成员
+struct JsonMember {
+    key: String,
+    // 键所在行号（从 1 开始）
+    line: usize,
+    // 值为字符串时，含引号的字符串字面量在内容中的字节范围
+    value: Option<Range<usize>>,
+}
+
+// 顶层对象的成员；serde_json 解析时重复的键以最后一次为准，也不保留值的原始写法，无法从解析结果中得知。
+// 跳过注释，以便宽松模式下的文件也能检查
+fn json_top_level_members(content: &str) -> Vec<JsonMember> {
+    let bytes = content.as_bytes();
+    let mut members = Vec::new();
+    let mut depth = 0usize;
+    let mut line = 1;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match (bytes[index], bytes.get(index + 1)) {
+            (b'\n', _) => line += 1,
+            (b'{' | b'[', _) => depth += 1,
+            (b'}' | b']', _) => depth = depth.saturating_sub(1),
+            (b'/', Some(b'/')) => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                index += 2;
+                while index < bytes.len() && !(bytes[index] == b'*' && bytes.get(index + 1) == Some(&b'/')) {
+                    line += usize::from(bytes[index] == b'\n');
+                    index += 1;
+                }
+                index += 1;
+            }
+            (b'"', _) => {
+                let start = index;
+                let Some(end) = string_literal_end(bytes, start) else { break };
+                let start_line = line;
+                line += bytes[start..end].iter().filter(|b| **b == b'\n').count();
+                index = end;
+
+                let mut after = bytes[end + 1..].iter().enumerate().filter(|(_, b)| !b.is_ascii_whitespace());
+                let is_key = after.next().is_some_and(|(_, b)| *b == b':');
+                if depth == 1 && is_key {
+                    // 值是字符串时记录其字面量范围，其他类型的值只记录键
+                    let value = after
+                        .next()
+                        .filter(|(_, b)| **b == b'"')
+                        .map(|(offset, _)| end + 1 + offset)
+                        .and_then(|value_start| Some(value_start..string_literal_end(bytes, value_start)? + 1));
+                    if let Ok(key) = serde_json::from_str::<String>(&content[start..=end]) {
+                        members.push(JsonMember { key, line: start_line, value });
+                    }
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    members
+}
+
+// start 处的 " 开始的字符串字面量结束引号的位置
+fn string_literal_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'"' => return Some(index),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+// lenient 为宽松模式（由扫描设置开启）：JSON 解析失败时去掉 // 和 /* */ 注释以及尾随逗号后重试。
+// 第二个值表示是否经过宽松解析；宽松解析也失败时返回按原文报告的错误位置
+fn parse_lang_entries_checked(
+    content: &str,
+    extension: &str,
+    lenient: bool,
+) -> Result<(HashMap<String, String>, bool), String> {
+    match extension {
+        "json" => {
+            let parse = |content: &str| {
+                let mut entries = HashMap::new();
+                let mut deserializer = serde_json::Deserializer::from_str(content);
+                for_each_json_member(&mut deserializer, |key, value| {
+                    if let Some(value) = value {
+                        entries.insert(key, value);
+                    }
+                })
+                .and_then(|_| deserializer.end())
+                .map(|_| entries)
+            };
+            match parse(content) {
+                Ok(entries) => Ok((entries, false)),
+                Err(e) if lenient => {
+                    parse(&strip_json_extensions(content)).map(|entries| (entries, true)).map_err(|_| format!("Invalid JSON: {}", e))
+                }
+                Err(e) => Err(format!("Invalid JSON: {}", e)),
+            }
+        }
+        "lang" => Ok((parse_legacy_lang(content), false)),
+        _ => Err(format!("Unsupported language file extension: {}", extension)),
+    }
+}
+
+// 逐个读取 JSON 语言文件顶层对象的成员，不构建整个文档；值不是字符串时传入 None。
+// 重复的键每次出现都会传入
+pub fn for_each_json_member<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    mut visit: impl FnMut(String, Option<String>),
+) -> Result<(), D::Error> {
+    for_each_json_member_at(deserializer, || (), |key, _, value| visit(key, value))
+}
+
+// 同 for_each_json_member，另在读完每个键、读取其值之前调用 position，结果随成员一起传入（如键所在的行）
+pub fn for_each_json_member_at<'de, D: Deserializer<'de>, P>(
+    deserializer: D,
+    position: impl FnMut() -> P,
+    visit: impl FnMut(String, P, Option<String>),
+) -> Result<(), D::Error> {
+    deserializer.deserialize_map(MemberVisitor { position, visit })
+}
+
+struct MemberVisitor<P, F> {
+    position: P,
+    visit: F,
+}
+
+impl<'de, T, P: FnMut() -> T, F: FnMut(String, T, Option<String>)> Visitor<'de> for MemberVisitor<P, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            let position = (self.position)();
+            let value = match map.next_value::<serde_json::Value>()? {
+                serde_json::Value::String(value) => Some(value),
+                _ => None,
+            };
+            (self.visit)(key, position, value);
+        }
+        Ok(())
+    }
+}
+
+// 去掉字符串以外的注释和 } ] 之前的尾随逗号，以及开头的 BOM
+fn strip_json_extensions(content: &str) -> String {
+    let chars: Vec<char> = content.trim_start_matches('\u{FEFF}').chars().collect();
+    let mut without_comments = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        if in_string {
+            without_comments.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.get(index + 1) {
+                    without_comments.push(*next);
+                    index += 1;
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            index += 1;
+            continue;
+        }
+
+        match (c, chars.get(index + 1)) {
+            ('/', Some('/')) => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                index += 2;
+                while index < chars.len() && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/')) {
+                    index += 1;
+                }
+                index += 2;
+                continue;
+            }
+            ('"', _) => in_string = true,
+            _ => {}
+        }
+        without_comments.push(c);
+        index += 1;
+    }
+
+    let chars: Vec<char> = without_comments.chars().collect();
+    let mut output = String::with_capacity(without_comments.len());
+    let mut in_string = false;
+    for (index, &c) in chars.iter().enumerate() {
+        if in_string {
+            // 转义的引号前有奇数个反斜杠
+            if c == '"' && chars[..index].iter().rev().take_while(|p| **p == '\\').count() % 2 == 0 {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = chars[index + 1..].iter().find(|n| !n.is_whitespace());
+            if matches!(next, Some('}' | ']')) {
+                continue;
+            }
+        }
+        output.push(c);
+    }
+    output
+}
+
+// 解析 1.12 及更早版本的 .lang：# 开头的注释行，以奇数个反斜杠结尾的行与下一行相连（下一行的前导空白忽略），
+// 行尾空白不属于值，\uXXXX 转义解码为字符；\n 等其他转义保持原样，与导出时的写法一致。重复的键以最后一次为准
+pub fn parse_legacy_lang(content: &str) -> HashMap<String, String> {
+    legacy_lang_lines(content).into_iter().map(|entry| (entry.key, entry.value)).collect()
+}
+
+// .lang 中的一个键值
+struct LegacyLangLine {
+    // 起始行号（从 1 开始）
+    line: usize,
+    key: String,
+    value: String,
+    // = 之后未解码的值；续行已合并为一行
+    raw: String,
+}
+
+// 按出现顺序返回每个键值
+fn legacy_lang_lines(content: &str) -> Vec<LegacyLangLine> {
+    let mut entries = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let mut logical = line.trim_start().to_string();
+        if logical.is_empty() || logical.starts_with('#') {
+            continue;
+        }
+        while ends_with_continuation(&logical) {
+            logical.pop();
+            match lines.next() {
+                Some((_, next)) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let Some((key, value)) = logical.split_once('=') else { continue };
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let raw = value.trim_end();
+        entries.push(LegacyLangLine {
+            line: index + 1,
+            key: decode_unicode_escapes(key),
+            value: decode_unicode_escapes(raw),
+            raw: raw.to_string(),
+        });
+    }
+
+    entries
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+// 解码 \uXXXX（含 UTF-16 代理对）；\\ 视为一个整体，不参与解码；无效的转义保持原样
+fn decode_unicode_escapes(value: &str) -> String {
+    if !value.contains("\\u") {
+        return value.to_string();
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('\\') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if rest.starts_with("\\\\") {
+            result.push_str("\\\\");
+            rest = &rest[2..];
+            continue;
+        }
+        match parse_unicode_escape(rest) {
+            Some((high, after)) if (0xD800..0xDC00).contains(&high) => {
+                match parse_unicode_escape(after).filter(|(low, _)| (0xDC00..0xE000).contains(low)) {
+                    Some((low, after_low)) => {
+                        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                        result.extend(char::from_u32(code));
+                        rest = after_low;
+                    }
+                    None => {
+                        result.push_str(&rest[..6]);
+                        rest = after;
+                    }
+                }
+            }
+            Some((code, after)) => {
+                match char::from_u32(code) {
+                    Some(c) => result.push(c),
+                    None => result.push_str(&rest[..6]),
+                }
+                rest = after;
+            }
+            None => {
+                result.push('\\');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// rest 以 \uXXXX 开头时返回码元和其后的内容
+fn parse_unicode_escape(rest: &str) -> Option<(u32, &str)> {
+    let hex = rest.strip_prefix("\\u")?.get(..4)?;
+    let code = u32::from_str_radix(hex, 16).ok()?;
+    Some((code, &rest[6..]))
+}
+
+// 按命名空间加载 JAR 内 assets/<namespace>/lang/<locale>.(json|lang) 的全部条目。
+// 旧版本使用 en_US.lang 这样的大小写，语言代码不区分大小写匹配
+pub fn load_jar_locale_entries(jar_path: &Path, locale: &str) -> Result<HashMap<String, HashMap<String, String>>, String> {
+    let mut jar = JarReader::open(jar_path)?;
+    let mut result: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+    for name in jar.entry_names() {
+        let Some((namespace, file_name)) = name
+            .strip_prefix("assets/")
+            .and_then(|rest| rest.split_once("/lang/"))
+        else {
+            continue;
+        };
+        let Some((file_locale, extension)) = file_name.rsplit_once('.') else {
+            continue;
+        };
+        if file_name.contains('/') || !same_locale(file_locale, locale) {
+            continue;
+        }
+
+        let entries = jar
+            .read_entry_text(&name)
+            .and_then(|text| parse_lang_entries(&text.text, extension));
+        if let Some(entries) = entries {
+            result.entry(namespace.to_string()).or_default().extend(entries);
+        }
+    }
+
+    Ok(result)
+}
+
+// 查找项目中 assets/<namespace>/lang/<locale>.(json|lang) 形式的语言文件；
+// 文件名的大小写和别名（zh_CN、no_no 等）按规范代码匹配
+pub fn find_locale_files(project_path: &Path, locale: &str) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+    let assets_dir = project_path.join("assets");
+
+    let Ok(namespace_entries) = fs::read_dir(&assets_dir) else {
+        return files;
+    };
+
+    for namespace_entry in namespace_entries.flatten() {
+        if !namespace_entry.path().is_dir() {
+            continue;
+        }
+
+        let namespace = namespace_entry.file_name().to_string_lossy().to_string();
+        let lang_dir = namespace_entry.path().join("lang");
+
+        let Ok(lang_entries) = fs::read_dir(&lang_dir) else {
+            continue;
+        };
+        let mut candidates: Vec<PathBuf> = lang_entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && lang_detect::is_language_file(path))
+            .filter(|path| path.file_stem().is_some_and(|stem| same_locale(&stem.to_string_lossy(), locale)))
+            .collect();
+
+        // 同一语言有多个文件时优先 json，其次按文件名排序保证结果稳定
+        candidates.sort_by_key(|path| (path.extension() != Some("json".as_ref()), path.clone()));
+        if let Some(lang_path) = candidates.into_iter().next() {
+            files.push((namespace, lang_path));
+        }
+    }
+
+    files.sort();
+    files
+}
+
+// 按命名空间加载某个语言的全部条目
+pub fn load_locale_entries(project_path: &Path, locale: &str) -> HashMap<String, HashMap<String, String>> {
+    find_locale_files(project_path, locale)
+        .into_iter()
+        .filter_map(|(namespace, lang_path)| read_lang_entries(&lang_path).map(|entries| (namespace, entries)))
+        .collect()
+}
+
+// 更新语言文件中已有键的值，返回实际修改的键数量
+pub fn update_lang_entries(lang_path: &Path, updates: &HashMap<String, String>) -> Result<u32, String> {
+    let (new_content, updated) = updated_lang_content(lang_path, updates)?;
+    if updated > 0 {
+        write_lang_file(lang_path, new_content)?;
+    }
+
+    Ok(updated)
+}
+
+// 计算更新后的文件内容和实际修改的键数量，不写入文件
+pub fn updated_lang_content(lang_path: &Path, updates: &HashMap<String, String>) -> Result<(String, u32), String> {
+    let content = charset::read_file(lang_path)
+        .map_err(|e| format!("Failed to read {}: {}", lang_path.display(), e))?
+        .text;
+    let mut updated = 0;
+
+    let new_content = if lang_path.extension().is_some_and(|ext| ext == "json") {
+        let mut json: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", lang_path.display(), e))?;
+        let object = json
+            .as_object_mut()
+            .ok_or_else(|| format!("{} is not a JSON object", lang_path.display()))?;
+
+        // 只替换修改的值的字符串字面量，其余内容（包括未修改条目的转义写法）逐字节保留；
+        // 要修改的值不是字符串时改为整体重新序列化
+        let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+        let mut spliceable = true;
+        for member in json_top_level_members(&content) {
+            let Some(new_value) = updates.get(&member.key) else { continue };
+            let Some(range) = member.value else {
+                spliceable = false;
+                continue;
+            };
+            if serde_json::from_str::<String>(&content[range.clone()]).ok().as_ref() != Some(new_value) {
+                replacements.push((range, serde_json::Value::String(new_value.clone()).to_string()));
+            }
+        }
+
+        for (key, value) in updates {
+            if let Some(existing) = object.get_mut(key) {
+                if existing.as_str() != Some(value.as_str()) {
+                    *existing = serde_json::Value::String(value.clone());
+                    updated += 1;
+                }
+            }
+        }
+
+        if spliceable {
+            let mut spliced = content;
+            for (range, literal) in replacements.into_iter().rev() {
+                spliced.replace_range(range, &literal);
+            }
+            spliced
+        } else {
+            serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?
+        }
+    } else {
+        let mut lines = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let replacement = (!trimmed.starts_with('#'))
+                .then(|| trimmed.split_once('='))
+                .flatten()
+                .and_then(|(key, value)| {
+                    updates
+                        .get(key.trim())
+                        .filter(|new_value| new_value.as_str() != value)
+                        .map(|new_value| format!("{}={}", key.trim(), new_value))
+                });
+
+            match replacement {
+                Some(new_line) => {
+                    lines.push(new_line);
+                    updated += 1;
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+        lines.join("\n") + "\n"
+    };
+
+    Ok((new_content, updated))
+}
+
+// 把语言文件中的旧键改名为新键并保留原值；旧键不存在或新键已存在时跳过，返回实际改名的旧键
+pub fn rename_lang_keys(lang_path: &Path, renames: &HashMap<String, String>) -> Result<Vec<String>, String> {
+    let (new_content, renamed) = renamed_lang_content(lang_path, renames)?;
+    if !renamed.is_empty() {
+        write_lang_file(lang_path, new_content)?;
+    }
+
+    Ok(renamed)
+}
+
+// 计算改名后的文件内容和实际改名的旧键，不写入文件
+pub fn renamed_lang_content(
+    lang_path: &Path,
+    renames: &HashMap<String, String>,
+) -> Result<(String, Vec<String>), String> {
+    let content = charset::read_file(lang_path)
+        .map_err(|e| format!("Failed to read {}: {}", lang_path.display(), e))?
+        .text;
+    let mut renamed = Vec::new();
+
+    let new_content = if lang_path.extension().is_some_and(|ext| ext == "json") {
+        let mut json: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", lang_path.display(), e))?;
+        let object = json
+            .as_object_mut()
+            .ok_or_else(|| format!("{} is not a JSON object", lang_path.display()))?;
+
+        // 逐个重建对象，改名后的键留在原位置
+        let existing: HashSet<String> = object.keys().cloned().collect();
+        let mut rebuilt = serde_json::Map::with_capacity(object.len());
+        for (key, value) in std::mem::take(object) {
+            let new_key = renames
+                .get(&key)
+                .filter(|new_key| !existing.contains(*new_key) && !rebuilt.contains_key(*new_key));
+            match new_key {
+                Some(new_key) => {
+                    rebuilt.insert(new_key.clone(), value);
+                    renamed.push(key);
+                }
+                None => {
+                    rebuilt.insert(key, value);
+                }
+            }
+        }
+        *object = rebuilt;
+
+        serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?
+    } else {
+        let existing_keys: HashSet<String> = parse_lang_entries(&content, "lang")
+            .map(|entries| entries.into_keys().collect())
+            .unwrap_or_default();
+        let mut lines = Vec::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let replacement = (!trimmed.starts_with('#'))
+                .then(|| trimmed.split_once('='))
+                .flatten()
+                .and_then(|(key, value)| {
+                    renames
+                        .get(key.trim())
+                        .filter(|new_key| !existing_keys.contains(*new_key))
+                        .map(|new_key| (key.trim().to_string(), format!("{}={}", new_key, value)))
+                });
+
+            match replacement {
+                Some((old_key, new_line)) => {
+                    lines.push(new_line);
+                    renamed.push(old_key);
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+        lines.join("\n") + "\n"
+    };
+
+    Ok((new_content, renamed))
+}
+
+// 按文件原来的编码和 BOM 写回；原编码无法表示新内容（如 Shift-JIS 文件中写入了中文译文）时改为 UTF-8
+fn write_lang_file(lang_path: &Path, content: String) -> Result<(), String> {
+    let original = charset::read_file(lang_path).ok().filter(|text| !text.is_utf8() || text.bom);
+    let bytes = match original {
+        Some(original) => charset::encode(&content, original.encoding, original.bom).unwrap_or_else(|| {
+            eprintln!(
+                "{} cannot be written back as {}; converted to UTF-8",
+                lang_path.display(),
+                original.encoding.name()
+            );
+            content.into_bytes()
+        }),
+        None => content.into_bytes(),
+    };
+    fs::write(lang_path, bytes).map_err(|e| format!("Failed to write {}: {}", lang_path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy(content: &str) -> Vec<(String, String)> {
+        let mut entries: Vec<_> = parse_legacy_lang(content).into_iter().collect();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn legacy_lang_skips_comments_and_blank_lines() {
+        let content = "# comment\n\n  item.foo.name = Foo  \n   # indented comment\nno separator\n=missing key\nitem.bar.name=a=b\n";
+        assert_eq!(
+            legacy(content),
+            vec![
+                ("item.bar.name".to_string(), "a=b".to_string()),
+                ("item.foo.name".to_string(), " Foo".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn legacy_lang_joins_continuation_lines() {
+        let content = "tile.a.name=first \\\n    second \\\n\tthird\ntile.b.name=next\n";
+        assert_eq!(
+            legacy(content),
+            vec![
+                ("tile.a.name".to_string(), "first second third".to_string()),
+                ("tile.b.name".to_string(), "next".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn legacy_lang_even_backslashes_do_not_continue() {
+        let content = "a=path\\\\\nb=value\n";
+        assert_eq!(
+            legacy(content),
+            vec![("a".to_string(), "path\\\\".to_string()), ("b".to_string(), "value".to_string())]
+        );
+    }
+
+    #[test]
+    fn legacy_lang_trailing_backslash_at_end_of_file() {
+        let entries = parse_legacy_lang("a=one\nb=dangling\\");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries.get("b").map(String::as_str), Some("dangling"));
+    }
+
+    #[test]
+    fn legacy_lang_decodes_unicode_escapes() {
+        let entries = parse_legacy_lang("gui.title=\\u4e2d\\u6587\nkey.\\u0041=x\nline=a\\nb\n");
+        assert_eq!(entries.get("gui.title").map(String::as_str), Some("中文"));
+        assert_eq!(entries.get("key.A").map(String::as_str), Some("x"));
+        // \n 等其他转义保持原样
+        assert_eq!(entries.get("line").map(String::as_str), Some("a\\nb"));
+    }
+
+    #[test]
+    fn unicode_escapes_handle_surrogates_and_invalid_sequences() {
+        assert_eq!(decode_unicode_escapes("\\ud83d\\ude00"), "😀");
+        // 缺少低代理的高代理保持原样
+        assert_eq!(decode_unicode_escapes("\\ud83dx"), "\\ud83dx");
+        assert_eq!(decode_unicode_escapes("\\ud83d\\u0041"), "\\ud83dA");
+        // \\ 是一个整体，其后的 u0041 不是转义
+        assert_eq!(decode_unicode_escapes("\\\\u0041"), "\\\\u0041");
+        assert_eq!(decode_unicode_escapes("\\uZZZZ \\u12"), "\\uZZZZ \\u12");
+        assert_eq!(decode_unicode_escapes("plain"), "plain");
+    }
+
+    fn stripped_entries(content: &str) -> HashMap<String, String> {
+        serde_json::from_str(&strip_json_extensions(content)).expect("stripped content should be valid JSON")
+    }
+
+    #[test]
+    fn strip_json_removes_comments_and_trailing_commas() {
+        let content = "\u{FEFF}{\n  // line comment\n  \"a\": \"1\", /* block\n comment */\n  \"b\": \"2\",\n}\n";
+        let entries = stripped_entries(content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["a"], "1");
+        assert_eq!(entries["b"], "2");
+    }
+
+    #[test]
+    fn strip_json_keeps_comment_markers_inside_strings() {
+        let content = r#"{
+            "url": "https://example.com/wiki", // 注释
+            "pattern": "/* not a comment */",
+            "quote": "say \"//hi\"",
+            "slash": "ends with \\", // 反斜杠结尾的字符串
+            "comma": "a,}",
+        }"#;
+        let entries = stripped_entries(content);
+        assert_eq!(entries["url"], "https://example.com/wiki");
+        assert_eq!(entries["pattern"], "/* not a comment */");
+        assert_eq!(entries["quote"], "say \"//hi\"");
+        assert_eq!(entries["slash"], "ends with \\");
+        assert_eq!(entries["comma"], "a,}");
+    }
+
+    #[test]
+    fn strip_json_trailing_comma_in_arrays() {
+        let stripped = strip_json_extensions("{\"list\": [1, 2, ], \"k\": \"v\" , }");
+        let value: serde_json::Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value["list"], serde_json::json!([1, 2]));
+        assert_eq!(value["k"], "v");
+    }
+
+    #[test]
+    fn strip_json_leaves_standard_json_unchanged() {
+        let content = "{\"a\": \"x, y\", \"b\": [\"]\"]}";
+        assert_eq!(strip_json_extensions(content), content);
+    }
+
+    fn duplicates(pairs: &[(&str, &[usize])]) -> Vec<(String, Vec<usize>)> {
+        pairs.iter().map(|(key, lines)| (key.to_string(), lines.to_vec())).collect()
+    }
+
+    #[test]
+    fn json_duplicate_keys_report_every_line_in_first_seen_order() {
+        let content = "{\n  \"b\": \"1\",\n  \"a\": \"2\",\n  \"c\": \"3\",\n  \"a\": \"4\",\n  \"b\": \"5\", \"b\": \"6\"\n}\n";
+        assert_eq!(find_duplicate_keys(content, "json"), duplicates(&[("b", &[2, 6, 6]), ("a", &[3, 5])]));
+    }
+
+    #[test]
+    fn json_duplicate_key_lines_skip_strings_comments_and_nested_objects() {
+        let content = r#"{
+  // "a": "commented out"
+  "a": "multi\nline \"quoted\" value",
+  /* "a": "block
+     comment" */
+  "nested": {
+    "a": "inner"
+  },
+  "esc\"aped": "1",
+  "esc\"aped": "2",
+  "a": "last"
+}"#;
+        assert_eq!(find_duplicate_keys(content, "json"), duplicates(&[("a", &[3, 11]), ("esc\"aped", &[9, 10])]));
+    }
+
+    #[test]
+    fn lang_duplicate_key_lines_use_the_first_line_of_continued_entries() {
+        let content = "# a=comment\na=first \\\n  continued\nb=1\n\na=second\n\\u0061=escaped\n";
+        assert_eq!(find_duplicate_keys(content, "lang"), duplicates(&[("a", &[2, 6, 7])]));
+    }
+
+    #[test]
+    fn duplicate_keys_empty_without_repeats_or_for_other_extensions() {
+        assert!(find_duplicate_keys("{\"a\": \"1\", \"b\": \"1\"}", "json").is_empty());
+        assert!(find_duplicate_keys("a=1\na=2\n", "txt").is_empty());
+    }
+
+    #[test]
+    fn duplicate_key_warning_lists_lines() {
+        let warnings = duplicate_key_warnings("en_us.json", &duplicates(&[("a", &[3, 11])]));
+        assert_eq!(warnings, vec!["en_us.json defines key a more than once (lines 3, 11); the last value is used".to_string()]);
+    }
+
+    #[test]
+    fn legacy_lang_last_duplicate_wins() {
+        let entries = parse_legacy_lang("a=first\na=second\n");
+        assert_eq!(entries.get("a").map(String::as_str), Some("second"));
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::charset;
+use crate::io_retry;
+use crate::lang;
+use crate::locale;
+
+// 路径像语言文件、但没有通过结构检查的文件及原因，用于调整识别规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedCandidate {
+    pub path: String,
+    pub reason: String,
+}
+
+// 对一个文件的识别结果
+pub enum Detection {
+    Language,
+    // 候选文件没有通过结构检查，扫描时列入扫描结果的调试列表
+    Rejected(RejectedCandidate),
+    NotCandidate,
+}
+
+// 扩展名为 json 或 lang、路径中含 lang 或 i18n 的文件是候选；候选还须文件名像语言代码，
+// 内容是扁平的键值表（JSON 顶层对象的值都是字符串，.lang 有 key=value 行），以排除路径中恰好含 lang 的配置文件
+pub fn detect(path: &Path) -> Detection {
+    if !is_candidate_path(path) {
+        return Detection::NotCandidate;
+    }
+    match check_structure(path) {
+        Ok(()) => Detection::Language,
+        Err(reason) => Detection::Rejected(RejectedCandidate { path: path.to_string_lossy().to_string(), reason }),
+    }
+}
+
+// 不需要被拒绝的原因时使用
+pub fn is_language_file(path: &Path) -> bool {
+    matches!(detect(path), Detection::Language)
+}
+
+fn is_candidate_path(path: &Path) -> bool {
+    let is_lang_extension = path.extension().and_then(|e| e.to_str()).is_some_and(|ext| ext == "json" || ext == "lang");
+    is_lang_extension && path.to_str().is_some_and(|path_str| path_str.contains("lang") || path_str.contains("i18n"))
+}
+
+fn check_structure(path: &Path) -> Result<(), String> {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    if !locale::is_locale_shaped(&stem) {
+        return Err(format!("file name {} is not a locale code", stem));
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => check_json(path),
+        _ => check_legacy(path),
+    }
+}
+
+// 流式检查，大文件也不整体读入
+fn check_json(path: &Path) -> Result<(), String> {
+    let file = io_retry::retry_io(|| File::open(path)).map_err(|e| format!("cannot be read: {}", e))?;
+    let mut non_string = None;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+    let parsed = lang::for_each_json_member(&mut deserializer, |key, value| {
+        if value.is_none() && non_string.is_none() {
+            non_string = Some(key);
+        }
+    })
+    .and_then(|_| deserializer.end());
+
+    match (parsed, non_string) {
+        (Ok(()), Some(key)) => Err(format!("not a flat key-value file: value of {} is not a string", key)),
+        (Ok(()), None) => Ok(()),
+        // 带 BOM、不是 UTF-8、需要宽松解析或有语法错误的文件仍按语言文件处理，解析失败时由扫描结果报告
+        (Err(_), _) => Ok(()),
+    }
+}
+
+// 空文件视为没有条目的语言文件；有内容但没有一行 key=value 的不是
+fn check_legacy(path: &Path) -> Result<(), String> {
+    let text = charset::read_file(path).map_err(|e| format!("cannot be read: {}", e))?;
+    let has_content = text.text.lines().any(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with('#')
+    });
+    if has_content && lang::parse_legacy_lang(&text.text).is_empty() {
+        return Err("no key=value lines".to_string());
+    }
+    Ok(())
+}
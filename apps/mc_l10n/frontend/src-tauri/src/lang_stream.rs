@@ -0,0 +1,176 @@
+use indexmap::IndexMap;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::formatting_codes::{self, KeyFormatting};
+use crate::io_retry;
+use crate::jar::JarReader;
+use crate::key_categories;
+use crate::lang::{self, ScannedLang};
+use crate::placeholders;
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// 超过该大小的 JSON 语言文件在扫描时流式解析：逐个成员累计统计，不把文件内容和全部的值读入内存
+pub const STREAMING_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+const BUFFER_CAPACITY: usize = 64 * 1024;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+// 扫描结果中记录的语言文件统计
+#[derive(Debug, Default)]
+pub struct LangFileStats {
+    pub key_count: u32,
+    pub duplicate_keys: u32,
+    pub placeholders: BTreeMap<String, Vec<String>>,
+    pub formatting: BTreeMap<String, KeyFormatting>,
+    pub key_categories: BTreeMap<String, u32>,
+    // 无法读取或解析时的原因，此时其他各项为空
+    pub parse_error: Option<String>,
+    // 非标准 JSON、非 UTF-8 编码和重复的键的警告，由扫描器并入扫描结果
+    pub warnings: Vec<String>,
+}
+
+impl LangFileStats {
+    fn from_scanned(locale: &str, scanned: ScannedLang) -> Self {
+        LangFileStats {
+            key_count: scanned.entries.len() as u32,
+            duplicate_keys: scanned.duplicate_keys,
+            placeholders: placeholders::source_inventory(locale, &scanned.entries),
+            formatting: formatting_codes::inventory(&scanned.entries),
+            key_categories: key_categories::count(&scanned.entries),
+            parse_error: None,
+            warnings: scanned.warnings,
+        }
+    }
+
+    fn from_result(locale: &str, result: Result<ScannedLang, String>) -> Self {
+        match result {
+            Ok(scanned) => Self::from_scanned(locale, scanned),
+            Err(e) => LangFileStats { parse_error: Some(e), ..Default::default() },
+        }
+    }
+}
+
+// 磁盘上的语言文件；lenient 见 lang::scan_lang
+pub fn scan_file(lang_path: &Path, locale: &str, lenient: bool) -> LangFileStats {
+    let is_large_json = lang_path.extension().is_some_and(|ext| ext == "json")
+        && fs::metadata(lang_path).is_ok_and(|metadata| metadata.len() > STREAMING_THRESHOLD);
+    if is_large_json {
+        let source_path = lang_path.to_string_lossy();
+        let streamed = io_retry::retry_io(|| File::open(lang_path))
+            .ok()
+            .and_then(|file| stream_json(file, locale, &source_path));
+        if let Some(stats) = streamed {
+            return stats;
+        }
+    }
+
+    LangFileStats::from_result(locale, lang::scan_lang_file(lang_path, lenient))
+}
+
+// 归档中的语言文件条目
+pub fn scan_jar_entry(
+    jar: &mut JarReader,
+    name: &str,
+    extension: &str,
+    source_path: &str,
+    locale: &str,
+    lenient: bool,
+) -> LangFileStats {
+    if extension == "json" && jar.entry_size(name).is_some_and(|size| size > STREAMING_THRESHOLD) {
+        match jar.stream_entry(name, |reader| stream_json(reader, locale, source_path)) {
+            Some(Some(stats)) => return stats,
+            // 其他编码、需要宽松解析或读取出错，整体读入后解析
+            Some(None) => {}
+            // 超过大小上限或读取超时，已记入归档的资源限制警告，不再整体读入
+            None => return LangFileStats::from_result(locale, Err("Failed to read archive entry".to_string())),
+        }
+    }
+
+    let result = jar
+        .read_entry_text(name)
+        .ok_or_else(|| "Failed to read archive entry".to_string())
+        .and_then(|text| lang::scan_lang(&text, extension, source_path, lenient));
+    LangFileStats::from_result(locale, result)
+}
+
+// 流式解析 UTF-8 编码的标准 JSON。其他编码、需要宽松解析或读取出错时返回 None，
+// 由调用方整体读入后按原有方式解析（同时得到相应的警告）
+fn stream_json(reader: impl Read, locale: &str, source_path: &str) -> Option<LangFileStats> {
+    let mut reader = BufReader::with_capacity(BUFFER_CAPACITY, reader);
+    let head = reader.fill_buf().ok()?;
+    if head.starts_with(UTF8_BOM) {
+        reader.consume(UTF8_BOM.len());
+    } else if head.starts_with(&[0xFF, 0xFE]) || head.starts_with(&[0xFE, 0xFF]) {
+        return None;
+    }
+
+    let newlines = Rc::new(Cell::new(0));
+    let mut deserializer = serde_json::Deserializer::from_reader(LineCounter { inner: reader, newlines: newlines.clone() });
+    let is_source = locale == SOURCE_LOCALE;
+    let mut stats = LangFileStats::default();
+    // 键第一次出现的行号；出现多次的键另记全部行号
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+    let mut repeated: IndexMap<String, Vec<usize>> = IndexMap::new();
+
+    // 在读取值之前取行号：此时刚读完键，已读到的换行数即键所在的行
+    let key_line = || newlines.get() + 1;
+    lang::for_each_json_member_at(&mut deserializer, key_line, |key, line, value| {
+        let first = match first_lines.get(&key) {
+            Some(first_line) => {
+                repeated.entry(key.clone()).or_insert_with(|| vec![*first_line]).push(line);
+                false
+            }
+            None => {
+                first_lines.insert(key.clone(), line);
+                true
+            }
+        };
+        let Some(value) = value else { return };
+
+        // 重复的键以最后一次的值为准
+        if first {
+            stats.key_count += 1;
+            *stats.key_categories.entry(key_categories::categorize(&key).to_string()).or_default() += 1;
+        } else {
+            stats.placeholders.remove(&key);
+            stats.formatting.remove(&key);
+        }
+        if is_source {
+            let found = placeholders::extract(&value);
+            if !found.is_empty() {
+                stats.placeholders.insert(key.clone(), found);
+            }
+        }
+        if let Some(formatting) = formatting_codes::analyze(&value) {
+            stats.formatting.insert(key, formatting);
+        }
+    })
+    .ok()?;
+    deserializer.end().ok()?;
+
+    let duplicates: Vec<(String, Vec<usize>)> = repeated.into_iter().collect();
+    stats.warnings = lang::duplicate_key_warnings(source_path, &duplicates);
+    stats.duplicate_keys = duplicates.len() as u32;
+    Some(stats)
+}
+
+// 统计已读取的换行数；serde_json 从数据流逐字节读取，读完一个键时计数正好停在键所在的行
+struct LineCounter<R> {
+    inner: R,
+    newlines: Rc<Cell<usize>>,
+}
+
+impl<R: Read> Read for LineCounter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let count = buf[..read].iter().filter(|b| **b == b'\n').count();
+        self.newlines.set(self.newlines.get() + count);
+        Ok(read)
+    }
+}
@@ -0,0 +1,360 @@
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::database;
+use crate::hashing::sha1_hex;
+use crate::lang;
+use crate::metadata_cache::MetadataCache;
+use crate::mod_metadata::extract_mod_metadata;
+use crate::profiling::ModTiming;
+use crate::scanners::LanguageResource;
+use crate::script_hooks::{ScriptHooks, ON_ENTRY_EXTRACTED};
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// 离线模式用到的 V6 表，与后端 database/models/tables.py 保持一致；映射计划表只在本地使用
+const LOCAL_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS core_projects (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uid TEXT UNIQUE NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS core_mods (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uid TEXT UNIQUE NOT NULL,
+    modid TEXT UNIQUE,
+    slug TEXT,
+    name TEXT NOT NULL,
+    homepage TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS core_language_files (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uid TEXT UNIQUE NOT NULL,
+    carrier_type TEXT CHECK(carrier_type IN ('mod','resource_pack','data_pack','override')) NOT NULL,
+    carrier_uid TEXT NOT NULL,
+    locale TEXT NOT NULL,
+    rel_path TEXT NOT NULL,
+    format TEXT CHECK(format IN ('json','lang','properties')) NOT NULL,
+    size INTEGER DEFAULT 0,
+    discovered_at TEXT NOT NULL,
+    UNIQUE(carrier_uid, locale, rel_path)
+);
+CREATE TABLE IF NOT EXISTS core_translation_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uid TEXT UNIQUE NOT NULL,
+    language_file_uid TEXT NOT NULL REFERENCES core_language_files(uid),
+    key TEXT NOT NULL,
+    src_text TEXT NOT NULL,
+    dst_text TEXT DEFAULT '',
+    status TEXT CHECK(status IN ('new','mt','reviewed','locked','rejected','conflict')) DEFAULT 'new',
+    qa_flags TEXT CHECK(json_valid(qa_flags)) DEFAULT '{}',
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL,
+    uida_keys_b64 TEXT,
+    uida_hash TEXT,
+    UNIQUE(language_file_uid, key)
+);
+CREATE TABLE IF NOT EXISTS local_mapping_plans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_uid TEXT NOT NULL REFERENCES core_projects(uid),
+    language_file_uid TEXT NOT NULL REFERENCES core_language_files(uid),
+    source_file TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    language TEXT NOT NULL,
+    key_hash TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(project_uid, language_file_uid)
+);
+";
+
+// 本地创建的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalProject {
+    pub project_uid: String,
+    pub name: String,
+    pub language_files: u32,
+    pub entries: u32,
+    pub mapping_plans: u32,
+}
+
+// 写入数据库前的一个语言文件
+pub struct LangFile {
+    carrier_type: &'static str,
+    carrier: Carrier,
+    namespace: String,
+    locale: String,
+    rel_path: String,
+    source_file: String,
+    format: String,
+    entries: HashMap<String, String>,
+}
+
+// MOD 载体按 modid 对应 core_mods，其他载体按来源路径生成 uid
+enum Carrier {
+    Mod {
+        mod_id: String,
+        name: String,
+        homepage: Option<String>,
+    },
+    Path(String),
+}
+
+// 读取 MOD 自身的原文语言文件和扫描到的语言资源
+pub fn collect_lang_files(
+    jar_paths: Vec<PathBuf>,
+    resources: Vec<LanguageResource>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> Vec<LangFile> {
+    let mod_files = limits.map(jar_paths, |limits, path| {
+        let metadata = extract_mod_metadata(&path, limits, cache, &mut ModTiming::default())?;
+        let entries = {
+            let _io = limits.io.acquire();
+            lang::load_jar_locale_entries(&path, SOURCE_LOCALE).ok()?
+        };
+        let jar_path = path.to_string_lossy().to_string();
+        Some(
+            entries
+                .into_iter()
+                .map(|(namespace, entries)| LangFile {
+                    carrier_type: "mod",
+                    carrier: Carrier::Mod {
+                        mod_id: metadata.mod_id.clone(),
+                        name: metadata.display_name.clone(),
+                        homepage: metadata.homepage.clone(),
+                    },
+                    rel_path: format!("assets/{}/lang/{}.json", namespace, SOURCE_LOCALE),
+                    source_file: format!("{}!/assets/{}/lang/{}.json", jar_path, namespace, SOURCE_LOCALE),
+                    format: "json".to_string(),
+                    locale: SOURCE_LOCALE.to_string(),
+                    namespace,
+                    entries,
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    // MOD 自身的语言文件已按 JAR 读取，不重复导入扫描结果中的 mod_jar 资源
+    let resources = resources.into_iter().filter(|r| &*r.source_type != "mod_jar").collect();
+    let resource_files = limits.map(resources, |limits, resource| {
+        let entries = {
+            let _io = limits.io.acquire();
+            lang::read_resource_entries(&resource.source_path)?
+        };
+        let format = match resource.source_path.rsplit_once('.') {
+            Some((_, "lang")) => "lang",
+            _ => "json",
+        };
+        let (carrier_type, carrier) = match (&*resource.source_type, &resource.bundled_pack) {
+            // 内置资源包的语言文件归属于附带它的 MOD
+            (_, Some(origin)) => (
+                "mod",
+                Carrier::Mod {
+                    mod_id: origin.mod_id.clone(),
+                    name: origin.mod_id.clone(),
+                    homepage: None,
+                },
+            ),
+            ("resourcepack" | "resourcepack_zip", None) => ("resource_pack", Carrier::Path(resource.source_path.clone())),
+            _ => ("override", Carrier::Path(resource.source_path.clone())),
+        };
+        Some(LangFile {
+            carrier_type,
+            carrier,
+            namespace: resource.namespace.to_string(),
+            locale: resource.locale.to_string(),
+            rel_path: resource.source_path.replace('\\', "/"),
+            source_file: resource.source_path.clone(),
+            format: format.to_string(),
+            entries,
+        })
+    });
+
+    let mut files: Vec<LangFile> = mod_files.into_iter().flatten().flatten().collect();
+    files.extend(resource_files.into_iter().flatten());
+    files
+}
+
+// 对提取到的原文和语言资源运行项目脚本的 on_entry_extracted 钩子
+pub fn apply_script_hooks(files: &mut [LangFile], hooks: &ScriptHooks) -> Result<(), String> {
+    for file in files {
+        hooks.apply(ON_ENTRY_EXTRACTED, &mut file.entries, &file.namespace, &file.locale, &file.source_file)?;
+    }
+    Ok(())
+}
+
+// 在本地 SQLite 库中创建项目：写入载体、语言文件、条目和每个语言文件的映射计划。
+// 重复创建时沿用已有的语言文件和译文，只更新原文
+pub fn create_local_project(
+    database_path: &Path,
+    name: &str,
+    description: Option<&str>,
+    files: Vec<LangFile>,
+) -> Result<LocalProject, String> {
+    let mut conn = open_local_database(database_path)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let project = write_project(&tx, name, description, &files).map_err(|e| format!("Failed to create local project: {}", e))?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(project)
+}
+
+// 打开本地数据库并确保离线模式的表存在
+pub fn open_local_database(database_path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = database_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create database directory: {}", e))?;
+    }
+    let conn = database::open(database_path)?;
+    conn.execute_batch(LOCAL_SCHEMA)
+        .map_err(|e| format!("Failed to initialize local database: {}", e))?;
+    Ok(conn)
+}
+
+fn write_project(
+    tx: &Transaction<'_>,
+    name: &str,
+    description: Option<&str>,
+    files: &[LangFile],
+) -> Result<LocalProject, rusqlite::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let project_uid = uuid::Uuid::new_v4().to_string();
+    tx.execute(
+        "INSERT INTO core_projects (uid, name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?4)",
+        params![project_uid, name, description, now],
+    )?;
+
+    // 目标语言文件的原文取同一命名空间的原文语言文件
+    let mut source_texts: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+    for file in files.iter().filter(|file| file.locale == SOURCE_LOCALE) {
+        let texts = source_texts.entry(file.namespace.as_str()).or_default();
+        for (key, value) in &file.entries {
+            texts.entry(key.as_str()).or_insert(value.as_str());
+        }
+    }
+
+    let mut project = LocalProject {
+        project_uid: project_uid.clone(),
+        name: name.to_string(),
+        language_files: 0,
+        entries: 0,
+        mapping_plans: 0,
+    };
+    let mut mod_uids: HashMap<String, String> = HashMap::new();
+
+    for file in files {
+        let carrier_uid = match &file.carrier {
+            Carrier::Mod { mod_id, name, homepage } => match mod_uids.get(mod_id) {
+                Some(uid) => uid.clone(),
+                None => {
+                    let uid = upsert_mod(tx, mod_id, name, homepage.as_deref(), &now)?;
+                    mod_uids.insert(mod_id.clone(), uid.clone());
+                    uid
+                }
+            },
+            Carrier::Path(path) => sha1_hex(path.as_bytes()),
+        };
+
+        tx.execute(
+            "INSERT OR IGNORE INTO core_language_files
+                (uid, carrier_type, carrier_uid, locale, rel_path, format, size, discovered_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                file.carrier_type,
+                carrier_uid,
+                file.locale,
+                file.rel_path,
+                file.format,
+                file.entries.len() as i64,
+                now
+            ],
+        )?;
+        let language_file_uid: String = tx.query_row(
+            "SELECT uid FROM core_language_files WHERE carrier_uid = ?1 AND locale = ?2 AND rel_path = ?3",
+            params![carrier_uid, file.locale, file.rel_path],
+            |row| row.get(0),
+        )?;
+        project.language_files += 1;
+
+        let is_source = file.locale == SOURCE_LOCALE;
+        let source = source_texts.get(file.namespace.as_str());
+        let mut statement = tx.prepare_cached(
+            "INSERT INTO core_translation_entries (uid, language_file_uid, key, src_text, dst_text, status, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, 'new', ?6)
+             ON CONFLICT(language_file_uid, key) DO UPDATE SET src_text = excluded.src_text, updated_at = excluded.updated_at",
+        )?;
+        for (key, value) in &file.entries {
+            let (src_text, dst_text) = if is_source {
+                (value.as_str(), "")
+            } else {
+                let src_text = source.and_then(|texts| texts.get(key.as_str())).copied().unwrap_or_default();
+                (src_text, value.as_str())
+            };
+            statement.execute(params![
+                uuid::Uuid::new_v4().to_string(),
+                language_file_uid,
+                key,
+                src_text,
+                dst_text,
+                now
+            ])?;
+            project.entries += 1;
+        }
+
+        tx.execute(
+            "INSERT INTO local_mapping_plans
+                (project_uid, language_file_uid, source_file, namespace, language, key_hash, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(project_uid, language_file_uid) DO UPDATE SET
+                key_hash = excluded.key_hash, updated_at = excluded.updated_at",
+            params![
+                project_uid,
+                language_file_uid,
+                file.source_file,
+                file.namespace,
+                file.locale,
+                key_hash(&file.entries),
+                now
+            ],
+        )?;
+        project.mapping_plans += 1;
+    }
+
+    Ok(project)
+}
+
+// 按 modid 查找已有的 MOD，不存在时创建
+fn upsert_mod(
+    tx: &Transaction<'_>,
+    mod_id: &str,
+    name: &str,
+    homepage: Option<&str>,
+    now: &str,
+) -> Result<String, rusqlite::Error> {
+    let existing: Option<String> = tx
+        .query_row("SELECT uid FROM core_mods WHERE modid = ?1", params![mod_id], |row| row.get(0))
+        .optional()?;
+    if let Some(uid) = existing {
+        return Ok(uid);
+    }
+
+    let uid = uuid::Uuid::new_v4().to_string();
+    tx.execute(
+        "INSERT INTO core_mods (uid, modid, name, homepage, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+        params![uid, mod_id, name, homepage, now],
+    )?;
+    Ok(uid)
+}
+
+// 语言文件键集合的摘要，键集合变化时映射计划需要重新生成
+fn key_hash(entries: &HashMap<String, String>) -> String {
+    let keys: BTreeSet<&str> = entries.keys().map(String::as_str).collect();
+    sha1_hex(keys.into_iter().collect::<Vec<_>>().join("\n").as_bytes())
+}
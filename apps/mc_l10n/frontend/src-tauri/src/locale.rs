@@ -0,0 +1,92 @@
+// Minecraft 支持的语言代码（小写、下划线分隔）
+const KNOWN_LOCALES: &[&str] = &[
+    "af_za", "ar_sa", "ast_es", "az_az", "ba_ru", "bar", "be_by", "bg_bg", "br_fr", "brb", "bs_ba",
+    "ca_es", "cs_cz", "cy_gb", "da_dk", "de_at", "de_ch", "de_de", "el_gr", "en_au", "en_ca", "en_gb",
+    "en_nz", "en_pt", "en_ud", "en_us", "enp", "enws", "eo_uy", "es_ar", "es_cl", "es_ec", "es_es",
+    "es_mx", "es_uy", "es_ve", "esan", "et_ee", "eu_es", "fa_ir", "fi_fi", "fil_ph", "fo_fo", "fr_ca",
+    "fr_fr", "fra_de", "fur_it", "fy_nl", "ga_ie", "gd_gb", "gl_es", "haw_us", "he_il", "hi_in",
+    "hr_hr", "hu_hu", "hy_am", "id_id", "ig_ng", "io_en", "is_is", "isv", "it_it", "ja_jp",
+    "jbo_en", "ka_ge", "kk_kz", "kn_in", "ko_kr", "ksh", "kw_gb", "la_la", "lb_lu", "li_li", "lmo",
+    "lo_la", "lol_us", "lt_lt", "lv_lv", "lzh", "mk_mk", "mn_mn", "ms_my", "mt_mt", "nah", "nds_de",
+    "nl_be", "nl_nl", "nn_no", "nb_no", "oc_fr", "ovd", "pl_pl", "pt_br", "pt_pt", "qya_aa", "ro_ro",
+    "rpr", "ru_ru", "ry_ua", "sah_sah", "se_no", "sk_sk", "sl_si", "so_so", "sq_al", "sr_cs", "sr_sp",
+    "sv_se", "sxu", "szl", "ta_in", "th_th", "tl_ph", "tlh_aa", "tok", "tr_tr", "tt_ru", "uk_ua",
+    "val_es", "vec_it", "vi_vn", "yi_de", "yo_ng", "zh_cn", "zh_hk", "zh_tw", "zlm_arab",
+];
+
+// 别名 -> 规范代码：旧版代码、BCP 47 风格代码和只有语言部分的简写
+const LOCALE_ALIASES: &[(&str, &str)] = &[
+    ("no_no", "nb_no"),
+    ("no", "nb_no"),
+    ("nb", "nb_no"),
+    ("nn", "nn_no"),
+    ("zh_hans", "zh_cn"),
+    ("zh_hans_cn", "zh_cn"),
+    ("zh_sg", "zh_cn"),
+    ("zh_hant", "zh_tw"),
+    ("zh_hant_tw", "zh_tw"),
+    ("zh_hant_hk", "zh_hk"),
+    ("zh_mo", "zh_hk"),
+    ("zh", "zh_cn"),
+    ("en", "en_us"),
+    ("ja", "ja_jp"),
+    ("ko", "ko_kr"),
+    ("ru", "ru_ru"),
+    ("de", "de_de"),
+    ("fr", "fr_fr"),
+    ("es", "es_es"),
+    ("it", "it_it"),
+    ("pt", "pt_br"),
+    ("pl", "pl_pl"),
+    ("uk", "uk_ua"),
+    ("tr", "tr_tr"),
+    ("iw_il", "he_il"),
+    ("in_id", "id_id"),
+];
+
+// 规范化后的语言代码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedLocale {
+    pub code: String,
+    // 不在 Minecraft 语言列表中
+    pub unknown: bool,
+}
+
+// 把语言代码规范化为 Minecraft 的形式：统一小写、连字符改为下划线、解析别名
+pub fn normalize_locale(raw: &str) -> NormalizedLocale {
+    let lowered = raw.trim().to_ascii_lowercase().replace('-', "_");
+    let code = LOCALE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lowered)
+        .map_or(lowered.clone(), |(_, canonical)| canonical.to_string());
+
+    NormalizedLocale {
+        unknown: !KNOWN_LOCALES.contains(&code.as_str()),
+        code,
+    }
+}
+
+// 只取规范代码
+pub fn canonical_locale(raw: &str) -> String {
+    normalize_locale(raw).code
+}
+
+// 两个语言代码规范化后是否相同，如 zh_CN 与 zh_cn、no_no 与 nb_no
+pub fn same_locale(a: &str, b: &str) -> bool {
+    canonical_locale(a) == canonical_locale(b)
+}
+
+// 文件名是否像语言代码：已知代码或别名，或 <2-3 个字母>[_<2-4 个字母或数字>]，最多两段后缀（如 qya_aa、zh_hant_tw）
+pub fn is_locale_shaped(raw: &str) -> bool {
+    if !normalize_locale(raw).unknown {
+        return true;
+    }
+    let lowered = raw.trim().to_ascii_lowercase().replace('-', "_");
+    let mut parts = lowered.split('_');
+    let language = parts.next().unwrap_or_default();
+    let regions: Vec<&str> = parts.collect();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && regions.len() <= 2
+        && regions.iter().all(|part| (2..=4).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
@@ -1,34 +1,142 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod backup;
+mod charset;
+mod class_strings;
+mod concurrency;
 mod config;
-
-use tauri::{Manager, Emitter};
+mod database;
+mod environment;
+mod errors;
+mod events;
+mod export;
+mod extensions;
+mod formatting_codes;
+mod glossary;
+mod hashing;
+mod instance_guard;
+mod intern;
+mod io_retry;
+mod ipc_payload;
+mod jar;
+mod key_categories;
+mod key_conflicts;
+mod key_migration;
+mod keychain;
+mod lang;
+mod lang_detect;
+mod lang_stream;
+mod local_project;
+mod locale;
+mod metadata_cache;
+mod metrics;
+mod mod_diff;
+mod mod_filter;
+mod mod_icon;
+mod mod_metadata;
+mod namespace_owner;
+mod pack_check;
+mod pack_convert;
+mod pack_import;
+mod pack_integrity;
+mod pack_merge;
+mod pack_meta;
+mod pack_module;
+mod pagination;
+mod placeholders;
+mod preflight;
+mod priority;
+mod profiling;
+mod project_bundle;
+mod progress;
+mod project_settings;
+mod quick_preview;
+mod remote_sftp;
+mod scan_delta;
+mod scan_queue;
+mod scanners;
+mod script_hooks;
+mod session;
+mod statistics;
+mod sync_scheduler;
+mod telemetry;
+mod theme;
+mod ui_catalog;
+mod undo_journal;
+mod validation;
+mod verified_download;
+mod worker_pool;
+mod workspace;
+
+use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::RwLock;
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use backup::{BackupSettings, RemoteBackup};
+use class_strings::HardcodedStringReport;
+use concurrency::{ScanControl, WorkLimits};
 use config::AppConfig;
+use database::EncryptionStatus;
+use environment::EnvironmentReport;
+use errors::{CommandError, ErrorCode, ErrorCodeInfo};
+use events::{AppEvent, EventType};
+use export::{ExportProfile, ExportRequest, ExportResult};
+use extensions::ExtensionInfo;
+use formatting_codes::FormattingStats;
+use instance_guard::InstanceStatus;
+use jar::{ArchiveDiagnostics, ArchiveIssue};
+use key_conflicts::KeyConflictReport;
+use key_migration::{KeyMigration, MigrationAuditEntry, MigrationProposal, MigrationResult};
+use lang_detect::{Detection, RejectedCandidate};
+use local_project::LocalProject;
+use metadata_cache::MetadataCache;
+use metrics::MetricsSnapshot;
+use mod_diff::ModDiffReport;
+use mod_filter::ModFilter;
+use mod_metadata::ModJarMetadata;
+use namespace_owner::NamespaceCollision;
+use pack_check::PackFreshnessReport;
+use pack_convert::{PackConversionRequest, PackConversionResult};
+use pack_import::curseforge::{self, ModpackFile};
+use pack_import::mrpack;
+use pack_merge::PackMergeResult;
+use pack_meta::{PackDescription, ResourcePackMeta};
+use pack_module::PackModuleResource;
+use pagination::{paginate, Page};
+use preflight::WriteOutcome;
+use priority::PriorityRules;
+use profiling::{ScanProfile, ScanProfiler};
+use progress::ProgressReporter;
+use project_bundle::{BundleExport, BundleImport};
+use project_settings::{ProjectSettings, ProjectSettingsStore};
+use quick_preview::QuickPreview;
+use remote_sftp::{RemoteServer, RemoteServerStore, RemoteSyncReport};
+use scan_delta::{ScanDelta, ScanSnapshot};
+use scan_queue::{ScanQueue, ScanQueueState};
+use scanners::{LanguageResource, ScanContext, ScanOutput, ScanPreset, ScannerInfo};
+use script_hooks::ScriptHooks;
+use session::SessionState;
+use statistics::ProjectStatistics;
+use sync_scheduler::{SyncRunReport, SyncSchedule, SyncStatus};
+use telemetry::{TelemetryReport, TelemetryStats};
+use theme::ThemeState;
+use ui_catalog::UiCatalog;
+use undo_journal::{JournalOperation, OperationKind};
+use validation::consistency::{ConsistencyReport, HarmonizeResult};
+use validation::glyphs::GlyphCoverageReport;
+use validation::profiles::ValidationProfile;
+use validation::ValidationIssue;
+use worker_pool::WorkerPool;
+use workspace::{WorkspaceRoot, WorkspaceStore};
 
 const BACKEND_URL: &str = "http://localhost:8000/api/v1";
 
-// 扫描进度结构
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ScanProgress {
-    scan_id: String,
-    phase: String,
-    progress: f64,
-    message: String,
-    current_file: Option<String>,
-    processed_files: u32,
-    total_files: u32,
-    estimated_remaining: Option<u32>,
-    updated_at: String,
-}
-
 // 扫描结果结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ScanResult {
@@ -37,14 +145,108 @@ struct ScanResult {
     scan_started_at: String,
     scan_completed_at: Option<String>,
     modpack_manifest: Option<ModpackManifest>,
+    // 项目是资源包时 pack.mcmeta 中的格式和描述
+    #[serde(default)]
+    resource_pack_meta: Option<ResourcePackMeta>,
     mod_jars: Vec<ModJarMetadata>,
     language_resources: Vec<LanguageResource>,
+    // 组合包模块（CustomNPCs 等）中的可翻译文本
+    #[serde(default)]
+    pack_modules: Vec<PackModuleResource>,
+    // deep 预设下类文件中找到的硬编码字符串
+    #[serde(default)]
+    hardcoded_strings: Option<HardcodedStringReport>,
+    #[serde(default)]
+    scan_preset: ScanPreset,
+    // 计算语言资源优先级时使用的规则
+    #[serde(default)]
+    priority_rules: PriorityRules,
+    // 扫描目标是整合包归档时为归档路径，project_path 为解压后的目录
+    #[serde(default)]
+    source_archive: Option<String>,
+    // 直接在归档内扫描（未解压）；此时 project_path 与 source_archive 相同
+    #[serde(default)]
+    archive_scan: bool,
+    // 导出包 manifest.json 中列出、但不包含在包内的 MOD 文件
+    #[serde(default)]
+    modpack_files: Vec<ModpackFile>,
+    total_mods: u32,
+    total_language_files: u32,
+    total_translatable_keys: u32,
+    supported_locales: Vec<String>,
+    warnings: Vec<String>,
+    errors: Vec<String>,
+    // 无法读取的 JAR/zip 及其中损坏的条目，带错误类型；文本形式同时并入 warnings/errors
+    #[serde(default)]
+    unreadable_archives: Vec<ArchiveIssue>,
+    // 语言文件中 § 格式代码的统计，各键的详情见 LanguageResource.formatting
+    #[serde(default)]
+    formatting: FormattingStats,
+    // 按命名空间和内容类型（item、block、gui 等）拆分的可翻译键数，合计等于 total_translatable_keys
+    #[serde(default)]
+    key_statistics: BTreeMap<String, BTreeMap<String, u32>>,
+    // 路径像语言文件、但没有通过结构检查的文件，供调试识别规则，不计入摘要
+    #[serde(default)]
+    rejected_lang_files: Vec<RejectedCandidate>,
+    // 性能报告单独查询，不随扫描结果传给前端
+    #[serde(skip)]
+    profile: Option<ScanProfile>,
+    // 与同一路径上一次扫描的差异，单独查询
+    #[serde(skip)]
+    delta: Option<ScanDelta>,
+}
+
+// enqueue_scans 的单个请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanRequest {
+    project_path: String,
+    preset: Option<ScanPreset>,
+    #[serde(default)]
+    priority: Option<i32>,
+}
+
+// 扫描结果摘要（不含模组和语言资源列表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanSummary {
+    scan_id: String,
+    project_path: String,
+    scan_started_at: String,
+    scan_completed_at: Option<String>,
+    modpack_manifest: Option<ModpackManifest>,
+    resource_pack_meta: Option<ResourcePackMeta>,
+    archive_scan: bool,
     total_mods: u32,
     total_language_files: u32,
     total_translatable_keys: u32,
     supported_locales: Vec<String>,
     warnings: Vec<String>,
     errors: Vec<String>,
+    unreadable_archives: Vec<ArchiveIssue>,
+    formatting: FormattingStats,
+    key_statistics: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl ScanResult {
+    fn summary(&self) -> ScanSummary {
+        ScanSummary {
+            scan_id: self.scan_id.clone(),
+            project_path: self.project_path.clone(),
+            scan_started_at: self.scan_started_at.clone(),
+            scan_completed_at: self.scan_completed_at.clone(),
+            modpack_manifest: self.modpack_manifest.clone(),
+            resource_pack_meta: self.resource_pack_meta.clone(),
+            archive_scan: self.archive_scan,
+            total_mods: self.total_mods,
+            total_language_files: self.total_language_files,
+            total_translatable_keys: self.total_translatable_keys,
+            supported_locales: self.supported_locales.clone(),
+            warnings: self.warnings.clone(),
+            errors: self.errors.clone(),
+            unreadable_archives: self.unreadable_archives.clone(),
+            formatting: self.formatting.clone(),
+            key_statistics: self.key_statistics.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,26 +262,26 @@ struct ModpackManifest {
     license: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ModJarMetadata {
-    mod_id: String,
-    display_name: String,
-    version: String,
-    loader: String,
-    authors: Vec<String>,
-    homepage: Option<String>,
-    description: Option<String>,
-    environment: String,
+// 语言资源分页查询的过滤条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ResourceFilter {
+    namespace: Option<String>,
+    locale: Option<String>,
+    source_type: Option<String>,
+    search: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct LanguageResource {
-    namespace: String,
-    locale: String,
-    source_path: String,
-    source_type: String,
-    key_count: u32,
-    priority: u32,
+impl ResourceFilter {
+    fn matches(&self, resource: &LanguageResource) -> bool {
+        self.namespace.as_ref().map_or(true, |ns| resource.namespace == **ns)
+            && self.locale.as_ref().map_or(true, |locale| locale::same_locale(&resource.locale, locale))
+            && self.source_type.as_ref().map_or(true, |st| resource.source_type == **st)
+            && self.search.as_ref().map_or(true, |search| {
+                let search = search.to_lowercase();
+                resource.namespace.to_lowercase().contains(&search)
+                    || resource.source_path.to_lowercase().contains(&search)
+            })
+    }
 }
 
 // 文件信息结构
@@ -100,6 +302,9 @@ struct SimpleScanResult {
     lang_files: Vec<FileInfo>,
     modpack_files: Vec<FileInfo>,
     errors: Vec<String>,
+    // 路径像语言文件、但没有通过结构检查的文件，供调试识别规则
+    #[serde(default)]
+    rejected_lang_files: Vec<RejectedCandidate>,
 }
 
 // Mod信息结构
@@ -118,7 +323,7 @@ struct ModInfo {
 }
 
 // 全局扫描状态
-type ScanState = Arc<Mutex<HashMap<String, ScanResult>>>;
+type ScanState = Arc<RwLock<HashMap<String, ScanResult>>>;
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
@@ -141,108 +346,486 @@ fn get_system_info() -> HashMap<String, String> {
 }
 
 #[tauri::command]
-async fn check_backend_connection(url: String) -> Result<bool, String> {
+async fn check_backend_connection(url: String) -> Result<bool, CommandError> {
     match reqwest::get(&url).await {
         Ok(response) => Ok(response.status().is_success()),
-        Err(e) => Err(format!("Failed to connect to backend: {}", e)),
+        Err(e) => Err(format!("Failed to connect to backend: {}", e).into()),
     }
 }
 
 #[tauri::command]
-async fn start_backend_server() -> Result<String, String> {
+async fn start_backend_server() -> Result<String, CommandError> {
     // 这里应该启动后端服务器
     // 暂时返回模拟的端口
     Ok("8000".to_string())
 }
 
 #[tauri::command]
-async fn open_external_url(url: String, app: tauri::AppHandle) -> Result<(), String> {
+async fn open_external_url(url: String, app: tauri::AppHandle) -> Result<(), CommandError> {
     tauri_plugin_shell::ShellExt::shell(&app)
         .open(&url, None)
-        .map_err(|e| format!("Failed to open URL: {}", e))
+        .map_err(|e| CommandError::from(format!("Failed to open URL: {}", e)))
 }
 
 #[tauri::command]
-async fn show_notification(title: String, body: String, app: tauri::AppHandle) -> Result<(), String> {
+async fn show_notification(title: String, body: String, app: tauri::AppHandle) -> Result<(), CommandError> {
     tauri_plugin_notification::NotificationExt::notification(&app)
         .builder()
         .title(title)
         .body(body)
         .show()
-        .map_err(|e| format!("Failed to show notification: {}", e))
+        .map_err(|e| CommandError::from(format!("Failed to show notification: {}", e)))
+}
+
+#[tauri::command]
+fn get_config() -> Result<AppConfig, CommandError> {
+    AppConfig::load().map_err(|e| CommandError::from(e.to_string()))
+}
+
+#[tauri::command]
+fn save_config(config: AppConfig) -> Result<(), CommandError> {
+    config.save().map_err(|e| CommandError::from(e.to_string()))
+}
+
+#[tauri::command]
+fn get_theme_state(app: tauri::AppHandle) -> ThemeState {
+    theme::current_state(&app, None)
+}
+
+// 修改主题配置并立即发送主题事件
+#[tauri::command]
+fn set_theme(theme: String, app: tauri::AppHandle) -> Result<ThemeState, CommandError> {
+    if !theme::is_valid_theme(&theme) {
+        return Err(format!("Unknown theme: {}", theme).into());
+    }
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
+    config.theme = theme;
+    config.save().map_err(|e| e.to_string())?;
+    Ok(theme::refresh(&app, None))
 }
 
+// 首次启动引导使用的环境探测，只读取目录和运行 java -version
 #[tauri::command]
-fn get_config() -> Result<AppConfig, String> {
-    AppConfig::load().map_err(|e| e.to_string())
+async fn detect_environment(workers: tauri::State<'_, WorkerPool>) -> Result<EnvironmentReport, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let result = workers.submit(move || environment::detect_environment(&config))?;
+    result.await.map_err(|_| CommandError::from("Environment detection task failed"))
 }
 
 #[tauri::command]
-fn save_config(config: AppConfig) -> Result<(), String> {
-    config.save().map_err(|e| e.to_string())
+fn complete_onboarding() -> Result<(), CommandError> {
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
+    config.onboarding_completed = true;
+    config.save().map_err(|e| CommandError::from(e.to_string()))
 }
 
 #[tauri::command]
-fn get_database_path() -> Result<String, String> {
+fn get_database_path() -> Result<String, CommandError> {
     let config = AppConfig::load().map_err(|e| e.to_string())?;
     Ok(config.get_database_path().to_string_lossy().to_string())
 }
 
 #[tauri::command]
-fn get_data_dir() -> Result<String, String> {
+fn get_data_dir() -> Result<String, CommandError> {
     let config = AppConfig::load().map_err(|e| e.to_string())?;
     Ok(config.get_data_dir().to_string_lossy().to_string())
 }
 
+// 扫描加入队列后立即返回扫描 id；priority 大的先开始，默认为 0
 #[tauri::command]
 async fn start_project_scan(
     project_path: String,
+    preset: Option<ScanPreset>,
+    priority: Option<i32>,
     app: tauri::AppHandle,
-    state: tauri::State<'_, ScanState>,
-) -> Result<String, String> {
-    let scan_id = uuid::Uuid::new_v4().to_string();
+) -> Result<String, CommandError> {
     let project_path_buf = PathBuf::from(&project_path);
     
     if !project_path_buf.exists() {
-        return Err("Project path does not exist".to_string());
+        return Err("Project path does not exist".into());
     }
     
-    let scan_id_clone = scan_id.clone();
+    // 指定的预设会保存到项目设置，下次未指定时沿用
+    let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+    let mut settings = store.get(&project_path);
+    let preset = match preset {
+        Some(preset) => {
+            settings.scan_preset = preset;
+            store.set(&project_path, settings);
+            store.save().map_err(|e| e.to_string())?;
+            preset
+        }
+        None => settings.scan_preset,
+    };
+    
+    Ok(app.state::<ScanQueue>().enqueue(&app, &project_path, preset, priority.unwrap_or(0)))
+}
+
+// 由扫描队列调用：在工作线程池中执行扫描并保存结果；通过 control 暂停或取消，取消后扫描提前结束，不保存结果
+async fn run_queued_scan(
+    app: &tauri::AppHandle,
+    scan_id: &str,
+    project_path: &str,
+    preset: ScanPreset,
+    control: ScanControl,
+) -> Result<(), String> {
     let app_clone = app.clone();
-    let state_clone = state.inner().clone();
     
-    // 在后台线程中执行扫描
-    tokio::spawn(async move {
-        let result = perform_project_scan(scan_id_clone.clone(), project_path, app_clone).await;
-        
-        // 保存扫描结果
-        if let Ok(scan_result) = result {
-            let mut scans = state_clone.lock().unwrap();
-            scans.insert(scan_id_clone, scan_result);
-        }
+    // 扫描是阻塞的文件 IO 和解析工作，放到独立的线程上执行，避免占用异步运行时和命令线程池
+    let worker_scan_id = scan_id.to_string();
+    let worker_project_path = project_path.to_string();
+    let scan = worker_pool::run_scan(move || {
+        // 整合包归档（CurseForge 导出包、.mrpack）先解压，再按普通目录扫描
+        let root = pack_import::prepare_scan_root(Path::new(&worker_project_path))?;
+        let mut scan_result =
+            perform_project_scan(worker_scan_id, root.path.to_string_lossy().to_string(), preset, app_clone, control)?;
+        scan_result.source_archive = root.archive.map(|archive| archive.to_string_lossy().to_string());
+        scan_result.warnings.extend(root.warnings);
+        Ok::<_, String>(scan_result)
     });
     
-    Ok(scan_id)
+    // 保存扫描结果
+    let mut scan_result = scan.await??;
+    if scan_result.source_archive.is_some() {
+        resolve_modpack_files(&mut scan_result).await;
+    }
+    app.state::<ScanState>().write().await.insert(scan_id.to_string(), scan_result);
+    record_workspace_scan(app, project_path, scan_id);
+    Ok(())
+}
+
+// 同时为多个项目排队扫描，返回与请求顺序一致的扫描 id
+#[tauri::command]
+async fn enqueue_scans(requests: Vec<ScanRequest>, app: tauri::AppHandle) -> Result<Vec<String>, CommandError> {
+    let mut scan_ids = Vec::with_capacity(requests.len());
+    for request in requests {
+        scan_ids.push(start_project_scan(request.project_path, request.preset, request.priority, app.clone()).await?);
+    }
+    Ok(scan_ids)
+}
+
+#[tauri::command]
+fn get_scan_queue(app: tauri::AppHandle) -> ScanQueueState {
+    app.state::<ScanQueue>().state()
+}
+
+// 调整或取消尚未开始的扫描
+#[tauri::command]
+fn set_scan_priority(scan_id: String, priority: i32, app: tauri::AppHandle) -> Result<(), CommandError> {
+    app.state::<ScanQueue>().set_priority(&app, &scan_id, priority).map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn cancel_queued_scan(scan_id: String, app: tauri::AppHandle) -> Result<(), CommandError> {
+    app.state::<ScanQueue>().cancel(&app, &scan_id).map_err(CommandError::from)
+}
+
+// 取消排队中或正在运行的扫描；运行中的扫描在当前步骤结束后停止
+#[tauri::command]
+fn cancel_scan(scan_id: String, app: tauri::AppHandle) -> Result<(), CommandError> {
+    app.state::<ScanQueue>().cancel_scan(&app, &scan_id).map_err(CommandError::from)
+}
+
+// 暂停正在运行的扫描，恢复后从暂停时的位置继续，已处理的 JAR 和目录不会重新扫描
+#[tauri::command]
+fn pause_scan(scan_id: String, app: tauri::AppHandle) -> Result<(), CommandError> {
+    app.state::<ScanQueue>().pause_scan(&app, &scan_id).map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn resume_scan(scan_id: String, app: tauri::AppHandle) -> Result<(), CommandError> {
+    app.state::<ScanQueue>().resume_scan(&app, &scan_id).map_err(CommandError::from)
+}
+
+// 列出导出包 manifest.json 中的 MOD 文件；配置了 API 密钥时通过 CurseForge API 解析文件名
+async fn resolve_modpack_files(scan_result: &mut ScanResult) {
+    let mut files = curseforge::read_manifest_files(Path::new(&scan_result.project_path));
+    if files.is_empty() {
+        return;
+    }
+
+    if let Some(api_key) = AppConfig::load().ok().and_then(|config| config.curseforge_api_key) {
+        if let Err(e) = curseforge::resolve_file_names(&mut files, &api_key).await {
+            scan_result.warnings.push(e);
+        }
+    }
+    scan_result.warnings.push(format!(
+        "{} mods listed in manifest.json are not included in the export and were not scanned",
+        files.len()
+    ));
+    scan_result.modpack_files = files;
+}
+
+// 下载整合包归档（.zip、.mrpack）后按普通扫描处理，可在安装前预览整合包；
+// checksum 为可选的 SHA-1、SHA-256 或 SHA-512，下载进度通过 "pack-download-progress" 事件上报
+#[tauri::command]
+async fn scan_from_url(
+    url: String,
+    checksum: Option<String>,
+    preset: Option<ScanPreset>,
+    priority: Option<i32>,
+    app: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    telemetry::record_feature("scan_from_url");
+    let archive = pack_import::download::download_pack(&app, &url, checksum.as_deref()).await?;
+    start_project_scan(archive.to_string_lossy().to_string(), preset, priority, app).await
 }
 
+// 按工作区 id 扫描，不需要前端传递原始路径
 #[tauri::command]
-fn get_scan_result(
+async fn start_workspace_scan(
+    workspace_id: String,
+    preset: Option<ScanPreset>,
+    priority: Option<i32>,
+    app: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    let store = WorkspaceStore::load().map_err(|e| e.to_string())?;
+    let project_path = store.get(&workspace_id)?.path.clone();
+    start_project_scan(project_path, preset, priority, app).await
+}
+
+// 扫描的路径属于某个工作区时更新其最近扫描记录
+fn record_workspace_scan(app: &tauri::AppHandle, project_path: &str, scan_id: &str) {
+    let Ok(mut store) = WorkspaceStore::load() else {
+        return;
+    };
+    if let Some(root) = store.record_scan(project_path, scan_id) {
+        if let Err(e) = store.save() {
+            eprintln!("Failed to save workspaces: {}", e);
+        }
+        workspace::notify(app, "scanned", &root);
+    }
+}
+
+#[tauri::command]
+async fn get_scan_summary(
     scan_id: String,
     state: tauri::State<'_, ScanState>,
-) -> Result<ScanResult, String> {
-    let scans = state.lock().unwrap();
+) -> Result<ScanSummary, CommandError> {
+    let scans = state.read().await;
     scans.get(&scan_id)
-        .cloned()
-        .ok_or_else(|| "Scan result not found".to_string())
+        .map(ScanResult::summary)
+        .ok_or_else(|| CommandError::from("Scan result not found"))
+}
+
+#[tauri::command]
+async fn get_scan_mods(
+    scan_id: String,
+    page: u32,
+    page_size: Option<u32>,
+    state: tauri::State<'_, ScanState>,
+) -> Result<Page<ModJarMetadata>, CommandError> {
+    let scans = state.read().await;
+    let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+    Ok(paginate(scan.mod_jars.iter(), page, page_size))
+}
+
+#[tauri::command]
+async fn get_scan_resources(
+    scan_id: String,
+    filter: Option<ResourceFilter>,
+    page: u32,
+    page_size: Option<u32>,
+    state: tauri::State<'_, ScanState>,
+) -> Result<Page<LanguageResource>, CommandError> {
+    let scans = state.read().await;
+    let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+    let filter = filter.unwrap_or_default();
+    Ok(paginate(scan.language_resources.iter().filter(|r| filter.matches(r)), page, page_size))
+}
+
+#[tauri::command]
+async fn get_scan_profile(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+) -> Result<ScanProfile, CommandError> {
+    let scans = state.read().await;
+    let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+    scan.profile.clone().ok_or_else(|| CommandError::from("Scan profile not available"))
+}
+
+#[tauri::command]
+async fn get_scan_delta(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+) -> Result<ScanDelta, CommandError> {
+    let scans = state.read().await;
+    let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+    scan.delta.clone().ok_or_else(|| CommandError::from("Scan delta not available"))
+}
+
+#[tauri::command]
+async fn clear_metadata_cache() -> Result<usize, CommandError> {
+    MetadataCache::open_default()?.clear().map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn list_content_scanners() -> Result<Vec<ScannerInfo>, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(scanners::list_scanners(&config))
+}
+
+#[tauri::command]
+async fn set_content_scanner_enabled(id: String, enabled: bool) -> Result<Vec<ScannerInfo>, CommandError> {
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
+    if !scanners::is_registered(&config, &id) {
+        return Err(format!("Unknown content scanner: {}", id).into());
+    }
+
+    config.disabled_scanners.retain(|disabled| disabled != &id);
+    if !enabled {
+        config.disabled_scanners.push(id);
+    }
+    config.save().map_err(|e| e.to_string())?;
+    Ok(scanners::list_scanners(&config))
+}
+
+// 扩展目录（data_dir/extensions）中的扩展及启用状态
+#[tauri::command]
+async fn list_extensions() -> Result<Vec<ExtensionInfo>, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(extensions::list_extensions(&config))
+}
+
+// 扩展是本地可执行程序，需要用户明确启用后才会在扫描和命令调用时运行
+#[tauri::command]
+async fn set_extension_enabled(id: String, enabled: bool) -> Result<Vec<ExtensionInfo>, CommandError> {
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
+    extensions::find_extension(&config, &id)?;
+
+    config.enabled_extensions.retain(|enabled_id| enabled_id != &id);
+    if enabled {
+        config.enabled_extensions.push(id);
+    }
+    config.save().map_err(|e| e.to_string())?;
+    Ok(extensions::list_extensions(&config))
+}
+
+// 调用扩展在 extension.json 中声明的命令；args 原样转发给扩展
+#[tauri::command]
+async fn invoke_extension_command(
+    extension_id: String,
+    command: String,
+    args: Option<Value>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<Value, CommandError> {
+    let result = workers.submit(move || {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        extensions::invoke_command(&config, &extension_id, &command, &args.unwrap_or(Value::Null))
+    })?;
+    result.await.map_err(|_| "Extension task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 高级扫描：需要解析所有类文件，耗时较长，只在用户主动请求时运行
+#[tauri::command]
+async fn scan_hardcoded_strings(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<HardcodedStringReport, CommandError> {
+    let project_path = {
+        let scans = state.read().await;
+        let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+        PathBuf::from(&scan.project_path)
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        class_strings::scan_hardcoded_strings(
+            mod_filter::project_jar_paths(&project_path, &config, &limits, cache.as_ref()),
+            &limits,
+            cache.as_ref(),
+            &ArchiveDiagnostics::default(),
+        )
+    })?;
+
+    result.await.map_err(|_| CommandError::from("Hardcoded string scan failed"))
+}
+
+// 离线模式：不经过后端，直接在本地 SQLite 库中创建项目
+#[tauri::command]
+async fn create_local_project_from_scan(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<LocalProject, CommandError> {
+    telemetry::record_feature("local_project");
+    let (project_path, project_name, resources) = {
+        let scans = state.read().await;
+        let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+        let project_name = scan
+            .modpack_manifest
+            .as_ref()
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| "New Project".to_string());
+        (scan.project_path.clone(), project_name, scan.language_resources.clone())
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let mut files = local_project::collect_lang_files(
+            mod_filter::project_jar_paths(Path::new(&project_path), &config, &limits, cache.as_ref()),
+            resources,
+            &limits,
+            cache.as_ref(),
+        );
+        if let Some(hooks) = ScriptHooks::for_project(&project_path)? {
+            local_project::apply_script_hooks(&mut files, &hooks)?;
+        }
+        local_project::create_local_project(&config.get_database_path(), &project_name, Some(&project_path), files)
+    })?;
+
+    result.await.map_err(|_| "Local project task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 把本地项目打包为 .thproj，供其他成员在没有共享服务器时接手
+#[tauri::command]
+async fn export_project_bundle(
+    project_uid: String,
+    output_path: String,
+    dry_run: Option<bool>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<WriteOutcome<BundleExport>, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let dry_run = dry_run.unwrap_or(false);
+    let result =
+        workers.submit(move || project_bundle::export_bundle(&config, &project_uid, Path::new(&output_path), dry_run))?;
+    result.await.map_err(|_| "Project bundle export task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 导入 .thproj；project_path 为本机的项目目录，未提供时沿用项目包中的路径
+#[tauri::command]
+async fn import_project_bundle(
+    bundle_path: String,
+    project_path: Option<String>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<BundleImport, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let result = workers.submit(move || project_bundle::import_bundle(&config, Path::new(&bundle_path), project_path))?;
+    result.await.map_err(|_| "Project bundle import task failed".to_string())?.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn get_scan_result(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+) -> Result<tauri::ipc::Response, CommandError> {
+    let scans = state.read().await;
+    let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+    ipc_payload::encode_payload(scan).map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn create_project_from_scan(
     scan_id: String,
     state: tauri::State<'_, ScanState>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let scan_result = {
-        let scans = state.lock().unwrap();
+        let scans = state.read().await;
         scans.get(&scan_id).cloned().ok_or("Scan result not found")?
     };
 
@@ -291,7 +874,7 @@ async fn create_project_from_scan(
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let response_json: serde_json::Value = response
@@ -311,7 +894,7 @@ async fn create_project_from_scan(
 // ==================== Local Data Commands ====================
 
 #[tauri::command]
-async fn get_local_entries() -> Result<Value, String> {
+async fn get_local_entries() -> Result<Value, CommandError> {
     let client = reqwest::Client::new();
     let url = format!("{}/local/entries", BACKEND_URL);
     
@@ -324,7 +907,7 @@ async fn get_local_entries() -> Result<Value, String> {
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let result = response
@@ -336,7 +919,7 @@ async fn get_local_entries() -> Result<Value, String> {
 }
 
 #[tauri::command]
-async fn get_mapping_plans() -> Result<Value, String> {
+async fn get_mapping_plans() -> Result<Value, CommandError> {
     let client = reqwest::Client::new();
     let url = format!("{}/local/plans", BACKEND_URL);
     
@@ -349,7 +932,7 @@ async fn get_mapping_plans() -> Result<Value, String> {
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let result = response
@@ -361,7 +944,7 @@ async fn get_mapping_plans() -> Result<Value, String> {
 }
 
 #[tauri::command]
-async fn get_outbound_queue() -> Result<Value, String> {
+async fn get_outbound_queue() -> Result<Value, CommandError> {
     let client = reqwest::Client::new();
     let url = format!("{}/local/queue", BACKEND_URL);
     
@@ -374,7 +957,7 @@ async fn get_outbound_queue() -> Result<Value, String> {
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let result = response
@@ -386,7 +969,7 @@ async fn get_outbound_queue() -> Result<Value, String> {
 }
 
 #[tauri::command]
-async fn get_mapping_links() -> Result<Value, String> {
+async fn get_mapping_links() -> Result<Value, CommandError> {
     let client = reqwest::Client::new();
     let url = format!("{}/local/links", BACKEND_URL);
     
@@ -399,7 +982,7 @@ async fn get_mapping_links() -> Result<Value, String> {
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let result = response
@@ -411,7 +994,7 @@ async fn get_mapping_links() -> Result<Value, String> {
 }
 
 #[tauri::command]
-async fn get_local_data_statistics() -> Result<Value, String> {
+async fn get_local_data_statistics() -> Result<Value, CommandError> {
     let client = reqwest::Client::new();
     let url = format!("{}/local/entries/statistics", BACKEND_URL);
     
@@ -424,7 +1007,7 @@ async fn get_local_data_statistics() -> Result<Value, String> {
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Backend API returned error: {} - {}", status, error_text));
+        return Err(format!("Backend API returned error: {} - {}", status, error_text).into());
     }
     
     let result = response
@@ -436,7 +1019,7 @@ async fn get_local_data_statistics() -> Result<Value, String> {
 }
 
 #[tauri::command]
-async fn import_local_data() -> Result<Value, String> {
+async fn import_local_data() -> Result<Value, CommandError> {
     // This is a placeholder. In a real app, you might trigger a background job.
     // For now, we'll just return a success message.
     Ok(serde_json::json!({ "message": "Import started successfully" }))
@@ -444,21 +1027,31 @@ async fn import_local_data() -> Result<Value, String> {
 
 
 // 执行项目扫描的主要逻辑
-async fn perform_project_scan(
+fn perform_project_scan(
     scan_id: String,
     project_path: String,
+    preset: ScanPreset,
     app: tauri::AppHandle,
+    control: ScanControl,
 ) -> Result<ScanResult, String> {
     let start_time = chrono::Utc::now();
     let project_path_buf = PathBuf::from(&project_path);
     
+    let progress = ProgressReporter::new(app.clone(), &scan_id).with_control(control.clone());
+    let profiler = ScanProfiler::new(&scan_id);
+    
     // 发送初始进度
-    emit_scan_progress(&app, &scan_id, "detecting_project_type", 0.0, "Detecting project type...", None, 0, 100, None).await;
+    profiler.phase("detecting_project_type");
+    progress.phase("detecting_project_type", 0.0, 10.0, "Detecting project type...", 0);
     
-    // 检测项目类型
-    let is_modpack = detect_modpack(&project_path_buf);
+    // 检测项目类型；开启 scan_archives_in_place 时 project_path 可以是未解压的 .mrpack 或 CurseForge 导出包
+    let config = AppConfig::load().unwrap_or_default();
+    let archive_scan = config.scan_archives_in_place
+        && (mrpack::is_mrpack(&project_path_buf) || curseforge::is_export_archive(&project_path_buf));
+    let is_modpack = archive_scan || detect_modpack(&project_path_buf);
     
-    emit_scan_progress(&app, &scan_id, "scanning_modpack", 10.0, "Scanning modpack manifest...", None, 10, 100, None).await;
+    profiler.phase("scanning_modpack");
+    progress.phase("scanning_modpack", 10.0, 30.0, "Scanning modpack manifest...", 0);
     
     // 扫描组合包清单
     let modpack_manifest = if is_modpack {
@@ -466,31 +1059,105 @@ async fn perform_project_scan(
     } else {
         None
     };
+    let resource_pack_meta = if is_modpack {
+        None
+    } else {
+        pack_meta::read_resource_pack_meta(&project_path_buf).ok()
+    };
+    check_cancelled(&progress)?;
+    
+    // 依次运行启用的内容扫描器（MOD JAR、资源包、KubeJS、CustomNPCs 等）
+    let limits = WorkLimits::from_config(&config).with_control(control);
+    let metadata_cache = MetadataCache::open_default().ok();
+    let mod_filter = ModFilter::for_project(&project_path);
+    let archive_diagnostics = ArchiveDiagnostics::default();
+    let context = ScanContext {
+        project_path: &project_path_buf,
+        preset,
+        limits: &limits,
+        cache: metadata_cache.as_ref(),
+        mod_filter: &mod_filter,
+        include_disabled_mods: config.include_disabled_mods,
+        mods_dir_depth: config.mods_dir_depth,
+        lenient_lang_json: config.lenient_lang_json,
+        progress: &progress,
+        profiler: &profiler,
+        archive_diagnostics: &archive_diagnostics,
+        mod_jars: OnceLock::new(),
+    };
+    let (modpack_manifest, output) = if archive_scan {
+        profiler.phase("scanning_archive");
+        progress.phase("scanning_archive", 30.0, 80.0, "Scanning modpack archive...", 0);
+        if mrpack::is_mrpack(&project_path_buf) {
+            let (index, output) = mrpack::scan_in_place(&context, &config)?;
+            (modrinth_manifest(&index), output)
+        } else {
+            let (manifest, output) = curseforge::scan_in_place(&context)?;
+            (curseforge_manifest(&manifest), output)
+        }
+    } else {
+        (modpack_manifest, scanners::run_scanners(&scanners::enabled_scanners(&config, preset), &context, 30.0, 80.0))
+    };
+    check_cancelled(&progress)?;
+    let ScanOutput {
+        mod_jars,
+        mut language_resources,
+        pack_modules,
+        hardcoded_strings,
+        mut warnings,
+        mut errors,
+        unreadable_archives,
+        rejected_lang_files,
+        ..
+    } = output;
+    // 无法打开的归档计为错误（扫描器已报告的不重复），归档中个别损坏的条目计为警告
+    for issue in &unreadable_archives {
+        if issue.entry.is_some() {
+            warnings.push(issue.describe());
+        } else if !errors.iter().any(|e| e.contains(&issue.path)) {
+            errors.push(issue.describe());
+        }
+    }
+    config.priority_rules.apply(&mut language_resources);
     
-    emit_scan_progress(&app, &scan_id, "scanning_mods", 30.0, "Scanning mod JAR files...", None, 30, 100, None).await;
-    
-    // 扫描模组JAR文件
-    let mod_jars = scan_mod_jars(&project_path_buf);
-    
-    emit_scan_progress(&app, &scan_id, "scanning_language_resources", 60.0, "Scanning language resources...", None, 60, 100, None).await;
-    
-    // 扫描语言资源
-    let language_resources = scan_language_resources(&project_path_buf);
-    
-    emit_scan_progress(&app, &scan_id, "generating_statistics", 80.0, "Generating statistics...", None, 80, 100, None).await;
+    profiler.phase("generating_statistics");
+    progress.phase("generating_statistics", 80.0, 90.0, "Generating statistics...", 0);
     
     // 计算统计信息
-    let total_mods = mod_jars.len() as u32;
+    // Jar-in-Jar 子 MOD 不单独计数；被禁用的 MOD 按设置决定是否计数
+    let total_mods = mod_jars
+        .iter()
+        .filter(|m| m.parent_mod_id.is_none() && (m.enabled || config.include_disabled_mods))
+        .count() as u32;
     let total_language_files = language_resources.len() as u32;
-    let total_translatable_keys: u32 = language_resources.iter().map(|r| r.key_count).sum();
+    let total_translatable_keys: u32 = language_resources.iter().map(|r| r.key_count).sum::<u32>()
+        + pack_modules.iter().map(|m| m.entries.len() as u32).sum::<u32>();
     let mut supported_locales: Vec<String> = language_resources.iter()
-        .map(|r| r.locale.clone())
+        .map(|r| r.locale.to_string())
         .collect::<std::collections::HashSet<String>>()
         .into_iter()
         .collect();
     supported_locales.sort();
+    let key_statistics = key_categories::summarize(&language_resources, &pack_modules);
+    let formatting = formatting_codes::stats(&language_resources);
+    warnings.extend(formatting_codes::malformed_warnings(&language_resources));
+    warnings.extend(scanners::parse_failure_warning(&language_resources));
+    
+    check_cancelled(&progress)?;
+    profiler.phase("computing_delta");
+    progress.phase("computing_delta", 90.0, 95.0, "Comparing with previous scan...", 0);
+    
+    // 与同一路径上一次扫描对比；quick 预设不提取原文，只比较 MOD
+    let jar_paths = (preset > ScanPreset::Quick).then(|| context.mod_jar_paths());
+    let snapshot = scan_delta::build_snapshot(&scan_id, &project_path_buf, &mod_jars, &pack_modules, jar_paths, &limits);
+    let delta = scan_delta::compute_delta(ScanSnapshot::load(&project_path).as_ref(), &snapshot);
+    if let Err(e) = snapshot.save() {
+        eprintln!("Failed to save scan snapshot: {}", e);
+    }
+    events::emit(&app, AppEvent::ScanDelta(&delta));
     
-    emit_scan_progress(&app, &scan_id, "validation", 95.0, "Validating scan results...", None, 95, 100, None).await;
+    profiler.phase("validation");
+    progress.phase("validation", 95.0, 100.0, "Validating scan results...", 0);
     
     // 创建扫描结果
     let scan_result = ScanResult {
@@ -499,46 +1166,47 @@ async fn perform_project_scan(
         scan_started_at: start_time.to_rfc3339(),
         scan_completed_at: Some(chrono::Utc::now().to_rfc3339()),
         modpack_manifest,
+        resource_pack_meta,
         mod_jars,
         language_resources,
+        pack_modules,
+        hardcoded_strings,
+        scan_preset: preset,
+        priority_rules: config.priority_rules.clone(),
+        source_archive: None,
+        archive_scan,
+        modpack_files: Vec::new(),
         total_mods,
         total_language_files,
         total_translatable_keys,
         supported_locales,
-        warnings: vec![], // TODO: Add actual warnings
-        errors: vec![], // TODO: Add actual errors
+        warnings,
+        errors,
+        unreadable_archives,
+        formatting,
+        key_statistics,
+        rejected_lang_files,
+        profile: Some(profiler.finish()),
+        delta: Some(delta),
     };
     
-    emit_scan_progress(&app, &scan_id, "completed", 100.0, "Scan completed successfully!", None, 100, 100, Some(0)).await;
+    progress.complete("completed", "Scan completed successfully!");
+    let duration_ms = (chrono::Utc::now() - start_time).num_milliseconds().max(0) as u64;
+    telemetry::record_scan(duration_ms, total_mods);
+    metrics::record_scan(duration_ms, total_mods, total_translatable_keys);
+    telemetry::record_feature(&format!("scan_{:?}", preset).to_lowercase());
     
     Ok(scan_result)
 }
 
-// 发送扫描进度事件
-async fn emit_scan_progress(
-    app: &tauri::AppHandle,
-    scan_id: &str,
-    phase: &str,
-    progress: f64,
-    message: &str,
-    current_file: Option<String>,
-    processed_files: u32,
-    total_files: u32,
-    estimated_remaining: Option<u32>,
-) {
-    let progress_data = ScanProgress {
-        scan_id: scan_id.to_string(),
-        phase: phase.to_string(),
-        progress,
-        message: message.to_string(),
-        current_file,
-        processed_files,
-        total_files,
-        estimated_remaining,
-        updated_at: chrono::Utc::now().to_rfc3339(),
-    };
-    
-    let _ = app.emit("scan-progress", progress_data);
+// 扫描阶段之间的检查点：暂停时在此等待恢复；被取消时发送最终的 cancelled 进度事件并返回错误，
+// 已收集的警告和归档读取记录随本次扫描的上下文丢弃，不影响同时运行的其他扫描
+fn check_cancelled(progress: &ProgressReporter) -> Result<(), String> {
+    if progress.checkpoint() {
+        return Ok(());
+    }
+    progress.cancelled("Scan cancelled");
+    Err(errors::tagged(ErrorCode::ScanCancelled, "Scan was cancelled"))
 }
 
 // 检测是否为组合包
@@ -587,14 +1255,17 @@ fn read_curseforge_manifest(project_path: &PathBuf) -> Option<ModpackManifest> {
     
     let content = fs::read_to_string(&manifest_path).ok()?;
     let json: serde_json::Value = serde_json::from_str(&content).ok()?;
-    
+    curseforge_manifest(&json)
+}
+
+fn curseforge_manifest(json: &serde_json::Value) -> Option<ModpackManifest> {
     Some(ModpackManifest {
         name: json.get("name")?.as_str()?.to_string(),
         version: json.get("version")?.as_str()?.to_string(),
         author: json.get("author")?.as_str().map(|s| s.to_string()),
         description: json.get("description")?.as_str().map(|s| s.to_string()),
         minecraft_version: json.get("minecraft")?.get("version")?.as_str()?.to_string(),
-        loader: "Forge".to_string(), // CurseForge 通常使用 Forge
+        loader: curseforge_loader_name(json).unwrap_or("Forge").to_string(),
         loader_version: json.get("minecraft")?.get("modLoaders")?.as_array()
             ?.first()?.get("id")?.as_str()?.to_string(),
         platform: "CurseForge".to_string(),
@@ -610,7 +1281,10 @@ fn read_modrinth_manifest(project_path: &PathBuf) -> Option<ModpackManifest> {
     
     let content = fs::read_to_string(&manifest_path).ok()?;
     let json: serde_json::Value = serde_json::from_str(&content).ok()?;
-    
+    modrinth_manifest(&json)
+}
+
+fn modrinth_manifest(json: &serde_json::Value) -> Option<ModpackManifest> {
     Some(ModpackManifest {
         name: json.get("name")?.as_str()?.to_string(),
         version: json.get("versionId")?.as_str()?.to_string(),
@@ -660,13 +1334,41 @@ fn read_multimc_manifest(project_path: &PathBuf) -> Option<ModpackManifest> {
         author: None,
         description: None,
         minecraft_version: extract_cfg_value(&content, "IntendedVersion").unwrap_or_else(|| "1.20.1".to_string()),
-        loader: "Forge".to_string(), // MultiMC 实例通常使用 Forge
+        loader: multimc_loader_name(project_path).unwrap_or("Forge").to_string(),
         loader_version: "latest".to_string(),
         platform: "MultiMC".to_string(),
         license: None,
     })
 }
 
+// CurseForge 的 modLoaders id 形如 "forge-47.2.0" 或 "neoforge-20.4.80"
+fn curseforge_loader_name(json: &serde_json::Value) -> Option<&'static str> {
+    let id = json.get("minecraft")?.get("modLoaders")?.as_array()?.first()?.get("id")?.as_str()?;
+    match id.split('-').next()? {
+        "neoforge" => Some("NeoForge"),
+        "forge" => Some("Forge"),
+        "fabric" => Some("Fabric"),
+        "quilt" => Some("Quilt"),
+        _ => None,
+    }
+}
+
+// MultiMC / Prism 在 mmc-pack.json 的组件列表中声明加载器
+fn multimc_loader_name(project_path: &PathBuf) -> Option<&'static str> {
+    let content = fs::read_to_string(project_path.join("mmc-pack.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let components = json.get("components")?.as_array()?;
+    [
+        ("net.neoforged", "NeoForge"),
+        ("net.minecraftforge", "Forge"),
+        ("org.quiltmc.quilt-loader", "Quilt"),
+        ("net.fabricmc.fabric-loader", "Fabric"),
+    ]
+    .iter()
+    .find(|(uid, _)| components.iter().any(|c| c.get("uid").and_then(|u| u.as_str()) == Some(*uid)))
+    .map(|(_, name)| *name)
+}
+
 fn extract_toml_value(content: &str, key: &str) -> Option<String> {
     for line in content.lines() {
         if let Some(stripped) = line.trim().strip_prefix(&format!("{} = ", key)) {
@@ -685,250 +1387,71 @@ fn extract_cfg_value(content: &str, key: &str) -> Option<String> {
     None
 }
 
-// 扫描模组JAR文件
-fn scan_mod_jars(project_path: &PathBuf) -> Vec<ModJarMetadata> {
-    let mut mod_jars = Vec::new();
-    
-    // 扫描 mods 目录
-    let mods_dir = project_path.join("mods");
-    if mods_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&mods_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().map_or(false, |ext| ext == "jar") {
-                    if let Some(mod_metadata) = extract_mod_metadata(&path) {
-                        mod_jars.push(mod_metadata);
-                    }
-                }
-            }
-        }
-    }
-    
-    // 如果是单个 JAR 文件项目
-    if let Ok(entries) = fs::read_dir(project_path) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "jar") {
-                if let Some(mod_metadata) = extract_mod_metadata(&path) {
-                    mod_jars.push(mod_metadata);
-                }
-            }
-        }
-    }
-    
-    mod_jars
-}
+// 新增的文件系统操作命令
 
-// 提取 MOD 元数据（简化版本）
-fn extract_mod_metadata(jar_path: &Path) -> Option<ModJarMetadata> {
-    // 从文件名推断基本信息
-    let file_name = jar_path.file_stem()?.to_str()?.to_string();
-    
-    // 尝试从文件名中提取版本信息
-    let (display_name, version) = parse_jar_filename(&file_name);
+#[tauri::command]
+async fn select_directory(app: tauri::AppHandle) -> Result<Option<String>, CommandError> {
+    // 使用 oneshot 通道异步等待对话框结果，避免在异步命令中阻塞运行时线程
+    let (sender, receiver) = tokio::sync::oneshot::channel();
     
-    // 在真实实现中，这里应该解压 JAR 文件并读取 fabric.mod.json 或 META-INF/mods.toml
-    Some(ModJarMetadata {
-        mod_id: file_name.to_lowercase().replace(' ', "_"),
-        display_name,
-        version,
-        loader: "unknown".to_string(), // 需要通过解析 JAR 内容确定
-        authors: vec!["Unknown".to_string()],
-        homepage: None,
-        description: Some(format!("Mod from {}", file_name)),
-        environment: "universal".to_string(),
-    })
-}
-
-// 从 JAR 文件名解析模组名和版本
-fn parse_jar_filename(filename: &str) -> (String, String) {
-    // 尝试不同的分隔符模式来提取版本
-    let separators = ["-", "_v", "_"];
+    app.dialog()
+        .file()
+        .set_title("Select Minecraft Directory")
+        .pick_folder(move |folder_path| {
+            let _ = sender.send(folder_path);
+        });
     
-    for sep in separators {
-        if let Some(pos) = filename.rfind(sep) {
-            let (name_part, version_part) = filename.split_at(pos);
-            let version_candidate = &version_part[sep.len()..];
-            
-            // 检查版本部分是否像版本号
-            if is_version_like(version_candidate) {
-                let clean_name = name_part.replace(['_', '-'], " ");
-                return (clean_name, version_candidate.to_string());
-            }
-        }
+    match receiver.await {
+        Ok(Some(path)) => Ok(Some(path.to_string())),
+        Ok(None) => Ok(None),
+        Err(_) => Err("Dialog operation failed".into()),
     }
-    
-    // 如果无法解析版本，返回文件名和默认版本
-    (filename.replace(['_', '-'], " "), "1.0.0".to_string())
 }
 
-// 检查字符串是否像版本号
-fn is_version_like(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
-    
-    // 版本号通常以数字开头
-    if !s.chars().next().unwrap_or('a').is_ascii_digit() {
-        return false;
-    }
+#[tauri::command]
+async fn scan_directory(
+    dir_path: String,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<SimpleScanResult, CommandError> {
+    let path = PathBuf::from(&dir_path);
     
-    // 版本号包含至少一个点
-    if !s.contains('.') {
-        return false;
+    if !path.exists() {
+        return Err("Directory does not exist".into());
     }
     
-    // 检查前几个字符是否符合版本格式（数字.数字）
-    let chars: Vec<char> = s.chars().take(5).collect();
-    if chars.len() >= 3 {
-        return chars[0].is_ascii_digit() && 
-               chars[1] == '.' && 
-               chars[2].is_ascii_digit();
-    }
+    // 递归扫描可能遍历整个实例目录，放到命令线程池中执行
+    let result = workers.submit(move || {
+        let mut jar_files = Vec::new();
+        let mut lang_files = Vec::new();
+        let mut modpack_files = Vec::new();
+        let mut errors = Vec::new();
+        let mut total_files = 0;
+        let mut rejected_lang_files = Vec::new();
+        
+        // 递归扫描目录
+        if let Err(e) = scan_directory_recursive(
+            &path,
+            &mut jar_files,
+            &mut lang_files,
+            &mut modpack_files,
+            &mut rejected_lang_files,
+            &mut total_files,
+            &mut errors,
+        ) {
+            errors.push(format!("Scan error: {}", e));
+        }
+        
+        SimpleScanResult {
+            total_files,
+            jar_files,
+            lang_files,
+            modpack_files,
+            errors,
+            rejected_lang_files,
+        }
+    })?;
     
-    false
-}
-
-// 扫描语言资源
-fn scan_language_resources(project_path: &PathBuf) -> Vec<LanguageResource> {
-    let mut language_resources = Vec::new();
-    
-    // 扫描资源包语言文件
-    scan_resourcepack_lang_files(project_path, &mut language_resources);
-    
-    // TODO: 扫描 JAR 文件中的语言资源（需要 ZIP 解压功能）
-    // scan_jar_lang_files(project_path, &mut language_resources);
-    
-    language_resources
-}
-
-// 扫描资源包语言文件
-fn scan_resourcepack_lang_files(project_path: &PathBuf, language_resources: &mut Vec<LanguageResource>) {
-    // 扫描 assets 目录结构
-    let assets_dir = project_path.join("assets");
-    if !assets_dir.exists() {
-        return;
-    }
-    
-    // 遍历 namespace 目录
-    if let Ok(namespace_entries) = fs::read_dir(&assets_dir) {
-        for namespace_entry in namespace_entries.flatten() {
-            if !namespace_entry.path().is_dir() {
-                continue;
-            }
-            
-            let namespace = namespace_entry.file_name().to_string_lossy().to_string();
-            let lang_dir = namespace_entry.path().join("lang");
-            
-            if lang_dir.exists() {
-                if let Ok(lang_entries) = fs::read_dir(&lang_dir) {
-                    for lang_entry in lang_entries.flatten() {
-                        let lang_path = lang_entry.path();
-                        if lang_path.is_file() && is_language_file(&lang_path) {
-                            if let Some(lang_resource) = create_language_resource(&lang_path, &namespace, "resourcepack") {
-                                language_resources.push(lang_resource);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-// 创建语言资源对象
-fn create_language_resource(lang_path: &Path, namespace: &str, source_type: &str) -> Option<LanguageResource> {
-    let file_name = lang_path.file_stem()?.to_str()?;
-    let locale = file_name.to_string();
-    
-    // 统计语言文件中的键数量
-    let key_count = count_language_keys(lang_path);
-    
-    Some(LanguageResource {
-        namespace: namespace.to_string(),
-        locale,
-        source_path: lang_path.to_string_lossy().to_string(),
-        source_type: source_type.to_string(),
-        key_count,
-        priority: 1,
-    })
-}
-
-// 统计语言文件中的键数量
-fn count_language_keys(lang_path: &Path) -> u32 {
-    if let Ok(content) = fs::read_to_string(lang_path) {
-        if lang_path.extension().map_or(false, |ext| ext == "json") {
-            // JSON 格式
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                if let Some(obj) = json.as_object() {
-                    return obj.len() as u32;
-                }
-            }
-        } else if lang_path.extension().map_or(false, |ext| ext == "lang") {
-            // .lang 格式 (key=value)
-            return content.lines().filter(|line| {
-                let trimmed = line.trim();
-                !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed.contains('=')
-            }).count() as u32;
-        }
-    }
-    0
-}
-
-// 新增的文件系统操作命令
-
-#[tauri::command]
-async fn select_directory(app: tauri::AppHandle) -> Result<Option<String>, String> {
-    use std::sync::{Arc, Mutex};
-    use std::sync::mpsc;
-    
-    let (sender, receiver) = mpsc::channel();
-    let sender = Arc::new(Mutex::new(Some(sender)));
-    
-    app.dialog()
-        .file()
-        .set_title("Select Minecraft Directory")
-        .pick_folder(move |folder_path| {
-            if let Ok(sender_guard) = sender.lock() {
-                if let Some(sender) = sender_guard.as_ref() {
-                    let _ = sender.send(folder_path);
-                }
-            }
-        });
-    
-    match receiver.recv() {
-        Ok(Some(path)) => Ok(Some(path.to_string())),
-        Ok(None) => Ok(None),
-        Err(_) => Err("Dialog operation failed".to_string()),
-    }
-}
-
-#[tauri::command]
-async fn scan_directory(dir_path: String) -> Result<SimpleScanResult, String> {
-    let path = Path::new(&dir_path);
-    
-    if !path.exists() {
-        return Err("Directory does not exist".to_string());
-    }
-    
-    let mut jar_files = Vec::new();
-    let mut lang_files = Vec::new();
-    let mut modpack_files = Vec::new();
-    let mut errors = Vec::new();
-    let mut total_files = 0;
-    
-    // 递归扫描目录
-    if let Err(e) = scan_directory_recursive(path, &mut jar_files, &mut lang_files, &mut modpack_files, &mut total_files, &mut errors) {
-        errors.push(format!("Scan error: {}", e));
-    }
-    
-    Ok(SimpleScanResult {
-        total_files,
-        jar_files,
-        lang_files,
-        modpack_files,
-        errors,
-    })
+    result.await.map_err(|_| CommandError::from("Scan task failed"))
 }
 
 fn scan_directory_recursive(
@@ -936,6 +1459,7 @@ fn scan_directory_recursive(
     jar_files: &mut Vec<FileInfo>,
     lang_files: &mut Vec<FileInfo>,
     modpack_files: &mut Vec<FileInfo>,
+    rejected_lang_files: &mut Vec<RejectedCandidate>,
     total_files: &mut u32,
     errors: &mut Vec<String>
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -963,17 +1487,21 @@ fn scan_directory_recursive(
                 modified_time,
             };
             
-            // 分类文件
+            // 分类文件；路径像语言文件但没有通过结构检查的另外记下
+            let detection = lang_detect::detect(&path);
             if file_name.ends_with(".jar") {
                 jar_files.push(file_info);
-            } else if is_language_file(&path) {
+            } else if matches!(detection, Detection::Language) {
                 lang_files.push(file_info);
             } else if is_modpack_file(&path) {
                 modpack_files.push(file_info);
             }
+            if let Detection::Rejected(candidate) = detection {
+                rejected_lang_files.push(candidate);
+            }
         } else if path.is_dir() {
             // 递归扫描子目录，但限制深度避免无限递归
-            if let Err(e) = scan_directory_recursive(&path, jar_files, lang_files, modpack_files, total_files, errors) {
+            if let Err(e) = scan_directory_recursive(&path, jar_files, lang_files, modpack_files, rejected_lang_files, total_files, errors) {
                 errors.push(format!("Error scanning {}: {}", path.display(), e));
             }
         }
@@ -982,17 +1510,6 @@ fn scan_directory_recursive(
     Ok(())
 }
 
-fn is_language_file(path: &Path) -> bool {
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        if ext == "json" || ext == "lang" {
-            if let Some(path_str) = path.to_str() {
-                return path_str.contains("lang") || path_str.contains("i18n");
-            }
-        }
-    }
-    false
-}
-
 fn is_modpack_file(path: &Path) -> bool {
     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
         matches!(name.to_lowercase().as_str(),
@@ -1005,7 +1522,7 @@ fn is_modpack_file(path: &Path) -> bool {
 }
 
 #[tauri::command]
-async fn parse_mod_jar(jar_path: String) -> Result<ModInfo, String> {
+async fn parse_mod_jar(jar_path: String) -> Result<ModInfo, CommandError> {
     // 这里应该实际解析JAR文件
     // 暂时返回模拟数据
     let path = Path::new(&jar_path);
@@ -1028,7 +1545,7 @@ async fn parse_mod_jar(jar_path: String) -> Result<ModInfo, String> {
 }
 
 #[tauri::command]
-async fn detect_project_type(dir_path: String) -> Result<String, String> {
+async fn detect_project_type(dir_path: String) -> Result<String, CommandError> {
     let path = Path::new(&dir_path);
     
     // 检查是否为modpack
@@ -1049,23 +1566,47 @@ async fn detect_project_type(dir_path: String) -> Result<String, String> {
     Ok("unknown".to_string())
 }
 
+// 选择目录时的快速预览：MOD 数量、估算键数、项目类型和加载器，不做完整扫描
+#[tauri::command]
+async fn quick_preview(path: String, workers: tauri::State<'_, WorkerPool>) -> Result<QuickPreview, CommandError> {
+    let result = workers.submit(move || quick_preview::quick_preview(Path::new(&path), &AppConfig::load().unwrap_or_default()))?;
+    result.await.map_err(|_| "Preview task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 读取 MOD 图标并返回 data URL；use_cache 为 true 时缓存到数据目录的 icons/ 下
+#[tauri::command]
+async fn extract_mod_icon(
+    jar_path: String,
+    use_cache: Option<bool>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<String, CommandError> {
+    let cache_dir = if use_cache.unwrap_or(false) {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        Some(config.get_data_dir().join("icons"))
+    } else {
+        None
+    };
+    let result = workers.submit(move || mod_icon::extract_mod_icon(Path::new(&jar_path), cache_dir.as_deref()))?;
+    result.await.map_err(|_| "Icon extraction task failed".to_string())?.map_err(CommandError::from)
+}
+
 #[tauri::command]
-async fn read_text_file(file_path: String) -> Result<String, String> {
+async fn read_text_file(file_path: String) -> Result<String, CommandError> {
     fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read file: {}", e))
+        .map_err(|e| CommandError::from(format!("Failed to read file: {}", e)))
 }
 
 #[tauri::command]
-async fn file_exists(file_path: String) -> Result<bool, String> {
+async fn file_exists(file_path: String) -> Result<bool, CommandError> {
     Ok(Path::new(&file_path).exists())
 }
 
 #[tauri::command]
-async fn list_directory(dir_path: String) -> Result<Vec<FileInfo>, String> {
+async fn list_directory(dir_path: String) -> Result<Vec<FileInfo>, CommandError> {
     let path = Path::new(&dir_path);
     
     if !path.exists() {
-        return Err("Directory does not exist".to_string());
+        return Err("Directory does not exist".into());
     }
     
     let mut files = Vec::new();
@@ -1097,34 +1638,731 @@ async fn list_directory(dir_path: String) -> Result<Vec<FileInfo>, String> {
 }
 
 #[tauri::command]
-async fn create_directory(dir_path: String) -> Result<(), String> {
+async fn check_instance_running(path: String) -> Result<InstanceStatus, CommandError> {
+    Ok(instance_guard::detect_running_instance(Path::new(&path)))
+}
+
+#[tauri::command]
+async fn create_directory(dir_path: String) -> Result<(), CommandError> {
     fs::create_dir_all(&dir_path)
-        .map_err(|e| format!("Failed to create directory: {}", e))
+        .map_err(|e| CommandError::from(format!("Failed to create directory: {}", e)))
 }
 
 #[tauri::command]
-async fn copy_file(source_path: String, dest_path: String) -> Result<(), String> {
+async fn copy_file(source_path: String, dest_path: String, force: Option<bool>) -> Result<(), CommandError> {
+    instance_guard::ensure_not_running(Path::new(&dest_path), force.unwrap_or(false))?;
+    undo_journal::record(
+        OperationKind::Overwrite,
+        &format!("Copy {} to {}", source_path, dest_path),
+        &[Path::new(&dest_path)],
+    )?;
     fs::copy(&source_path, &dest_path)
         .map(|_| ())
-        .map_err(|e| format!("Failed to copy file: {}", e))
+        .map_err(|e| CommandError::from(format!("Failed to copy file: {}", e)))
 }
 
 #[tauri::command]
-async fn delete_file(file_path: String) -> Result<(), String> {
+async fn delete_file(file_path: String, force: Option<bool>) -> Result<(), CommandError> {
     let path = Path::new(&file_path);
+    instance_guard::ensure_not_running(path, force.unwrap_or(false))?;
+    undo_journal::record(OperationKind::Delete, &format!("Delete {}", file_path), &[path])?;
     
     if path.is_dir() {
         fs::remove_dir_all(path)
-            .map_err(|e| format!("Failed to delete directory: {}", e))
+            .map_err(|e| CommandError::from(format!("Failed to delete directory: {}", e)))
     } else {
         fs::remove_file(path)
-            .map_err(|e| format!("Failed to delete file: {}", e))
+            .map_err(|e| CommandError::from(format!("Failed to delete file: {}", e)))
+    }
+}
+
+// 撤销最近一次删除、覆盖或语言文件修改；没有可撤销的操作时返回 None
+#[tauri::command]
+async fn undo_last_operation() -> Result<Option<JournalOperation>, CommandError> {
+    undo_journal::undo_last().map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn list_undo_operations() -> Result<Vec<JournalOperation>, CommandError> {
+    undo_journal::list_operations().map_err(CommandError::from)
+}
+
+// 应用自身的界面文本，由后端加载并与基准语言比对
+#[tauri::command]
+fn get_ui_catalog(locale: String) -> Result<UiCatalog, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    ui_catalog::get_catalog(&config, &locale).map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn list_ui_locales() -> Result<Vec<String>, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(ui_catalog::list_locales(&config))
+}
+
+// ==================== Validation Commands ====================
+
+#[tauri::command]
+async fn check_glyph_coverage(pack_path: String, locale: String) -> Result<GlyphCoverageReport, CommandError> {
+    validation::glyphs::check_glyph_coverage(Path::new(&pack_path), &locale).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn check_translation_consistency(
+    pack_path: String,
+    source_locale: String,
+    target_locale: String,
+) -> Result<ConsistencyReport, CommandError> {
+    validation::consistency::check_consistency(Path::new(&pack_path), &source_locale, &target_locale).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn harmonize_translations(
+    pack_path: String,
+    target_locale: String,
+    source: String,
+    translation: String,
+    keys: Vec<String>,
+    force: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<WriteOutcome<HarmonizeResult>, CommandError> {
+    telemetry::record_feature("harmonize");
+    let pack_path = Path::new(&pack_path);
+    if dry_run.unwrap_or(false) {
+        let planned = validation::consistency::plan_harmonize(pack_path, &target_locale, &translation, &keys)?;
+        let mut report = preflight::check(planned, true);
+        preflight::check_instance(&mut report, pack_path, force.unwrap_or(false));
+        return Ok(WriteOutcome::DryRun(report));
+    }
+
+    instance_guard::ensure_not_running(pack_path, force.unwrap_or(false))?;
+    validation::consistency::harmonize_translations(pack_path, &target_locale, &source, &translation, &keys)
+        .map(WriteOutcome::Applied).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn check_whitespace(
+    pack_path: String,
+    source_locale: String,
+    target_locale: String,
+) -> Result<Vec<ValidationIssue>, CommandError> {
+    Ok(validation::whitespace::check_whitespace(Path::new(&pack_path), &source_locale, &target_locale))
+}
+
+#[tauri::command]
+fn list_validation_profiles() -> Vec<ValidationProfile> {
+    validation::profiles::builtin_profiles()
+}
+
+#[tauri::command]
+async fn run_validation(
+    project_path: String,
+    profile: Option<String>,
+    target_locale: String,
+) -> Result<tauri::ipc::Response, CommandError> {
+    telemetry::record_feature("validation");
+    let report = validation::profiles::run_validation(&project_path, profile.as_deref(), &target_locale)?;
+    ipc_payload::encode_payload(&report).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn get_project_statistics(
+    project_path: String,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<ProjectStatistics, CommandError> {
+    // 统计需要读取全部语言文件并对每个语言运行校验，放到命令线程池中执行
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let (included, excluded) = ModFilter::for_project(&project_path).partition_jar_paths(
+            scanners::mod_jars::collect_mod_jar_paths(Path::new(&project_path), &config),
+            &limits,
+            cache.as_ref(),
+        );
+        let excluded_namespaces = mod_filter::excluded_namespaces(&included, &excluded, &limits);
+        let statistics = statistics::project_statistics(&project_path, &config.get_database_path(), &excluded_namespaces)?;
+        metrics::record_coverage(&statistics);
+        Ok(statistics)
+    })?;
+
+    result.await.map_err(|_| "Statistics task failed".to_string())?
+}
+
+#[tauri::command]
+fn get_project_settings(project_path: String) -> ProjectSettings {
+    project_settings::load_project_settings(&project_path)
+}
+
+#[tauri::command]
+fn save_project_settings(project_path: String, settings: ProjectSettings) -> Result<(), CommandError> {
+    validation::profiles::find_profile(&settings.validation_profile)?;
+
+    let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+    store.set(&project_path, settings);
+    store.save().map_err(|e| CommandError::from(e.to_string()))
+}
+
+// ==================== Mod Update Commands ====================
+
+#[tauri::command]
+async fn diff_mod_jars(
+    old_jar_path: String,
+    new_jar_path: String,
+    locale: Option<String>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<ModDiffReport, CommandError> {
+    let locale = locale.unwrap_or_else(|| validation::profiles::SOURCE_LOCALE.to_string());
+    let result = workers.submit(move || {
+        mod_diff::diff_mod_jars(Path::new(&old_jar_path), Path::new(&new_jar_path), &locale)
+    })?;
+
+    result.await.map_err(|_| "Diff task failed".to_string())?.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn propose_key_migrations(
+    old_jar_path: String,
+    new_jar_path: String,
+    project_path: String,
+    target_locale: String,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<MigrationProposal, CommandError> {
+    let result = workers.submit(move || {
+        key_migration::propose_key_migrations(
+            Path::new(&old_jar_path),
+            Path::new(&new_jar_path),
+            Path::new(&project_path),
+            &target_locale,
+        )
+    })?;
+
+    result.await.map_err(|_| "Migration proposal task failed".to_string())?.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn apply_key_migrations(
+    project_path: String,
+    target_locale: String,
+    mod_id: String,
+    migrations: Vec<KeyMigration>,
+    force: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<WriteOutcome<MigrationResult>, CommandError> {
+    telemetry::record_feature("key_migration");
+    let project_path = Path::new(&project_path);
+    if dry_run.unwrap_or(false) {
+        let planned = key_migration::plan_key_migrations(project_path, &target_locale, &migrations)?;
+        let mut report = preflight::check(planned, true);
+        preflight::check_instance(&mut report, project_path, force.unwrap_or(false));
+        return Ok(WriteOutcome::DryRun(report));
+    }
+
+    instance_guard::ensure_not_running(project_path, force.unwrap_or(false))?;
+    key_migration::apply_key_migrations(project_path, &target_locale, &mod_id, &migrations).map(WriteOutcome::Applied).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn check_translation_pack_freshness(
+    scan_id: String,
+    pack_path: String,
+    target_locale: String,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<PackFreshnessReport, CommandError> {
+    let project_path = {
+        let scans = state.read().await;
+        let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+        PathBuf::from(&scan.project_path)
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let owners = project_settings::load_project_settings(&project_path.to_string_lossy()).namespace_owners;
+        pack_check::check_pack_freshness(
+            Path::new(&pack_path),
+            mod_filter::project_jar_paths(&project_path, &config, &limits, cache.as_ref()),
+            &target_locale,
+            &limits,
+            cache.as_ref(),
+            &owners,
+        )
+    })?;
+
+    result.await.map_err(|_| "Pack check task failed".to_string())?.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn detect_namespace_collisions(
+    scan_id: String,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<Vec<NamespaceCollision>, CommandError> {
+    let project_path = {
+        let scans = state.read().await;
+        let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+        scan.project_path.clone()
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let jars = pack_check::load_jar_namespaces(
+            mod_filter::project_jar_paths(Path::new(&project_path), &config, &limits, cache.as_ref()),
+            &limits,
+            cache.as_ref(),
+        );
+        let owners = project_settings::load_project_settings(&project_path).namespace_owners;
+        namespace_owner::detect_namespace_collisions(&jars, &owners)
+    })?;
+
+    result.await.map_err(|_| CommandError::from("Namespace collision task failed"))
+}
+
+#[tauri::command]
+async fn detect_key_conflicts(
+    scan_id: String,
+    locale: String,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<KeyConflictReport, CommandError> {
+    let (project_path, resources) = {
+        let scans = state.read().await;
+        let scan = scans.get(&scan_id).ok_or("Scan result not found")?;
+        (PathBuf::from(&scan.project_path), scan.language_resources.clone())
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        key_conflicts::detect_key_conflicts(
+            &locale,
+            mod_filter::project_jar_paths(&project_path, &config, &limits, cache.as_ref()),
+            resources,
+            &config.priority_rules,
+            &limits,
+            cache.as_ref(),
+        )
+    })?;
+
+    result.await.map_err(|_| CommandError::from("Key conflict task failed"))
+}
+
+// 指定命名空间归属的 MOD；mod_id 为空时取消指定
+#[tauri::command]
+fn pin_namespace_owner(project_path: String, namespace: String, mod_id: Option<String>) -> Result<(), CommandError> {
+    let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+    let mut settings = store.get(&project_path);
+    match mod_id {
+        Some(mod_id) => settings.namespace_owners.insert(namespace, mod_id),
+        None => settings.namespace_owners.remove(&namespace),
+    };
+    store.set(&project_path, settings);
+    store.save().map_err(|e| CommandError::from(e.to_string()))
+}
+
+// 设置项目中不参与扫描、统计和导出的 MOD
+#[tauri::command]
+fn set_excluded_mods(project_path: String, mod_ids: Vec<String>) -> Result<ProjectSettings, CommandError> {
+    let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+    let mut settings = store.get(&project_path);
+    settings.excluded_mods = mod_ids.into_iter().filter(|id| !id.trim().is_empty()).collect();
+    store.set(&project_path, settings.clone());
+    store.save().map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+// 设置项目的 Rhai 脚本钩子；保存前先编译校验，None 表示不使用脚本
+#[tauri::command]
+fn set_script_hooks(project_path: String, script_path: Option<String>) -> Result<ProjectSettings, CommandError> {
+    let script_path = script_path.filter(|path| !path.trim().is_empty());
+    if let Some(script) = &script_path {
+        script_hooks::validate_script(&project_path, script)?;
     }
+
+    let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+    let mut settings = store.get(&project_path);
+    settings.script_hooks = script_path;
+    store.set(&project_path, settings.clone());
+    store.save().map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+#[tauri::command]
+async fn get_migration_audit_log(project_path: Option<String>) -> Result<Vec<MigrationAuditEntry>, CommandError> {
+    let log = key_migration::MigrationAuditLog::load().map_err(|e| e.to_string())?;
+    Ok(log
+        .entries
+        .into_iter()
+        .filter(|entry| project_path.as_ref().map_or(true, |path| &entry.project_path == path))
+        .collect())
+}
+
+// ==================== Workspace Commands ====================
+
+#[tauri::command]
+fn list_workspaces() -> Result<Vec<WorkspaceRoot>, CommandError> {
+    let store = WorkspaceStore::load().map_err(|e| e.to_string())?;
+    Ok(store.list())
+}
+
+#[tauri::command]
+fn add_workspace(name: String, path: String, app: tauri::AppHandle) -> Result<WorkspaceRoot, CommandError> {
+    let mut store = WorkspaceStore::load().map_err(|e| e.to_string())?;
+    let root = store.add(&name, &path)?;
+    store.save().map_err(|e| e.to_string())?;
+    workspace::allow_in_fs_scope(&app, &root);
+    workspace::notify(&app, "added", &root);
+    Ok(root)
+}
+
+#[tauri::command]
+fn update_workspace(
+    id: String,
+    name: Option<String>,
+    pinned: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<WorkspaceRoot, CommandError> {
+    let mut store = WorkspaceStore::load().map_err(|e| e.to_string())?;
+    let root = store.update(&id, name, pinned)?;
+    store.save().map_err(|e| e.to_string())?;
+    workspace::notify(&app, "updated", &root);
+    Ok(root)
+}
+
+#[tauri::command]
+fn remove_workspace(id: String, app: tauri::AppHandle) -> Result<WorkspaceRoot, CommandError> {
+    let mut store = WorkspaceStore::load().map_err(|e| e.to_string())?;
+    let root = store.remove(&id)?;
+    store.save().map_err(|e| e.to_string())?;
+    workspace::notify(&app, "removed", &root);
+    Ok(root)
+}
+
+// ==================== Remote Server Commands ====================
+
+#[tauri::command]
+fn list_remote_servers() -> Result<Vec<RemoteServer>, CommandError> {
+    let store = RemoteServerStore::load().map_err(|e| e.to_string())?;
+    Ok(store.servers)
+}
+
+// 新增或修改远程服务器；secret 为密码或私钥口令，只保存到系统钥匙串
+#[tauri::command]
+fn save_remote_server(mut server: RemoteServer, secret: Option<String>) -> Result<RemoteServer, CommandError> {
+    if server.id.is_empty() {
+        server.id = uuid::Uuid::new_v4().to_string();
+    }
+    if let Some(secret) = secret {
+        remote_sftp::store_secret(&server.id, &secret)?;
+    }
+
+    let mut store = RemoteServerStore::load().map_err(|e| e.to_string())?;
+    store.upsert(server.clone());
+    store.save().map_err(|e| e.to_string())?;
+    Ok(server)
+}
+
+#[tauri::command]
+fn remove_remote_server(id: String) -> Result<(), CommandError> {
+    let mut store = RemoteServerStore::load().map_err(|e| e.to_string())?;
+    store.remove(&id)?;
+    store.save().map_err(|e| e.to_string())?;
+    remote_sftp::delete_secret(&id).map_err(CommandError::from)
+}
+
+// 把远程服务器上的 MOD、任务和插件语言文件下载到本地同步目录，返回的目录可直接扫描
+#[tauri::command]
+async fn sync_remote_server(id: String, workers: tauri::State<'_, WorkerPool>) -> Result<RemoteSyncReport, CommandError> {
+    telemetry::record_feature("remote_sync");
+    let server = RemoteServerStore::load().map_err(|e| e.to_string())?.get(&id)?.clone();
+    let limit = SyncSchedule::load().map_err(|e| e.to_string())?.download_limit();
+
+    let result = workers.submit(move || {
+        let (report, fingerprint) = remote_sftp::sync_server(&server, limit).inspect_err(|_| metrics::record_sync(None))?;
+        metrics::record_sync(Some(&report));
+        // 首次连接时记录主机密钥指纹
+        if server.host_key_sha256.is_none() {
+            let mut store = RemoteServerStore::load().map_err(|e| e.to_string())?;
+            store.upsert(RemoteServer { host_key_sha256: Some(fingerprint), ..server });
+            store.save().map_err(|e| e.to_string())?;
+        }
+        Ok(report)
+    })?;
+
+    result.await.map_err(|_| "Remote sync task failed".to_string())?
+}
+
+#[tauri::command]
+fn get_sync_schedule() -> Result<SyncSchedule, CommandError> {
+    SyncSchedule::load().map_err(|e| CommandError::from(e.to_string()))
+}
+
+// 保存同步计划和限速设置；上次同步的时间和错误由调度器维护
+#[tauri::command]
+fn save_sync_schedule(schedule: SyncSchedule) -> Result<SyncSchedule, CommandError> {
+    let previous = SyncSchedule::load().map_err(|e| e.to_string())?;
+    let schedule = SyncSchedule {
+        last_sync_at: previous.last_sync_at,
+        last_error: previous.last_error,
+        ..schedule
+    };
+    schedule.save().map_err(|e| e.to_string())?;
+    Ok(schedule)
+}
+
+#[tauri::command]
+fn get_sync_status() -> Result<SyncStatus, CommandError> {
+    sync_scheduler::status().map_err(CommandError::from)
+}
+
+// 立即同步；force 为 true 时忽略免打扰时段和计费网络设置
+#[tauri::command]
+async fn run_sync_now(force: Option<bool>, app: tauri::AppHandle) -> Result<SyncRunReport, CommandError> {
+    telemetry::record_feature("remote_sync");
+    sync_scheduler::sync_now(&app, force.unwrap_or(false)).await.map_err(CommandError::from)
+}
+
+// ==================== Telemetry Commands ====================
+
+// 后端发送的全部事件及负载版本，供前端生成和校验事件绑定
+#[tauri::command]
+fn list_event_types() -> Vec<EventType> {
+    events::event_types()
+}
+
+// 全部错误代码及处理建议，供前端显示和支持人员对照
+#[tauri::command]
+fn list_error_codes() -> Vec<ErrorCodeInfo> {
+    errors::error_codes()
+}
+
+// 上次异常退出时未完成的扫描、未发送的队列和未保存的编辑内容；没有时返回 null
+#[tauri::command]
+fn get_recoverable_session() -> Result<Option<SessionState>, CommandError> {
+    session::recoverable().map_err(CommandError::from)
+}
+
+// 用户选择恢复或放弃后调用
+#[tauri::command]
+fn discard_recoverable_session() -> Result<(), CommandError> {
+    session::discard_recoverable().map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn save_unsent_queue(items: Vec<Value>) -> Result<(), CommandError> {
+    session::set_unsent_queue(items).map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn save_editor_buffer(id: String, content: String, context: Option<Value>) -> Result<(), CommandError> {
+    session::save_editor_buffer(&id, content, context).map_err(CommandError::from)
+}
+
+#[tauri::command]
+fn discard_editor_buffer(id: String) -> Result<(), CommandError> {
+    session::discard_editor_buffer(&id).map_err(CommandError::from)
+}
+
+// 本次运行累计的扫描、导出、同步指标和最近计算的覆盖率
+#[tauri::command]
+fn get_metrics() -> MetricsSnapshot {
+    metrics::snapshot()
+}
+
+// 本地累计的统计，即下一次上报的完整内容
+#[tauri::command]
+fn preview_telemetry() -> Result<TelemetryReport, CommandError> {
+    let stats = TelemetryStats::load().map_err(|e| e.to_string())?;
+    Ok(stats.report())
+}
+
+// 关闭时同时删除本地累计的统计
+#[tauri::command]
+fn set_telemetry_enabled(enabled: bool) -> Result<(), CommandError> {
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
+    config.telemetry_enabled = enabled;
+    config.save().map_err(|e| e.to_string())?;
+    if !enabled {
+        TelemetryStats::clear().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ==================== Database Encryption Commands ====================
+
+#[tauri::command]
+fn get_database_encryption() -> Result<EncryptionStatus, CommandError> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    database::encryption_status(&config.get_database_path()).map_err(CommandError::from)
+}
+
+// 把现有的明文数据库迁移为 SQLCipher 加密数据库，密钥保存在系统钥匙串中
+#[tauri::command]
+async fn enable_database_encryption(workers: tauri::State<'_, WorkerPool>) -> Result<EncryptionStatus, CommandError> {
+    let result = workers.submit(|| {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        database::enable_encryption(&config.get_database_path())
+    })?;
+    result.await.map_err(|_| "Encryption task failed".to_string())?.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn disable_database_encryption(workers: tauri::State<'_, WorkerPool>) -> Result<EncryptionStatus, CommandError> {
+    let result = workers.submit(|| {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        database::disable_encryption(&config.get_database_path())
+    })?;
+    result.await.map_err(|_| "Decryption task failed".to_string())?.map_err(CommandError::from)
+}
+
+// ==================== Backup Commands ====================
+
+#[tauri::command]
+fn get_backup_settings() -> Result<BackupSettings, CommandError> {
+    BackupSettings::load().map_err(|e| CommandError::from(e.to_string()))
+}
+
+// 保存备份目标和计划；secret 为 WebDAV 密码或 S3 私有访问密钥，只保存到系统钥匙串
+#[tauri::command]
+fn save_backup_settings(settings: BackupSettings, secret: Option<String>) -> Result<BackupSettings, CommandError> {
+    let previous = BackupSettings::load().map_err(|e| e.to_string())?;
+    let settings = BackupSettings {
+        last_backup_at: previous.last_backup_at,
+        last_error: previous.last_error,
+        ..settings
+    };
+
+    match (&settings.target, secret) {
+        (None, _) => backup::delete_secret()?,
+        (Some(_), Some(secret)) => backup::store_secret(&secret)?,
+        (Some(_), None) => {}
+    }
+    settings.save().map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+#[tauri::command]
+async fn run_backup(workers: tauri::State<'_, WorkerPool>) -> Result<RemoteBackup, CommandError> {
+    telemetry::record_feature("backup");
+    backup::backup_now(&workers).await.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn list_backups() -> Result<Vec<RemoteBackup>, CommandError> {
+    backup::list_backups().await.map_err(CommandError::from)
+}
+
+// 恢复后原数据库保留在同目录下的 .before-restore 文件中
+#[tauri::command]
+async fn restore_backup(name: String, workers: tauri::State<'_, WorkerPool>) -> Result<Vec<String>, CommandError> {
+    backup::restore_backup(&name, &workers).await.map_err(CommandError::from)
+}
+
+// 只保留最近 keep_last 份备份（至少一份），返回删除的备份名
+#[tauri::command]
+async fn prune_backups(keep_last: u32) -> Result<Vec<String>, CommandError> {
+    backup::prune_backups(keep_last).await.map_err(CommandError::from)
+}
+
+// ==================== Export Commands ====================
+
+#[tauri::command]
+fn list_export_profiles() -> Vec<ExportProfile> {
+    export::builtin_profiles()
+}
+
+#[tauri::command]
+async fn get_pack_description(pack_path: String) -> Result<PackDescription, CommandError> {
+    pack_meta::read_pack_description(Path::new(&pack_path)).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn export_translations(
+    request: ExportRequest,
+    state: tauri::State<'_, ScanState>,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<WriteOutcome<ExportResult>, CommandError> {
+    telemetry::record_feature("export");
+    let profile = export::find_profile(&request.profile)?;
+    // 试运行时实例正在运行只记入预检报告
+    if !request.dry_run {
+        instance_guard::ensure_not_running(Path::new(&request.output_dir), request.force)?;
+    }
+
+    // 从扫描结果所在目录收集 JAR，用于判断各命名空间的运行环境
+    let instance_path = {
+        let scans = state.read().await;
+        let scan = scans.get(&request.scan_id).ok_or("Scan result not found")?;
+        PathBuf::from(&scan.project_path)
+    };
+
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let result = export::export_translations(
+            &request,
+            &profile,
+            scanners::mod_jars::collect_mod_jar_paths(&instance_path, &config),
+            &limits,
+            cache.as_ref(),
+        );
+        match &result {
+            Ok(WriteOutcome::DryRun(_)) => {}
+            Ok(WriteOutcome::Applied(result)) => {
+                metrics::record_export(Some(result.outputs.iter().map(|output| output.key_count).sum()))
+            }
+            Err(_) => metrics::record_export(None),
+        }
+        result
+    })?;
+
+    result.await.map_err(|_| "Export task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 把已有的资源包转换到另一个 Minecraft 版本；提供 project_path 时报告目标实例中已不存在的键
+#[tauri::command]
+async fn convert_resource_pack(
+    request: PackConversionRequest,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<WriteOutcome<PackConversionResult>, CommandError> {
+    telemetry::record_feature("convert_pack");
+    let result = workers.submit(move || {
+        let config = AppConfig::load().unwrap_or_default();
+        let limits = WorkLimits::from_config(&config);
+        let cache = MetadataCache::open_default().ok();
+        let jar_paths = request
+            .project_path
+            .as_ref()
+            .map(|project_path| scanners::mod_jars::collect_mod_jar_paths(Path::new(project_path), &config))
+            .unwrap_or_default();
+        pack_convert::convert_pack(&request, jar_paths, &limits, cache.as_ref())
+    })?;
+
+    result.await.map_err(|_| "Pack conversion task failed".to_string())?.map_err(CommandError::from)
+}
+
+// 合并多个社区翻译包，返回自动合并的条目和需要在界面中选择的冲突
+#[tauri::command]
+async fn merge_translation_packs(
+    pack_paths: Vec<String>,
+    target_locale: String,
+    workers: tauri::State<'_, WorkerPool>,
+) -> Result<PackMergeResult, CommandError> {
+    telemetry::record_feature("merge_packs");
+    let result = workers.submit(move || pack_merge::merge_packs(&pack_paths, &target_locale))?;
+
+    result.await.map_err(|_| "Pack merge task failed".to_string())?.map_err(CommandError::from)
 }
 
 fn main() {
     // 初始化扫描状态
-    let scan_state: ScanState = Arc::new(Mutex::new(HashMap::new()));
+    let scan_state: ScanState = Arc::new(RwLock::new(HashMap::new()));
     
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
@@ -1135,6 +2373,8 @@ fn main() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .manage(scan_state)
+        .manage(WorkerPool::for_commands())
+        .manage(ScanQueue::default())
         .setup(|app| {
             // 应用启动时的初始化逻辑
             let window = app.get_webview_window("main").unwrap();
@@ -1149,9 +2389,55 @@ fn main() {
                 eprintln!("Failed to initialize app config: {}", e);
             }
             
-            // 在开发模式下打开开发者工具
+            // 提前报告配置有误的自定义提取规则，扫描时这些规则只记为错误
+            if let Ok(config) = AppConfig::load() {
+                for error in config.extraction_rules.iter().filter_map(|rule| scanners::custom_rules::validate(rule).err()) {
+                    eprintln!("{}", error);
+                }
+            }
+            
+            // 开始新的会话；上次异常退出留下的内容转存为待恢复的会话
+            if let Err(e) = session::begin() {
+                eprintln!("Failed to start session: {}", e);
+            }
+            
+            // 已批准的项目根目录加入文件系统插件的放行范围
+            if let Ok(store) = WorkspaceStore::load() {
+                for root in &store.roots {
+                    workspace::allow_in_fs_scope(app.handle(), root);
+                }
+            }
+            
+            // 发送初始主题，并在系统主题变化或窗口重新获得焦点（高对比度可能已切换）时更新
+            theme::refresh(app.handle(), window.theme().ok());
+            let handle = app.handle().clone();
+            window.on_window_event(move |event| match event {
+                tauri::WindowEvent::ThemeChanged(system_theme) => {
+                    theme::refresh(&handle, Some(*system_theme));
+                }
+                tauri::WindowEvent::Focused(true) => {
+                    theme::refresh(&handle, None);
+                }
+                _ => {}
+            });
+            
+            // 定时备份
+            backup::start_scheduler(app.handle().clone());
+            
+            // 定时同步远程服务器并发送未发送的队列
+            sync_scheduler::start_scheduler(app.handle().clone());
+            
+            // 用户开启后才会记录和上报匿名使用统计
+            telemetry::start_reporter();
+            
+            // 配置了监听地址时提供本机指标服务
+            metrics::start_exporter();
+            
+            // 在开发模式下打开开发者工具，并在界面文本文件变化时通知前端
             #[cfg(debug_assertions)]
             window.open_devtools();
+            #[cfg(debug_assertions)]
+            ui_catalog::start_dev_watcher(app.handle().clone());
             
             Ok(())
         })
@@ -1165,11 +2451,39 @@ fn main() {
             show_notification,
             get_config,
             save_config,
+            get_theme_state,
+            set_theme,
+            detect_environment,
+            complete_onboarding,
             get_database_path,
             get_data_dir,
             start_project_scan,
+            scan_from_url,
+            start_workspace_scan,
+            enqueue_scans,
+            get_scan_queue,
+            set_scan_priority,
+            cancel_queued_scan,
+            cancel_scan,
+            pause_scan,
+            resume_scan,
             get_scan_result,
+            get_scan_summary,
+            get_scan_mods,
+            get_scan_resources,
+            get_scan_profile,
+            get_scan_delta,
+            clear_metadata_cache,
+            list_content_scanners,
+            set_content_scanner_enabled,
+            list_extensions,
+            set_extension_enabled,
+            invoke_extension_command,
+            scan_hardcoded_strings,
             create_project_from_scan,
+            create_local_project_from_scan,
+            export_project_bundle,
+            import_project_bundle,
             get_local_entries,
             get_mapping_plans,
             get_outbound_queue,
@@ -1181,13 +2495,91 @@ fn main() {
             scan_directory,
             parse_mod_jar,
             detect_project_type,
+            quick_preview,
+            extract_mod_icon,
             read_text_file,
             file_exists,
             list_directory,
             create_directory,
             copy_file,
-            delete_file
+            delete_file,
+            check_instance_running,
+            undo_last_operation,
+            list_undo_operations,
+            get_ui_catalog,
+            list_ui_locales,
+            // 校验命令
+            check_glyph_coverage,
+            check_translation_consistency,
+            harmonize_translations,
+            check_whitespace,
+            list_validation_profiles,
+            run_validation,
+            get_project_statistics,
+            get_project_settings,
+            save_project_settings,
+            // MOD 更新命令
+            diff_mod_jars,
+            propose_key_migrations,
+            apply_key_migrations,
+            get_migration_audit_log,
+            check_translation_pack_freshness,
+            detect_namespace_collisions,
+            pin_namespace_owner,
+            set_excluded_mods,
+            set_script_hooks,
+            detect_key_conflicts,
+            // 工作区命令
+            list_workspaces,
+            add_workspace,
+            update_workspace,
+            remove_workspace,
+            // 远程服务器命令
+            list_remote_servers,
+            save_remote_server,
+            remove_remote_server,
+            sync_remote_server,
+            get_sync_schedule,
+            save_sync_schedule,
+            get_sync_status,
+            run_sync_now,
+            // 事件命令
+            list_event_types,
+            list_error_codes,
+            // 会话恢复命令
+            get_recoverable_session,
+            discard_recoverable_session,
+            save_unsent_queue,
+            save_editor_buffer,
+            discard_editor_buffer,
+            // 使用统计命令
+            get_metrics,
+            preview_telemetry,
+            set_telemetry_enabled,
+            // 数据库加密命令
+            get_database_encryption,
+            enable_database_encryption,
+            disable_database_encryption,
+            // 备份命令
+            get_backup_settings,
+            save_backup_settings,
+            run_backup,
+            list_backups,
+            restore_backup,
+            prune_backups,
+            // 导出命令
+            list_export_profiles,
+            get_pack_description,
+            export_translations,
+            convert_resource_pack,
+            merge_translation_packs
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // 正常退出时删除会话文件，下次启动不提示恢复
+            if let tauri::RunEvent::Exit = event {
+                session::end();
+            }
+        });
 }
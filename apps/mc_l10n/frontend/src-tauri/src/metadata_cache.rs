@@ -0,0 +1,130 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::config::AppConfig;
+use crate::mod_metadata::ModJarMetadata;
+
+// 元数据解析逻辑变化时递增，旧版本的缓存条目会被视为未命中
+pub const METADATA_PARSER_VERSION: i64 = 4;
+
+// 以 JAR 的 SHA-1 为键的元数据缓存，不同项目共享同一份缓存
+pub struct MetadataCache {
+    conn: Mutex<Connection>,
+}
+
+impl MetadataCache {
+    pub fn open(db_path: &Path) -> Result<Self, String> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create cache directory: {}", e))?;
+        }
+
+        let conn = Connection::open(db_path).map_err(|e| format!("Failed to open metadata cache: {}", e))?;
+        // 早期版本按命名空间和语言建立的 lang_map_cache 表从未写入过，直接重建
+        if conn.prepare("SELECT namespace FROM lang_map_cache LIMIT 0").is_ok() {
+            conn.execute_batch("DROP TABLE lang_map_cache;")
+                .map_err(|e| format!("Failed to initialize metadata cache: {}", e))?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS mod_metadata_cache (
+                sha1 TEXT PRIMARY KEY,
+                parser_version INTEGER NOT NULL,
+                metadata_json TEXT NOT NULL,
+                cached_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS lang_map_cache (
+                sha1 TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                parser_version INTEGER NOT NULL,
+                files_json TEXT NOT NULL,
+                cached_at TEXT NOT NULL,
+                PRIMARY KEY (sha1, scope)
+            );",
+        )
+        .map_err(|e| format!("Failed to initialize metadata cache: {}", e))?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    // 打开数据目录下的默认缓存库
+    pub fn open_default() -> Result<Self, String> {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        Self::open(&config.get_data_dir().join("cache").join("metadata_cache.db"))
+    }
+
+    pub fn get_metadata(&self, sha1: &str) -> Option<ModJarMetadata> {
+        let conn = self.conn.lock().ok()?;
+        let metadata_json: String = conn
+            .query_row(
+                "SELECT metadata_json FROM mod_metadata_cache WHERE sha1 = ?1 AND parser_version = ?2",
+                params![sha1, METADATA_PARSER_VERSION],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()??;
+
+        serde_json::from_str(&metadata_json).ok()
+    }
+
+    pub fn put_metadata(&self, sha1: &str, metadata: &ModJarMetadata) {
+        let Ok(metadata_json) = serde_json::to_string(metadata) else {
+            return;
+        };
+        let Ok(conn) = self.conn.lock() else {
+            return;
+        };
+
+        // 缓存写入失败不影响扫描结果
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO mod_metadata_cache (sha1, parser_version, metadata_json, cached_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![sha1, METADATA_PARSER_VERSION, metadata_json, chrono::Utc::now().to_rfc3339()],
+        );
+    }
+
+    // JAR 中已解析的语言文件。scope 区分扫描器以及影响解析结果的扫描选项
+    pub fn get_lang_map<T: DeserializeOwned>(&self, sha1: &str, scope: &str) -> Option<Vec<T>> {
+        let conn = self.conn.lock().ok()?;
+        let files_json: String = conn
+            .query_row(
+                "SELECT files_json FROM lang_map_cache WHERE sha1 = ?1 AND scope = ?2 AND parser_version = ?3",
+                params![sha1, scope, METADATA_PARSER_VERSION],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()??;
+
+        serde_json::from_str(&files_json).ok()
+    }
+
+    pub fn put_lang_map<T: Serialize>(&self, sha1: &str, scope: &str, files: &[T]) {
+        let Ok(files_json) = serde_json::to_string(files) else {
+            return;
+        };
+        let Ok(conn) = self.conn.lock() else {
+            return;
+        };
+
+        // 缓存写入失败不影响扫描结果
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO lang_map_cache (sha1, scope, parser_version, files_json, cached_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![sha1, scope, METADATA_PARSER_VERSION, files_json, chrono::Utc::now().to_rfc3339()],
+        );
+    }
+
+    // 清空缓存，返回删除的条目数
+    pub fn clear(&self) -> Result<usize, String> {
+        let conn = self.conn.lock().map_err(|_| "Metadata cache lock poisoned".to_string())?;
+        let metadata = conn
+            .execute("DELETE FROM mod_metadata_cache", [])
+            .map_err(|e| e.to_string())?;
+        let lang_maps = conn
+            .execute("DELETE FROM lang_map_cache", [])
+            .map_err(|e| e.to_string())?;
+        Ok(metadata + lang_maps)
+    }
+}
@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::config::AppConfig;
+use crate::remote_sftp::RemoteSyncReport;
+use crate::statistics::ProjectStatistics;
+
+// 本次运行期间累计的指标；计数器在应用重启后归零，符合 Prometheus 计数器的约定
+static METRICS: Mutex<MetricsSnapshot> = Mutex::new(MetricsSnapshot::new());
+
+// 读取请求头的超时，避免异常连接占住监听线程
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsFormat {
+    #[default]
+    Prometheus,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub scans_total: u64,
+    pub scan_duration_seconds_sum: f64,
+    pub last_scan_mods: u32,
+    pub last_scan_keys: u32,
+    pub exports_total: u64,
+    pub export_failures_total: u64,
+    pub exported_keys_total: u64,
+    pub syncs_total: u64,
+    pub sync_failures_total: u64,
+    pub sync_files_downloaded_total: u64,
+    pub sync_bytes_downloaded_total: u64,
+    // 项目路径 -> 语言 -> 覆盖率，在计算项目统计时更新
+    pub coverage: BTreeMap<String, BTreeMap<String, LocaleCoverage>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocaleCoverage {
+    pub translated_keys: u32,
+    pub missing_keys: u32,
+    pub coverage: f64,
+}
+
+impl MetricsSnapshot {
+    const fn new() -> Self {
+        Self {
+            scans_total: 0,
+            scan_duration_seconds_sum: 0.0,
+            last_scan_mods: 0,
+            last_scan_keys: 0,
+            exports_total: 0,
+            export_failures_total: 0,
+            exported_keys_total: 0,
+            syncs_total: 0,
+            sync_failures_total: 0,
+            sync_files_downloaded_total: 0,
+            sync_bytes_downloaded_total: 0,
+            coverage: BTreeMap::new(),
+        }
+    }
+
+    // Prometheus 文本格式
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP th_suite_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE th_suite_{} {}", name, kind);
+            let _ = writeln!(out, "th_suite_{} {}", name, value);
+        };
+        metric("scans_total", "counter", "Completed scans.", self.scans_total.to_string());
+        metric("scan_duration_seconds_sum", "counter", "Total time spent in completed scans.", self.scan_duration_seconds_sum.to_string());
+        metric("last_scan_mods", "gauge", "Mods found by the last scan.", self.last_scan_mods.to_string());
+        metric("last_scan_keys", "gauge", "Translatable keys found by the last scan.", self.last_scan_keys.to_string());
+        metric("exports_total", "counter", "Completed exports.", self.exports_total.to_string());
+        metric("export_failures_total", "counter", "Failed exports.", self.export_failures_total.to_string());
+        metric("exported_keys_total", "counter", "Keys written by exports.", self.exported_keys_total.to_string());
+        metric("syncs_total", "counter", "Completed remote server syncs.", self.syncs_total.to_string());
+        metric("sync_failures_total", "counter", "Failed remote server syncs.", self.sync_failures_total.to_string());
+        metric("sync_files_downloaded_total", "counter", "Files downloaded by remote syncs.", self.sync_files_downloaded_total.to_string());
+        metric("sync_bytes_downloaded_total", "counter", "Bytes downloaded by remote syncs.", self.sync_bytes_downloaded_total.to_string());
+
+        let gauges = [
+            ("translation_coverage", "Translated share of source keys, 0 to 1."),
+            ("translated_keys", "Translated keys per project and locale."),
+            ("missing_keys", "Untranslated keys per project and locale."),
+        ];
+        for (name, help) in gauges {
+            let _ = writeln!(out, "# HELP th_suite_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE th_suite_{} gauge", name);
+            for (project, locales) in &self.coverage {
+                for (locale, coverage) in locales {
+                    let value = match name {
+                        "translation_coverage" => coverage.coverage.to_string(),
+                        "translated_keys" => coverage.translated_keys.to_string(),
+                        _ => coverage.missing_keys.to_string(),
+                    };
+                    let _ = writeln!(
+                        out,
+                        "th_suite_{}{{project=\"{}\",locale=\"{}\"}} {}",
+                        name,
+                        escape_label(project),
+                        escape_label(locale),
+                        value
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    fn render(&self, format: MetricsFormat) -> String {
+        match format {
+            MetricsFormat::Prometheus => self.to_prometheus(),
+            MetricsFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+pub fn snapshot() -> MetricsSnapshot {
+    METRICS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// 修改指标后同步写入文件输出
+fn update(apply: impl FnOnce(&mut MetricsSnapshot)) {
+    let snapshot = {
+        let mut metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+        apply(&mut metrics);
+        metrics.clone()
+    };
+    if let Err(e) = write_file_sink(&snapshot) {
+        eprintln!("Failed to write metrics file: {}", e);
+    }
+}
+
+pub fn record_scan(duration_ms: u64, total_mods: u32, total_keys: u32) {
+    update(|metrics| {
+        metrics.scans_total += 1;
+        metrics.scan_duration_seconds_sum += duration_ms as f64 / 1000.0;
+        metrics.last_scan_mods = total_mods;
+        metrics.last_scan_keys = total_keys;
+    });
+}
+
+pub fn record_export(key_count: Option<u32>) {
+    update(|metrics| match key_count {
+        Some(key_count) => {
+            metrics.exports_total += 1;
+            metrics.exported_keys_total += key_count as u64;
+        }
+        None => metrics.export_failures_total += 1,
+    });
+}
+
+pub fn record_sync(report: Option<&RemoteSyncReport>) {
+    update(|metrics| match report {
+        Some(report) => {
+            metrics.syncs_total += 1;
+            metrics.sync_files_downloaded_total += report.files_downloaded as u64;
+            metrics.sync_bytes_downloaded_total += report.bytes_downloaded;
+        }
+        None => metrics.sync_failures_total += 1,
+    });
+}
+
+pub fn record_coverage(statistics: &ProjectStatistics) {
+    update(|metrics| {
+        let locales = statistics
+            .locales
+            .iter()
+            .map(|locale| {
+                let coverage = LocaleCoverage {
+                    translated_keys: locale.translated_keys,
+                    missing_keys: locale.missing_keys,
+                    coverage: locale.coverage,
+                };
+                (locale.locale.clone(), coverage)
+            })
+            .collect();
+        metrics.coverage.insert(statistics.project_path.clone(), locales);
+    });
+}
+
+// 配置了 metrics_file 时按 metrics_format 覆盖写入，先写临时文件再改名
+fn write_file_sink(snapshot: &MetricsSnapshot) -> Result<(), String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let Some(path) = config.metrics_file.as_deref().filter(|path| !path.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    fs::write(&partial, snapshot.render(config.metrics_format)).map_err(|e| e.to_string())?;
+    fs::rename(&partial, path).map_err(|e| e.to_string())
+}
+
+// 配置了 metrics_listen 时在本机地址上提供 /metrics（Prometheus）和 /metrics.json
+pub fn start_exporter() {
+    let Ok(config) = AppConfig::load() else {
+        return;
+    };
+    let Some(listen) = config.metrics_listen.filter(|listen| !listen.trim().is_empty()) else {
+        return;
+    };
+
+    // 指标中包含项目路径，只允许监听回环地址
+    let address: SocketAddr = match listen.trim().parse() {
+        Ok(address) => address,
+        Err(e) => {
+            eprintln!("Invalid metrics_listen address {}: {}", listen, e);
+            return;
+        }
+    };
+    if !address.ip().is_loopback() {
+        eprintln!("metrics_listen must be a loopback address, got {}", address);
+        return;
+    }
+
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to listen on {} for metrics: {}", address, e);
+            return;
+        }
+    };
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = serve(stream) {
+                eprintln!("Metrics request failed: {}", e);
+            }
+        }
+    });
+}
+
+fn serve(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // 读完请求头，忽略内容
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (status, content_type, body) = match path {
+        "/metrics" => ("200 OK", "text/plain; version=0.0.4", snapshot().to_prometheus()),
+        "/metrics.json" => ("200 OK", "application/json", snapshot().render(MetricsFormat::Json)),
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
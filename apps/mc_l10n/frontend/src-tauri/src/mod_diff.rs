@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::concurrency::WorkLimits;
+use crate::lang;
+use crate::mod_metadata::{extract_mod_metadata, ModJarMetadata};
+use crate::profiling::ModTiming;
+
+// 单个键在两个版本之间的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyChange {
+    pub namespace: String,
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+// 同一 MOD 两个版本之间的原文差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModDiffReport {
+    pub mod_id: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub locale: String,
+    pub added: Vec<KeyChange>,
+    pub removed: Vec<KeyChange>,
+    pub changed: Vec<KeyChange>,
+    pub unchanged_count: u32,
+}
+
+// 比较同一 MOD 新旧两个 JAR 中指定语言的全部键
+pub fn diff_mod_jars(old_jar: &Path, new_jar: &Path, locale: &str) -> Result<ModDiffReport, String> {
+    let old_metadata = read_metadata(old_jar)?;
+    let new_metadata = read_metadata(new_jar)?;
+    if old_metadata.mod_id != new_metadata.mod_id {
+        return Err(format!(
+            "JARs belong to different mods: {} and {}",
+            old_metadata.mod_id, new_metadata.mod_id
+        ));
+    }
+
+    let old_entries = lang::load_jar_locale_entries(old_jar, locale)?;
+    let new_entries = lang::load_jar_locale_entries(new_jar, locale)?;
+    let empty = HashMap::new();
+
+    let mut report = ModDiffReport {
+        mod_id: new_metadata.mod_id,
+        old_version: old_metadata.version,
+        new_version: new_metadata.version,
+        locale: locale.to_string(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged_count: 0,
+    };
+
+    let namespaces: BTreeSet<&String> = old_entries.keys().chain(new_entries.keys()).collect();
+    for namespace in namespaces {
+        let old = old_entries.get(namespace).unwrap_or(&empty);
+        let new = new_entries.get(namespace).unwrap_or(&empty);
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+        for key in keys {
+            let change = |old_value: Option<&String>, new_value: Option<&String>| KeyChange {
+                namespace: namespace.clone(),
+                key: key.clone(),
+                old_value: old_value.cloned(),
+                new_value: new_value.cloned(),
+            };
+
+            match (old.get(key), new.get(key)) {
+                (None, Some(value)) => report.added.push(change(None, Some(value))),
+                (Some(value), None) => report.removed.push(change(Some(value), None)),
+                (Some(old_value), Some(new_value)) if old_value != new_value => {
+                    report.changed.push(change(Some(old_value), Some(new_value)))
+                }
+                _ => report.unchanged_count += 1,
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn read_metadata(jar_path: &Path) -> Result<ModJarMetadata, String> {
+    if !jar_path.is_file() {
+        return Err(format!("JAR file does not exist: {}", jar_path.display()));
+    }
+
+    extract_mod_metadata(jar_path, &WorkLimits::new(1, 1), None, &mut ModTiming::default())
+        .ok_or_else(|| format!("Failed to read mod metadata from {}", jar_path.display()))
+}
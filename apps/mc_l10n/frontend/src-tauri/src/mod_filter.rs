@@ -0,0 +1,121 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::config::AppConfig;
+use crate::jar::JarReader;
+use crate::metadata_cache::MetadataCache;
+use crate::mod_metadata::{extract_mod_metadata, ModJarMetadata};
+use crate::profiling::ModTiming;
+use crate::project_settings;
+use crate::scanners::mod_jars::collect_mod_jar_paths;
+
+// 项目中被用户排除的 MOD（前置库、服务端工具等），扫描、统计和导出都跳过这些 MOD
+#[derive(Debug, Clone, Default)]
+pub struct ModFilter {
+    excluded: BTreeSet<String>,
+}
+
+impl ModFilter {
+    pub fn new(excluded: BTreeSet<String>) -> Self {
+        Self { excluded }
+    }
+
+    // 读取项目设置中的排除列表
+    pub fn for_project(project_path: &str) -> Self {
+        Self::new(project_settings::load_project_settings(project_path).excluded_mods)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.excluded.is_empty()
+    }
+
+    pub fn excludes(&self, mod_id: &str) -> bool {
+        self.excluded.contains(mod_id)
+    }
+
+    // 按 MOD id 把 JAR 分为保留和排除两组，保持原有顺序；排除列表为空时不读取元数据。
+    // 无法读取元数据的 JAR 按文件名推断的 id 判断
+    pub fn partition_jar_paths(
+        &self,
+        jar_paths: Vec<PathBuf>,
+        limits: &WorkLimits,
+        cache: Option<&MetadataCache>,
+    ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        if self.is_empty() {
+            return (jar_paths, Vec::new());
+        }
+
+        let (included, excluded) = self.partition_with_metadata(jar_paths, limits, cache);
+        (included.into_iter().map(|(path, _)| path).collect(), excluded)
+    }
+
+    // 同 partition_jar_paths，但总是读取元数据，保留的 JAR 附带其元数据供调用方复用
+    pub fn partition_with_metadata(
+        &self,
+        jar_paths: Vec<PathBuf>,
+        limits: &WorkLimits,
+        cache: Option<&MetadataCache>,
+    ) -> (Vec<(PathBuf, Option<ModJarMetadata>)>, Vec<PathBuf>) {
+        let decisions = limits.map(jar_paths, |limits, path| {
+            let metadata = extract_mod_metadata(&path, limits, cache, &mut ModTiming::default());
+            let excluded = metadata.as_ref().is_some_and(|metadata| self.excludes(&metadata.mod_id));
+            (path, metadata, excluded)
+        });
+
+        let (excluded, included): (Vec<_>, Vec<_>) = decisions.into_iter().partition(|(_, _, excluded)| *excluded);
+        (
+            included.into_iter().map(|(path, metadata, _)| (path, metadata)).collect(),
+            excluded.into_iter().map(|(path, _, _)| path).collect(),
+        )
+    }
+}
+
+// 项目中参与扫描、统计和导出的 MOD JAR
+pub fn project_jar_paths(
+    project_path: &Path,
+    config: &AppConfig,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> Vec<PathBuf> {
+    ModFilter::for_project(&project_path.to_string_lossy())
+        .partition_jar_paths(collect_mod_jar_paths(project_path, config), limits, cache)
+        .0
+}
+
+// 只由被排除的 MOD 提供的命名空间；与保留的 MOD 共用的命名空间不算
+pub fn excluded_namespaces(included: &[PathBuf], excluded: &[PathBuf], limits: &WorkLimits) -> HashSet<String> {
+    if excluded.is_empty() {
+        return HashSet::new();
+    }
+
+    let collect = |paths: &[PathBuf]| -> HashSet<String> {
+        limits
+            .map(paths.to_vec(), |limits, path| {
+                let _io = limits.io.acquire();
+                lang_namespaces(&path)
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    };
+    let kept = collect(included);
+    collect(excluded).into_iter().filter(|namespace| !kept.contains(namespace)).collect()
+}
+
+// 只列出 assets/<namespace>/lang/ 下的条目，不解析语言文件
+fn lang_namespaces(jar_path: &Path) -> Vec<String> {
+    let Ok(jar) = JarReader::open(jar_path) else {
+        return Vec::new();
+    };
+    let namespaces: BTreeSet<String> = jar
+        .entry_names()
+        .iter()
+        .filter_map(|name| {
+            let rest = name.strip_prefix("assets/")?;
+            let (namespace, path) = rest.split_once('/')?;
+            path.starts_with("lang/").then(|| namespace.to_string())
+        })
+        .collect();
+    namespaces.into_iter().collect()
+}
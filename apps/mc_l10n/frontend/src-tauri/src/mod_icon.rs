@@ -0,0 +1,77 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fs;
+use std::path::Path;
+
+use crate::errors::{self, ErrorCode};
+use crate::hashing;
+use crate::jar::JarReader;
+use crate::mod_metadata::read_icon_path;
+
+// 超过该大小的图标不返回，避免把大图整个传给前端
+const MAX_ICON_BYTES: usize = 1024 * 1024;
+
+// 超过该边长的图标视为无效
+const MAX_ICON_DIMENSION: u32 = 2048;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+// 读取 MOD 描述文件声明的 PNG 图标，返回 data URL；提供 cache_dir 时按 JAR 的 SHA-1 缓存解码结果
+pub fn extract_mod_icon(jar_path: &Path, cache_dir: Option<&Path>) -> Result<String, String> {
+    let cache_path = match cache_dir {
+        Some(dir) => {
+            let sha1 = hashing::sha1_file(jar_path)
+                .map_err(|e| format!("Failed to read {}: {}", jar_path.display(), e))?;
+            Some(dir.join(format!("{}.png", sha1)))
+        }
+        None => None,
+    };
+    if let Some(png) = cache_path.as_deref().and_then(|path| fs::read(path).ok()) {
+        if validate_png(&png).is_ok() {
+            return Ok(data_url(&png));
+        }
+    }
+
+    let mut jar = JarReader::open(jar_path)?;
+    let icon_path = read_icon_path(&mut jar)
+        .ok_or_else(|| errors::tagged(ErrorCode::NotFound, format!("{} declares no icon", jar_path.display())))?;
+    let png = jar.read_entry(&icon_path).ok_or_else(|| {
+        errors::tagged(ErrorCode::NotFound, format!("Icon {} not found in {}", icon_path, jar_path.display()))
+    })?;
+    validate_png(&png).map_err(|e| format!("Invalid icon {} in {}: {}", icon_path, jar_path.display(), e))?;
+
+    // 缓存写入失败不影响返回结果
+    if let Some(path) = cache_path {
+        let _ = write_cache(&path, &png);
+    }
+    Ok(data_url(&png))
+}
+
+fn write_cache(path: &Path, png: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, png)
+}
+
+// 检查 PNG 签名和 IHDR 中的尺寸，不解码像素数据
+fn validate_png(data: &[u8]) -> Result<(u32, u32), String> {
+    if data.len() > MAX_ICON_BYTES {
+        return Err(format!("larger than {} KB", MAX_ICON_BYTES / 1024));
+    }
+    // 签名之后第一个块必须是 IHDR：长度(4) + 类型(4) + 宽(4) + 高(4)
+    if data.len() < 24 || data[..8] != PNG_SIGNATURE || &data[12..16] != b"IHDR" {
+        return Err("not a PNG image".to_string());
+    }
+
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    if width == 0 || height == 0 || width > MAX_ICON_DIMENSION || height > MAX_ICON_DIMENSION {
+        return Err(format!("unsupported size {}x{}", width, height));
+    }
+    Ok((width, height))
+}
+
+fn data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(png))
+}
@@ -0,0 +1,667 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::concurrency::{MemoryBudget, WorkLimits};
+use crate::hashing::{self, Fingerprint};
+use crate::jar::{ArchiveDiagnostics, JarReader, MAX_NESTED_DEPTH};
+use crate::metadata_cache::MetadataCache;
+use crate::profiling::{timed, ModTiming};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModJarMetadata {
+    pub mod_id: String,
+    pub display_name: String,
+    pub version: String,
+    pub loader: String,
+    pub authors: Vec<String>,
+    pub homepage: Option<String>,
+    pub description: Option<String>,
+    pub environment: String,
+    // Jar-in-Jar 子 MOD 所属的父 MOD id；顶层 JAR 为 None
+    #[serde(default)]
+    pub parent_mod_id: Option<String>,
+    // JAR 内容的摘要；扫描时计算，其他调用只在使用缓存时顺带给出
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    // 元数据的来源；manifest 和 file_name 的可信度依次降低
+    #[serde(default)]
+    pub metadata_source: MetadataSource,
+    // 被启动器禁用（重命名为 .jar.disabled/.jar.old）的 MOD 为 false；Jar-in-Jar 子 MOD 随父 MOD
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // 位于 mods 的子目录（如 mods/1.20.1/）时为相对于 mods 的路径
+    #[serde(default)]
+    pub subdirectory: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataSource {
+    // 加载器描述文件（fabric.mod.json、mods.toml 等）
+    #[default]
+    Descriptor,
+    // 没有描述文件时读取 META-INF/MANIFEST.MF
+    Manifest,
+    // 只能从文件名推断
+    FileName,
+}
+
+impl ModJarMetadata {
+    pub fn set_fingerprint(&mut self, fingerprint: Fingerprint) {
+        self.sha1 = Some(fingerprint.sha1);
+        self.sha256 = Some(fingerprint.sha256);
+    }
+}
+
+// JAR 中的加载器描述文件原文
+struct DescriptorFiles {
+    fabric_mod_json: Option<String>,
+    quilt_mod_json: Option<String>,
+    // NeoForge 1.20.2 起使用 neoforge.mods.toml
+    neoforge_mods_toml: Option<String>,
+    mods_toml: Option<String>,
+    // 1.7–1.12 的 Forge MOD 只有 mcmod.info
+    mcmod_info: Option<String>,
+    // 不属于加载器描述文件，只在其他文件都不存在时作为回退
+    manifest: Option<String>,
+}
+
+impl DescriptorFiles {
+    // 只读取需要的描述文件条目，不解压其他内容
+    fn read(jar: &mut JarReader) -> Self {
+        Self {
+            fabric_mod_json: jar.read_entry_string("fabric.mod.json"),
+            quilt_mod_json: jar.read_entry_string("quilt.mod.json"),
+            neoforge_mods_toml: jar.read_entry_string("META-INF/neoforge.mods.toml"),
+            mods_toml: jar.read_entry_string("META-INF/mods.toml"),
+            mcmod_info: jar.read_entry_string("mcmod.info"),
+            manifest: jar.read_entry_string("META-INF/MANIFEST.MF"),
+        }
+    }
+
+    fn parse(&self, fallback_version: &str) -> Option<ModJarMetadata> {
+        self.fabric_mod_json
+            .as_deref()
+            .and_then(parse_fabric_mod_json)
+            .or_else(|| self.quilt_mod_json.as_deref().and_then(parse_quilt_mod_json))
+            .or_else(|| {
+                self.neoforge_mods_toml
+                    .as_deref()
+                    .and_then(|content| parse_mods_toml(content, Some("neoforge"), fallback_version))
+            })
+            .or_else(|| {
+                self.mods_toml
+                    .as_deref()
+                    .and_then(|content| parse_mods_toml(content, None, fallback_version))
+            })
+            .or_else(|| {
+                self.mcmod_info
+                    .as_deref()
+                    .and_then(|content| parse_mcmod_info(content, fallback_version))
+            })
+    }
+
+    // 没有加载器描述文件时从清单属性读取；id 仍按文件名推断，与文件名回退保持一致
+    fn parse_manifest(&self, file_stem: &str) -> Option<ModJarMetadata> {
+        let attributes = parse_manifest_attributes(self.manifest.as_deref()?);
+        let title = attributes.get("Implementation-Title").filter(|title| !title.is_empty())?;
+        let (_, fallback_version) = parse_jar_filename(file_stem);
+        let vendor = attributes
+            .get("Specification-Vendor")
+            .or_else(|| attributes.get("Implementation-Vendor"))
+            .filter(|vendor| !vendor.is_empty());
+
+        Some(ModJarMetadata {
+            mod_id: file_stem.to_lowercase().replace(' ', "_"),
+            display_name: title.clone(),
+            version: attributes
+                .get("Implementation-Version")
+                .filter(|version| !version.is_empty() && !version.contains("${"))
+                .cloned()
+                .unwrap_or(fallback_version),
+            loader: "unknown".to_string(),
+            authors: vec![vendor.cloned().unwrap_or_else(|| "Unknown".to_string())],
+            homepage: None,
+            description: None,
+            environment: "universal".to_string(),
+            parent_mod_id: None,
+            sha1: None,
+            sha256: None,
+            metadata_source: MetadataSource::Manifest,
+            enabled: true,
+            subdirectory: None,
+        })
+    }
+
+    // 描述文件中声明的图标在 JAR 中的路径；Fabric/Quilt 按尺寸声明多个图标时取最大的一个
+    fn icon_path(&self) -> Option<String> {
+        let json_icon = |icon: &serde_json::Value| match icon {
+            serde_json::Value::String(path) => Some(path.clone()),
+            serde_json::Value::Object(sizes) => sizes
+                .iter()
+                .filter_map(|(size, path)| Some((size.parse::<u32>().ok()?, path.as_str()?)))
+                .max_by_key(|(size, _)| *size)
+                .map(|(_, path)| path.to_string()),
+            _ => None,
+        };
+        let parse_json = |content: &Option<String>| {
+            content.as_deref().and_then(|content| serde_json::from_str::<serde_json::Value>(content).ok())
+        };
+        let toml_logo = |content: &Option<String>| {
+            let toml: toml::Value = toml::from_str(content.as_deref()?).ok()?;
+            toml.get("mods")
+                .and_then(|mods| mods.as_array())
+                .and_then(|mods| mods.first())
+                .and_then(|entry| entry.get("logoFile"))
+                .or_else(|| toml.get("logoFile"))
+                .and_then(|logo| logo.as_str())
+                .map(str::to_string)
+        };
+
+        let path = parse_json(&self.fabric_mod_json)
+            .and_then(|json| json.get("icon").and_then(json_icon))
+            .or_else(|| {
+                parse_json(&self.quilt_mod_json).and_then(|json| {
+                    json.pointer("/quilt_loader/metadata/icon").and_then(json_icon)
+                })
+            })
+            .or_else(|| toml_logo(&self.neoforge_mods_toml))
+            .or_else(|| toml_logo(&self.mods_toml))
+            .or_else(|| {
+                parse_json(&self.mcmod_info).and_then(|json| {
+                    let entry = match &json {
+                        serde_json::Value::Array(mods) => mods.first()?.clone(),
+                        other => other.get("modList")?.as_array()?.first()?.clone(),
+                    };
+                    entry.get("logoFile")?.as_str().map(str::to_string)
+                })
+            })?;
+
+        // 路径相对于 JAR 根目录，部分 MOD 会写成以 / 开头
+        let path = path.trim_start_matches('/').to_string();
+        (!path.is_empty()).then_some(path)
+    }
+}
+
+// 读取 JAR 中描述文件声明的图标路径
+pub fn read_icon_path(jar: &mut JarReader) -> Option<String> {
+    DescriptorFiles::read(jar).icon_path()
+}
+
+// 提取 MOD 元数据：优先读取 JAR 内的加载器描述文件，失败时回退到文件名推断。
+// 读取 JAR 占用 IO 许可，解析占用 CPU 许可；提供缓存时先计算指纹，按 SHA-1 查找已解析的结果。
+// 各步骤耗时记录到 timing 中
+pub fn extract_mod_metadata(
+    jar_path: &Path,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+    timing: &mut ModTiming,
+) -> Option<ModJarMetadata> {
+    let fingerprint = cache.and_then(|_| {
+        let _io = limits.io.acquire();
+        timed(&mut timing.hash_ms, || hashing::fingerprint_file(jar_path).ok())
+    });
+    let sha1 = fingerprint.as_ref().map(|fingerprint| fingerprint.sha1.clone());
+    let with_fingerprint = |mut metadata: ModJarMetadata| {
+        if let Some(fingerprint) = fingerprint.clone() {
+            metadata.set_fingerprint(fingerprint);
+        }
+        metadata
+    };
+
+    if let (Some(cache), Some(sha1)) = (cache, sha1.as_deref()) {
+        if let Some(metadata) = cache.get_metadata(sha1) {
+            timing.cache_hit = true;
+            return Some(with_fingerprint(metadata));
+        }
+    }
+
+    let descriptors = {
+        let _io = limits.io.acquire();
+        match timed(&mut timing.zip_open_ms, || JarReader::open(jar_path)) {
+            Ok(mut jar) => Some(timed(&mut timing.read_ms, || DescriptorFiles::read(&mut jar))),
+            Err(e) => {
+                timing.error = Some(e);
+                None
+            }
+        }
+    };
+
+    let _cpu = limits.cpu.acquire();
+    let fallback_version = mod_file_stem(jar_path)
+        .map(|s| parse_jar_filename(s).1)
+        .unwrap_or_else(|| "1.0.0".to_string());
+
+    let parsed = timed(&mut timing.parse_ms, || {
+        descriptors.as_ref().and_then(|files| files.parse(&fallback_version))
+    });
+
+    match parsed {
+        Some(metadata) => {
+            if let (Some(cache), Some(sha1)) = (cache, sha1.as_deref()) {
+                cache.put_metadata(sha1, &metadata);
+            }
+            Some(with_fingerprint(metadata))
+        }
+        // 清单和文件名推断的 id 取决于文件名而不是内容，不写入缓存
+        None => {
+            let file_stem = mod_file_stem(jar_path).unwrap_or_default();
+            descriptors
+                .and_then(|files| files.parse_manifest(file_stem))
+                .or_else(|| metadata_from_filename(jar_path))
+                .map(with_fingerprint)
+        }
+    }
+}
+
+// 从已打开的归档读取 MOD 元数据，如整合包中未解压的 JAR；读不到描述文件时按文件名推断
+pub fn read_mod_metadata(jar: &mut JarReader, file_name: &str) -> Option<ModJarMetadata> {
+    let stem = file_name.strip_suffix(".jar").unwrap_or(file_name);
+    let files = DescriptorFiles::read(jar);
+    files
+        .parse(&parse_jar_filename(stem).1)
+        .or_else(|| files.parse_manifest(stem))
+        .or_else(|| metadata_from_filename(Path::new(file_name)))
+}
+
+// 递归读取 JAR 中嵌入的子 MOD（Fabric 的 META-INF/jars/ 和 Forge 的 jarjar），parent_mod_id 指向直接包含它的 MOD。
+// 嵌套 JAR 没有独立的文件，不使用元数据缓存
+pub fn extract_nested_mod_metadata(
+    jar_path: &Path,
+    parent: &ModJarMetadata,
+    limits: &WorkLimits,
+    diagnostics: &ArchiveDiagnostics,
+) -> Vec<ModJarMetadata> {
+    let _io = limits.io.acquire();
+    let mut nested = Vec::new();
+    if let Ok(mut jar) = JarReader::open_with(jar_path, diagnostics) {
+        collect_nested_mods(&mut jar, &parent.mod_id, 0, &limits.memory, &mut nested);
+    }
+    nested
+}
+
+fn collect_nested_mods(
+    jar: &mut JarReader,
+    parent_id: &str,
+    depth: usize,
+    budget: &MemoryBudget,
+    out: &mut Vec<ModJarMetadata>,
+) {
+    let nested = jar.nested_jar_entries();
+    if depth >= MAX_NESTED_DEPTH {
+        if !nested.is_empty() {
+            jar.record_nesting_limit();
+        }
+        return;
+    }
+
+    for name in nested {
+        let file_name = name.rsplit('/').next().unwrap_or(&name);
+        let stem = file_name.strip_suffix(".jar").unwrap_or(file_name);
+        let Some(fingerprint) = jar.fingerprint_entry(&name) else { continue };
+        let Some(mut nested_jar) = jar.open_nested(&name, budget) else { continue };
+
+        // 没有描述文件的普通库 JAR 不是 MOD，不列出
+        let fallback_version = parse_jar_filename(stem).1;
+        let Some(mut metadata) = DescriptorFiles::read(&mut nested_jar).parse(&fallback_version) else { continue };
+        metadata.parent_mod_id = Some(parent_id.to_string());
+        metadata.set_fingerprint(fingerprint);
+        let mod_id = metadata.mod_id.clone();
+        out.push(metadata);
+        collect_nested_mods(&mut nested_jar, &mod_id, depth + 1, budget, out);
+    }
+}
+
+fn parse_fabric_mod_json(content: &str) -> Option<ModJarMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mod_id = json.get("id")?.as_str()?.to_string();
+
+    let authors = json
+        .get("authors")
+        .and_then(|a| a.as_array())
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|author| match author {
+                    serde_json::Value::String(name) => Some(name.clone()),
+                    other => other.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let environment = match json.get("environment").and_then(|e| e.as_str()) {
+        Some("client") => "client",
+        Some("server") => "server",
+        _ => "universal",
+    };
+
+    Some(ModJarMetadata {
+        display_name: json.get("name").and_then(|n| n.as_str()).unwrap_or(&mod_id).to_string(),
+        version: json.get("version").and_then(|v| v.as_str()).unwrap_or("unknown").to_string(),
+        loader: "fabric".to_string(),
+        authors,
+        homepage: json
+            .get("contact")
+            .and_then(|c| c.get("homepage"))
+            .and_then(|h| h.as_str())
+            .map(|s| s.to_string()),
+        description: json.get("description").and_then(|d| d.as_str()).map(|s| s.to_string()),
+        environment: environment.to_string(),
+        parent_mod_id: None,
+        sha1: None,
+        sha256: None,
+        metadata_source: MetadataSource::Descriptor,
+        enabled: true,
+        subdirectory: None,
+        mod_id,
+    })
+}
+
+fn parse_quilt_mod_json(content: &str) -> Option<ModJarMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let loader = json.get("quilt_loader")?;
+    let mod_id = loader.get("id")?.as_str()?.to_string();
+    let metadata = loader.get("metadata");
+
+    let authors = metadata
+        .and_then(|m| m.get("contributors"))
+        .and_then(|c| c.as_object())
+        .map(|contributors| contributors.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let environment = match json
+        .get("minecraft")
+        .and_then(|m| m.get("environment"))
+        .and_then(|e| e.as_str())
+    {
+        Some("client") => "client",
+        Some("dedicated_server") => "server",
+        _ => "universal",
+    };
+
+    Some(ModJarMetadata {
+        display_name: metadata
+            .and_then(|m| m.get("name"))
+            .and_then(|n| n.as_str())
+            .unwrap_or(&mod_id)
+            .to_string(),
+        version: loader.get("version").and_then(|v| v.as_str()).unwrap_or("unknown").to_string(),
+        loader: "quilt".to_string(),
+        authors,
+        homepage: metadata
+            .and_then(|m| m.get("contact"))
+            .and_then(|c| c.get("homepage"))
+            .and_then(|h| h.as_str())
+            .map(|s| s.to_string()),
+        description: metadata
+            .and_then(|m| m.get("description"))
+            .and_then(|d| d.as_str())
+            .map(|s| s.to_string()),
+        environment: environment.to_string(),
+        parent_mod_id: None,
+        sha1: None,
+        sha256: None,
+        metadata_source: MetadataSource::Descriptor,
+        enabled: true,
+        subdirectory: None,
+        mod_id,
+    })
+}
+
+// 解析 mods.toml 中的第一个 [[mods]] 条目；未指定加载器时按依赖判断是 Forge 还是 NeoForge
+fn parse_mods_toml(content: &str, loader: Option<&str>, fallback_version: &str) -> Option<ModJarMetadata> {
+    let toml: toml::Value = toml::from_str(content).ok()?;
+    let loader = loader.unwrap_or_else(|| mods_toml_loader(&toml));
+    let mod_entry = toml.get("mods")?.as_array()?.first()?;
+    let mod_id = mod_entry.get("modId")?.as_str()?.to_string();
+
+    // ${file.jarVersion} 等占位符在构建时才会替换，此时回退到文件名中的版本
+    let version = mod_entry
+        .get("version")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.contains("${"))
+        .unwrap_or(fallback_version)
+        .to_string();
+
+    let authors = match mod_entry.get("authors").or_else(|| toml.get("authors")) {
+        Some(toml::Value::String(authors)) => authors
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect(),
+        Some(toml::Value::Array(authors)) => authors
+            .iter()
+            .filter_map(|a| a.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Some(ModJarMetadata {
+        display_name: mod_entry
+            .get("displayName")
+            .and_then(|n| n.as_str())
+            .unwrap_or(&mod_id)
+            .to_string(),
+        version,
+        loader: loader.to_string(),
+        authors,
+        homepage: mod_entry
+            .get("displayURL")
+            .or_else(|| toml.get("displayURL"))
+            .and_then(|u| u.as_str())
+            .map(|s| s.to_string()),
+        description: mod_entry
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(|s| s.trim().to_string()),
+        environment: "universal".to_string(),
+        parent_mod_id: None,
+        sha1: None,
+        sha256: None,
+        metadata_source: MetadataSource::Descriptor,
+        enabled: true,
+        subdirectory: None,
+        mod_id,
+    })
+}
+
+// 1.20.1 的 NeoForge MOD 仍使用 META-INF/mods.toml，只能通过声明的 neoforge 依赖区分
+fn mods_toml_loader(toml: &toml::Value) -> &'static str {
+    let depends_on_neoforge = toml
+        .get("dependencies")
+        .and_then(|d| d.as_table())
+        .into_iter()
+        .flat_map(|dependencies| dependencies.values())
+        .filter_map(|entries| entries.as_array())
+        .flatten()
+        .any(|entry| entry.get("modId").and_then(|id| id.as_str()) == Some("neoforge"));
+    if depends_on_neoforge {
+        "neoforge"
+    } else {
+        "forge"
+    }
+}
+
+// 解析 mcmod.info 中的第一个 MOD 条目；兼容顶层数组和 modListVersion 2 的 {"modList": [...]} 两种写法
+fn parse_mcmod_info(content: &str, fallback_version: &str) -> Option<ModJarMetadata> {
+    // 部分 MOD 的 mcmod.info 带有 BOM
+    let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}')).ok()?;
+    let mod_entry = match &json {
+        serde_json::Value::Array(mods) => mods.first()?,
+        other => other.get("modList")?.as_array()?.first()?,
+    };
+    let mod_id = mod_entry.get("modid")?.as_str()?.to_string();
+
+    // ${version} 等占位符在构建时才会替换，此时回退到文件名中的版本
+    let version = mod_entry
+        .get("version")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty() && !v.contains("${"))
+        .unwrap_or(fallback_version)
+        .to_string();
+
+    // 旧版本使用 authors，modListVersion 2 起改为 authorList
+    let authors = mod_entry
+        .get("authorList")
+        .or_else(|| mod_entry.get("authors"))
+        .and_then(|a| a.as_array())
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|a| a.as_str().map(|s| s.trim().to_string()))
+                .filter(|a| !a.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(ModJarMetadata {
+        display_name: mod_entry
+            .get("name")
+            .and_then(|n| n.as_str())
+            .filter(|n| !n.is_empty())
+            .unwrap_or(&mod_id)
+            .to_string(),
+        version,
+        loader: "forge".to_string(),
+        authors,
+        homepage: mod_entry
+            .get("url")
+            .and_then(|u| u.as_str())
+            .filter(|u| !u.is_empty())
+            .map(|s| s.to_string()),
+        description: mod_entry
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        environment: "universal".to_string(),
+        parent_mod_id: None,
+        sha1: None,
+        sha256: None,
+        metadata_source: MetadataSource::Descriptor,
+        enabled: true,
+        subdirectory: None,
+        mod_id,
+    })
+}
+
+// 从文件名推断 MOD 元数据（无法读取 JAR 内容时使用）
+fn metadata_from_filename(jar_path: &Path) -> Option<ModJarMetadata> {
+    // 从文件名推断基本信息
+    let file_name = mod_file_stem(jar_path)?.to_string();
+
+    // 尝试从文件名中提取版本信息
+    let (display_name, version) = parse_jar_filename(&file_name);
+
+    Some(ModJarMetadata {
+        mod_id: file_name.to_lowercase().replace(' ', "_"),
+        display_name,
+        version,
+        loader: "unknown".to_string(),
+        authors: vec!["Unknown".to_string()],
+        homepage: None,
+        description: Some(format!("Mod from {}", file_name)),
+        environment: "universal".to_string(),
+        parent_mod_id: None,
+        sha1: None,
+        sha256: None,
+        metadata_source: MetadataSource::FileName,
+        enabled: true,
+        subdirectory: None,
+    })
+}
+
+// 解析 MANIFEST.MF 主段（第一个空行之前）的属性；以空格开头的行是上一行的续行
+fn parse_manifest_attributes(content: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+
+    for line in content.lines() {
+        if line.is_empty() {
+            break;
+        }
+        if let Some(continuation) = line.strip_prefix(' ') {
+            if let Some((_, value)) = current.as_mut() {
+                value.push_str(continuation);
+            }
+            continue;
+        }
+        if let Some((name, value)) = current.take() {
+            attributes.insert(name, value);
+        }
+        current = line
+            .split_once(':')
+            .map(|(name, value)| (name.trim().to_string(), value.trim_start().to_string()));
+    }
+    if let Some((name, value)) = current {
+        attributes.insert(name, value);
+    }
+    attributes
+}
+
+// 去掉 .jar 以及启动器禁用 MOD 时追加的 .disabled/.old 后缀
+fn mod_file_stem(jar_path: &Path) -> Option<&str> {
+    let file_name = jar_path.file_name()?.to_str()?;
+    let file_name = file_name
+        .strip_suffix(".disabled")
+        .or_else(|| file_name.strip_suffix(".old"))
+        .unwrap_or(file_name);
+    Some(file_name.strip_suffix(".jar").unwrap_or(file_name))
+}
+
+// 从 JAR 文件名解析模组名和版本
+pub fn parse_jar_filename(filename: &str) -> (String, String) {
+    // 尝试不同的分隔符模式来提取版本
+    let separators = ["-", "_v", "_"];
+
+    for sep in separators {
+        if let Some(pos) = filename.rfind(sep) {
+            let (name_part, version_part) = filename.split_at(pos);
+            let version_candidate = &version_part[sep.len()..];
+
+            // 检查版本部分是否像版本号
+            if is_version_like(version_candidate) {
+                let clean_name = name_part.replace(['_', '-'], " ");
+                return (clean_name, version_candidate.to_string());
+            }
+        }
+    }
+
+    // 如果无法解析版本，返回文件名和默认版本
+    (filename.replace(['_', '-'], " "), "1.0.0".to_string())
+}
+
+// 检查字符串是否像版本号
+fn is_version_like(s: &str) -> bool {
+    if s.is_empty() {
+        return false;
+    }
+
+    // 版本号通常以数字开头
+    if !s.chars().next().unwrap_or('a').is_ascii_digit() {
+        return false;
+    }
+
+    // 版本号包含至少一个点
+    if !s.contains('.') {
+        return false;
+    }
+
+    // 检查前几个字符是否符合版本格式（数字.数字）
+    let chars: Vec<char> = s.chars().take(5).collect();
+    if chars.len() >= 3 {
+        return chars[0].is_ascii_digit() &&
+               chars[1] == '.' &&
+               chars[2].is_ascii_digit();
+    }
+
+    false
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::mod_metadata::ModJarMetadata;
+use crate::pack_check::JarNamespaces;
+
+// 声明了某个命名空间的 MOD
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceClaimant {
+    pub mod_id: String,
+    pub mod_name: String,
+    pub mod_version: String,
+    pub jar_path: String,
+    pub key_count: u32,
+}
+
+// 多个不同 MOD（分支、附属）提供同一命名空间的语言文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceCollision {
+    pub namespace: String,
+    pub claimants: Vec<NamespaceClaimant>,
+    // 用户指定的归属 MOD
+    pub pinned_owner: Option<String>,
+    // 当前用于归属和导出的 MOD：指定的或扫描顺序中的第一个
+    pub owner: String,
+}
+
+// 找出被两个及以上不同 MOD 声明的命名空间
+pub fn detect_namespace_collisions(
+    jars: &[JarNamespaces],
+    owners: &BTreeMap<String, String>,
+) -> Vec<NamespaceCollision> {
+    let mut claims: BTreeMap<&str, Vec<&JarNamespaces>> = BTreeMap::new();
+    for jar in jars {
+        for namespace in jar.entries.keys() {
+            claims.entry(namespace.as_str()).or_default().push(jar);
+        }
+    }
+
+    claims
+        .into_iter()
+        .filter_map(|(namespace, claimants)| {
+            // 同一 MOD 的重复 JAR 不算冲突
+            let mod_ids: HashSet<&str> = claimants.iter().map(|jar| jar.metadata.mod_id.as_str()).collect();
+            if mod_ids.len() < 2 {
+                return None;
+            }
+
+            let owner = resolve_owner(namespace, jars, owners).unwrap_or(&claimants[0].metadata);
+            Some(NamespaceCollision {
+                namespace: namespace.to_string(),
+                owner: owner.mod_id.clone(),
+                pinned_owner: owners.get(namespace).cloned(),
+                claimants: claimants
+                    .iter()
+                    .map(|jar| NamespaceClaimant {
+                        mod_id: jar.metadata.mod_id.clone(),
+                        mod_name: jar.metadata.display_name.clone(),
+                        mod_version: jar.metadata.version.clone(),
+                        jar_path: jar.jar_path.to_string_lossy().to_string(),
+                        key_count: jar.entries.get(namespace).map_or(0, |entries| entries.len() as u32),
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+// 用户为命名空间指定的归属 MOD；指定的 MOD 不再提供该命名空间时忽略
+pub fn resolve_owner<'a>(
+    namespace: &str,
+    jars: &'a [JarNamespaces],
+    owners: &BTreeMap<String, String>,
+) -> Option<&'a ModJarMetadata> {
+    let pinned = owners.get(namespace)?;
+    jars.iter()
+        .find(|jar| &jar.metadata.mod_id == pinned && jar.entries.contains_key(namespace))
+        .map(|jar| &jar.metadata)
+}
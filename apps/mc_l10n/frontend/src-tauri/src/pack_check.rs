@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::lang;
+use crate::metadata_cache::MetadataCache;
+use crate::namespace_owner;
+use crate::mod_metadata::{extract_mod_metadata, ModJarMetadata};
+use crate::profiling::ModTiming;
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// 翻译包中某个命名空间相对已安装 MOD 的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamespaceStatus {
+    UpToDate,
+    Stale,
+    NotInstalled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceFreshness {
+    pub namespace: String,
+    pub status: NamespaceStatus,
+    pub mod_id: Option<String>,
+    pub mod_version: Option<String>,
+    pub source_keys: u32,
+    pub translated_keys: u32,
+    // MOD 中存在但翻译包中没有的键
+    pub missing_keys: Vec<String>,
+    // 翻译包中存在但 MOD 已删除或改名的键
+    pub obsolete_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackFreshnessReport {
+    pub pack_path: String,
+    pub target_locale: String,
+    pub stale_namespaces: u32,
+    pub namespaces: Vec<NamespaceFreshness>,
+}
+
+// 已安装 MOD 在某个命名空间下的原文
+pub struct InstalledNamespace {
+    pub mod_id: String,
+    pub mod_version: String,
+    pub environment: String,
+    pub keys: HashSet<String>,
+}
+
+// 对比翻译资源包（目录或 zip）与已安装 MOD 的原文键，找出需要重新生成的命名空间
+pub fn check_pack_freshness(
+    pack_path: &Path,
+    jar_paths: Vec<PathBuf>,
+    target_locale: &str,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+    owners: &BTreeMap<String, String>,
+) -> Result<PackFreshnessReport, String> {
+    let pack_entries = if pack_path.is_dir() {
+        lang::load_locale_entries(pack_path, target_locale)
+    } else {
+        lang::load_jar_locale_entries(pack_path, target_locale)?
+    };
+
+    let installed = load_installed_namespaces(jar_paths, limits, cache, owners);
+    let mut namespaces = Vec::new();
+
+    let pack_entries: BTreeMap<_, _> = pack_entries.into_iter().collect();
+    for (namespace, translations) in pack_entries {
+        let translated: HashSet<&String> = translations.keys().collect();
+
+        let Some(installed) = installed.get(&namespace) else {
+            namespaces.push(NamespaceFreshness {
+                namespace,
+                status: NamespaceStatus::NotInstalled,
+                mod_id: None,
+                mod_version: None,
+                source_keys: 0,
+                translated_keys: translated.len() as u32,
+                missing_keys: Vec::new(),
+                obsolete_keys: Vec::new(),
+            });
+            continue;
+        };
+
+        let mut missing_keys: Vec<String> = installed
+            .keys
+            .iter()
+            .filter(|key| !translated.contains(key))
+            .cloned()
+            .collect();
+        let mut obsolete_keys: Vec<String> = translated
+            .iter()
+            .filter(|key| !installed.keys.contains(**key))
+            .map(|key| key.to_string())
+            .collect();
+        missing_keys.sort();
+        obsolete_keys.sort();
+
+        let status = if missing_keys.is_empty() && obsolete_keys.is_empty() {
+            NamespaceStatus::UpToDate
+        } else {
+            NamespaceStatus::Stale
+        };
+
+        namespaces.push(NamespaceFreshness {
+            namespace,
+            status,
+            mod_id: Some(installed.mod_id.clone()),
+            mod_version: Some(installed.mod_version.clone()),
+            source_keys: installed.keys.len() as u32,
+            translated_keys: translated.len() as u32,
+            missing_keys,
+            obsolete_keys,
+        });
+    }
+
+    Ok(PackFreshnessReport {
+        pack_path: pack_path.to_string_lossy().to_string(),
+        target_locale: target_locale.to_string(),
+        stale_namespaces: namespaces.iter().filter(|n| n.status == NamespaceStatus::Stale).count() as u32,
+        namespaces,
+    })
+}
+
+// 单个 JAR 的元数据及其原文语言文件
+pub struct JarNamespaces {
+    pub metadata: ModJarMetadata,
+    pub jar_path: PathBuf,
+    pub entries: HashMap<String, HashMap<String, String>>,
+}
+
+// 读取每个 JAR 的元数据和原文语言文件，保持 jar_paths 的顺序
+pub fn load_jar_namespaces(
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> Vec<JarNamespaces> {
+    let per_jar = limits.map(jar_paths, |limits, path| {
+        let metadata = extract_mod_metadata(&path, limits, cache, &mut ModTiming::default())?;
+        let entries = {
+            let _io = limits.io.acquire();
+            lang::load_jar_locale_entries(&path, SOURCE_LOCALE).ok()?
+        };
+        Some(JarNamespaces { metadata, jar_path: path, entries })
+    });
+
+    per_jar.into_iter().flatten().collect()
+}
+
+// 按命名空间汇总已安装 MOD 的原文；owners 为用户指定的命名空间归属（命名空间 -> MOD id）
+pub fn load_installed_namespaces(
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+    owners: &BTreeMap<String, String>,
+) -> HashMap<String, InstalledNamespace> {
+    let jars = load_jar_namespaces(jar_paths, limits, cache);
+
+    let mut installed: HashMap<String, InstalledNamespace> = HashMap::new();
+    for jar in &jars {
+        for (namespace, source_entries) in &jar.entries {
+            let owner = namespace_owner::resolve_owner(namespace, &jars, owners).unwrap_or(&jar.metadata);
+            // 多个 JAR 共用同一命名空间时合并原文键，归属取指定的或第一个 JAR
+            installed
+                .entry(namespace.clone())
+                .or_insert_with(|| InstalledNamespace {
+                    mod_id: owner.mod_id.clone(),
+                    mod_version: owner.version.clone(),
+                    environment: owner.environment.clone(),
+                    keys: HashSet::new(),
+                })
+                .keys
+                .extend(source_entries.keys().cloned());
+        }
+    }
+
+    installed
+}
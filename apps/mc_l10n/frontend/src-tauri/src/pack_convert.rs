@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::jar::JarReader;
+use crate::lang;
+use crate::locale::canonical_locale;
+use crate::metadata_cache::MetadataCache;
+use crate::pack_check;
+use crate::pack_integrity::{self, PackPlan};
+use crate::preflight::{self, WriteOutcome};
+
+// 资源包 pack_format 从 4（1.13）起使用小写的 <locale>.json，之前使用 zh_CN.lang
+const FIRST_JSON_LANG_FORMAT: u32 = 4;
+
+// Minecraft 版本（前缀匹配，靠前的优先）与资源包 pack_format
+const RESOURCE_PACK_FORMATS: &[(&str, u32)] = &[
+    ("1.21.4", 46),
+    ("1.21.3", 42),
+    ("1.21.2", 42),
+    ("1.21", 34),
+    ("1.20.6", 32),
+    ("1.20.5", 32),
+    ("1.20.4", 22),
+    ("1.20.3", 22),
+    ("1.20.2", 18),
+    ("1.20", 15),
+    ("1.19.4", 13),
+    ("1.19.3", 12),
+    ("1.19", 9),
+    ("1.18", 8),
+    ("1.17", 7),
+    ("1.16.5", 6),
+    ("1.16.4", 6),
+    ("1.16.3", 6),
+    ("1.16.2", 6),
+    ("1.16", 5),
+    ("1.15", 5),
+    ("1.14", 4),
+    ("1.13", 4),
+    ("1.12", 3),
+    ("1.11", 3),
+    ("1.10", 2),
+    ("1.9", 2),
+    ("1.8", 1),
+    ("1.7", 1),
+    ("1.6", 1),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackConversionRequest {
+    // 目录或 zip 形式的资源包
+    pub pack_path: String,
+    pub output_dir: String,
+    // 目标 Minecraft 版本，如 1.20.1
+    pub target_version: String,
+    // 目标版本的实例目录；提供时与其中 MOD 的原文键比对
+    #[serde(default)]
+    pub project_path: Option<String>,
+    // 只预检并返回将要写入的文件，不写入
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// 目标版本的 MOD 中已不存在的键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedKeys {
+    pub namespace: String,
+    pub locale: String,
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackConversionResult {
+    pub output_path: String,
+    pub source_pack_format: Option<u32>,
+    pub target_pack_format: u32,
+    // json 或 lang
+    pub lang_format: String,
+    pub converted_files: u32,
+    pub removed_keys: Vec<RemovedKeys>,
+    // 目标实例中没有 MOD 提供的命名空间
+    pub unknown_namespaces: Vec<String>,
+}
+
+pub fn resource_pack_format(version: &str) -> Option<u32> {
+    let version = version.trim();
+    RESOURCE_PACK_FORMATS
+        .iter()
+        .find(|(prefix, _)| version == *prefix || version.starts_with(&format!("{}.", prefix)))
+        .map(|(_, format)| *format)
+}
+
+// 把资源包转换到目标版本：改写 pack_format，按需在 .lang 与 .json 之间转换语言文件，其余文件原样复制
+pub fn convert_pack(
+    request: &PackConversionRequest,
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> Result<WriteOutcome<PackConversionResult>, String> {
+    let target_format = resource_pack_format(&request.target_version)
+        .ok_or_else(|| format!("Unsupported Minecraft version: {}", request.target_version))?;
+    let json_lang = target_format >= FIRST_JSON_LANG_FORMAT;
+
+    let pack_path = Path::new(&request.pack_path);
+    let files = read_all_files(pack_path)?;
+    let mcmeta = files
+        .get("pack.mcmeta")
+        .ok_or_else(|| format!("No pack.mcmeta in {}", pack_path.display()))?;
+    let mut mcmeta: serde_json::Value = serde_json::from_slice(mcmeta)
+        .map_err(|e| format!("Failed to parse pack.mcmeta: {}", e))?;
+    let pack = mcmeta
+        .get_mut("pack")
+        .and_then(|pack| pack.as_object_mut())
+        .ok_or("pack.mcmeta has no pack section")?;
+    let source_format = pack.get("pack_format").and_then(|f| f.as_u64()).map(|f| f as u32);
+    pack.insert("pack_format".to_string(), target_format.into());
+    // 1.20.2 起的 supported_formats 对旧版本无意义，转换后按新的 pack_format 为准
+    pack.remove("supported_formats");
+
+    let stem = pack_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let output_path = Path::new(&request.output_dir).join(format!("{}_{}", stem, request.target_version.trim()));
+    if output_path.exists() && !request.dry_run {
+        return Err(format!("{} already exists", output_path.display()));
+    }
+
+    // 先生成转换后的全部文件，试运行时只预检不写入
+    let mut outputs: Vec<(String, Cow<[u8]>)> = Vec::new();
+
+    // (根目录, 命名空间, 规范语言代码) -> 合并后的条目
+    let mut lang_files: BTreeMap<(String, String, String), HashMap<String, String>> = BTreeMap::new();
+    for (name, bytes) in &files {
+        if name == "pack.mcmeta" {
+            continue;
+        }
+        let extension = pack_integrity::lang_file_extension(name);
+        let entries = extension.and_then(|ext| lang::parse_lang_entries(&String::from_utf8_lossy(bytes), ext));
+        match (extension, entries) {
+            (Some(_), Some(entries)) => {
+                let parts: Vec<&str> = name.split('/').collect();
+                let locale = parts[3].rsplit_once('.').map_or(parts[3], |(stem, _)| stem);
+                lang_files
+                    .entry((parts[0].to_string(), parts[1].to_string(), canonical_locale(locale)))
+                    .or_default()
+                    .extend(entries);
+            }
+            _ => outputs.push((name.clone(), Cow::Borrowed(bytes))),
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&mcmeta).map_err(|e| e.to_string())?;
+    outputs.push(("pack.mcmeta".to_string(), Cow::Owned(content.into_bytes())));
+
+    let mut plan = PackPlan {
+        pack_format: target_format,
+        lang_files: BTreeMap::new(),
+    };
+    for ((root, namespace, locale), entries) in &lang_files {
+        let relative = if json_lang {
+            format!("{}/{}/lang/{}.json", root, namespace, locale)
+        } else {
+            format!("{}/{}/lang/{}.lang", root, namespace, legacy_locale_name(locale))
+        };
+        let content = if json_lang {
+            let sorted: BTreeMap<&String, &String> = entries.iter().collect();
+            serde_json::to_string_pretty(&sorted).map_err(|e| e.to_string())?
+        } else {
+            to_legacy_lang(entries)
+        };
+        plan.lang_files.insert(relative.clone(), entries.len());
+        outputs.push((relative, Cow::Owned(content.into_bytes())));
+    }
+
+    if request.dry_run {
+        let planned = outputs
+            .iter()
+            .map(|(name, bytes)| (output_path.join(name), bytes.len() as u64))
+            .collect();
+        let mut report = preflight::check(planned, false);
+        if output_path.exists() {
+            report.add_problem(format!("{} already exists", output_path.display()));
+        }
+        return Ok(WriteOutcome::DryRun(report));
+    }
+
+    for (name, bytes) in &outputs {
+        write_file(&output_path.join(name), bytes)?;
+    }
+
+    let problems = pack_integrity::verify_pack(&output_path, &plan)?;
+    if !problems.is_empty() {
+        return Err(format!(
+            "Converted pack {} failed the integrity check:\n{}",
+            output_path.display(),
+            problems.join("\n")
+        ));
+    }
+
+    let (removed_keys, unknown_namespaces) = if request.project_path.is_some() {
+        find_removed_keys(&lang_files, jar_paths, limits, cache)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    Ok(WriteOutcome::Applied(PackConversionResult {
+        output_path: output_path.to_string_lossy().to_string(),
+        source_pack_format: source_format,
+        target_pack_format: target_format,
+        lang_format: if json_lang { "json" } else { "lang" }.to_string(),
+        converted_files: lang_files.len() as u32,
+        removed_keys,
+        unknown_namespaces,
+    }))
+}
+
+// 与目标实例中 MOD 的原文键比对
+fn find_removed_keys(
+    lang_files: &BTreeMap<(String, String, String), HashMap<String, String>>,
+    jar_paths: Vec<PathBuf>,
+    limits: &WorkLimits,
+    cache: Option<&MetadataCache>,
+) -> (Vec<RemovedKeys>, Vec<String>) {
+    let installed = pack_check::load_installed_namespaces(jar_paths, limits, cache, &BTreeMap::new());
+    let mut removed = Vec::new();
+    let mut unknown = Vec::new();
+
+    for ((root, namespace, locale), entries) in lang_files {
+        if root != "assets" {
+            continue;
+        }
+        let Some(installed) = installed.get(namespace) else {
+            if !unknown.contains(namespace) {
+                unknown.push(namespace.clone());
+            }
+            continue;
+        };
+        let mut keys: Vec<String> = entries.keys().filter(|key| !installed.keys.contains(*key)).cloned().collect();
+        if keys.is_empty() {
+            continue;
+        }
+        keys.sort();
+        removed.push(RemovedKeys {
+            namespace: namespace.clone(),
+            locale: locale.clone(),
+            keys,
+        });
+    }
+
+    (removed, unknown)
+}
+
+// 旧版文件名的语言部分小写、地区部分大写，如 zh_CN
+fn legacy_locale_name(locale: &str) -> String {
+    match locale.split_once('_') {
+        Some((language, region)) => format!("{}_{}", language, region.to_ascii_uppercase()),
+        None => locale.to_string(),
+    }
+}
+
+// .lang 每行一个条目，值中的换行写成 \n
+fn to_legacy_lang(entries: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<&String, &String> = entries.iter().collect();
+    sorted
+        .into_iter()
+        .map(|(key, value)| format!("{}={}\n", key, value.replace('\n', "\\n")))
+        .collect()
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// 以 / 分隔的相对路径读取包内全部文件
+fn read_all_files(pack_path: &Path) -> Result<BTreeMap<String, Vec<u8>>, String> {
+    let mut files = BTreeMap::new();
+
+    if pack_path.is_dir() {
+        let mut pending = vec![pack_path.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(pack_path) else {
+                    continue;
+                };
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let bytes = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                files.insert(name, bytes);
+            }
+        }
+    } else {
+        let mut reader = JarReader::open(pack_path)?;
+        for name in reader.entry_names() {
+            if name.ends_with('/') {
+                continue;
+            }
+            let bytes = reader
+                .read_entry(&name)
+                .ok_or_else(|| format!("Failed to read {} in {}", name, pack_path.display()))?;
+            files.insert(name, bytes);
+        }
+    }
+
+    Ok(files)
+}
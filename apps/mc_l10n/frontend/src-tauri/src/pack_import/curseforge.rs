@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{archive_has_entry, extract_archive, override_entries, read_archive_json, scan_overrides};
+use crate::scanners::{ScanContext, ScanOutput};
+
+const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+
+// CurseForge 导出包 manifest.json 中列出的 MOD 文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModpackFile {
+    pub project_id: u64,
+    pub file_id: u64,
+    pub required: bool,
+    // 通过 CurseForge API 解析；未配置 API 密钥或请求失败时为空
+    pub file_name: Option<String>,
+    pub display_name: Option<String>,
+}
+
+// 根目录带 manifest.json 的 zip 视为 CurseForge 导出包
+pub fn is_export_archive(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        && archive_has_entry(path, "manifest.json")
+}
+
+// 覆盖目录名以清单为准
+fn overrides_dir(manifest: &serde_json::Value) -> &str {
+    manifest.get("overrides").and_then(|v| v.as_str()).unwrap_or("overrides")
+}
+
+// 解压 manifest.json 和 overrides 目录
+pub fn extract_export(archive_path: &Path) -> Result<PathBuf, String> {
+    let manifest = read_archive_json(archive_path, "manifest.json")?;
+    extract_archive(archive_path, &["manifest.json"], &[overrides_dir(&manifest).to_string()])
+}
+
+// 不解压扫描导出包中的 overrides 目录；manifest.json 列出的 MOD 不在导出包中，扫描后单独列出。
+// 返回清单内容和扫描输出
+pub fn scan_in_place(ctx: &ScanContext<'_>) -> Result<(serde_json::Value, ScanOutput), String> {
+    let manifest = read_archive_json(ctx.project_path, "manifest.json")?;
+    let prefix = format!("{}/", overrides_dir(&manifest).trim_end_matches('/'));
+    let overrides = override_entries(ctx.project_path, &[prefix.as_str()]);
+    let output = scan_overrides(ctx, &overrides, &[prefix.as_str()], Vec::new())?;
+    Ok((manifest, output))
+}
+
+// 读取 manifest.json 的 files 列表；在归档内扫描时 scan_root 为导出包本身
+pub fn read_manifest_files(scan_root: &Path) -> Vec<ModpackFile> {
+    let json = if scan_root.is_file() {
+        read_archive_json(scan_root, "manifest.json").ok()
+    } else {
+        fs::read_to_string(scan_root.join("manifest.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    };
+    let Some(json) = json else {
+        return Vec::new();
+    };
+
+    json.get("files")
+        .and_then(|files| files.as_array())
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|file| {
+                    Some(ModpackFile {
+                        project_id: file.get("projectID")?.as_u64()?,
+                        file_id: file.get("fileID")?.as_u64()?,
+                        required: file.get("required").and_then(|v| v.as_bool()).unwrap_or(true),
+                        file_name: None,
+                        display_name: None,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 通过 CurseForge API 批量查询文件名；失败时保留未解析的列表
+pub async fn resolve_file_names(files: &mut [ModpackFile], api_key: &str) -> Result<(), String> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let file_ids: Vec<u64> = files.iter().map(|file| file.file_id).collect();
+    let response = reqwest::Client::new()
+        .post(format!("{}/mods/files", CURSEFORGE_API_URL))
+        .header("x-api-key", api_key)
+        .json(&serde_json::json!({ "fileIds": file_ids }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to call CurseForge API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("CurseForge API returned error: {}", response.status()));
+    }
+
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse CurseForge response: {}", e))?;
+    for data in json.get("data").and_then(|d| d.as_array()).into_iter().flatten() {
+        let Some(file_id) = data.get("id").and_then(|v| v.as_u64()) else {
+            continue;
+        };
+        if let Some(file) = files.iter_mut().find(|file| file.file_id == file_id) {
+            file.file_name = data.get("fileName").and_then(|v| v.as_str()).map(str::to_string);
+            file.display_name = data.get("displayName").and_then(|v| v.as_str()).map(str::to_string);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::events::{self, AppEvent};
+use crate::verified_download::{self, ExpectedFile};
+
+// 两次下载进度事件之间的最小间隔
+const MIN_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+// 地址中没有可用文件名时使用的文件名
+const DEFAULT_FILE_NAME: &str = "pack.zip";
+
+// 下载进度事件 "pack-download-progress" 的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    pub url: String,
+    pub downloaded_bytes: u64,
+    // 服务器未提供 Content-Length 时为空
+    pub total_bytes: Option<u64>,
+    pub done: bool,
+}
+
+// 下载整合包归档到 data_dir/downloads/<SHA-1>/<文件名>。超过 max_pack_download_mb 时中止；
+// checksum 可以是 SHA-1、SHA-256 或 SHA-512，校验通过前文件只存在于待定目录
+pub async fn download_pack(app: &tauri::AppHandle, url: &str, checksum: Option<&str>) -> Result<PathBuf, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Unsupported URL scheme: {}", parsed.scheme()));
+    }
+    let expected = checksum
+        .filter(|checksum| !checksum.trim().is_empty())
+        .map(ExpectedFile::from_checksum)
+        .transpose()?
+        .unwrap_or_default();
+
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let max_bytes = config.max_pack_download_mb.saturating_mul(1024 * 1024);
+
+    let mut progress = DownloadProgress {
+        url: url.to_string(),
+        downloaded_bytes: 0,
+        total_bytes: None,
+        done: false,
+    };
+    let mut last_emit: Option<Instant> = None;
+    let file = verified_download::download_verified(&config, url, &expected, max_bytes, |downloaded, total| {
+        progress.downloaded_bytes = downloaded;
+        progress.total_bytes = total;
+        if !last_emit.is_some_and(|last| last.elapsed() < MIN_EMIT_INTERVAL) {
+            events::emit(app, AppEvent::PackDownloadProgress(&progress));
+            last_emit = Some(Instant::now());
+        }
+    })
+    .await?;
+    progress.downloaded_bytes = file.size;
+    progress.done = true;
+    events::emit(app, AppEvent::PackDownloadProgress(&progress));
+
+    // 按重定向后的地址取文件名，扩展名决定按哪种整合包格式导入；同一个归档已经下载过时复用
+    let target = config.get_data_dir().join("downloads").join(&file.sha1).join(file_name_from_url(&file.url));
+    if target.is_file() {
+        file.discard();
+        return Ok(target);
+    }
+    file.promote(&target)?;
+    Ok(target)
+}
+
+// 地址路径的最后一段，只保留文件名中安全的字符
+fn file_name_from_url(url: &reqwest::Url) -> String {
+    let name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(|segment| urlencoding::decode(segment).map(|s| s.into_owned()).unwrap_or_else(|_| segment.to_string()))
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ' | '+') { c } else { '_' })
+        .collect();
+
+    if name.trim_matches('.').is_empty() {
+        DEFAULT_FILE_NAME.to_string()
+    } else {
+        name
+    }
+}
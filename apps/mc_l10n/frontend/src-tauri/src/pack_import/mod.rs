@@ -0,0 +1,295 @@
+pub mod curseforge;
+pub mod download;
+pub mod mrpack;
+pub mod multimc;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+use crate::concurrency::MemoryBudget;
+use crate::config::AppConfig;
+use crate::hashing::sha1_file;
+use crate::jar::{ArchiveDiagnostics, JarReader};
+use crate::mod_metadata::{extract_mod_metadata, read_mod_metadata};
+use crate::profiling::ModTiming;
+use crate::scanners::mod_lang::{archive_lang_files, scan_archive_lang_files, ArchiveLangFile};
+use crate::scanners::{LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+
+// 解压完成的标记文件，中途失败的目录不会被复用
+const COMPLETE_MARKER: &str = ".th_import_complete";
+
+// 不解压扫描时 MOD JAR 的来源
+enum ModSource {
+    // 覆盖目录中的条目名
+    Embedded(String),
+    // 在归档外找到的本地文件
+    Local(PathBuf),
+}
+
+// 实际扫描的目录；扫描目标是整合包归档时为解压后的目录
+pub struct ScanRoot {
+    pub path: PathBuf,
+    pub archive: Option<PathBuf>,
+    // 导入过程中的非致命问题，如无法获取的 MOD 文件
+    pub warnings: Vec<String>,
+}
+
+// 扫描目标是整合包归档（CurseForge 导出包、.mrpack、MultiMC/Prism 实例）时解压到数据目录，否则原样返回
+pub fn prepare_scan_root(target: &Path) -> Result<ScanRoot, String> {
+    let mut root = ScanRoot {
+        path: target.to_path_buf(),
+        archive: Some(target.to_path_buf()),
+        warnings: Vec::new(),
+    };
+
+    // 在归档内扫描时 path 保持为归档本身
+    let in_place = AppConfig::load().is_ok_and(|config| config.scan_archives_in_place);
+    if in_place && (mrpack::is_mrpack(target) || curseforge::is_export_archive(target)) {
+        return Ok(root);
+    }
+
+    if mrpack::is_mrpack(target) {
+        let config = AppConfig::load().map_err(|e| e.to_string())?;
+        root.path = mrpack::import_mrpack(target, &config, &mut root.warnings)?;
+    } else if curseforge::is_export_archive(target) {
+        root.path = curseforge::extract_export(target)?;
+    } else if let Some(instance_dir) = multimc::find_instance_dir(target) {
+        root.path = multimc::extract_instance(target, &instance_dir)?;
+    } else {
+        root.archive = None;
+    }
+    Ok(root)
+}
+
+// 归档根目录中是否有某个条目
+fn archive_has_entry(path: &Path, name: &str) -> bool {
+    File::open(path)
+        .ok()
+        .and_then(|file| ZipArchive::new(io::BufReader::new(file)).ok())
+        .is_some_and(|mut archive| archive.by_name(name).is_ok())
+}
+
+// 归档中的全部条目名；不是 zip 时为空
+fn archive_entry_names(path: &Path) -> Vec<String> {
+    File::open(path)
+        .ok()
+        .and_then(|file| ZipArchive::new(io::BufReader::new(file)).ok())
+        .map(|archive| archive.file_names().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+// 读取归档根目录中的 JSON 文件
+fn read_archive_json(archive_path: &Path, name: &str) -> Result<serde_json::Value, String> {
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut archive = ZipArchive::new(io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read archive {}: {}", archive_path.display(), e))?;
+    let mut entry = archive.by_name(name).map_err(|e| format!("{}: {}", name, e))?;
+    serde_json::from_reader(&mut entry).map_err(|e| format!("Invalid {}: {}", name, e))
+}
+
+// 把 root_files（按文件名放到根目录）和各 prefixes 目录（去掉前缀）下的内容解压到
+// data_dir/imports/<归档摘要>/；后面的前缀覆盖前面的同名文件。同一个归档只解压一次
+fn extract_archive(archive_path: &Path, root_files: &[&str], prefixes: &[String]) -> Result<PathBuf, String> {
+    let digest = sha1_file(archive_path).map_err(|e| format!("Failed to read {}: {}", archive_path.display(), e))?;
+    let imports_dir = AppConfig::load().map_err(|e| e.to_string())?.get_data_dir().join("imports");
+    let target = imports_dir.join(&digest);
+    if target.join(COMPLETE_MARKER).exists() {
+        return Ok(target);
+    }
+
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut archive = ZipArchive::new(io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read archive {}: {}", archive_path.display(), e))?;
+
+    // 先解压到临时目录，完成后再改名，避免留下不完整的目录
+    let staging = imports_dir.join(format!("{}.partial", digest));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+
+    let root_pass = std::iter::once(None);
+    let prefix_passes = prefixes.iter().map(|prefix| Some(PathBuf::from(prefix.trim_end_matches('/'))));
+    for prefix in root_pass.chain(prefix_passes) {
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+            // 拒绝包含 .. 或绝对路径的条目
+            let Some(name) = entry.enclosed_name() else {
+                continue;
+            };
+            let relative = match &prefix {
+                None if root_files.iter().any(|file| name == Path::new(file)) => match name.file_name() {
+                    Some(file_name) => PathBuf::from(file_name),
+                    None => continue,
+                },
+                None => continue,
+                Some(prefix) => match name.strip_prefix(prefix) {
+                    Ok(rest) if !rest.as_os_str().is_empty() => rest.to_path_buf(),
+                    _ => continue,
+                },
+            };
+
+            let output = staging.join(&relative);
+            if entry.is_dir() {
+                fs::create_dir_all(&output).map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+                continue;
+            }
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let mut out = File::create(&output).map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+            io::copy(&mut entry, &mut out).map_err(|e| format!("Failed to extract {}: {}", relative.display(), e))?;
+        }
+    }
+
+    File::create(staging.join(COMPLETE_MARKER)).map_err(|e| e.to_string())?;
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(&staging, &target).map_err(|e| format!("Failed to finish import: {}", e))?;
+    Ok(target)
+}
+
+// 覆盖目录中的文件：相对路径 -> 归档内条目名；prefixes 按顺序应用，后面的覆盖前面的同名文件
+fn override_entries(archive_path: &Path, prefixes: &[&str]) -> BTreeMap<String, String> {
+    let names = archive_entry_names(archive_path);
+    let mut overrides = BTreeMap::new();
+    for prefix in prefixes {
+        for name in &names {
+            match name.strip_prefix(prefix) {
+                Some(relative) if !relative.is_empty() && !relative.ends_with('/') => {
+                    overrides.insert(relative.to_string(), name.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+    overrides
+}
+
+// 相对路径是否为 dir 下一层、扩展名为 extension 的文件
+fn is_top_level_file(relative: &str, dir: &str, extension: &str) -> bool {
+    relative
+        .strip_prefix(dir)
+        .is_some_and(|file| !file.contains('/') && file.to_ascii_lowercase().ends_with(extension))
+}
+
+// 不解压扫描整合包归档：覆盖目录中的 mods/*.jar、resourcepacks/*.zip 和（deep 预设下）KubeJS 语言文件
+// 在归档内读取，source_path 使用 "<归档>!/<条目>" 形式；local_mods 为在归档外找到的 MOD 文件
+fn scan_overrides(
+    ctx: &ScanContext<'_>,
+    overrides: &BTreeMap<String, String>,
+    prefixes: &[&str],
+    local_mods: Vec<PathBuf>,
+) -> Result<ScanOutput, String> {
+    let archive_path = ctx.project_path;
+    let label = archive_path.to_string_lossy().to_string();
+    let mut output = ScanOutput::default();
+
+    let sources: Vec<ModSource> = overrides
+        .iter()
+        .filter(|(relative, _)| is_top_level_file(relative, "mods/", ".jar"))
+        .map(|(_, name)| ModSource::Embedded(name.clone()))
+        .chain(local_mods.into_iter().map(ModSource::Local))
+        .collect();
+
+    ctx.progress.set_total(sources.len() as u32);
+    let per_mod = ctx.limits.map(sources, |limits, source| {
+        let (item, scanned) = match source {
+            ModSource::Embedded(name) => {
+                let item = format!("{}!/{}", label, name);
+                let _io = limits.io.acquire();
+                let scanned = read_embedded(archive_path, &name, &limits.memory, ctx.archive_diagnostics).map(|mut jar| {
+                    let file_name = name.rsplit('/').next().unwrap_or(&name);
+                    (read_mod_metadata(&mut jar, file_name), archive_lang_files(&mut jar, "", &item, ctx.lenient_lang_json))
+                });
+                (item, scanned)
+            }
+            ModSource::Local(path) => {
+                let metadata = extract_mod_metadata(&path, limits, ctx.cache, &mut ModTiming::default());
+                let _io = limits.io.acquire();
+                (path.to_string_lossy().to_string(), scan_archive_lang_files(&path, ctx.archive_diagnostics, ctx.lenient_lang_json)
+                    .map(|files| (metadata, files)))
+            }
+        };
+        match &scanned {
+            Ok((Some(metadata), _)) => ctx.progress.mod_done(&item, &metadata.display_name),
+            _ => ctx.progress.file_done(&item),
+        }
+        scanned
+    });
+
+    // 无法读取的 JAR 逐个报告，不中断扫描
+    for result in per_mod {
+        match result {
+            Ok((Some(metadata), files)) if !ctx.mod_filter.excludes(&metadata.mod_id) => {
+                push_lang_files(ctx, &mut output, files, "mod_jar");
+                output.mod_jars.push(metadata);
+            }
+            Ok(_) => {}
+            Err(e) => output.errors.push(e),
+        }
+    }
+
+    let _io = ctx.limits.io.acquire();
+    for name in overrides
+        .iter()
+        .filter(|(relative, _)| is_top_level_file(relative, "resourcepacks/", ".zip"))
+        .map(|(_, name)| name)
+    {
+        match read_embedded(archive_path, name, &ctx.limits.memory, ctx.archive_diagnostics) {
+            Ok(mut pack) => {
+                let files = archive_lang_files(&mut pack, "", &format!("{}!/{}", label, name), ctx.lenient_lang_json);
+                push_lang_files(ctx, &mut output, files, "resourcepack_zip");
+            }
+            Err(e) => output.errors.push(e),
+        }
+    }
+
+    // 与目录扫描一致，KubeJS 语言文件在 standard 及以上预设读取
+    if ctx.preset >= ScanPreset::Standard {
+        let mut archive = JarReader::open_with(archive_path, ctx.archive_diagnostics)?;
+        for prefix in prefixes {
+            let files = archive_lang_files(&mut archive, &format!("{}kubejs/", prefix), &label, ctx.lenient_lang_json);
+            push_lang_files(ctx, &mut output, files, "kubejs");
+        }
+    }
+
+    ctx.collect_archive_diagnostics(&mut output);
+    Ok(output)
+}
+
+// 打开归档中的一个 JAR 或 zip；未压缩存储时直接在归档文件上读取，否则在内存预算内读入内存
+fn read_embedded(
+    archive_path: &Path,
+    name: &str,
+    budget: &MemoryBudget,
+    diagnostics: &ArchiveDiagnostics,
+) -> Result<JarReader, String> {
+    JarReader::open_with(archive_path, diagnostics)?
+        .open_nested(name, budget)
+        .ok_or_else(|| format!("Failed to read {}!/{}", archive_path.display(), name))
+}
+
+fn push_lang_files(ctx: &ScanContext<'_>, output: &mut ScanOutput, files: Vec<ArchiveLangFile>, source_type: &str) {
+    for file in files {
+        ctx.profiler.record_lang_file(&file.source_path, file.parse_ms, file.key_count);
+        ctx.progress.lang_file_done(&file.namespace, &file.source_path, file.key_count);
+        output.warnings.extend(file.warnings);
+        output.language_resources.push(LanguageResource {
+            namespace: output.interner.intern(&file.namespace),
+            locale: output.interner.intern(&file.locale),
+            source_path: file.source_path,
+            source_type: output.interner.intern(source_type),
+            key_count: file.key_count,
+            duplicate_keys: file.duplicate_keys,
+            placeholders: file.placeholders,
+            formatting: file.formatting,
+            key_categories: file.key_categories,
+            parse_status: ParseStatus::from_error(&file.parse_error),
+            parse_error: file.parse_error,
+            // 扫描结束后按优先级规则计算
+            priority: 0,
+            bundled_pack: None,
+        });
+    }
+}
@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::{
+    archive_has_entry, extract_archive, is_top_level_file, override_entries, read_archive_json, scan_overrides,
+};
+use crate::concurrency::WorkLimits;
+use crate::config::AppConfig;
+use crate::hashing::sha1_file;
+use crate::scanners::{ScanContext, ScanOutput};
+use crate::verified_download::{self, ExpectedFile};
+
+const INDEX_FILE: &str = "modrinth.index.json";
+
+// 按顺序应用的覆盖目录，后面的覆盖前面的同名文件
+const OVERRIDE_PREFIXES: [&str; 2] = ["overrides/", "client-overrides/"];
+
+// modrinth.index.json 中引用的文件
+struct IndexFile {
+    path: PathBuf,
+    sha1: String,
+    sha512: Option<String>,
+    size: Option<u64>,
+    downloads: Vec<String>,
+}
+
+// 引用文件的获取结果
+enum Fetched {
+    Present,
+    Located,
+    Downloaded,
+    Missing,
+    Failed(String),
+}
+
+// .mrpack 扩展名，或根目录带 modrinth.index.json 的 zip
+pub fn is_mrpack(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("mrpack") => true,
+        Some(ext) if ext.eq_ignore_ascii_case("zip") => archive_has_entry(path, INDEX_FILE),
+        _ => false,
+    }
+}
+
+// 解压索引和 overrides（client-overrides 覆盖其上），再按配置在本地目录中查找或下载引用的 MOD 文件
+pub fn import_mrpack(archive_path: &Path, config: &AppConfig, warnings: &mut Vec<String>) -> Result<PathBuf, String> {
+    let root = extract_archive(
+        archive_path,
+        &[INDEX_FILE],
+        &["overrides".to_string(), "client-overrides".to_string()],
+    )?;
+
+    let files = read_index_files(&root)?;
+    let limits = WorkLimits::from_config(config);
+    let results = limits.map(files, |limits, file| {
+        let _io = limits.io.acquire();
+        fetch_file(&root, &file, config)
+    });
+
+    let mut missing = 0;
+    for result in results {
+        match result {
+            Fetched::Missing => missing += 1,
+            Fetched::Failed(message) => warnings.push(message),
+            Fetched::Present | Fetched::Located | Fetched::Downloaded => {}
+        }
+    }
+    if missing > 0 {
+        warnings.push(format!(
+            "{} files referenced by {} were not found locally and were not scanned",
+            missing, INDEX_FILE
+        ));
+    }
+    Ok(root)
+}
+
+// 不解压直接扫描 .mrpack；索引引用的 MOD 只扫描在 mod_search_dirs 中找到的，不下载。返回索引内容和扫描输出
+pub fn scan_in_place(ctx: &ScanContext<'_>, config: &AppConfig) -> Result<(serde_json::Value, ScanOutput), String> {
+    let index = read_archive_json(ctx.project_path, INDEX_FILE)?;
+    let overrides = override_entries(ctx.project_path, &OVERRIDE_PREFIXES);
+
+    let mut local_mods = Vec::new();
+    let mut missing = 0;
+    for file in parse_index_files(&index) {
+        let relative = file.path.to_string_lossy().replace('\\', "/");
+        if !is_top_level_file(&relative, "mods/", ".jar") || overrides.contains_key(&relative) {
+            continue;
+        }
+        match locate_file(&file, config) {
+            Some(path) => local_mods.push(path),
+            None => missing += 1,
+        }
+    }
+
+    let mut output = scan_overrides(ctx, &overrides, &OVERRIDE_PREFIXES, local_mods)?;
+    if missing > 0 {
+        output.warnings.push(format!(
+            "{} mods referenced by {} were not found locally and were not scanned",
+            missing, INDEX_FILE
+        ));
+    }
+    Ok((index, output))
+}
+
+fn read_index_files(root: &Path) -> Result<Vec<IndexFile>, String> {
+    let content = fs::read_to_string(root.join(INDEX_FILE)).map_err(|e| format!("Failed to read {}: {}", INDEX_FILE, e))?;
+    let index: serde_json::Value = serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", INDEX_FILE, e))?;
+    Ok(parse_index_files(&index))
+}
+
+fn parse_index_files(index: &serde_json::Value) -> Vec<IndexFile> {
+    index
+        .get("files")
+        .and_then(|files| files.as_array())
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|file| {
+                    let path = PathBuf::from(file.get("path")?.as_str()?);
+                    // 只接受相对路径，拒绝 .. 等会写到解压目录之外的路径
+                    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+                        return None;
+                    }
+                    Some(IndexFile {
+                        path,
+                        sha1: file.get("hashes")?.get("sha1")?.as_str()?.to_ascii_lowercase(),
+                        sha512: file
+                            .get("hashes")
+                            .and_then(|hashes| hashes.get("sha512"))
+                            .and_then(|sha512| sha512.as_str())
+                            .map(str::to_ascii_lowercase),
+                        size: file.get("fileSize").and_then(|size| size.as_u64()),
+                        downloads: file
+                            .get("downloads")
+                            .and_then(|d| d.as_array())
+                            .map(|urls| urls.iter().filter_map(|u| u.as_str().map(str::to_string)).collect())
+                            .unwrap_or_default(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 在 mod_search_dirs 中按文件名查找 SHA-1 一致的文件
+fn locate_file(file: &IndexFile, config: &AppConfig) -> Option<PathBuf> {
+    let file_name = file.path.file_name()?;
+    config
+        .mod_search_dirs
+        .iter()
+        .map(|dir| Path::new(dir).join(file_name))
+        .find(|candidate| sha1_file(candidate).is_ok_and(|sha1| sha1 == file.sha1))
+}
+
+// 已解压过的文件直接复用；否则先在 mod_search_dirs 中按文件名查找并校验 SHA-1，
+// 找不到且开启 download_pack_mods 时依次尝试下载地址
+fn fetch_file(root: &Path, file: &IndexFile, config: &AppConfig) -> Fetched {
+    let target = root.join(&file.path);
+    if sha1_file(&target).is_ok_and(|sha1| sha1 == file.sha1) {
+        return Fetched::Present;
+    }
+    if let Some(parent) = target.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            return Fetched::Failed(format!("Failed to create {}: {}", parent.display(), e));
+        }
+    }
+
+    if let Some(source) = locate_file(file, config) {
+        return match fs::copy(&source, &target) {
+            Ok(_) => Fetched::Located,
+            Err(e) => Fetched::Failed(format!("Failed to copy {}: {}", source.display(), e)),
+        };
+    }
+
+    if !config.download_pack_mods || file.downloads.is_empty() {
+        return Fetched::Missing;
+    }
+
+    // 索引中的大小和全部摘要都要一致，校验通过前文件留在待定目录
+    let expected = ExpectedFile {
+        size: file.size,
+        sha1: Some(file.sha1.clone()),
+        sha256: None,
+        sha512: file.sha512.clone(),
+    };
+    let max_bytes = config.max_pack_download_mb.saturating_mul(1024 * 1024);
+    let mut last_error = String::new();
+    for url in &file.downloads {
+        let download = verified_download::download_verified(config, url, &expected, max_bytes, |_, _| {});
+        match tauri::async_runtime::block_on(download) {
+            Ok(verified) => {
+                return match verified.promote(&target) {
+                    Ok(()) => Fetched::Downloaded,
+                    Err(e) => Fetched::Failed(e),
+                };
+            }
+            Err(e) => last_error = e,
+        }
+    }
+    Fetched::Failed(format!("Failed to download {}: {}", file.path.display(), last_error))
+}
@@ -0,0 +1,31 @@
+use std::path::{Path, PathBuf};
+
+use super::{archive_entry_names, extract_archive};
+
+const PACK_FILE: &str = "mmc-pack.json";
+
+// 导出的实例归档中 mmc-pack.json 所在的目录前缀（根目录时为空，否则以 / 结尾）；
+// 不是实例归档时返回 None
+pub fn find_instance_dir(path: &Path) -> Option<String> {
+    if !path.is_file() || !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+        return None;
+    }
+
+    // 实例文件位于根目录或唯一的一层实例目录中
+    archive_entry_names(path)
+        .into_iter()
+        .filter_map(|name| name.strip_suffix(PACK_FILE).map(str::to_string))
+        .filter(|prefix| prefix.is_empty() || (prefix.ends_with('/') && prefix.matches('/').count() == 1))
+        .min_by_key(|prefix| prefix.len())
+}
+
+// 解压实例描述文件和游戏目录（.minecraft 或 minecraft）
+pub fn extract_instance(archive_path: &Path, instance_dir: &str) -> Result<PathBuf, String> {
+    let pack_file = format!("{}{}", instance_dir, PACK_FILE);
+    let instance_cfg = format!("{}instance.cfg", instance_dir);
+    extract_archive(
+        archive_path,
+        &[pack_file.as_str(), instance_cfg.as_str()],
+        &[format!("{}minecraft", instance_dir), format!("{}.minecraft", instance_dir)],
+    )
+}
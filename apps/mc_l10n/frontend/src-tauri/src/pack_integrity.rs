@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::jar::JarReader;
+use crate::lang;
+
+// 导出时计划写入的内容：pack.mcmeta 的 pack_format 和每个语言文件（包内相对路径）的键数
+#[derive(Debug, Clone, Default)]
+pub struct PackPlan {
+    pub pack_format: u32,
+    pub lang_files: BTreeMap<String, usize>,
+}
+
+// 重新读取生成的资源包（目录或 zip），返回与计划不符之处；为空表示通过
+pub fn verify_pack(pack_path: &Path, plan: &PackPlan) -> Result<Vec<String>, String> {
+    let files = read_pack_files(pack_path)?;
+    let mut problems = Vec::new();
+
+    match files.get("pack.mcmeta") {
+        Some(content) => problems.extend(check_mcmeta(content, plan.pack_format)),
+        None => problems.push("pack.mcmeta is missing".to_string()),
+    }
+
+    // 包内所有语言文件都必须能解析，包括计划之外的文件
+    for (path, content) in &files {
+        let Some(extension) = lang_file_extension(path) else {
+            continue;
+        };
+        let Some(entries) = lang::parse_lang_entries(content, extension) else {
+            problems.push(format!("{} is not a valid language file", path));
+            continue;
+        };
+        if let Some(&expected) = plan.lang_files.get(path) {
+            if entries.len() != expected {
+                problems.push(format!("{} has {} keys, expected {}", path, entries.len(), expected));
+            }
+        }
+    }
+
+    for path in plan.lang_files.keys() {
+        if !files.contains_key(path) {
+            problems.push(format!("{} is missing", path));
+        }
+    }
+
+    Ok(problems)
+}
+
+fn check_mcmeta(content: &str, expected_format: u32) -> Vec<String> {
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(e) => return vec![format!("pack.mcmeta is not valid JSON: {}", e)],
+    };
+    let Some(pack) = json.get("pack") else {
+        return vec!["pack.mcmeta has no \"pack\" section".to_string()];
+    };
+
+    let mut problems = Vec::new();
+    match pack.get("pack_format").and_then(|format| format.as_u64()) {
+        Some(format) if format == expected_format as u64 => {}
+        Some(format) => problems.push(format!("pack.mcmeta pack_format is {}, expected {}", format, expected_format)),
+        None => problems.push("pack.mcmeta has no numeric pack_format".to_string()),
+    }
+    if pack.get("description").is_none() {
+        problems.push("pack.mcmeta has no description".to_string());
+    }
+    problems
+}
+
+// assets|data/<namespace>/lang/<file>.json|.lang
+pub fn lang_file_extension(path: &str) -> Option<&str> {
+    let parts: Vec<&str> = path.split('/').collect();
+    if parts.len() != 4 || !matches!(parts[0], "assets" | "data") || parts[2] != "lang" {
+        return None;
+    }
+    Path::new(parts[3]).extension().and_then(|ext| ext.to_str()).filter(|ext| matches!(*ext, "json" | "lang"))
+}
+
+// 以 / 分隔的相对路径读取包内的 pack.mcmeta 和语言文件
+fn read_pack_files(pack_path: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut files = BTreeMap::new();
+
+    if pack_path.is_dir() {
+        if let Ok(content) = fs::read_to_string(pack_path.join("pack.mcmeta")) {
+            files.insert("pack.mcmeta".to_string(), content);
+        }
+        for root in ["assets", "data"] {
+            let Ok(namespaces) = fs::read_dir(pack_path.join(root)) else {
+                continue;
+            };
+            for namespace in namespaces.flatten() {
+                let Ok(lang_files) = fs::read_dir(namespace.path().join("lang")) else {
+                    continue;
+                };
+                for lang_file in lang_files.flatten() {
+                    let relative = format!(
+                        "{}/{}/lang/{}",
+                        root,
+                        namespace.file_name().to_string_lossy(),
+                        lang_file.file_name().to_string_lossy()
+                    );
+                    if lang_file_extension(&relative).is_none() {
+                        continue;
+                    }
+                    let content = fs::read_to_string(lang_file.path())
+                        .map_err(|e| format!("Failed to read {}: {}", lang_file.path().display(), e))?;
+                    files.insert(relative, content);
+                }
+            }
+        }
+    } else {
+        let mut reader = JarReader::open(pack_path)?;
+        for name in reader.entry_names() {
+            if name != "pack.mcmeta" && lang_file_extension(&name).is_none() {
+                continue;
+            }
+            // 无法按 UTF-8 读取的文件记为空内容，解析时报告为无效
+            let content = reader.read_entry_string(&name).unwrap_or_default();
+            files.insert(name, content);
+        }
+    }
+
+    Ok(files)
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::lang;
+
+// 同一个键在各翻译包中的一种译文
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeCandidate {
+    pub value: String,
+    // 提供该译文的翻译包路径，按输入顺序
+    pub packs: Vec<String>,
+}
+
+// 各翻译包译文不一致、需要用户选择的键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub namespace: String,
+    pub key: String,
+    pub candidates: Vec<MergeCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackMergeResult {
+    pub target_locale: String,
+    pub packs: Vec<String>,
+    // 无冲突的键：只有一个包提供，或各包译文相同
+    pub merged: BTreeMap<String, BTreeMap<String, String>>,
+    pub merged_keys: u32,
+    // 多个包提供了相同译文、自动合并的键数
+    pub auto_resolved: u32,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+// 合并多个同一整合包的翻译包（目录或 zip）；译文相同的键自动合并，其余作为冲突返回
+pub fn merge_packs(pack_paths: &[String], target_locale: &str) -> Result<PackMergeResult, String> {
+    if pack_paths.len() < 2 {
+        return Err("At least two packs are required to merge".to_string());
+    }
+
+    // 命名空间 -> 键 -> 各包的译文（保持输入顺序）
+    let mut values: BTreeMap<String, BTreeMap<String, Vec<(usize, String)>>> = BTreeMap::new();
+    for (index, pack_path) in pack_paths.iter().enumerate() {
+        let path = Path::new(pack_path);
+        let entries = if path.is_dir() {
+            lang::load_locale_entries(path, target_locale)
+        } else {
+            lang::load_jar_locale_entries(path, target_locale)?
+        };
+        if entries.is_empty() {
+            return Err(format!("No {} translations found in {}", target_locale, pack_path));
+        }
+
+        for (namespace, entries) in entries {
+            let keys = values.entry(namespace).or_default();
+            for (key, value) in entries {
+                keys.entry(key).or_default().push((index, value));
+            }
+        }
+    }
+
+    let mut result = PackMergeResult {
+        target_locale: target_locale.to_string(),
+        packs: pack_paths.to_vec(),
+        merged: BTreeMap::new(),
+        merged_keys: 0,
+        auto_resolved: 0,
+        conflicts: Vec::new(),
+    };
+
+    for (namespace, keys) in values {
+        for (key, provided) in keys {
+            let mut candidates: Vec<MergeCandidate> = Vec::new();
+            for (index, value) in &provided {
+                let pack = pack_paths[*index].clone();
+                match candidates.iter_mut().find(|candidate| candidate.value == *value) {
+                    Some(candidate) => candidate.packs.push(pack),
+                    None => candidates.push(MergeCandidate {
+                        value: value.clone(),
+                        packs: vec![pack],
+                    }),
+                }
+            }
+
+            if candidates.len() == 1 {
+                if provided.len() > 1 {
+                    result.auto_resolved += 1;
+                }
+                result.merged_keys += 1;
+                result
+                    .merged
+                    .entry(namespace.clone())
+                    .or_default()
+                    .insert(key, candidates.remove(0).value);
+            } else {
+                result.conflicts.push(MergeConflict {
+                    namespace: namespace.clone(),
+                    key,
+                    candidates,
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::jar::JarReader;
+
+// 生成的包中存放包描述翻译键的命名空间
+pub const DESCRIPTION_NAMESPACE: &str = "th_suite";
+
+// 资源包/数据包 pack.mcmeta 中的描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackDescription {
+    pub pack_name: String,
+    pub pack_format: Option<u32>,
+    // 文本组件展开后的纯文本，作为翻译原文
+    pub text: String,
+    // 原始文本组件
+    pub component: Value,
+    // 描述本身使用 translate 组件时的翻译键
+    pub translate_key: Option<String>,
+}
+
+// 资源包 pack.mcmeta 中的版本信息，用于展示兼容的 Minecraft 版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcePackMeta {
+    pub pack_format: u32,
+    pub description: String,
+    // supported_formats 声明的 [最小, 最大] 格式范围（1.20.2 起）
+    pub supported_formats: Option<(u32, u32)>,
+    // 按格式范围推算的 Minecraft 版本，如 "1.20–1.20.1"；格式未知时为 None
+    pub minecraft_versions: Option<String>,
+}
+
+// 资源包格式首次出现的 Minecraft 版本区间，按格式升序
+const RESOURCE_PACK_FORMATS: &[(u32, &str, &str)] = &[
+    (1, "1.6.1", "1.8.9"),
+    (2, "1.9", "1.10.2"),
+    (3, "1.11", "1.12.2"),
+    (4, "1.13", "1.14.4"),
+    (5, "1.15", "1.16.1"),
+    (6, "1.16.2", "1.16.5"),
+    (7, "1.17", "1.17.1"),
+    (8, "1.18", "1.18.2"),
+    (9, "1.19", "1.19.2"),
+    (12, "1.19.3", "1.19.3"),
+    (13, "1.19.4", "1.19.4"),
+    (15, "1.20", "1.20.1"),
+    (18, "1.20.2", "1.20.2"),
+    (22, "1.20.3", "1.20.4"),
+    (32, "1.20.5", "1.20.6"),
+    (34, "1.21", "1.21.1"),
+    (42, "1.21.2", "1.21.3"),
+    (46, "1.21.4", "1.21.4"),
+    (55, "1.21.5", "1.21.5"),
+    (63, "1.21.6", "1.21.6"),
+    (64, "1.21.7", "1.21.8"),
+];
+
+// 读取目录或 zip 形式的包中的 pack.mcmeta
+fn read_pack_mcmeta(pack_path: &Path) -> Result<Value, String> {
+    let content = if pack_path.is_dir() {
+        fs::read_to_string(pack_path.join("pack.mcmeta"))
+            .map_err(|e| format!("Failed to read pack.mcmeta in {}: {}", pack_path.display(), e))?
+    } else {
+        JarReader::open(pack_path)?
+            .read_entry_string("pack.mcmeta")
+            .ok_or_else(|| format!("No pack.mcmeta in {}", pack_path.display()))?
+    };
+
+    serde_json::from_str(content.trim_start_matches('\u{FEFF}')).map_err(|e| format!("Failed to parse pack.mcmeta: {}", e))
+}
+
+// 读取资源包的格式和描述；supported_formats 可以是单个数字、[min, max] 或
+// {"min_inclusive": .., "max_inclusive": ..}
+pub fn read_resource_pack_meta(pack_path: &Path) -> Result<ResourcePackMeta, String> {
+    let json = read_pack_mcmeta(pack_path)?;
+    let pack = json.get("pack").ok_or("pack.mcmeta has no pack section")?;
+    let pack_format = pack
+        .get("pack_format")
+        .and_then(|f| f.as_u64())
+        .ok_or("pack.mcmeta has no pack_format")? as u32;
+
+    let as_format = |value: Option<&Value>| value.and_then(|v| v.as_u64()).map(|v| v as u32);
+    let supported_formats = pack.get("supported_formats").and_then(|formats| match formats {
+        Value::Number(_) => as_format(Some(formats)).map(|format| (format, format)),
+        Value::Array(range) => Some((as_format(range.first())?, as_format(range.get(1))?)),
+        Value::Object(range) => Some((as_format(range.get("min_inclusive"))?, as_format(range.get("max_inclusive"))?)),
+        _ => None,
+    });
+
+    let (min_format, max_format) = supported_formats.unwrap_or((pack_format, pack_format));
+    Ok(ResourcePackMeta {
+        pack_format,
+        description: flatten_text_component(pack.get("description").unwrap_or(&Value::Null)),
+        supported_formats,
+        minecraft_versions: minecraft_versions(min_format.min(pack_format), max_format.max(pack_format)),
+    })
+}
+
+// 格式区间对应的 Minecraft 版本区间；两端都不在已知格式范围内时为 None
+fn minecraft_versions(min_format: u32, max_format: u32) -> Option<String> {
+    let known: Vec<_> = RESOURCE_PACK_FORMATS
+        .iter()
+        .filter(|(format, _, _)| (min_format..=max_format).contains(format))
+        .collect();
+    let (_, first, _) = known.first()?;
+    let (_, _, last) = known.last()?;
+    Some(if first == last { first.to_string() } else { format!("{}–{}", first, last) })
+}
+
+// 读取目录或 zip 形式的包中的 pack.mcmeta 描述
+pub fn read_pack_description(pack_path: &Path) -> Result<PackDescription, String> {
+    let json = read_pack_mcmeta(pack_path)?;
+    let pack = json.get("pack").ok_or("pack.mcmeta has no pack section")?;
+    let component = pack.get("description").cloned().unwrap_or(Value::String(String::new()));
+
+    Ok(PackDescription {
+        pack_name: pack_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        pack_format: pack.get("pack_format").and_then(|f| f.as_u64()).map(|f| f as u32),
+        text: flatten_text_component(&component),
+        translate_key: component.get("translate").and_then(|k| k.as_str()).map(|s| s.to_string()),
+        component,
+    })
+}
+
+// 把文本组件（字符串、对象或数组）展开为纯文本；translate 组件优先使用 fallback
+pub fn flatten_text_component(component: &Value) -> String {
+    match component {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts.iter().map(flatten_text_component).collect(),
+        Value::Object(object) => {
+            let own = object
+                .get("text")
+                .and_then(|t| t.as_str())
+                .or_else(|| object.get("fallback").and_then(|f| f.as_str()))
+                .or_else(|| object.get("translate").and_then(|t| t.as_str()))
+                .unwrap_or_default();
+            let extra: String = object
+                .get("extra")
+                .and_then(|e| e.as_array())
+                .map(|parts| parts.iter().map(flatten_text_component).collect())
+                .unwrap_or_default();
+            format!("{}{}", own, extra)
+        }
+        Value::Number(number) => number.to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Null => String::new(),
+    }
+}
+
+// 生成包描述的翻译键，包名中的非法字符替换为下划线
+pub fn description_key(pack_name: &str) -> String {
+    let id: String = pack_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    format!("pack.{}.{}.description", DESCRIPTION_NAMESPACE, id)
+}
+
+// 多语言描述：游戏按当前语言解析 translate 组件，未加载到翻译时显示 fallback
+pub fn localized_description(key: &str, fallback: &str) -> Value {
+    serde_json::json!({
+        "translate": key,
+        "fallback": fallback,
+    })
+}
+
+// 选取某个语言的描述，缺失时依次回退到 en_us 和任意一个已有描述
+pub fn description_for_locale<'a>(descriptions: &'a BTreeMap<String, String>, locale: &str) -> Option<&'a str> {
+    descriptions
+        .get(locale)
+        .or_else(|| descriptions.get("en_us"))
+        .or_else(|| descriptions.values().next())
+        .map(|s| s.as_str())
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+// 组合包模块（CustomNPCs、FTB Quests 等）中的一条可翻译文本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslatableEntry {
+    // 由文件相对路径和字段名生成的稳定键
+    pub key: String,
+    pub source: String,
+    pub source_path: String,
+    pub kind: String,
+    // 原文中的占位符，见 placeholders::extract
+    #[serde(default)]
+    pub placeholders: Vec<String>,
+    // 文本在 source_path 中的行号（从 1 开始），来源是脚本等文本文件时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+// 组合包模块的扫描结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackModuleResource {
+    pub module_type: String,
+    pub root_path: String,
+    pub entries: Vec<TranslatableEntry>,
+}
+
+// 把路径片段规范化为键的一部分：小写字母、数字和下划线
+pub fn key_segment(segment: &str) -> String {
+    let normalized: String = segment
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    normalized.trim_matches('_').to_string()
+}
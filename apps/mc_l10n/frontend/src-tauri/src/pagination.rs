@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+// 分页结果，page 从 1 开始
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total_items: u32,
+    pub total_pages: u32,
+}
+
+// 从迭代器中取出指定页，只克隆该页的元素
+pub fn paginate<'a, T, I>(items: I, page: u32, page_size: Option<u32>) -> Page<T>
+where
+    T: Clone + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    let page = page.max(1);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let skip = ((page - 1) as usize).saturating_mul(page_size as usize);
+
+    let mut total_items = 0u32;
+    let mut page_items = Vec::new();
+    for (index, item) in items.enumerate() {
+        total_items += 1;
+        if index >= skip && page_items.len() < page_size as usize {
+            page_items.push(item.clone());
+        }
+    }
+
+    Page {
+        items: page_items,
+        page,
+        page_size,
+        total_items,
+        total_pages: total_items.div_ceil(page_size),
+    }
+}
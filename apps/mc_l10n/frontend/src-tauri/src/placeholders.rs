@@ -0,0 +1,93 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// Java 格式说明符的转换字符，t/T 之后还需要一个日期时间转换字符
+const CONVERSIONS: &str = "bBhHsScCdoxXeEfgGaA";
+
+// 提取文本中的占位符，按出现顺序保留原始写法：Java 格式说明符（%s、%1$s、%.2f、%tY）
+// 和花括号模板（{name}、{0}）。%% 和 %n 不是参数，不计入
+pub fn extract(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut found = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let length = match chars[index] {
+            '%' => format_specifier_length(&chars[index..]),
+            '{' => brace_template_length(&chars[index..]),
+            _ => None,
+        };
+        match length {
+            Some(length) => {
+                let token: String = chars[index..index + length].iter().collect();
+                if token != "%%" && token != "%n" {
+                    found.push(token);
+                }
+                index += length;
+            }
+            None => index += 1,
+        }
+    }
+
+    found
+}
+
+// 源语言文件中每个含占位符的键及其占位符；其他语言不记录
+pub fn source_inventory(locale: &str, entries: &HashMap<String, String>) -> BTreeMap<String, Vec<String>> {
+    if locale != SOURCE_LOCALE {
+        return BTreeMap::new();
+    }
+    entries
+        .iter()
+        .filter_map(|(key, value)| {
+            let found = extract(value);
+            (!found.is_empty()).then(|| (key.clone(), found))
+        })
+        .collect()
+}
+
+// %[参数序号$ 或 <][标志][宽度][.精度]转换字符；不识别空格标志，避免把 "50% chance" 之类的正文当作占位符
+fn format_specifier_length(chars: &[char]) -> Option<usize> {
+    if matches!(chars.get(1), Some('%' | 'n')) {
+        return Some(2);
+    }
+
+    let mut index = 1;
+    let digits = count_digits(&chars[index..]);
+    if digits > 0 && chars.get(index + digits) == Some(&'$') {
+        index += digits + 1;
+    } else if chars.get(index) == Some(&'<') {
+        index += 1;
+    }
+    while chars.get(index).is_some_and(|c| "-#+0,(".contains(*c)) {
+        index += 1;
+    }
+    index += count_digits(&chars[index..]);
+    if chars.get(index) == Some(&'.') {
+        let precision = count_digits(&chars[index + 1..]);
+        if precision == 0 {
+            return None;
+        }
+        index += 1 + precision;
+    }
+
+    match chars.get(index) {
+        Some('t' | 'T') if chars.get(index + 1).is_some_and(|c| c.is_ascii_alphabetic()) => Some(index + 2),
+        Some(c) if CONVERSIONS.contains(*c) => Some(index + 1),
+        _ => None,
+    }
+}
+
+// {名称}：名称由字母、数字和 _ . - 组成；{{ 开头的不是模板
+fn brace_template_length(chars: &[char]) -> Option<usize> {
+    let name = chars[1..]
+        .iter()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        .count();
+    (name > 0 && chars.get(name + 1) == Some(&'}')).then_some(name + 2)
+}
+
+fn count_digits(chars: &[char]) -> usize {
+    chars.iter().take_while(|c| c.is_ascii_digit()).count()
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::instance_guard;
+
+// 写入方式：新建、整体覆盖已有文件，或在已有文件上修改部分内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    Create,
+    Overwrite,
+    Modify,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub action: FileAction,
+    // 写入后的文件大小
+    pub size: u64,
+}
+
+// 试运行结果：将要写入的文件和预检发现的问题，不修改任何文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunReport {
+    // 始终为 true，便于前端区分试运行结果和实际执行的结果
+    pub dry_run: bool,
+    pub files: Vec<PlannedFile>,
+    // 相对现有文件需要新增的磁盘空间
+    pub required_bytes: u64,
+    // 目标所在磁盘的可用空间，无法获取时为空
+    pub available_bytes: Option<u64>,
+    pub unwritable_paths: Vec<String>,
+    pub locked_files: Vec<String>,
+    pub problems: Vec<String>,
+    // 没有发现问题，实际执行时预计可以完成
+    pub ok: bool,
+}
+
+impl DryRunReport {
+    pub fn add_problem(&mut self, problem: String) {
+        self.problems.push(problem);
+        self.ok = false;
+    }
+}
+
+// 写入类命令的返回值：实际执行时与原来的结果相同，试运行时为预检报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum WriteOutcome<T> {
+    Applied(T),
+    DryRun(DryRunReport),
+}
+
+// 预检将要写入的文件：modify 为 true 表示在已有文件上修改，否则已有文件会被覆盖
+pub fn check(planned: Vec<(PathBuf, u64)>, modify: bool) -> DryRunReport {
+    let mut report = DryRunReport {
+        dry_run: true,
+        files: Vec::new(),
+        required_bytes: 0,
+        available_bytes: None,
+        unwritable_paths: Vec::new(),
+        locked_files: Vec::new(),
+        problems: Vec::new(),
+        ok: true,
+    };
+    let mut checked_dirs: Vec<PathBuf> = Vec::new();
+
+    for (path, size) in planned {
+        let existing_size = fs::metadata(&path).ok().filter(|meta| meta.is_file()).map(|meta| meta.len());
+        let action = match existing_size {
+            None => FileAction::Create,
+            Some(_) if modify => FileAction::Modify,
+            Some(_) => FileAction::Overwrite,
+        };
+        report.required_bytes += size.saturating_sub(existing_size.unwrap_or(0));
+
+        match existing_size {
+            // 以写方式打开但不截断，不会改变文件内容；Windows 下被其他程序独占时打开失败
+            Some(_) => match OpenOptions::new().write(true).open(&path) {
+                Err(e) if is_lock_error(&e) => report.locked_files.push(path.to_string_lossy().to_string()),
+                Err(_) => report.unwritable_paths.push(path.to_string_lossy().to_string()),
+                Ok(_) => {}
+            },
+            None => {
+                // 目录尚不存在时检查最近的已存在上级目录
+                let dir = path.parent().and_then(|parent| parent.ancestors().find(|dir| dir.exists()));
+                match dir {
+                    Some(dir) if !checked_dirs.iter().any(|checked| checked == dir) => {
+                        if is_read_only(dir) {
+                            report.unwritable_paths.push(dir.to_string_lossy().to_string());
+                        }
+                        checked_dirs.push(dir.to_path_buf());
+                    }
+                    Some(_) => {}
+                    None => report.unwritable_paths.push(path.to_string_lossy().to_string()),
+                }
+            }
+        }
+
+        report.files.push(PlannedFile {
+            path: path.to_string_lossy().to_string(),
+            action,
+            size,
+        });
+    }
+
+    if let Some(dir) = checked_dirs.first().cloned().or_else(|| {
+        report.files.first().and_then(|file| Path::new(&file.path).parent().map(Path::to_path_buf))
+    }) {
+        report.available_bytes = available_space(&dir);
+    }
+
+    if !report.unwritable_paths.is_empty() {
+        report.add_problem(format!("No write permission: {}", report.unwritable_paths.join(", ")));
+    }
+    if !report.locked_files.is_empty() {
+        report.add_problem(format!("Files are in use by another program: {}", report.locked_files.join(", ")));
+    }
+    if let Some(available) = report.available_bytes.filter(|available| *available < report.required_bytes) {
+        report.add_problem(format!(
+            "Not enough disk space: {} bytes required, {} bytes available",
+            report.required_bytes, available
+        ));
+    }
+
+    report
+}
+
+// 把实例运行检查的结果记为问题，而不是直接拒绝试运行
+pub fn check_instance(report: &mut DryRunReport, path: &Path, force: bool) {
+    if let Err(e) = instance_guard::ensure_not_running(path, force) {
+        report.add_problem(e);
+    }
+}
+
+// Windows 的共享冲突（32）和锁定冲突（33）表示文件被其他程序占用
+fn is_lock_error(error: &std::io::Error) -> bool {
+    cfg!(windows) && matches!(error.raw_os_error(), Some(32) | Some(33))
+}
+
+fn is_read_only(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.permissions().readonly())
+}
+
+// Windows 下查询所在驱动器的可用空间
+#[cfg(windows)]
+fn available_space(dir: &Path) -> Option<u64> {
+    use std::os::windows::process::CommandExt;
+    // 不为 powershell.exe 弹出控制台窗口
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let script = format!("(Get-Item -LiteralPath '{}').PSDrive.Free", dir.to_string_lossy().replace('\'', "''"));
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+// 其他系统使用 df 的 POSIX 输出，第二行第四列为以 KB 计的可用空间
+#[cfg(not(windows))]
+fn available_space(dir: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(dir).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let kilobytes: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::scanners::LanguageResource;
+
+// 没有配置权重的来源类型使用的优先级
+const DEFAULT_SOURCE_WEIGHT: u32 = 10;
+
+// 按路径匹配调整优先级的规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathRule {
+    // 匹配 source_path 的通配模式，* 匹配任意字符（包括路径分隔符）
+    pub pattern: String,
+    // 匹配时加到优先级上的值，可以为负
+    pub adjust: i64,
+}
+
+// 语言资源优先级规则：合并时优先级高的资源覆盖低的
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityRules {
+    // 来源类型（resourcepack、kubejs、bundled_pack 等）的基础优先级
+    #[serde(default = "default_source_weights")]
+    pub source_weights: BTreeMap<String, u32>,
+    // 依次应用所有匹配的路径规则
+    #[serde(default)]
+    pub path_rules: Vec<PathRule>,
+    // 按 MOD id 或命名空间直接指定最终优先级
+    #[serde(default)]
+    pub mod_overrides: BTreeMap<String, u32>,
+}
+
+impl Default for PriorityRules {
+    fn default() -> Self {
+        Self {
+            source_weights: default_source_weights(),
+            path_rules: Vec::new(),
+            mod_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+// 用户资源包覆盖 KubeJS，KubeJS 覆盖 MOD 内置资源包，内置资源包覆盖 MOD 自身的语言文件
+fn default_source_weights() -> BTreeMap<String, u32> {
+    [("resourcepack", 100), ("resourcepack_zip", 100), ("kubejs", 80), ("bundled_pack", 20), ("mod_jar", 0)]
+        .into_iter()
+        .map(|(source_type, weight)| (source_type.to_string(), weight))
+        .collect()
+}
+
+impl PriorityRules {
+    pub fn resolve(&self, resource: &LanguageResource) -> u32 {
+        let owner = resource.bundled_pack.as_ref().map(|origin| origin.mod_id.as_str());
+        if let Some(priority) = owner
+            .and_then(|mod_id| self.mod_overrides.get(mod_id))
+            .or_else(|| self.mod_overrides.get(&*resource.namespace))
+        {
+            return *priority;
+        }
+
+        let base = self
+            .source_weights
+            .get(&*resource.source_type)
+            .copied()
+            .unwrap_or(DEFAULT_SOURCE_WEIGHT) as i64;
+        let path = resource.source_path.replace('\\', "/");
+        let adjusted = self
+            .path_rules
+            .iter()
+            .filter(|rule| wildcard_match(&rule.pattern, &path))
+            .fold(base, |priority, rule| priority.saturating_add(rule.adjust));
+
+        adjusted.clamp(0, u32::MAX as i64) as u32
+    }
+
+    // 按规则计算每个资源的优先级
+    pub fn apply(&self, resources: &mut [LanguageResource]) {
+        for resource in resources {
+            resource.priority = self.resolve(resource);
+        }
+    }
+}
+
+// 只支持 * 的通配匹配
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // 让上一个 * 多匹配一个字符后重试
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
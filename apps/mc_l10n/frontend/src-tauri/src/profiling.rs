@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// 报告中保留的最慢 MOD / 语言文件数量
+const MAX_REPORTED_ITEMS: usize = 50;
+
+// 单个扫描阶段的耗时
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub duration_ms: f64,
+}
+
+// 单个 MOD JAR 的处理耗时；各项只统计拿到 IO/CPU 许可之后的实际工作时间
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModTiming {
+    pub path: String,
+    pub hash_ms: f64,
+    pub zip_open_ms: f64,
+    pub read_ms: f64,
+    pub parse_ms: f64,
+    pub total_ms: f64,
+    pub cache_hit: bool,
+    // 无法读取 JAR 时的错误，此时元数据由文件名推断
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// 单个语言文件的解析耗时
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangFileTiming {
+    pub path: String,
+    pub parse_ms: f64,
+    pub key_count: u32,
+}
+
+// 扫描性能报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProfile {
+    pub scan_id: String,
+    pub total_ms: f64,
+    pub phases: Vec<PhaseTiming>,
+    pub mod_count: u32,
+    pub cache_hits: u32,
+    pub total_hash_ms: f64,
+    pub total_zip_open_ms: f64,
+    pub total_read_ms: f64,
+    pub total_parse_ms: f64,
+    pub lang_file_count: u32,
+    pub total_lang_parse_ms: f64,
+    // 按耗时降序，只保留最慢的部分
+    pub slowest_mods: Vec<ModTiming>,
+    pub slowest_lang_files: Vec<LangFileTiming>,
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// 执行并把耗时累加到 slot
+pub fn timed<R>(slot: &mut f64, work: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = work();
+    *slot += millis(start.elapsed());
+    result
+}
+
+struct ProfilerState {
+    phases: Vec<PhaseTiming>,
+    current_phase: Option<(String, Instant)>,
+    mods: Vec<ModTiming>,
+    lang_files: Vec<LangFileTiming>,
+}
+
+// 记录一次扫描的各阶段和各文件耗时，可在工作线程间共享
+pub struct ScanProfiler {
+    scan_id: String,
+    started: Instant,
+    state: Mutex<ProfilerState>,
+}
+
+impl ScanProfiler {
+    pub fn new(scan_id: &str) -> Self {
+        Self {
+            scan_id: scan_id.to_string(),
+            started: Instant::now(),
+            state: Mutex::new(ProfilerState {
+                phases: Vec::new(),
+                current_phase: None,
+                mods: Vec::new(),
+                lang_files: Vec::new(),
+            }),
+        }
+    }
+
+    // 结束上一阶段并开始计时新阶段
+    pub fn phase(&self, phase: &str) {
+        let mut state = self.lock_state();
+        Self::close_phase(&mut state);
+        state.current_phase = Some((phase.to_string(), Instant::now()));
+    }
+
+    pub fn record_mod(&self, timing: ModTiming) {
+        self.lock_state().mods.push(timing);
+    }
+
+    pub fn record_lang_file(&self, path: &str, parse_ms: f64, key_count: u32) {
+        self.lock_state().lang_files.push(LangFileTiming {
+            path: path.to_string(),
+            parse_ms,
+            key_count,
+        });
+    }
+
+    pub fn finish(self) -> ScanProfile {
+        let total_ms = millis(self.started.elapsed());
+        let mut state = self.state.into_inner().unwrap_or_else(|e| e.into_inner());
+        Self::close_phase(&mut state);
+
+        let ProfilerState { phases, mut mods, mut lang_files, .. } = state;
+        let sum = |f: fn(&ModTiming) -> f64| mods.iter().map(f).sum::<f64>();
+
+        let total_hash_ms = sum(|m| m.hash_ms);
+        let total_zip_open_ms = sum(|m| m.zip_open_ms);
+        let total_read_ms = sum(|m| m.read_ms);
+        let total_parse_ms = sum(|m| m.parse_ms);
+        let mod_count = mods.len() as u32;
+        let cache_hits = mods.iter().filter(|m| m.cache_hit).count() as u32;
+        let lang_file_count = lang_files.len() as u32;
+        let total_lang_parse_ms = lang_files.iter().map(|f| f.parse_ms).sum();
+
+        mods.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        mods.truncate(MAX_REPORTED_ITEMS);
+        lang_files.sort_by(|a, b| b.parse_ms.total_cmp(&a.parse_ms));
+        lang_files.truncate(MAX_REPORTED_ITEMS);
+
+        ScanProfile {
+            scan_id: self.scan_id,
+            total_ms,
+            phases,
+            mod_count,
+            cache_hits,
+            total_hash_ms,
+            total_zip_open_ms,
+            total_read_ms,
+            total_parse_ms,
+            lang_file_count,
+            total_lang_parse_ms,
+            slowest_mods: mods,
+            slowest_lang_files: lang_files,
+        }
+    }
+
+    fn close_phase(state: &mut ProfilerState) {
+        if let Some((phase, started)) = state.current_phase.take() {
+            state.phases.push(PhaseTiming {
+                phase,
+                duration_ms: millis(started.elapsed()),
+            });
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ProfilerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::concurrency::ScanControl;
+use crate::events::{self, AppEvent};
+use crate::session;
+
+// 两次进度事件之间的最小间隔（最多每秒 10 个事件）
+const MIN_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+// 单个事件中最多携带的合并文件名数量
+const MAX_COALESCED_FILES: usize = 20;
+
+// 扫描进度结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProgress {
+    pub scan_id: String,
+    pub phase: String,
+    pub progress: f64,
+    pub message: String,
+    pub current_file: Option<String>,
+    pub processed_files: u32,
+    pub total_files: u32,
+    pub estimated_remaining: Option<u32>,
+    pub updated_at: String,
+    // 自上一个事件以来处理过的文件（被节流合并的部分）
+    #[serde(default)]
+    pub recent_files: Vec<String>,
+    #[serde(default)]
+    pub detail: ScanDetail,
+    // 扫描已暂停，恢复后从当前位置继续
+    #[serde(default)]
+    pub paused: bool,
+}
+
+// 结构化的子进度，供详细进度面板展示
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanDetail {
+    // 当前阶段已处理到第几个条目（MOD、资源包等），以及条目总数
+    pub item_index: u32,
+    pub item_count: u32,
+    pub current_item: Option<String>,
+    // 当前条目中正在处理的语言文件
+    pub current_lang_file: Option<String>,
+    // 整个扫描到目前为止找到的键数量，不随阶段切换清零
+    pub keys_found: u64,
+}
+
+struct ReporterState {
+    phase: String,
+    message: String,
+    range: (f64, f64),
+    processed: u32,
+    total: u32,
+    phase_started: Instant,
+    last_emit: Option<Instant>,
+    current_file: Option<String>,
+    recent_files: Vec<String>,
+    current_item: Option<String>,
+    current_lang_file: Option<String>,
+    keys_found: u64,
+    paused: bool,
+    dirty: bool,
+}
+
+// 节流并合并扫描进度事件；阶段切换和完成事件总是立即发送
+pub struct ProgressReporter {
+    app: tauri::AppHandle,
+    scan_id: String,
+    state: Mutex<ReporterState>,
+    control: ScanControl,
+}
+
+impl ProgressReporter {
+    pub fn new(app: tauri::AppHandle, scan_id: &str) -> Self {
+        Self {
+            app,
+            scan_id: scan_id.to_string(),
+            state: Mutex::new(ReporterState {
+                phase: String::new(),
+                message: String::new(),
+                range: (0.0, 0.0),
+                processed: 0,
+                total: 0,
+                phase_started: Instant::now(),
+                last_emit: None,
+                current_file: None,
+                recent_files: Vec::new(),
+                current_item: None,
+                current_lang_file: None,
+                keys_found: 0,
+                paused: false,
+                dirty: false,
+            }),
+            control: ScanControl::default(),
+        }
+    }
+
+    pub fn with_control(self, control: ScanControl) -> Self {
+        Self { control, ..self }
+    }
+
+    // 扫描的检查点：暂停时发送暂停事件并在会话中记录当前位置，等待恢复后再发送一次进度，
+    // 已处理的条目不会重新处理；返回 false 表示已取消
+    pub fn checkpoint(&self) -> bool {
+        if !self.control.is_paused() {
+            return !self.control.is_cancelled();
+        }
+
+        {
+            let mut state = self.lock_state();
+            if !state.paused {
+                state.paused = true;
+                self.emit(&mut state, None);
+                let current_item = state.current_item.clone().or_else(|| state.current_file.clone());
+                session::pause_scan(&self.scan_id, true, state.processed, current_item);
+            }
+        }
+        let proceed = self.control.checkpoint();
+        let mut state = self.lock_state();
+        if proceed && state.paused {
+            state.paused = false;
+            self.emit(&mut state, None);
+            let current_item = state.current_item.clone().or_else(|| state.current_file.clone());
+            session::pause_scan(&self.scan_id, false, state.processed, current_item);
+        }
+        proceed
+    }
+
+    // 进入新阶段：先发出上一阶段未发送的进度，再立即发送阶段开始事件
+    pub fn phase(&self, phase: &str, start: f64, end: f64, message: &str, total_items: u32) {
+        let mut state = self.lock_state();
+        if state.dirty {
+            self.emit(&mut state, None);
+        }
+
+        state.phase = phase.to_string();
+        state.message = message.to_string();
+        state.range = (start, end);
+        state.processed = 0;
+        state.total = total_items;
+        state.phase_started = Instant::now();
+        state.current_file = None;
+        state.recent_files.clear();
+        state.current_item = None;
+        state.current_lang_file = None;
+        self.emit(&mut state, None);
+        drop(state);
+        session::checkpoint_scan(&self.scan_id, phase, start);
+    }
+
+    // 阶段开始后才知道总数时补充设置
+    pub fn set_total(&self, total_items: u32) {
+        self.lock_state().total = total_items;
+    }
+
+    // 当前阶段处理完一个文件；在节流间隔内只记录，不发送
+    pub fn file_done(&self, file: &str) {
+        self.item_done(file, None);
+    }
+
+    // 当前阶段处理完一个 MOD，同时记录其名称
+    pub fn mod_done(&self, file: &str, mod_name: &str) {
+        self.item_done(file, Some(mod_name));
+    }
+
+    // 读取了某个条目中的一个语言文件，累计找到的键数量
+    pub fn lang_file_done(&self, item: &str, lang_file: &str, key_count: u32) {
+        let mut state = self.lock_state();
+        state.current_item = Some(item.to_string());
+        state.current_lang_file = Some(lang_file.to_string());
+        state.keys_found += key_count as u64;
+        state.dirty = true;
+        self.emit_if_due(&mut state);
+    }
+
+    fn item_done(&self, file: &str, item: Option<&str>) {
+        let mut state = self.lock_state();
+        state.processed += 1;
+        state.current_file = Some(file.to_string());
+        if let Some(item) = item {
+            state.current_item = Some(item.to_string());
+        }
+        if state.recent_files.len() < MAX_COALESCED_FILES {
+            state.recent_files.push(file.to_string());
+        }
+        state.dirty = true;
+        self.emit_if_due(&mut state);
+        drop(state);
+        // 暂停在处理完一个条目之后生效；取消由调用方在下一个检查点处理
+        self.checkpoint();
+    }
+
+    // 发送最终的 100% 事件，不受节流限制
+    pub fn complete(&self, phase: &str, message: &str) {
+        let mut state = self.lock_state();
+        state.phase = phase.to_string();
+        state.message = message.to_string();
+        state.range = (100.0, 100.0);
+        state.processed = state.total;
+        self.emit(&mut state, Some(0));
+    }
+
+    // 扫描被取消时的最终事件，进度停在取消时的位置，不受节流限制
+    pub fn cancelled(&self, message: &str) {
+        let mut state = self.lock_state();
+        state.phase = "cancelled".to_string();
+        state.message = message.to_string();
+        self.emit(&mut state, Some(0));
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ReporterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn emit_if_due(&self, state: &mut ReporterState) {
+        let due = state.last_emit.map_or(true, |last| last.elapsed() >= MIN_EMIT_INTERVAL);
+        if due {
+            self.emit(state, None);
+        }
+    }
+
+    fn emit(&self, state: &mut ReporterState, estimated_remaining: Option<u32>) {
+        let (start, end) = state.range;
+        let fraction = if state.total > 0 {
+            (state.processed as f64 / state.total as f64).min(1.0)
+        } else {
+            0.0
+        };
+
+        // 按当前阶段的处理速度估算剩余秒数
+        let estimated_remaining = estimated_remaining.or_else(|| {
+            (state.processed > 0 && state.total > state.processed).then(|| {
+                let per_item = state.phase_started.elapsed().as_secs_f64() / state.processed as f64;
+                (per_item * (state.total - state.processed) as f64).ceil() as u32
+            })
+        });
+
+        let progress_data = ScanProgress {
+            scan_id: self.scan_id.clone(),
+            phase: state.phase.clone(),
+            progress: start + (end - start) * fraction,
+            message: state.message.clone(),
+            current_file: state.current_file.clone(),
+            processed_files: state.processed,
+            total_files: state.total,
+            estimated_remaining,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            recent_files: std::mem::take(&mut state.recent_files),
+            detail: ScanDetail {
+                item_index: state.processed,
+                item_count: state.total,
+                current_item: state.current_item.clone(),
+                current_lang_file: state.current_lang_file.clone(),
+                keys_found: state.keys_found,
+            },
+            paused: state.paused,
+        };
+
+        state.last_emit = Some(Instant::now());
+        state.dirty = false;
+        events::emit(&self.app, AppEvent::ScanProgress(&progress_data));
+    }
+}
@@ -0,0 +1,462 @@
+use rusqlite::{params, OptionalExtension, Transaction};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::config::AppConfig;
+use crate::database;
+use crate::glossary::{Glossary, GlossaryEntry};
+use crate::jar::JarReader;
+use crate::local_project;
+use crate::preflight::{self, WriteOutcome};
+use crate::project_settings::{ProjectSettings, ProjectSettingsStore};
+use crate::scan_delta::ScanSnapshot;
+
+// 项目包的扩展名
+const BUNDLE_EXTENSION: &str = "thproj";
+
+// 项目包结构的版本；无法读取更高版本的项目包
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+// 项目包中的文件
+const MANIFEST_ENTRY: &str = "manifest.json";
+const ENTRIES_ENTRY: &str = "entries.json";
+const GLOSSARY_ENTRY: &str = "glossary.json";
+const SETTINGS_ENTRY: &str = "settings.json";
+const SNAPSHOT_ENTRY: &str = "scan_snapshot.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    pub project_uid: String,
+    pub name: String,
+    // 导出机器上的项目路径，导入时可以改为本机路径
+    pub project_path: Option<String>,
+}
+
+// 项目的语言文件和条目；MOD 载体按 modid 记录，导入时对应到本机的 core_mods
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BundleEntries {
+    mods: Vec<BundleMod>,
+    language_files: Vec<BundleLanguageFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleMod {
+    modid: String,
+    name: String,
+    slug: Option<String>,
+    homepage: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleLanguageFile {
+    carrier_type: String,
+    // MOD 载体为 modid，其他载体为来源路径的摘要
+    carrier: String,
+    locale: String,
+    rel_path: String,
+    format: String,
+    source_file: String,
+    namespace: String,
+    key_hash: String,
+    entries: Vec<BundleEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleEntry {
+    key: String,
+    src_text: String,
+    dst_text: String,
+    status: String,
+    qa_flags: String,
+    updated_at: String,
+}
+
+// 导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleExport {
+    pub output_path: String,
+    pub project_uid: String,
+    pub language_files: u32,
+    pub entries: u32,
+    pub glossary_entries: u32,
+    pub includes_settings: bool,
+    pub includes_snapshot: bool,
+}
+
+// 导入结果；条目按更新时间合并，本机较新的译文保持不变
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleImport {
+    pub project_uid: String,
+    pub name: String,
+    pub project_path: Option<String>,
+    pub language_files: u32,
+    pub entries_imported: u32,
+    pub entries_kept: u32,
+    pub glossary_imported: u32,
+    pub settings_imported: bool,
+    pub snapshot_imported: bool,
+}
+
+// 把本地项目的条目、译文、术语表、项目设置和最近一次扫描快照打包为单个 .thproj 文件
+pub fn export_bundle(
+    config: &AppConfig,
+    project_uid: &str,
+    output_path: &Path,
+    dry_run: bool,
+) -> Result<WriteOutcome<BundleExport>, String> {
+    let conn = database::open_read_only(&config.get_database_path())?;
+    let (name, project_path): (String, Option<String>) = conn
+        .query_row(
+            "SELECT name, description FROM core_projects WHERE uid = ?1",
+            params![project_uid],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Project not found: {}", project_uid))?;
+    let output_path = if output_path.extension().is_none() {
+        output_path.with_extension(BUNDLE_EXTENSION)
+    } else {
+        output_path.to_path_buf()
+    };
+    let entries = read_entries(&conn, project_uid).map_err(|e| format!("Failed to read project entries: {}", e))?;
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        project_uid: project_uid.to_string(),
+        name,
+        project_path: project_path.clone(),
+    };
+    let glossary = Glossary::load().map_err(|e| e.to_string())?;
+    let settings = match &project_path {
+        Some(path) => ProjectSettingsStore::load().map_err(|e| e.to_string())?.projects.get(path).cloned(),
+        None => None,
+    };
+    let snapshot = project_path.as_deref().and_then(ScanSnapshot::load);
+
+    // 先在内存中生成项目包，试运行时可以给出准确的文件大小
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    add_json(&mut writer, MANIFEST_ENTRY, &manifest, options)?;
+    add_json(&mut writer, ENTRIES_ENTRY, &entries, options)?;
+    add_json(&mut writer, GLOSSARY_ENTRY, &glossary, options)?;
+    if let Some(settings) = &settings {
+        add_json(&mut writer, SETTINGS_ENTRY, settings, options)?;
+    }
+    if let Some(snapshot) = &snapshot {
+        add_json(&mut writer, SNAPSHOT_ENTRY, snapshot, options)?;
+    }
+    let bytes = writer.finish().map_err(|e| format!("Failed to write project bundle: {}", e))?.into_inner();
+
+    if dry_run {
+        return Ok(WriteOutcome::DryRun(preflight::check(vec![(output_path, bytes.len() as u64)], false)));
+    }
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+    fs::write(&output_path, bytes).map_err(|e| format!("Failed to write {}: {}", output_path.display(), e))?;
+
+    Ok(WriteOutcome::Applied(BundleExport {
+        output_path: output_path.to_string_lossy().to_string(),
+        project_uid: project_uid.to_string(),
+        language_files: entries.language_files.len() as u32,
+        entries: entries.language_files.iter().map(|file| file.entries.len() as u32).sum(),
+        glossary_entries: glossary.entries.len() as u32,
+        includes_settings: settings.is_some(),
+        includes_snapshot: snapshot.is_some(),
+    }))
+}
+
+fn add_json<W: Write + std::io::Seek>(
+    writer: &mut ZipWriter<W>,
+    name: &str,
+    value: &impl Serialize,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    let content = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    writer.start_file(name, options).map_err(|e| format!("Failed to write project bundle: {}", e))?;
+    writer.write_all(&content).map_err(|e| format!("Failed to write project bundle: {}", e))
+}
+
+// 项目的语言文件通过映射计划关联
+fn read_entries(conn: &rusqlite::Connection, project_uid: &str) -> Result<BundleEntries, rusqlite::Error> {
+    let mut bundle = BundleEntries::default();
+
+    let mut statement = conn.prepare(
+        "SELECT DISTINCT m.modid, m.name, m.slug, m.homepage
+         FROM local_mapping_plans p
+         JOIN core_language_files f ON f.uid = p.language_file_uid
+         JOIN core_mods m ON m.uid = f.carrier_uid
+         WHERE p.project_uid = ?1 AND f.carrier_type = 'mod' AND m.modid IS NOT NULL
+         ORDER BY m.modid",
+    )?;
+    bundle.mods = statement
+        .query_map(params![project_uid], |row| {
+            Ok(BundleMod {
+                modid: row.get(0)?,
+                name: row.get(1)?,
+                slug: row.get(2)?,
+                homepage: row.get(3)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+
+    let mut statement = conn.prepare(
+        "SELECT f.uid, f.carrier_type, COALESCE(m.modid, f.carrier_uid), f.locale, f.rel_path, f.format,
+                p.source_file, p.namespace, p.key_hash
+         FROM local_mapping_plans p
+         JOIN core_language_files f ON f.uid = p.language_file_uid
+         LEFT JOIN core_mods m ON f.carrier_type = 'mod' AND m.uid = f.carrier_uid
+         WHERE p.project_uid = ?1
+         ORDER BY f.rel_path, f.locale",
+    )?;
+    let files = statement
+        .query_map(params![project_uid], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                BundleLanguageFile {
+                    carrier_type: row.get(1)?,
+                    carrier: row.get(2)?,
+                    locale: row.get(3)?,
+                    rel_path: row.get(4)?,
+                    format: row.get(5)?,
+                    source_file: row.get(6)?,
+                    namespace: row.get(7)?,
+                    key_hash: row.get(8)?,
+                    entries: Vec::new(),
+                },
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut statement = conn.prepare(
+        "SELECT key, src_text, COALESCE(dst_text, ''), COALESCE(status, 'new'), COALESCE(qa_flags, '{}'), updated_at
+         FROM core_translation_entries WHERE language_file_uid = ?1 ORDER BY key",
+    )?;
+    for (language_file_uid, mut file) in files {
+        file.entries = statement
+            .query_map(params![language_file_uid], |row| {
+                Ok(BundleEntry {
+                    key: row.get(0)?,
+                    src_text: row.get(1)?,
+                    dst_text: row.get(2)?,
+                    status: row.get(3)?,
+                    qa_flags: row.get(4)?,
+                    updated_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        bundle.language_files.push(file);
+    }
+
+    Ok(bundle)
+}
+
+// 导入项目包；project_path 为本机的项目路径，未提供时沿用项目包中的路径
+pub fn import_bundle(config: &AppConfig, bundle_path: &Path, project_path: Option<String>) -> Result<BundleImport, String> {
+    let mut archive = JarReader::open(bundle_path)?;
+    let manifest: BundleManifest = read_json(&mut archive, MANIFEST_ENTRY)?.ok_or("Not a project bundle: manifest.json is missing")?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Project bundle format {} is newer than supported version {}",
+            manifest.format_version, BUNDLE_FORMAT_VERSION
+        ));
+    }
+    let entries: BundleEntries = read_json(&mut archive, ENTRIES_ENTRY)?.unwrap_or_default();
+    let glossary: Option<Glossary> = read_json(&mut archive, GLOSSARY_ENTRY)?;
+    let settings: Option<ProjectSettings> = read_json(&mut archive, SETTINGS_ENTRY)?;
+    let snapshot: Option<ScanSnapshot> = read_json(&mut archive, SNAPSHOT_ENTRY)?;
+    let project_path = project_path.or_else(|| manifest.project_path.clone());
+
+    let mut result = BundleImport {
+        project_uid: manifest.project_uid.clone(),
+        name: manifest.name.clone(),
+        project_path: project_path.clone(),
+        language_files: 0,
+        entries_imported: 0,
+        entries_kept: 0,
+        glossary_imported: 0,
+        settings_imported: false,
+        snapshot_imported: false,
+    };
+
+    let mut conn = local_project::open_local_database(&config.get_database_path())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    write_entries(&tx, &manifest, project_path.as_deref(), &entries, &mut result)
+        .map_err(|e| format!("Failed to import project bundle: {}", e))?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    if let Some(glossary) = glossary {
+        result.glossary_imported = merge_glossary(glossary.entries)?;
+    }
+
+    if let Some(path) = &project_path {
+        if let Some(settings) = settings {
+            let mut store = ProjectSettingsStore::load().map_err(|e| e.to_string())?;
+            store.set(path, settings);
+            store.save().map_err(|e| e.to_string())?;
+            result.settings_imported = true;
+        }
+        // 快照按项目路径保存，改为本机路径后下一次扫描可以直接比较变化
+        if let Some(mut snapshot) = snapshot {
+            snapshot.project_path = path.clone();
+            snapshot.save().map_err(|e| e.to_string())?;
+            result.snapshot_imported = true;
+        }
+    }
+
+    Ok(result)
+}
+
+fn read_json<T: DeserializeOwned>(archive: &mut JarReader, name: &str) -> Result<Option<T>, String> {
+    let Some(content) = archive.read_entry_string(name) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Invalid {} in project bundle: {}", name, e))
+}
+
+fn write_entries(
+    tx: &Transaction<'_>,
+    manifest: &BundleManifest,
+    project_path: Option<&str>,
+    bundle: &BundleEntries,
+    result: &mut BundleImport,
+) -> Result<(), rusqlite::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    // 沿用原项目 uid，重复导入同一项目时合并而不是新建
+    tx.execute(
+        "INSERT INTO core_projects (uid, name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?4)
+         ON CONFLICT(uid) DO UPDATE SET name = excluded.name, description = excluded.description, updated_at = excluded.updated_at",
+        params![manifest.project_uid, manifest.name, project_path, now],
+    )?;
+
+    for bundle_mod in &bundle.mods {
+        tx.execute(
+            "INSERT OR IGNORE INTO core_mods (uid, modid, slug, name, homepage, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                bundle_mod.modid,
+                bundle_mod.slug,
+                bundle_mod.name,
+                bundle_mod.homepage,
+                now
+            ],
+        )?;
+    }
+
+    for file in &bundle.language_files {
+        let carrier_uid = if file.carrier_type == "mod" {
+            tx.query_row("SELECT uid FROM core_mods WHERE modid = ?1", params![file.carrier], |row| row.get(0))?
+        } else {
+            file.carrier.clone()
+        };
+        tx.execute(
+            "INSERT OR IGNORE INTO core_language_files
+                (uid, carrier_type, carrier_uid, locale, rel_path, format, size, discovered_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                file.carrier_type,
+                carrier_uid,
+                file.locale,
+                file.rel_path,
+                file.format,
+                file.entries.len() as i64,
+                now
+            ],
+        )?;
+        let language_file_uid: String = tx.query_row(
+            "SELECT uid FROM core_language_files WHERE carrier_uid = ?1 AND locale = ?2 AND rel_path = ?3",
+            params![carrier_uid, file.locale, file.rel_path],
+            |row| row.get(0),
+        )?;
+        result.language_files += 1;
+
+        // 只有项目包中的条目更新时间较新时才覆盖本机的条目
+        let mut statement = tx.prepare_cached(
+            "INSERT INTO core_translation_entries
+                (uid, language_file_uid, key, src_text, dst_text, status, qa_flags, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(language_file_uid, key) DO UPDATE SET
+                src_text = excluded.src_text, dst_text = excluded.dst_text, status = excluded.status,
+                qa_flags = excluded.qa_flags, updated_at = excluded.updated_at
+             WHERE excluded.updated_at > core_translation_entries.updated_at",
+        )?;
+        for entry in &file.entries {
+            let changed = statement.execute(params![
+                uuid::Uuid::new_v4().to_string(),
+                language_file_uid,
+                entry.key,
+                entry.src_text,
+                entry.dst_text,
+                entry.status,
+                entry.qa_flags,
+                entry.updated_at
+            ])?;
+            if changed > 0 {
+                result.entries_imported += 1;
+            } else {
+                result.entries_kept += 1;
+            }
+        }
+
+        // 来源路径位于原项目目录下时改为本机路径
+        let source_file = match (manifest.project_path.as_deref(), project_path) {
+            (Some(old), Some(new)) if file.source_file.starts_with(old) => format!("{}{}", new, &file.source_file[old.len()..]),
+            _ => file.source_file.clone(),
+        };
+        tx.execute(
+            "INSERT INTO local_mapping_plans
+                (project_uid, language_file_uid, source_file, namespace, language, key_hash, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(project_uid, language_file_uid) DO UPDATE SET
+                source_file = excluded.source_file, key_hash = excluded.key_hash, updated_at = excluded.updated_at",
+            params![
+                manifest.project_uid,
+                language_file_uid,
+                source_file,
+                file.namespace,
+                file.locale,
+                file.key_hash,
+                now
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// 同一原文和语言的术语保留更新时间较新的一条
+fn merge_glossary(entries: Vec<GlossaryEntry>) -> Result<u32, String> {
+    let mut glossary = Glossary::load().map_err(|e| e.to_string())?;
+    let mut imported = 0;
+    for entry in entries {
+        match glossary
+            .entries
+            .iter_mut()
+            .find(|existing| existing.source == entry.source && existing.locale == entry.locale)
+        {
+            Some(existing) if existing.updated_at >= entry.updated_at => continue,
+            Some(existing) => *existing = entry,
+            None => glossary.entries.push(entry),
+        }
+        imported += 1;
+    }
+    if imported > 0 {
+        glossary.save().map_err(|e| e.to_string())?;
+    }
+    Ok(imported)
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::AppConfig;
+use crate::scanners::ScanPreset;
+use crate::validation::Severity;
+
+// 单个项目的本地设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    #[serde(default = "default_validation_profile")]
+    pub validation_profile: String,
+    #[serde(default)]
+    pub severity_overrides: BTreeMap<String, Severity>,
+    // 上一次扫描使用的预设，未指定预设时沿用
+    #[serde(default)]
+    pub scan_preset: ScanPreset,
+    // 多个 MOD 提供同一命名空间时用户指定的归属（命名空间 -> MOD id）
+    #[serde(default)]
+    pub namespace_owners: BTreeMap<String, String>,
+    // 不参与扫描、统计和导出的 MOD id
+    #[serde(default)]
+    pub excluded_mods: BTreeSet<String>,
+    // Rhai 脚本路径（绝对路径或相对于项目目录），提供 on_entry_extracted、on_before_export 钩子
+    #[serde(default)]
+    pub script_hooks: Option<String>,
+}
+
+impl Default for ProjectSettings {
+    fn default() -> Self {
+        Self {
+            validation_profile: default_validation_profile(),
+            severity_overrides: BTreeMap::new(),
+            scan_preset: ScanPreset::default(),
+            namespace_owners: BTreeMap::new(),
+            excluded_mods: BTreeSet::new(),
+            script_hooks: None,
+        }
+    }
+}
+
+fn default_validation_profile() -> String {
+    "release".to_string()
+}
+
+// 所有项目的设置，按项目路径索引，保存在数据目录下的 project_settings.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectSettingsStore {
+    pub projects: BTreeMap<String, ProjectSettings>,
+}
+
+impl ProjectSettingsStore {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if store_path.exists() {
+            let content = fs::read_to_string(&store_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if let Some(parent) = store_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&store_path, content)?;
+        Ok(())
+    }
+
+    pub fn get(&self, project_path: &str) -> ProjectSettings {
+        self.projects.get(project_path).cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, project_path: &str, settings: ProjectSettings) {
+        self.projects.insert(project_path.to_string(), settings);
+    }
+
+    fn get_store_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("project_settings.json"))
+    }
+}
+
+// 读取单个项目的设置
+pub fn load_project_settings(project_path: &str) -> ProjectSettings {
+    ProjectSettingsStore::load()
+        .map(|store| store.get(project_path))
+        .unwrap_or_default()
+}
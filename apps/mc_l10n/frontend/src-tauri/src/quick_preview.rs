@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::jar::JarReader;
+use crate::lang;
+use crate::locale::same_locale;
+use crate::scanners::mod_jars::collect_mod_jar_paths;
+use crate::validation::profiles::SOURCE_LOCALE;
+use crate::workspace;
+
+// 最多抽样读取的 JAR 数量，用于估算键数和判断加载器
+const SAMPLE_SIZE: usize = 12;
+
+// 抽样读取的时间预算，超出后用已读取的样本估算
+const SAMPLE_BUDGET: Duration = Duration::from_millis(1500);
+
+// 选择目录时的快速预览，不解压、不写入数据库；键数为按抽样估算的近似值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickPreview {
+    pub path: String,
+    // modpack、mod_jar、resourcepack、directory 或 file
+    pub project_type: String,
+    // forge、neoforge、fabric 或 quilt
+    pub loader: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub mod_count: u32,
+    pub sampled_mods: u32,
+    pub estimated_keys: u32,
+    pub elapsed_ms: u64,
+}
+
+pub fn quick_preview(path: &Path, config: &AppConfig) -> Result<QuickPreview, String> {
+    if !path.exists() {
+        return Err(format!("Path not found: {}", path.display()));
+    }
+    let started = Instant::now();
+    let project_type = workspace::detect_project_type(path);
+
+    let is_jar = path.extension().is_some_and(|ext| ext == "jar");
+    let (mut loader, minecraft_version) = if path.is_dir() {
+        instance_manifest(path)
+    } else if !is_jar {
+        match JarReader::open(path) {
+            Ok(mut archive) => read_instance_manifest(|name| archive.read_entry_string(name)),
+            Err(_) => (None, None),
+        }
+    } else {
+        (None, None)
+    };
+
+    let (mod_count, sampled_mods, estimated_keys, sampled_loader) = if is_jar {
+        let (keys, jar_loader) = sample_jar(path).unwrap_or_default();
+        (1, 1, keys, jar_loader)
+    } else if path.is_file() {
+        // 整合包归档不解压，只统计其中的 JAR 和引用的 MOD；键数只计入归档中直接包含的语言文件
+        let (keys, _) = sample_jar(path).unwrap_or_default();
+        (count_archive_mods(path), 0, keys, None)
+    } else if project_type == "resourcepack" {
+        (0, 0, count_source_keys(path), None)
+    } else {
+        sample_jars(collect_mod_jar_paths(path, config))
+    };
+    if loader.is_none() {
+        loader = sampled_loader;
+    }
+
+    Ok(QuickPreview {
+        path: path.to_string_lossy().to_string(),
+        project_type: project_type.to_string(),
+        loader,
+        minecraft_version,
+        mod_count,
+        sampled_mods,
+        estimated_keys,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+// 实例目录中清单记录的加载器和游戏版本
+pub fn instance_manifest(dir: &Path) -> (Option<String>, Option<String>) {
+    read_instance_manifest(|name| fs::read_to_string(dir.join(name)).ok())
+}
+
+// 从启动器或整合包清单中读取加载器和游戏版本；read_file 按相对路径读取目录或归档中的文件
+fn read_instance_manifest(mut read_file: impl FnMut(&str) -> Option<String>) -> (Option<String>, Option<String>) {
+    let mut read_json = |name: &str| -> Option<Value> { serde_json::from_str(&read_file(name)?).ok() };
+
+    // MultiMC / Prism
+    if let Some(pack) = read_json("mmc-pack.json") {
+        let components = pack.get("components").and_then(|c| c.as_array()).cloned().unwrap_or_default();
+        let component_version = |uid: &str| {
+            components
+                .iter()
+                .find(|component| component.get("uid").and_then(|u| u.as_str()) == Some(uid))
+                .and_then(|component| component.get("version").and_then(|v| v.as_str()).map(str::to_string))
+        };
+        let loader = [
+            ("net.neoforged", "neoforge"),
+            ("net.minecraftforge", "forge"),
+            ("org.quiltmc.quilt-loader", "quilt"),
+            ("net.fabricmc.fabric-loader", "fabric"),
+        ]
+        .iter()
+        .find(|(uid, _)| component_version(uid).is_some())
+        .map(|(_, loader)| loader.to_string());
+        return (loader, component_version("net.minecraft"));
+    }
+
+    // CurseForge
+    if let Some(manifest) = read_json("manifest.json") {
+        let minecraft = manifest.get("minecraft");
+        let loader = minecraft
+            .and_then(|m| m.get("modLoaders"))
+            .and_then(|loaders| loaders.as_array())
+            .and_then(|loaders| loaders.first())
+            .and_then(|loader| loader.get("id").and_then(|id| id.as_str()))
+            .and_then(|id| id.split('-').next())
+            .map(str::to_string);
+        let version = minecraft.and_then(|m| m.get("version")).and_then(|v| v.as_str()).map(str::to_string);
+        return (loader, version);
+    }
+
+    // Modrinth
+    if let Some(index) = read_json("modrinth.index.json") {
+        let dependencies = index.get("dependencies");
+        let loader = [("neoforge", "neoforge"), ("forge", "forge"), ("quilt-loader", "quilt"), ("fabric-loader", "fabric")]
+            .iter()
+            .find(|(key, _)| dependencies.and_then(|d| d.get(*key)).is_some())
+            .map(|(_, loader)| loader.to_string());
+        let version = dependencies.and_then(|d| d.get("minecraft")).and_then(|v| v.as_str()).map(str::to_string);
+        return (loader, version);
+    }
+
+    (None, None)
+}
+
+// 均匀抽样读取 JAR，按样本的平均键数估算总数；加载器取样本中最多的一种
+fn sample_jars(mut jar_paths: Vec<PathBuf>) -> (u32, u32, u32, Option<String>) {
+    jar_paths.sort();
+    let mod_count = jar_paths.len();
+    if mod_count == 0 {
+        return (0, 0, 0, None);
+    }
+
+    let step = mod_count.div_ceil(SAMPLE_SIZE).max(1);
+    let started = Instant::now();
+    let mut sampled = 0u32;
+    let mut sampled_keys = 0u64;
+    let mut loaders: HashMap<String, u32> = HashMap::new();
+    for jar_path in jar_paths.iter().step_by(step) {
+        if started.elapsed() >= SAMPLE_BUDGET {
+            break;
+        }
+        let Some((keys, loader)) = sample_jar(jar_path) else {
+            continue;
+        };
+        sampled += 1;
+        sampled_keys += keys as u64;
+        if let Some(loader) = loader {
+            *loaders.entry(loader).or_default() += 1;
+        }
+    }
+
+    let estimated = if sampled > 0 {
+        (sampled_keys as f64 / sampled as f64 * mod_count as f64).round() as u32
+    } else {
+        0
+    };
+    let loader = loaders.into_iter().max_by_key(|(_, count)| *count).map(|(loader, _)| loader);
+    (mod_count as u32, sampled, estimated, loader)
+}
+
+// 只读取中央目录和原文语言文件
+fn sample_jar(jar_path: &Path) -> Option<(u32, Option<String>)> {
+    let mut jar = JarReader::open(jar_path).ok()?;
+    let names = jar.entry_names();
+
+    let loader = [
+        ("META-INF/neoforge.mods.toml", "neoforge"),
+        ("META-INF/mods.toml", "forge"),
+        ("mcmod.info", "forge"),
+        ("quilt.mod.json", "quilt"),
+        ("fabric.mod.json", "fabric"),
+    ]
+    .iter()
+    .find(|(file, _)| names.iter().any(|name| name == file))
+    .map(|(_, loader)| loader.to_string());
+
+    let mut keys = 0u32;
+    for name in &names {
+        let Some((_, file_name)) = name.strip_prefix("assets/").and_then(|rest| rest.split_once("/lang/")) else {
+            continue;
+        };
+        let Some((file_locale, extension)) = file_name.rsplit_once('.') else {
+            continue;
+        };
+        if file_name.contains('/') || !same_locale(file_locale, SOURCE_LOCALE) {
+            continue;
+        }
+        let entries = jar
+            .read_entry_string(name)
+            .and_then(|content| lang::parse_lang_entries(&content, extension));
+        keys += entries.map_or(0, |entries| entries.len() as u32);
+    }
+
+    Some((keys, loader))
+}
+
+// 归档中的 JAR 数量，加上 modrinth.index.json 中引用的 MOD 文件数
+fn count_archive_mods(path: &Path) -> u32 {
+    let Ok(mut archive) = JarReader::open(path) else {
+        return 0;
+    };
+    let bundled = archive.entry_names().iter().filter(|name| name.ends_with(".jar")).count();
+    let referenced = archive
+        .read_entry_string("modrinth.index.json")
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|index| {
+            let files = index.get("files")?.as_array()?;
+            Some(
+                files
+                    .iter()
+                    .filter(|file| file.get("path").and_then(|p| p.as_str()).is_some_and(|p| p.starts_with("mods/")))
+                    .count(),
+            )
+        })
+        .unwrap_or(0);
+    (bundled + referenced) as u32
+}
+
+// 资源包按原文语言文件计数
+fn count_source_keys(path: &Path) -> u32 {
+    lang::load_locale_entries(path, SOURCE_LOCALE)
+        .values()
+        .map(|entries| entries.len() as u32)
+        .sum()
+}
@@ -0,0 +1,305 @@
+use serde::{Deserialize, Serialize};
+use ssh2::{HashType, Session, Sftp};
+use std::fs::{self, File};
+use std::io;
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::hashing::sha1_hex;
+use crate::keychain;
+use crate::sync_scheduler::ThrottledReader;
+
+// 网络操作超时（毫秒）
+const SESSION_TIMEOUT_MS: u32 = 30_000;
+
+// 插件目录中被视为语言文件的目录名
+const PLUGIN_LANG_DIRS: &[&str] = &["lang", "langs", "language", "languages", "locale", "locales", "messages"];
+
+// 远程服务器的认证方式；密码或私钥口令保存在系统钥匙串中，不写入配置文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteAuth {
+    Password,
+    KeyFile { private_key_path: String },
+    Agent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteServer {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub username: String,
+    pub auth: RemoteAuth,
+    // 服务器目录（包含 mods/、config/、plugins/ 的目录）
+    pub remote_root: String,
+    // 首次连接时记录的主机密钥 SHA-256 指纹，之后连接时校验
+    #[serde(default)]
+    pub host_key_sha256: Option<String>,
+}
+
+fn default_port() -> u16 {
+    22
+}
+
+// 一次同步的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSyncReport {
+    pub server_id: String,
+    // 下载到的本地目录，可以直接作为扫描目标
+    pub local_path: String,
+    pub files_downloaded: u32,
+    pub files_unchanged: u32,
+    pub bytes_downloaded: u64,
+    pub errors: Vec<String>,
+}
+
+// 所有远程服务器，保存在数据目录下的 remote_servers.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteServerStore {
+    pub servers: Vec<RemoteServer>,
+}
+
+impl RemoteServerStore {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if store_path.exists() {
+            let content = fs::read_to_string(&store_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if let Some(parent) = store_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&store_path, content)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Result<&RemoteServer, String> {
+        self.servers
+            .iter()
+            .find(|server| server.id == id)
+            .ok_or_else(|| format!("Remote server not found: {}", id))
+    }
+
+    // 按 id 新增或替换
+    pub fn upsert(&mut self, server: RemoteServer) {
+        match self.servers.iter_mut().find(|s| s.id == server.id) {
+            Some(existing) => *existing = server,
+            None => self.servers.push(server),
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<RemoteServer, String> {
+        let index = self
+            .servers
+            .iter()
+            .position(|server| server.id == id)
+            .ok_or_else(|| format!("Remote server not found: {}", id))?;
+        Ok(self.servers.remove(index))
+    }
+
+    fn get_store_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("remote_servers.json"))
+    }
+}
+
+// 保存密码或私钥口令到系统钥匙串
+pub fn store_secret(server_id: &str, secret: &str) -> Result<(), String> {
+    keychain::store_secret(&keychain_account(server_id), secret)
+}
+
+pub fn delete_secret(server_id: &str) -> Result<(), String> {
+    keychain::delete_secret(&keychain_account(server_id))
+}
+
+fn keychain_account(server_id: &str) -> String {
+    format!("sftp:{}", server_id)
+}
+
+// 建立已认证的 SFTP 会话；返回会话和本次看到的主机密钥指纹
+fn connect(server: &RemoteServer) -> Result<(Session, Sftp, String), String> {
+    let tcp = TcpStream::connect((server.host.as_str(), server.port))
+        .map_err(|e| format!("Failed to connect to {}:{}: {}", server.host, server.port, e))?;
+    let mut session = Session::new().map_err(|e| e.to_string())?;
+    session.set_timeout(SESSION_TIMEOUT_MS);
+    session.set_tcp_stream(tcp);
+    session.handshake().map_err(|e| format!("SSH handshake failed: {}", e))?;
+
+    // 指纹变化可能是中间人攻击，拒绝连接，需要用户确认后清除记录的指纹
+    let fingerprint = session
+        .host_key_hash(HashType::Sha256)
+        .map(|hash| hash.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+        .ok_or("Server did not provide a host key")?;
+    if let Some(expected) = &server.host_key_sha256 {
+        if expected != &fingerprint {
+            return Err(format!("Host key of {} has changed (expected {}, got {})", server.host, expected, fingerprint));
+        }
+    }
+
+    let secret = keychain::load_secret(&keychain_account(&server.id))?;
+    match &server.auth {
+        RemoteAuth::Password => {
+            let password = secret.ok_or("No password stored for this server")?;
+            session.userauth_password(&server.username, &password)
+        }
+        RemoteAuth::KeyFile { private_key_path } => session.userauth_pubkey_file(
+            &server.username,
+            None,
+            Path::new(private_key_path),
+            secret.as_deref(),
+        ),
+        RemoteAuth::Agent => session.userauth_agent(&server.username),
+    }
+    .map_err(|e| format!("Authentication failed: {}", e))?;
+
+    let sftp = session.sftp().map_err(|e| format!("Failed to start SFTP: {}", e))?;
+    Ok((session, sftp, fingerprint))
+}
+
+// 每个远程服务器单独一个同步目录
+pub fn local_sync_dir(server_id: &str) -> Result<PathBuf, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(config.get_data_dir().join("remote").join(sha1_hex(server_id.as_bytes())))
+}
+
+// 下载 mods/*.jar、config/ftbquests/ 和插件语言文件；大小和修改时间未变的文件跳过。
+// 返回同步结果和首次连接时记录的主机密钥指纹；max_bytes_per_sec 为下载限速
+pub fn sync_server(server: &RemoteServer, max_bytes_per_sec: Option<u64>) -> Result<(RemoteSyncReport, String), String> {
+    let (_session, sftp, fingerprint) = connect(server)?;
+    let local_root = local_sync_dir(&server.id)?;
+    let remote_root = Path::new(&server.remote_root);
+
+    let mut files = Vec::new();
+    list_files(&sftp, &remote_root.join("mods"), false, &mut files);
+    files.retain(|(path, _)| path.extension().is_some_and(|ext| ext == "jar"));
+    list_files(&sftp, &remote_root.join("config").join("ftbquests"), true, &mut files);
+
+    let mut plugin_files = Vec::new();
+    list_files(&sftp, &remote_root.join("plugins"), true, &mut plugin_files);
+    files.extend(plugin_files.into_iter().filter(|(path, _)| is_plugin_lang_file(remote_root, path)));
+
+    let mut report = RemoteSyncReport {
+        server_id: server.id.clone(),
+        local_path: local_root.to_string_lossy().to_string(),
+        files_downloaded: 0,
+        files_unchanged: 0,
+        bytes_downloaded: 0,
+        errors: Vec::new(),
+    };
+
+    for (remote_path, stat) in files {
+        let Ok(relative) = remote_path.strip_prefix(remote_root) else {
+            continue;
+        };
+        let local_path = local_root.join(relative);
+        if is_unchanged(&local_path, &stat) {
+            report.files_unchanged += 1;
+            continue;
+        }
+
+        match download(&sftp, &remote_path, &local_path, stat.mtime, max_bytes_per_sec) {
+            Ok(bytes) => {
+                report.files_downloaded += 1;
+                report.bytes_downloaded += bytes;
+            }
+            Err(e) => report.errors.push(format!("{}: {}", remote_path.display(), e)),
+        }
+    }
+
+    Ok((report, fingerprint))
+}
+
+// 列出目录中的文件；目录不存在时忽略
+fn list_files(sftp: &Sftp, dir: &Path, recursive: bool, files: &mut Vec<(PathBuf, ssh2::FileStat)>) {
+    let Ok(entries) = sftp.readdir(dir) else {
+        return;
+    };
+    for (path, stat) in entries {
+        if stat.is_dir() {
+            if recursive {
+                list_files(sftp, &path, true, files);
+            }
+        } else {
+            files.push((path, stat));
+        }
+    }
+}
+
+// plugins/<插件>/ 下语言目录中的文件，或插件根目录下的 messages*.yml
+fn is_plugin_lang_file(remote_root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(remote_root.join("plugins")) else {
+        return false;
+    };
+    let components: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_ascii_lowercase())
+        .collect();
+    if components.len() < 2 {
+        return false;
+    }
+
+    let file_name = &components[components.len() - 1];
+    let in_lang_dir = components[1..components.len() - 1]
+        .iter()
+        .any(|dir| PLUGIN_LANG_DIRS.contains(&dir.as_str()));
+    in_lang_dir || (components.len() == 2 && file_name.starts_with("messages") && file_name.ends_with(".yml"))
+}
+
+// 本地文件的大小和修改时间与远程一致
+fn is_unchanged(local_path: &Path, stat: &ssh2::FileStat) -> bool {
+    let Ok(metadata) = fs::metadata(local_path) else {
+        return false;
+    };
+    let local_mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs());
+    stat.size == Some(metadata.len()) && stat.mtime.is_some() && stat.mtime == local_mtime
+}
+
+// 先写入临时文件再改名，并把修改时间设为远程的修改时间，供下次同步比较
+fn download(
+    sftp: &Sftp,
+    remote_path: &Path,
+    local_path: &Path,
+    mtime: Option<u64>,
+    max_bytes_per_sec: Option<u64>,
+) -> Result<u64, String> {
+    if let Some(parent) = local_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut remote = sftp.open(remote_path).map_err(|e| e.to_string())?;
+    let mut partial = local_path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let mut local = File::create(&partial).map_err(|e| e.to_string())?;
+    let bytes = match max_bytes_per_sec {
+        Some(limit) => io::copy(&mut ThrottledReader::new(remote, limit), &mut local),
+        None => io::copy(&mut remote, &mut local),
+    }
+    .map_err(|e| e.to_string())?;
+
+    if let Some(mtime) = mtime {
+        let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(mtime);
+        let _ = local.set_modified(modified);
+    }
+    drop(local);
+    fs::rename(&partial, local_path).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
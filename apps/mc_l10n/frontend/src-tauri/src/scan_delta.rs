@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::WorkLimits;
+use crate::config::AppConfig;
+use crate::hashing::sha1_hex;
+use crate::lang;
+use crate::mod_metadata::ModJarMetadata;
+use crate::pack_module::PackModuleResource;
+use crate::validation::profiles::SOURCE_LOCALE;
+
+// 上一次扫描的快照：MOD 版本和原文键的内容摘要，用于与下一次扫描对比
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    pub scan_id: String,
+    pub project_path: String,
+    pub scanned_at: String,
+    // mod_id -> 版本
+    pub mods: BTreeMap<String, String>,
+    // "<namespace>:<key>" -> 原文摘要；quick 预设不提取原文，为空
+    pub keys: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModChange {
+    pub mod_id: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+// 本次扫描相对同一路径上一次扫描的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDelta {
+    pub scan_id: String,
+    pub project_path: String,
+    // 首次扫描时为空
+    pub previous_scan_id: Option<String>,
+    pub previous_scanned_at: Option<String>,
+    pub mods_added: Vec<ModChange>,
+    pub mods_removed: Vec<ModChange>,
+    pub mods_updated: Vec<ModChange>,
+    // 任一次扫描没有提取原文时不比较键
+    pub keys_compared: bool,
+    pub keys_added: Vec<String>,
+    pub keys_removed: Vec<String>,
+    pub keys_changed: Vec<String>,
+}
+
+impl ScanSnapshot {
+    // 读取某个路径上一次扫描的快照
+    pub fn load(project_path: &str) -> Option<Self> {
+        let content = fs::read_to_string(Self::get_snapshot_path(project_path).ok()?).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot_path = Self::get_snapshot_path(&self.project_path)?;
+
+        if let Some(parent) = snapshot_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // 大型整合包的快照可达数十万个键，不做格式化
+        let content = serde_json::to_string(self)?;
+        fs::write(&snapshot_path, content)?;
+        Ok(())
+    }
+
+    // 按项目路径的摘要命名，每个路径只保留最近一次
+    fn get_snapshot_path(project_path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config
+            .get_data_dir()
+            .join("scan_snapshots")
+            .join(format!("{}.json", sha1_hex(project_path.as_bytes()))))
+    }
+}
+
+// 由扫描结果生成快照；给出 jar_paths 时读取项目和这些 MOD JAR 中的原文
+pub fn build_snapshot(
+    scan_id: &str,
+    project_path: &Path,
+    mod_jars: &[ModJarMetadata],
+    pack_modules: &[PackModuleResource],
+    jar_paths: Option<Vec<PathBuf>>,
+    limits: &WorkLimits,
+) -> ScanSnapshot {
+    let mods = mod_jars
+        .iter()
+        .map(|metadata| (metadata.mod_id.clone(), metadata.version.clone()))
+        .collect();
+
+    let keys = jar_paths.map(|jar_paths| {
+        let mut keys = BTreeMap::new();
+        let per_jar = limits.map(jar_paths, |limits, path| {
+            let _io = limits.io.acquire();
+            lang::load_jar_locale_entries(&path, SOURCE_LOCALE).ok()
+        });
+
+        // 项目中的语言文件覆盖同名的 MOD 原文
+        let project_entries = lang::load_locale_entries(project_path, SOURCE_LOCALE);
+        for entries in per_jar.into_iter().flatten().chain(std::iter::once(project_entries)) {
+            for (namespace, namespace_entries) in entries {
+                for (key, value) in namespace_entries {
+                    keys.insert(format!("{}:{}", namespace, key), value_digest(&value));
+                }
+            }
+        }
+
+        for module in pack_modules {
+            for entry in &module.entries {
+                keys.insert(format!("{}:{}", module.module_type, entry.key), value_digest(&entry.source));
+            }
+        }
+        keys
+    });
+
+    ScanSnapshot {
+        scan_id: scan_id.to_string(),
+        project_path: project_path.to_string_lossy().to_string(),
+        scanned_at: chrono::Utc::now().to_rfc3339(),
+        mods,
+        keys,
+    }
+}
+
+// 截短的摘要足以发现原文变化，同时控制快照体积
+fn value_digest(value: &str) -> String {
+    sha1_hex(value.as_bytes())[..16].to_string()
+}
+
+pub fn compute_delta(previous: Option<&ScanSnapshot>, current: &ScanSnapshot) -> ScanDelta {
+    let mut delta = ScanDelta {
+        scan_id: current.scan_id.clone(),
+        project_path: current.project_path.clone(),
+        previous_scan_id: previous.map(|p| p.scan_id.clone()),
+        previous_scanned_at: previous.map(|p| p.scanned_at.clone()),
+        mods_added: Vec::new(),
+        mods_removed: Vec::new(),
+        mods_updated: Vec::new(),
+        keys_compared: false,
+        keys_added: Vec::new(),
+        keys_removed: Vec::new(),
+        keys_changed: Vec::new(),
+    };
+    let Some(previous) = previous else {
+        return delta;
+    };
+
+    for (mod_id, version) in &current.mods {
+        match previous.mods.get(mod_id) {
+            None => delta.mods_added.push(ModChange {
+                mod_id: mod_id.clone(),
+                old_version: None,
+                new_version: Some(version.clone()),
+            }),
+            Some(old_version) if old_version != version => delta.mods_updated.push(ModChange {
+                mod_id: mod_id.clone(),
+                old_version: Some(old_version.clone()),
+                new_version: Some(version.clone()),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (mod_id, version) in &previous.mods {
+        if !current.mods.contains_key(mod_id) {
+            delta.mods_removed.push(ModChange {
+                mod_id: mod_id.clone(),
+                old_version: Some(version.clone()),
+                new_version: None,
+            });
+        }
+    }
+
+    if let (Some(old_keys), Some(new_keys)) = (&previous.keys, &current.keys) {
+        delta.keys_compared = true;
+        for (key, digest) in new_keys {
+            match old_keys.get(key) {
+                None => delta.keys_added.push(key.clone()),
+                Some(old_digest) if old_digest != digest => delta.keys_changed.push(key.clone()),
+                Some(_) => {}
+            }
+        }
+        delta.keys_removed = old_keys
+            .keys()
+            .filter(|key| !new_keys.contains_key(*key))
+            .cloned()
+            .collect();
+    }
+
+    delta
+}
@@ -0,0 +1,448 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::concurrency::ScanControl;
+use crate::config::AppConfig;
+use crate::errors::{self, ErrorCode};
+use crate::events::{self, AppEvent};
+use crate::scanners::ScanPreset;
+use crate::session;
+
+// 队列中最多保留的已结束任务，更早的从队列状态中移除
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanJobState {
+    Pending,
+    Running,
+    // 运行中被暂停，恢复后从暂停的位置继续；暂停期间不占用扫描名额
+    Paused,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanJob {
+    pub scan_id: String,
+    pub project_path: String,
+    pub preset: ScanPreset,
+    // 数值大的先开始，相同时按加入顺序
+    pub priority: i32,
+    pub state: ScanJobState,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+    // 已请求恢复的暂停扫描，等待空出名额后继续
+    #[serde(default)]
+    pub resume_pending: bool,
+    #[serde(skip)]
+    order: u64,
+}
+
+// 扫描队列状态，同时作为 "scan-queue-changed" 事件的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanQueueState {
+    pub max_concurrent: usize,
+    pub pending: u32,
+    pub running: u32,
+    #[serde(default)]
+    pub paused: u32,
+    pub jobs: Vec<ScanJob>,
+}
+
+// 多个项目的扫描按优先级排队，同时运行的扫描数不超过 AppConfig.max_concurrent_scans
+#[derive(Default)]
+pub struct ScanQueue {
+    inner: Mutex<QueueInner>,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    jobs: Vec<ScanJob>,
+    next_order: u64,
+    // 运行中（含已暂停）的扫描的控制
+    controls: HashMap<String, ScanControl>,
+}
+
+impl ScanQueue {
+    // 加入队列并在有空闲名额时立即开始，返回扫描 id
+    pub fn enqueue(&self, app: &AppHandle, project_path: &str, preset: ScanPreset, priority: i32) -> String {
+        let scan_id = self.push(project_path, preset, priority);
+        // 扫描完成前程序异常退出时，下次启动可以按相同路径和预设重新扫描
+        session::start_scan(&scan_id, project_path, preset);
+        dispatch(app);
+        scan_id
+    }
+
+    fn push(&self, project_path: &str, preset: ScanPreset, priority: i32) -> String {
+        let scan_id = uuid::Uuid::new_v4().to_string();
+        let mut inner = self.lock();
+        let order = inner.next_order;
+        inner.next_order += 1;
+        inner.jobs.push(ScanJob {
+            scan_id: scan_id.clone(),
+            project_path: project_path.to_string(),
+            preset,
+            priority,
+            state: ScanJobState::Pending,
+            queued_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            resume_pending: false,
+            order,
+        });
+        scan_id
+    }
+
+    pub fn state(&self) -> ScanQueueState {
+        let inner = self.lock();
+        let count = |state| inner.jobs.iter().filter(|job| job.state == state).count() as u32;
+        ScanQueueState {
+            max_concurrent: max_concurrent(),
+            pending: count(ScanJobState::Pending),
+            running: count(ScanJobState::Running),
+            paused: count(ScanJobState::Paused),
+            jobs: inner.jobs.clone(),
+        }
+    }
+
+    // 只能调整尚未开始的扫描
+    pub fn set_priority(&self, app: &AppHandle, scan_id: &str, priority: i32) -> Result<(), String> {
+        self.update_pending(scan_id, |job| job.priority = priority)?;
+        notify(app);
+        Ok(())
+    }
+
+    pub fn cancel(&self, app: &AppHandle, scan_id: &str) -> Result<(), String> {
+        self.update_pending(scan_id, |job| {
+            job.state = ScanJobState::Cancelled;
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        })?;
+        session::finish_scan(scan_id);
+        notify(app);
+        Ok(())
+    }
+
+    // 尚未开始的扫描直接移出等待；运行中或已暂停的扫描设置取消标记，扫描在下一个检查点停止，
+    // 结束时发送 "cancelled" 进度事件并标记为已取消
+    pub fn cancel_scan(&self, app: &AppHandle, scan_id: &str) -> Result<(), String> {
+        let control = {
+            let inner = self.lock();
+            let job = inner
+                .jobs
+                .iter()
+                .find(|job| job.scan_id == scan_id)
+                .ok_or_else(|| errors::tagged(ErrorCode::ScanNotFound, format!("Scan not found in queue: {}", scan_id)))?;
+            match job.state {
+                ScanJobState::Pending => None,
+                ScanJobState::Running | ScanJobState::Paused => inner.controls.get(scan_id).cloned(),
+                _ => return Err(format!("Scan {} has already finished", scan_id)),
+            }
+        };
+        match control {
+            Some(control) => {
+                control.cancel();
+                Ok(())
+            }
+            None => self.cancel(app, scan_id),
+        }
+    }
+
+    // 暂停运行中的扫描：各工作线程处理完手头的条目后在检查点等待，已完成的部分保留。
+    // 暂停期间释放扫描名额，排队的扫描可以开始
+    pub fn pause_scan(&self, app: &AppHandle, scan_id: &str) -> Result<(), String> {
+        self.pause(scan_id)?;
+        dispatch(app);
+        Ok(())
+    }
+
+    // 有空闲名额时立即继续，否则在空出名额时先于排队的扫描继续
+    pub fn resume_scan(&self, app: &AppHandle, scan_id: &str) -> Result<(), String> {
+        self.request_resume(scan_id)?;
+        dispatch(app);
+        Ok(())
+    }
+
+    fn pause(&self, scan_id: &str) -> Result<(), String> {
+        self.update_started(scan_id, ScanJobState::Running, |job, control| {
+            control.pause();
+            job.state = ScanJobState::Paused;
+        })
+    }
+
+    fn request_resume(&self, scan_id: &str) -> Result<(), String> {
+        self.update_started(scan_id, ScanJobState::Paused, |job, _| job.resume_pending = true)
+    }
+
+    fn update_started(
+        &self,
+        scan_id: &str,
+        expected: ScanJobState,
+        change: impl FnOnce(&mut ScanJob, &ScanControl),
+    ) -> Result<(), String> {
+        let mut inner = self.lock();
+        let control = inner.controls.get(scan_id).cloned();
+        let job = inner
+            .jobs
+            .iter_mut()
+            .find(|job| job.scan_id == scan_id)
+            .ok_or_else(|| errors::tagged(ErrorCode::ScanNotFound, format!("Scan not found in queue: {}", scan_id)))?;
+        let control = match control {
+            Some(control) if job.state == expected => control,
+            _ => {
+                let expected = if expected == ScanJobState::Paused { "paused" } else { "running" };
+                return Err(errors::tagged(ErrorCode::InvalidInput, format!("Scan {} is not {}", scan_id, expected)));
+            }
+        };
+        change(job, &control);
+        Ok(())
+    }
+
+    fn update_pending(&self, scan_id: &str, change: impl FnOnce(&mut ScanJob)) -> Result<(), String> {
+        let mut inner = self.lock();
+        let job = inner
+            .jobs
+            .iter_mut()
+            .find(|job| job.scan_id == scan_id)
+            .ok_or_else(|| format!("Scan not found in queue: {}", scan_id))?;
+        if job.state != ScanJobState::Pending {
+            return Err(format!("Scan {} has already started", scan_id));
+        }
+        change(job);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 按空闲名额恢复已请求恢复的扫描，再取出可以开始的任务并标记为运行中，同时创建各自的控制。
+    // 已暂停的扫描不占用名额
+    fn take_startable(&self, max_concurrent: usize) -> Vec<(ScanJob, ScanControl)> {
+        let mut inner = self.lock();
+        let QueueInner { jobs, controls, .. } = &mut *inner;
+        let running = jobs.iter().filter(|job| job.state == ScanJobState::Running).count();
+        let mut free = max_concurrent.saturating_sub(running);
+        while free > 0 {
+            let Some(job) = next_job(jobs, |job| job.state == ScanJobState::Paused && job.resume_pending) else {
+                break;
+            };
+            if let Some(control) = controls.get(&job.scan_id) {
+                control.resume();
+            }
+            job.state = ScanJobState::Running;
+            job.resume_pending = false;
+            free -= 1;
+        }
+
+        let mut started = Vec::new();
+        while free > 0 {
+            let Some(job) = next_job(jobs, |job| job.state == ScanJobState::Pending) else {
+                break;
+            };
+            job.state = ScanJobState::Running;
+            job.started_at = Some(chrono::Utc::now().to_rfc3339());
+            let control = ScanControl::default();
+            controls.insert(job.scan_id.clone(), control.clone());
+            started.push((job.clone(), control));
+            free -= 1;
+        }
+        started
+    }
+
+    fn finish(&self, scan_id: &str, result: Result<(), String>) {
+        let mut inner = self.lock();
+        inner.controls.remove(scan_id);
+        if let Some(job) = inner.jobs.iter_mut().find(|job| job.scan_id == scan_id) {
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            job.resume_pending = false;
+            match result {
+                Ok(()) => job.state = ScanJobState::Done,
+                Err(e) if errors::classify(&e).0 == ErrorCode::ScanCancelled => job.state = ScanJobState::Cancelled,
+                Err(e) => {
+                    job.state = ScanJobState::Failed;
+                    job.error = Some(e);
+                }
+            }
+        }
+
+        let is_finished = |job: &ScanJob| {
+            matches!(job.state, ScanJobState::Done | ScanJobState::Failed | ScanJobState::Cancelled)
+        };
+        let finished = inner.jobs.iter().filter(|job| is_finished(job)).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        inner.jobs.retain(|job| {
+            if excess > 0 && is_finished(job) {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+}
+
+// 优先级最高的任务，相同时取最早加入的
+fn next_job(jobs: &mut [ScanJob], ready: impl Fn(&ScanJob) -> bool) -> Option<&mut ScanJob> {
+    jobs.iter_mut()
+        .filter(|job| ready(job))
+        .max_by(|a, b| a.priority.cmp(&b.priority).then(b.order.cmp(&a.order)))
+}
+
+fn max_concurrent() -> usize {
+    AppConfig::load().map(|config| config.max_concurrent_scans).unwrap_or(1).max(1)
+}
+
+fn notify(app: &AppHandle) {
+    let state = app.state::<ScanQueue>().state();
+    events::emit(app, AppEvent::ScanQueueChanged(&state));
+}
+
+// 按优先级开始排队的扫描；每个扫描结束后再次调度
+fn dispatch(app: &AppHandle) {
+    let queue = app.state::<ScanQueue>();
+    for (job, control) in queue.take_startable(max_concurrent()) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = crate::run_queued_scan(&app, &job.scan_id, &job.project_path, job.preset, control).await;
+            session::finish_scan(&job.scan_id);
+            app.state::<ScanQueue>().finish(&job.scan_id, result);
+            dispatch(&app);
+        });
+    }
+    notify(app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(queue: &ScanQueue, max_concurrent: usize) -> Vec<String> {
+        queue.take_startable(max_concurrent).into_iter().map(|(job, _)| job.project_path).collect()
+    }
+
+    fn scan_id(queue: &ScanQueue, project_path: &str) -> String {
+        queue.lock().jobs.iter().find(|job| job.project_path == project_path).unwrap().scan_id.clone()
+    }
+
+    fn job_state(queue: &ScanQueue, project_path: &str) -> ScanJobState {
+        queue.lock().jobs.iter().find(|job| job.project_path == project_path).unwrap().state
+    }
+
+    #[test]
+    fn higher_priority_starts_first_then_queue_order() {
+        let queue = ScanQueue::default();
+        queue.push("a", ScanPreset::Standard, 0);
+        queue.push("b", ScanPreset::Standard, 5);
+        queue.push("c", ScanPreset::Standard, 5);
+        queue.push("d", ScanPreset::Standard, -1);
+        queue.push("e", ScanPreset::Standard, 0);
+
+        assert_eq!(start(&queue, 10), vec!["b", "c", "a", "e", "d"]);
+    }
+
+    #[test]
+    fn running_scans_never_exceed_the_limit() {
+        let queue = ScanQueue::default();
+        for project in ["a", "b", "c"] {
+            queue.push(project, ScanPreset::Standard, 0);
+        }
+
+        assert_eq!(start(&queue, 2), vec!["a", "b"]);
+        assert!(start(&queue, 2).is_empty());
+        assert_eq!(job_state(&queue, "c"), ScanJobState::Pending);
+
+        // 结束一个扫描后空出一个名额，失败和取消同样释放名额
+        queue.finish(&scan_id(&queue, "a"), Ok(()));
+        assert_eq!(start(&queue, 2), vec!["c"]);
+        queue.finish(&scan_id(&queue, "b"), Err("Failed to read mods".to_string()));
+        queue.finish(&scan_id(&queue, "c"), Err(errors::tagged(ErrorCode::ScanCancelled, "Scan was cancelled")));
+        assert_eq!(job_state(&queue, "a"), ScanJobState::Done);
+        assert_eq!(job_state(&queue, "b"), ScanJobState::Failed);
+        assert_eq!(job_state(&queue, "c"), ScanJobState::Cancelled);
+        assert!(start(&queue, 2).is_empty());
+    }
+
+    #[test]
+    fn lowering_the_limit_does_not_stop_running_scans() {
+        let queue = ScanQueue::default();
+        for project in ["a", "b", "c"] {
+            queue.push(project, ScanPreset::Standard, 0);
+        }
+        assert_eq!(start(&queue, 2).len(), 2);
+
+        // 上限降为 1 后，运行中的 2 个扫描都结束前不再开始新的扫描
+        queue.finish(&scan_id(&queue, "a"), Ok(()));
+        assert!(start(&queue, 1).is_empty());
+        queue.finish(&scan_id(&queue, "b"), Ok(()));
+        assert_eq!(start(&queue, 1), vec!["c"]);
+    }
+
+    #[test]
+    fn paused_scans_free_their_slot_until_resumed() {
+        let queue = ScanQueue::default();
+        for project in ["a", "b", "c"] {
+            queue.push(project, ScanPreset::Standard, 0);
+        }
+        let (_, control) = queue.take_startable(1).pop().unwrap();
+
+        // 暂停 a 后 b 可以开始
+        queue.pause(&scan_id(&queue, "a")).unwrap();
+        assert!(control.is_paused());
+        assert_eq!(start(&queue, 1), vec!["b"]);
+
+        // 名额已满时请求恢复，a 保持暂停，直到 b 结束后先于排队的 c 继续
+        queue.request_resume(&scan_id(&queue, "a")).unwrap();
+        assert!(start(&queue, 1).is_empty());
+        assert_eq!(job_state(&queue, "a"), ScanJobState::Paused);
+        assert!(control.is_paused());
+        queue.finish(&scan_id(&queue, "b"), Ok(()));
+        assert!(start(&queue, 1).is_empty());
+        assert_eq!(job_state(&queue, "a"), ScanJobState::Running);
+        assert!(!control.is_paused());
+        assert_eq!(job_state(&queue, "c"), ScanJobState::Pending);
+    }
+
+    #[test]
+    fn only_running_scans_can_be_paused_and_only_paused_ones_resumed() {
+        let queue = ScanQueue::default();
+        queue.push("a", ScanPreset::Standard, 0);
+        let a = scan_id(&queue, "a");
+        assert!(queue.pause(&a).is_err());
+        assert_eq!(start(&queue, 1), vec!["a"]);
+        assert!(queue.request_resume(&a).is_err());
+        queue.pause(&a).unwrap();
+        assert!(queue.pause(&a).is_err());
+        assert_eq!(errors::classify(&queue.pause("missing").unwrap_err()).0, ErrorCode::ScanNotFound);
+    }
+
+    #[test]
+    fn priority_changes_apply_to_pending_scans() {
+        let queue = ScanQueue::default();
+        queue.push("a", ScanPreset::Standard, 0);
+        queue.push("b", ScanPreset::Standard, 0);
+        queue.update_pending(&scan_id(&queue, "b"), |job| job.priority = 1).unwrap();
+        assert_eq!(start(&queue, 1), vec!["b"]);
+        assert!(queue.update_pending(&scan_id(&queue, "b"), |job| job.priority = 2).is_err());
+    }
+
+    #[test]
+    fn finished_jobs_are_trimmed() {
+        let queue = ScanQueue::default();
+        for index in 0..MAX_FINISHED_JOBS + 5 {
+            queue.push(&index.to_string(), ScanPreset::Quick, 0);
+        }
+        for (job, _) in queue.take_startable(usize::MAX) {
+            queue.finish(&job.scan_id, Ok(()));
+        }
+        let jobs = &queue.lock().jobs;
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS);
+        // 先结束的先移除
+        assert_eq!(jobs[0].project_path, "5");
+    }
+}
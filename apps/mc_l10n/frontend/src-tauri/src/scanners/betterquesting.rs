@@ -0,0 +1,173 @@
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::io_retry;
+use crate::pack_module::{PackModuleResource, TranslatableEntry};
+use crate::placeholders;
+
+pub const MODULE_TYPE: &str = "betterquesting";
+
+// 任务和任务线中可翻译的属性及其在键中的名称
+const TEXT_PROPERTIES: &[(&str, &str)] = &[("name", "name"), ("desc", "description")];
+
+// BetterQuesting 扫描器：config/betterquesting/DefaultQuests.json，以及新版按文件拆分的
+// DefaultQuests/ 目录中的任务名称和描述
+pub struct BetterQuestingScanner;
+
+impl ContentScanner for BetterQuestingScanner {
+    fn id(&self) -> &'static str {
+        MODULE_TYPE
+    }
+
+    fn name(&self) -> &'static str {
+        "BetterQuesting quests"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_pack_modules"
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Deep
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let root = ctx.project_path.join("config").join("betterquesting");
+        let mut files = Vec::new();
+        {
+            let _io = ctx.limits.io.acquire();
+            let database = root.join("DefaultQuests.json");
+            if database.is_file() {
+                files.push(database);
+            }
+            collect_json_files(&root.join("DefaultQuests"), &mut files);
+        }
+
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        for path in files {
+            let json = {
+                let _io = ctx.limits.io.acquire();
+                io_retry::read_to_string(&path).map_err(|e| e.to_string()).and_then(|content| {
+                    serde_json::from_str::<Value>(content.trim_start_matches('\u{FEFF}')).map_err(|e| e.to_string())
+                })
+            };
+            let json = match json {
+                Ok(json) => json,
+                Err(e) => {
+                    output.warnings.push(format!("Failed to read {}: {}", path.display(), e));
+                    continue;
+                }
+            };
+
+            let source_path = path.to_string_lossy().to_string();
+            let mut found = Vec::new();
+            collect_entries(&json, &source_path, &mut found);
+            entries.extend(found.into_iter().filter(|entry| seen.insert(entry.key.clone())));
+        }
+
+        if !entries.is_empty() {
+            output.pack_modules.push(PackModuleResource {
+                module_type: MODULE_TYPE.to_string(),
+                root_path: root.to_string_lossy().to_string(),
+                entries,
+            });
+        }
+    }
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_json_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+}
+
+// 数据库文件中的任务在 questDatabase 下，任务线在 questLines 下；拆分后的文件本身就是一个任务或任务线。
+// 通过 questID/lineID 字段识别，不依赖文件位置
+fn collect_entries(value: &Value, source_path: &str, entries: &mut Vec<TranslatableEntry>) {
+    let Some(object) = value.as_object() else { return };
+
+    if let Some(id) = quest_id(object) {
+        push_properties(object, "quest", &id, source_path, entries);
+        return;
+    }
+    if let Some(id) = field(object, "lineID").and_then(Value::as_i64) {
+        push_properties(object, "quest_line", &id.to_string(), source_path, entries);
+        return;
+    }
+    for name in ["questDatabase", "questLines"] {
+        for child in field(object, name).map(children).unwrap_or_default() {
+            collect_entries(child, source_path, entries);
+        }
+    }
+}
+
+// 键为 betterquesting.<quest|quest_line>.<ID>.<name|description>
+fn push_properties(object: &Map<String, Value>, kind: &str, id: &str, source_path: &str, entries: &mut Vec<TranslatableEntry>) {
+    let Some(properties) = field(object, "properties")
+        .and_then(Value::as_object)
+        .and_then(|properties| field(properties, "betterquesting"))
+        .and_then(Value::as_object)
+    else {
+        return;
+    };
+
+    for (property, suffix) in TEXT_PROPERTIES {
+        let Some(text) = field(properties, property).and_then(Value::as_str) else { continue };
+        if text.trim().is_empty() || is_translation_key(text) {
+            continue;
+        }
+        entries.push(TranslatableEntry {
+            key: format!("{}.{}.{}.{}", MODULE_TYPE, kind, id, suffix),
+            source: text.to_string(),
+            source_path: source_path.to_string(),
+            kind: kind.to_string(),
+            placeholders: placeholders::extract(text),
+            line: None,
+        });
+    }
+}
+
+// 旧版为 questID，新版拆分为 questIDHigh/questIDLow 两个 32 位整数
+fn quest_id(object: &Map<String, Value>) -> Option<String> {
+    if let Some(id) = field(object, "questID").and_then(Value::as_i64) {
+        return Some(id.to_string());
+    }
+    let high = field(object, "questIDHigh").and_then(Value::as_i64)?;
+    let low = field(object, "questIDLow").and_then(Value::as_i64)?;
+    Some(((high << 32) | (low & 0xFFFF_FFFF)).to_string())
+}
+
+// BetterQuesting 以 NBT 转换的 JSON 保存，键带类型后缀（如 "name:8"、"questDatabase:9"），按去掉后缀的名称查找
+fn field<'a>(object: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    object
+        .iter()
+        .find(|(key, _)| key.split_once(':').map_or(key.as_str(), |(base, _)| base) == name)
+        .map(|(_, value)| value)
+}
+
+// NBT 列表转换后可能是数组，也可能是以序号为键的对象
+fn children(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(object) => object.values().collect(),
+        _ => Vec::new(),
+    }
+}
+
+// 已经本地化的任务包中名称和描述是语言键
+fn is_translation_key(text: &str) -> bool {
+    text.contains('.')
+        && !text.ends_with('.')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+
+use super::mod_lang::{cached_lang_files, CachedLangFile};
+use super::{BundledPackOrigin, ContentScanner, LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+use crate::concurrency::MemoryBudget;
+use crate::formatting_codes::KeyFormatting;
+use crate::jar::{ArchiveDiagnostics, JarReader, MAX_NESTED_DEPTH};
+use crate::lang_stream;
+use crate::locale;
+use crate::profiling::millis;
+
+// 内置资源包扫描器：MOD 在 JAR 中附带的额外资源包（resourcepacks/<name>/、程序员美术风格包、
+// 嵌套的 zip 资源包等）以及 Jar-in-Jar 子 MOD 中的语言文件，均归属于外层 MOD；
+// deep 预设下还会读取 JAR 中嵌套的其他依赖 JAR
+pub struct BundledPackScanner;
+
+// JAR 内置资源包中的一个语言文件
+#[derive(Serialize, Deserialize)]
+struct BundledLangFile {
+    namespace: String,
+    locale: String,
+    pack_name: String,
+    source_path: String,
+    key_count: u32,
+    duplicate_keys: u32,
+    placeholders: BTreeMap<String, Vec<String>>,
+    formatting: BTreeMap<String, KeyFormatting>,
+    key_categories: BTreeMap<String, u32>,
+    parse_error: Option<String>,
+    warnings: Vec<String>,
+    // 从缓存读出时为 0
+    #[serde(skip)]
+    parse_ms: f64,
+}
+
+impl CachedLangFile for BundledLangFile {
+    fn labelled_paths(&mut self) -> Vec<&mut String> {
+        std::iter::once(&mut self.source_path).chain(self.warnings.iter_mut()).collect()
+    }
+}
+
+impl ContentScanner for BundledPackScanner {
+    fn id(&self) -> &'static str {
+        "bundled_packs"
+    }
+
+    fn name(&self) -> &'static str {
+        "bundled resource packs"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_bundled_packs"
+    }
+
+    fn weight(&self) -> f64 {
+        2.0
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let mod_jars: Vec<_> = ctx.mod_jars().iter().collect();
+        let include_nested_jars = ctx.preset >= ScanPreset::Deep;
+        let scope = format!("bundled_packs;nested_jars={};lenient={}", include_nested_jars, ctx.lenient_lang_json);
+        ctx.progress.set_total(mod_jars.len() as u32);
+
+        let per_jar = ctx.limits.map(mod_jars, |limits, jar| {
+            let path = &jar.path;
+            let jar_path = path.to_string_lossy().to_string();
+            let sha1 = jar.metadata.as_ref().and_then(|metadata| metadata.sha1.as_deref());
+            let files = cached_lang_files(ctx.cache, sha1, &scope, &jar_path, ctx.archive_diagnostics, |diagnostics| {
+                let _io = limits.io.acquire();
+                scan_jar(path, include_nested_jars, &limits.memory, diagnostics, ctx.lenient_lang_json)
+            });
+            let files = match files {
+                Ok(files) if !files.is_empty() => files,
+                result => {
+                    ctx.progress.file_done(&jar_path);
+                    return result.map(|_| None);
+                }
+            };
+
+            // 语言文件归属于附带该资源包的 MOD
+            let mod_id = jar
+                .metadata
+                .as_ref()
+                .map(|metadata| metadata.mod_id.clone())
+                .unwrap_or_else(|| path.file_stem().unwrap_or_default().to_string_lossy().to_string());
+            for file in &files {
+                ctx.progress.lang_file_done(&mod_id, &file.source_path, file.key_count);
+            }
+            ctx.progress.mod_done(&jar_path, &mod_id);
+            Ok(Some((mod_id, jar_path, files)))
+        });
+
+        // 无法读取的 JAR 逐个报告，不中断扫描
+        let mut found = Vec::new();
+        for result in per_jar {
+            match result {
+                Ok(jar) => found.extend(jar),
+                Err(e) => output.errors.push(e),
+            }
+        }
+
+        for (mod_id, jar_path, files) in found {
+            for file in files {
+                ctx.profiler.record_lang_file(&file.source_path, file.parse_ms, file.key_count);
+                output.warnings.extend(file.warnings);
+                output.language_resources.push(LanguageResource {
+                    namespace: output.interner.intern(&file.namespace),
+                    locale: output.interner.intern(&file.locale),
+                    source_path: file.source_path,
+                    source_type: output.interner.intern("bundled_pack"),
+                    key_count: file.key_count,
+                    duplicate_keys: file.duplicate_keys,
+                    placeholders: file.placeholders,
+                    formatting: file.formatting,
+                    key_categories: file.key_categories,
+                    parse_status: ParseStatus::from_error(&file.parse_error),
+                    parse_error: file.parse_error,
+                    // 扫描结束后按优先级规则计算
+                    priority: 0,
+                    bundled_pack: Some(BundledPackOrigin {
+                        mod_id: mod_id.clone(),
+                        jar_path: jar_path.clone(),
+                        pack_name: file.pack_name,
+                    }),
+                });
+            }
+        }
+    }
+}
+
+// 查找 JAR 中根目录 assets/ 以外的语言文件，以及嵌套 zip 资源包和嵌套 JAR 中的语言文件
+fn scan_jar(
+    path: &Path,
+    include_nested_jars: bool,
+    budget: &MemoryBudget,
+    diagnostics: &ArchiveDiagnostics,
+    lenient: bool,
+) -> Result<Vec<BundledLangFile>, String> {
+    let mut jar = JarReader::open_with(path, diagnostics)?;
+    let mut files = Vec::new();
+    scan_archive(&mut jar, &path.to_string_lossy(), include_nested_jars, 0, budget, lenient, &mut files);
+    Ok(files)
+}
+
+// 嵌套 JAR 递归扫描，其中的子 MOD 也可能附带内置资源包或再嵌套 JAR
+fn scan_archive(
+    jar: &mut JarReader,
+    jar_label: &str,
+    include_nested_jars: bool,
+    depth: usize,
+    budget: &MemoryBudget,
+    lenient: bool,
+    files: &mut Vec<BundledLangFile>,
+) {
+    // Fabric 的 META-INF/jars/ 和 Forge jarjar 清单中列出的是 Jar-in-Jar 子 MOD，总是读取；
+    // META-INF/ 下其他依赖 JAR 只在 deep 预设下读取
+    let sub_mods = jar.nested_jar_entries();
+    for name in jar.entry_names() {
+        let nested_jar = sub_mods.contains(&name)
+            || (include_nested_jars && name.starts_with("META-INF/") && name.ends_with(".jar"));
+        if name.ends_with(".zip") || nested_jar {
+            let nested_label = format!("{}!/{}", jar_label, name);
+            let Some(mut nested) = jar.open_nested(&name, budget) else { continue };
+            let file_name = name.rsplit('/').next().unwrap_or(&name);
+            let pack_name = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+
+            for inner in nested.entry_names() {
+                let Some(rest) = inner.strip_prefix("assets/") else { continue };
+                let source_path = format!("{}!/{}", nested_label, inner);
+                if let Some(file) = read_lang_file(&mut nested, &inner, rest, pack_name, source_path, lenient) {
+                    files.push(file);
+                }
+            }
+            if nested_jar && depth + 1 < MAX_NESTED_DEPTH {
+                scan_archive(&mut nested, &nested_label, include_nested_jars, depth + 1, budget, lenient, files);
+            } else if nested_jar && !nested.nested_jar_entries().is_empty() {
+                nested.record_nesting_limit();
+            }
+            continue;
+        }
+
+        // 根目录下的 assets/ 是 MOD 自身的语言文件，不属于内置资源包
+        let Some((pack_root, rest)) = name.split_once("/assets/") else { continue };
+        let pack_name = pack_root.rsplit('/').next().unwrap_or(pack_root);
+        let source_path = format!("{}!/{}", jar_label, name);
+        if let Some(file) = read_lang_file(jar, &name, rest, pack_name, source_path, lenient) {
+            files.push(file);
+        }
+    }
+}
+
+// rest 为资源包 assets/ 之后的路径：<namespace>/lang/<locale>.<ext>
+fn read_lang_file(
+    reader: &mut JarReader,
+    entry: &str,
+    rest: &str,
+    pack_name: &str,
+    source_path: String,
+    lenient: bool,
+) -> Option<BundledLangFile> {
+    let (namespace, file_name) = rest.split_once("/lang/")?;
+    if namespace.contains('/') || file_name.contains('/') {
+        return None;
+    }
+    let (locale, extension) = file_name.rsplit_once('.')?;
+    if !matches!(extension, "json" | "lang") {
+        return None;
+    }
+
+    let started = Instant::now();
+    let locale = locale::canonical_locale(locale);
+    let stats = lang_stream::scan_jar_entry(reader, entry, extension, &source_path, &locale, lenient);
+
+    Some(BundledLangFile {
+        namespace: namespace.to_string(),
+        pack_name: pack_name.to_string(),
+        source_path,
+        key_count: stats.key_count,
+        duplicate_keys: stats.duplicate_keys,
+        placeholders: stats.placeholders,
+        formatting: stats.formatting,
+        key_categories: stats.key_categories,
+        parse_error: stats.parse_error,
+        warnings: stats.warnings,
+        locale,
+        parse_ms: millis(started.elapsed()),
+    })
+}
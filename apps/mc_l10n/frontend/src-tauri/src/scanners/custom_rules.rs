@@ -0,0 +1,348 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::config::AppConfig;
+use crate::io_retry;
+use crate::pack_module::{key_segment, PackModuleResource, TranslatableEntry};
+use crate::placeholders;
+
+pub const MODULE_TYPE: &str = "custom_rule";
+
+// 用户在配置中定义的提取规则：glob 选出项目中的文件，json_path 和/或 regex 指出文本所在位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionRule {
+    // 规则名称，作为提取结果的 kind
+    pub name: String,
+    // 相对于项目目录的文件模式：* 和 ? 匹配一段路径中的字符，** 匹配任意层目录
+    pub glob: String,
+    // JSON 文件中文本的位置，如 $.pages[*].text、$..title；选中的字符串值都会被提取
+    #[serde(default)]
+    pub json_path: Option<String>,
+    // 在文件内容（或 json_path 选中的字符串）中匹配文本；有捕获组时取第一个捕获组
+    #[serde(default)]
+    pub regex: Option<String>,
+    // 包含该规则的最低扫描预设
+    #[serde(default)]
+    pub preset: ScanPreset,
+}
+
+// 规则的配置错误，启动时和扫描时报告
+pub fn validate(rule: &ExtractionRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("Extraction rule has no name".to_string());
+    }
+    if rule.glob.trim().is_empty() {
+        return Err(format!("Extraction rule {} has no glob", rule.name));
+    }
+    if rule.json_path.is_none() && rule.regex.is_none() {
+        return Err(format!("Extraction rule {} needs a json_path or a regex", rule.name));
+    }
+    if let Some(path) = &rule.json_path {
+        parse_json_path(path).map_err(|e| format!("Extraction rule {}: invalid json_path: {}", rule.name, e))?;
+    }
+    if let Some(pattern) = &rule.regex {
+        Regex::new(pattern).map_err(|e| format!("Extraction rule {}: invalid regex: {}", rule.name, e))?;
+    }
+    Ok(())
+}
+
+// 每条规则一个扫描器，可以像内置扫描器一样单独关闭
+pub fn scanners(config: &AppConfig) -> Vec<Box<dyn ContentScanner>> {
+    config
+        .extraction_rules
+        .iter()
+        .map(|rule| {
+            Box::new(RuleScanner { id: format!("custom.{}", key_segment(&rule.name)), rule: rule.clone() })
+                as Box<dyn ContentScanner>
+        })
+        .collect()
+}
+
+struct RuleScanner {
+    id: String,
+    rule: ExtractionRule,
+}
+
+impl ContentScanner for RuleScanner {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.rule.name
+    }
+
+    fn phase(&self) -> &str {
+        "scanning_custom_rules"
+    }
+
+    fn preset(&self) -> ScanPreset {
+        self.rule.preset
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        if let Err(e) = validate(&self.rule) {
+            output.errors.push(e);
+            return;
+        }
+        let json_path = self.rule.json_path.as_deref().map(|path| parse_json_path(path).unwrap_or_default());
+        let regex = self.rule.regex.as_deref().and_then(|pattern| Regex::new(pattern).ok());
+
+        let files = {
+            let _io = ctx.limits.io.acquire();
+            matching_files(ctx.project_path, &self.rule.glob)
+        };
+        let mut entries = Vec::new();
+        for (relative, path) in files {
+            let content = {
+                let _io = ctx.limits.io.acquire();
+                io_retry::read_to_string(&path)
+            };
+            let content = match content {
+                Ok(content) => content,
+                Err(e) => {
+                    output.warnings.push(format!("{}: failed to read {}: {}", self.rule.name, path.display(), e));
+                    continue;
+                }
+            };
+
+            let texts = match &json_path {
+                Some(steps) => match serde_json::from_str::<Value>(content.trim_start_matches('\u{FEFF}')) {
+                    Ok(json) => json_texts(&json, steps, regex.as_ref()),
+                    Err(e) => {
+                        output.warnings.push(format!("{}: {} is not valid JSON: {}", self.rule.name, path.display(), e));
+                        continue;
+                    }
+                },
+                None => regex.as_ref().map(|regex| regex_texts(&content, regex)).unwrap_or_default(),
+            };
+
+            let prefix = format!(
+                "custom.{}.{}",
+                key_segment(&self.rule.name),
+                relative.split('/').map(key_segment).collect::<Vec<_>>().join(".")
+            );
+            for (index, (text, line)) in texts.into_iter().enumerate() {
+                entries.push(TranslatableEntry {
+                    key: format!("{}.{}", prefix, index + 1),
+                    placeholders: placeholders::extract(&text),
+                    source: text,
+                    source_path: path.to_string_lossy().to_string(),
+                    kind: self.rule.name.clone(),
+                    line,
+                });
+            }
+        }
+
+        if !entries.is_empty() {
+            output.pack_modules.push(PackModuleResource {
+                module_type: MODULE_TYPE.to_string(),
+                root_path: ctx.project_path.to_string_lossy().to_string(),
+                entries,
+            });
+        }
+    }
+}
+
+// 从 glob 中第一个通配段之前的目录开始遍历，跳过 . 开头的目录；返回相对路径和完整路径
+fn matching_files(project_path: &Path, glob: &str) -> Vec<(String, PathBuf)> {
+    let pattern: Vec<&str> = glob.trim_start_matches("./").split('/').filter(|s| !s.is_empty()).collect();
+    let literal = pattern.iter().take_while(|segment| !segment.contains(['*', '?'])).count();
+    // 最后一段是文件名，不作为起始目录
+    let base = pattern[..literal.min(pattern.len().saturating_sub(1))]
+        .iter()
+        .fold(project_path.to_path_buf(), |path, segment| path.join(segment));
+
+    let mut files = Vec::new();
+    let mut pending = vec![base];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if !entry.file_name().to_string_lossy().starts_with('.') {
+                    pending.push(path);
+                }
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(project_path) else { continue };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            let segments: Vec<&str> = relative.split('/').collect();
+            if glob_match(&pattern, &segments) {
+                files.push((relative, path));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn glob_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_match(rest, &path[skip..])),
+        Some((segment, rest)) => {
+            path.split_first().is_some_and(|(name, path_rest)| segment_match(segment, name) && glob_match(rest, path_rest))
+        }
+    }
+}
+
+// 一段路径内的 * 和 ? 通配
+fn segment_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JsonStep {
+    Field(String),
+    Index(usize),
+    // * 或 [*]：对象的所有值或数组的所有元素
+    Any,
+    // ..name：任意深度下名为 name 的字段
+    Descendant(String),
+}
+
+// 支持 $、.name、['name']、[n]、[*]、.* 和 ..name
+fn parse_json_path(path: &str) -> Result<Vec<JsonStep>, String> {
+    let chars: Vec<char> = path.trim().strip_prefix('$').unwrap_or(path.trim()).chars().collect();
+    let mut steps = Vec::new();
+    let mut index = 0;
+    let read_name = |index: &mut usize| {
+        let start = *index;
+        while *index < chars.len() && !matches!(chars[*index], '.' | '[') {
+            *index += 1;
+        }
+        chars[start..*index].iter().collect::<String>()
+    };
+
+    while index < chars.len() {
+        match chars[index] {
+            '.' if chars.get(index + 1) == Some(&'.') => {
+                index += 2;
+                let name = read_name(&mut index);
+                if name.is_empty() {
+                    return Err("expected a field name after ..".to_string());
+                }
+                steps.push(JsonStep::Descendant(name));
+            }
+            '.' => {
+                index += 1;
+                let name = read_name(&mut index);
+                match name.as_str() {
+                    "" => return Err("expected a field name after .".to_string()),
+                    "*" => steps.push(JsonStep::Any),
+                    _ => steps.push(JsonStep::Field(name)),
+                }
+            }
+            '[' => {
+                let end = chars[index..].iter().position(|c| *c == ']').ok_or("unclosed [")? + index;
+                let inner: String = chars[index + 1..end].iter().collect();
+                let inner = inner.trim();
+                if inner == "*" {
+                    steps.push(JsonStep::Any);
+                } else if let Some(name) = inner.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                    steps.push(JsonStep::Field(name.to_string()));
+                } else {
+                    let position = inner.parse().map_err(|_| format!("invalid index [{}]", inner))?;
+                    steps.push(JsonStep::Index(position));
+                }
+                index = end + 1;
+            }
+            _ if steps.is_empty() => {
+                let name = read_name(&mut index);
+                steps.push(JsonStep::Field(name));
+            }
+            c => return Err(format!("unexpected {}", c)),
+        }
+    }
+
+    Ok(steps)
+}
+
+fn select<'a>(value: &'a Value, steps: &[JsonStep], out: &mut Vec<&'a Value>) {
+    let Some((step, rest)) = steps.split_first() else {
+        out.push(value);
+        return;
+    };
+    match (step, value) {
+        (JsonStep::Field(name), Value::Object(object)) => {
+            if let Some(child) = object.get(name) {
+                select(child, rest, out);
+            }
+        }
+        (JsonStep::Index(position), Value::Array(items)) => {
+            if let Some(child) = items.get(*position) {
+                select(child, rest, out);
+            }
+        }
+        (JsonStep::Any, Value::Object(object)) => object.values().for_each(|child| select(child, rest, out)),
+        (JsonStep::Any, Value::Array(items)) => items.iter().for_each(|child| select(child, rest, out)),
+        (JsonStep::Descendant(name), _) => {
+            if let Some(child) = value.as_object().and_then(|object| object.get(name)) {
+                select(child, rest, out);
+            }
+            let children: Vec<&Value> = match value {
+                Value::Object(object) => object.values().collect(),
+                Value::Array(items) => items.iter().collect(),
+                _ => Vec::new(),
+            };
+            for child in children {
+                select(child, steps, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+// json_path 选中的非空字符串；同时配置了 regex 时只保留其中的匹配
+fn json_texts(json: &Value, steps: &[JsonStep], regex: Option<&Regex>) -> Vec<(String, Option<u32>)> {
+    let mut selected = Vec::new();
+    select(json, steps, &mut selected);
+    selected
+        .into_iter()
+        .filter_map(Value::as_str)
+        .flat_map(|text| match regex {
+            Some(regex) => regex_texts(text, regex).into_iter().map(|(text, _)| text).collect(),
+            None => vec![text.to_string()],
+        })
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| (text, None))
+        .collect()
+}
+
+// 每个匹配（或第一个捕获组）及其所在行号
+fn regex_texts(content: &str, regex: &Regex) -> Vec<(String, Option<u32>)> {
+    regex
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let matched = captures.get(1).or_else(|| captures.get(0))?;
+            let line = content[..matched.start()].matches('\n').count() as u32 + 1;
+            (!matched.as_str().trim().is_empty()).then(|| (matched.as_str().to_string(), Some(line)))
+        })
+        .collect()
+}
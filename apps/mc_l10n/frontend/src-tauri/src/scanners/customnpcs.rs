@@ -0,0 +1,207 @@
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::io_retry;
+use crate::pack_module::{key_segment, PackModuleResource, TranslatableEntry};
+use crate::placeholders;
+
+pub const MODULE_TYPE: &str = "custom_npcs";
+
+// CustomNPCs 扫描器：提取存档中的对话、任务和 NPC 名称
+pub struct CustomNpcsScanner;
+
+impl ContentScanner for CustomNpcsScanner {
+    fn id(&self) -> &'static str {
+        MODULE_TYPE
+    }
+
+    fn name(&self) -> &'static str {
+        "CustomNPCs data"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_pack_modules"
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Deep
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        output.pack_modules.extend(scan_customnpcs(ctx.project_path));
+    }
+}
+
+// 查找实例中的 customnpcs 数据目录：实例根目录、服务端 world 目录和各存档目录
+pub fn find_customnpcs_dirs(project_path: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![project_path.join("customnpcs"), project_path.join("world").join("customnpcs")];
+
+    if let Ok(saves) = fs::read_dir(project_path.join("saves")) {
+        candidates.extend(saves.flatten().map(|save| save.path().join("customnpcs")));
+    }
+
+    candidates.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+// 提取对话、任务和 NPC 克隆中的可翻译文本
+pub fn scan_customnpcs(project_path: &Path) -> Vec<PackModuleResource> {
+    find_customnpcs_dirs(project_path)
+        .into_iter()
+        .filter_map(|root| {
+            let mut entries = Vec::new();
+            scan_category_dir(&root, "dialogs", "dialog", &mut entries, dialog_fields);
+            scan_category_dir(&root, "quests", "quest", &mut entries, quest_fields);
+            scan_category_dir(&root, "clones", "npc", &mut entries, npc_fields);
+
+            (!entries.is_empty()).then(|| PackModuleResource {
+                module_type: MODULE_TYPE.to_string(),
+                root_path: root.to_string_lossy().to_string(),
+                entries,
+            })
+        })
+        .collect()
+}
+
+// 遍历 <root>/<dir>/<category>/<id>.json，键为 customnpcs.<kind>.<category>.<id>.<field>
+fn scan_category_dir(
+    root: &Path,
+    dir: &str,
+    kind: &str,
+    entries: &mut Vec<TranslatableEntry>,
+    fields: fn(&Value) -> Vec<(String, String)>,
+) {
+    let Ok(categories) = fs::read_dir(root.join(dir)) else {
+        return;
+    };
+
+    let mut files: Vec<(String, PathBuf)> = categories
+        .flatten()
+        .filter(|category| category.path().is_dir())
+        .flat_map(|category| {
+            let category_name = category.file_name().to_string_lossy().to_string();
+            fs::read_dir(category.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(move |file| (category_name.clone(), file.path()))
+        })
+        .filter(|(_, path)| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    for (category, path) in files {
+        let Some(json) = io_retry::read_to_string(&path).ok().and_then(|content| parse_nbt_json(&content)) else {
+            continue;
+        };
+        let id = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let prefix = format!("customnpcs.{}.{}.{}", kind, key_segment(&category), key_segment(&id));
+
+        for (field, text) in fields(&json) {
+            if text.trim().is_empty() {
+                continue;
+            }
+            entries.push(TranslatableEntry {
+                key: format!("{}.{}", prefix, field),
+                placeholders: placeholders::extract(&text),
+                source: text,
+                source_path: path.to_string_lossy().to_string(),
+                kind: kind.to_string(),
+                line: None,
+            });
+        }
+    }
+}
+
+fn dialog_fields(json: &Value) -> Vec<(String, String)> {
+    let mut fields = string_fields(json, &[("DialogTitle", "title"), ("DialogText", "text")]);
+
+    if let Some(options) = json.get("Options").and_then(|o| o.as_array()) {
+        for option in options {
+            let slot = option.get("OptionSlot").and_then(|s| s.as_i64()).unwrap_or_default();
+            if let Some(title) = option.get("Option").and_then(|o| o.get("Title")).and_then(|t| t.as_str()) {
+                fields.push((format!("option.{}", slot), title.to_string()));
+            }
+        }
+    }
+
+    fields
+}
+
+fn quest_fields(json: &Value) -> Vec<(String, String)> {
+    string_fields(
+        json,
+        &[("Title", "title"), ("Text", "text"), ("CompleteText", "complete_text")],
+    )
+}
+
+fn npc_fields(json: &Value) -> Vec<(String, String)> {
+    string_fields(json, &[("Name", "name"), ("Title", "title")])
+}
+
+fn string_fields(json: &Value, names: &[(&str, &str)]) -> Vec<(String, String)> {
+    names
+        .iter()
+        .filter_map(|(field, key)| {
+            json.get(*field)
+                .and_then(|v| v.as_str())
+                .map(|text| (key.to_string(), text.to_string()))
+        })
+        .collect()
+}
+
+// CustomNPCs 以 NBT 风格的 JSON 保存数据（如 1b、0.5f、[I;1,2]），解析前去掉类型后缀
+fn parse_nbt_json(content: &str) -> Option<Value> {
+    serde_json::from_str(content)
+        .ok()
+        .or_else(|| serde_json::from_str(&strip_nbt_suffixes(content)).ok())
+}
+
+fn strip_nbt_suffixes(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut output = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if in_string {
+            output.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.get(index + 1) {
+                    output.push(*next);
+                    index += 1;
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            index += 1;
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                output.push(c);
+            }
+            // 类型化数组前缀 [B; [I; [L;
+            '[' if matches!(chars.get(index + 1), Some('B' | 'I' | 'L')) && chars.get(index + 2) == Some(&';') => {
+                output.push('[');
+                index += 2;
+            }
+            // 数字后紧跟类型后缀且后面是分隔符时去掉后缀
+            'b' | 'B' | 's' | 'S' | 'l' | 'L' | 'f' | 'F' | 'd' | 'D'
+                if index > 0
+                    && chars[index - 1].is_ascii_digit()
+                    && chars
+                        .get(index + 1)
+                        .map_or(true, |next| matches!(next, ',' | '}' | ']') || next.is_whitespace()) => {}
+            _ => output.push(c),
+        }
+        index += 1;
+    }
+
+    output
+}
@@ -0,0 +1,230 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::io_retry;
+use crate::jar::{ArchiveDiagnostics, JarReader};
+use crate::pack_module::{key_segment, PackModuleResource, TranslatableEntry};
+use crate::placeholders;
+
+pub const MODULE_TYPE: &str = "datapack";
+
+// 数据包扫描器：datapacks/ 下目录或 zip 形式的数据包中，data/** 里的 JSON（进度、战利品表等）
+// 和 .mcfunction 命令中的文本组件。translate 组件记录引用的语言键，text 组件记录字面文本
+pub struct DatapackScanner;
+
+impl ContentScanner for DatapackScanner {
+    fn id(&self) -> &'static str {
+        MODULE_TYPE
+    }
+
+    fn name(&self) -> &'static str {
+        "datapacks"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_pack_modules"
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Deep
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let Ok(packs) = fs::read_dir(ctx.project_path.join("datapacks")) else {
+            return;
+        };
+        let mut packs: Vec<PathBuf> = packs.flatten().map(|entry| entry.path()).collect();
+        packs.sort();
+
+        for pack in packs {
+            let _io = ctx.limits.io.acquire();
+            let files = if pack.is_dir() {
+                Ok(read_dir_files(&pack))
+            } else if pack.extension().is_some_and(|ext| ext == "zip") {
+                read_zip_files(&pack, ctx.archive_diagnostics)
+            } else {
+                continue;
+            };
+            let files = match files {
+                Ok(files) => files,
+                Err(e) => {
+                    output.errors.push(e);
+                    continue;
+                }
+            };
+
+            let pack_name = pack.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let entries = extract_entries(&pack_name, files);
+            if !entries.is_empty() {
+                output.pack_modules.push(PackModuleResource {
+                    module_type: MODULE_TYPE.to_string(),
+                    root_path: pack.to_string_lossy().to_string(),
+                    entries,
+                });
+            }
+        }
+    }
+}
+
+// 数据包中的一个文件：data/ 下的相对路径、source_path 和内容
+struct DataFile {
+    relative: String,
+    source_path: String,
+    content: String,
+}
+
+fn is_data_file(relative: &str) -> bool {
+    relative.starts_with("data/") && (relative.ends_with(".json") || relative.ends_with(".mcfunction"))
+}
+
+fn read_dir_files(pack: &Path) -> Vec<DataFile> {
+    let mut files = Vec::new();
+    let mut pending = vec![pack.join("data")];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(pack) else { continue };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !is_data_file(&relative) {
+                continue;
+            }
+            if let Ok(content) = io_retry::read_to_string(&path) {
+                files.push(DataFile { relative, source_path: path.to_string_lossy().to_string(), content });
+            }
+        }
+    }
+    files
+}
+
+fn read_zip_files(pack: &Path, diagnostics: &ArchiveDiagnostics) -> Result<Vec<DataFile>, String> {
+    let mut zip = JarReader::open_with(pack, diagnostics)?;
+    let label = pack.to_string_lossy();
+    let files = zip
+        .entry_names()
+        .into_iter()
+        .filter(|name| is_data_file(name))
+        .filter_map(|name| {
+            let content = zip.read_entry_string(&name)?;
+            Some(DataFile { source_path: format!("{}!/{}", label, name), relative: name, content })
+        })
+        .collect();
+    Ok(files)
+}
+
+// 字面文本的键为 datapack.<包名>.<文件路径>.<序号>；translate 组件的键就是它引用的语言键，
+// 原文取 fallback，没有时为语言键本身。同一数据包中重复的键只保留第一次出现
+fn extract_entries(pack_name: &str, mut files: Vec<DataFile>) -> Vec<TranslatableEntry> {
+    files.sort_by(|a, b| a.relative.cmp(&b.relative));
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    for file in files {
+        let mut components = Vec::new();
+        if file.relative.ends_with(".json") {
+            if let Ok(json) = serde_json::from_str::<Value>(file.content.trim_start_matches('\u{FEFF}')) {
+                collect_components(&json, &mut components);
+            }
+        } else {
+            for line in file.content.lines().map(str::trim).filter(|line| !line.starts_with('#')) {
+                collect_command_components(line, &mut components);
+            }
+        }
+
+        let path = file.relative.trim_start_matches("data/");
+        let path = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+        let prefix = format!(
+            "datapack.{}.{}",
+            key_segment(pack_name),
+            path.split('/').map(key_segment).collect::<Vec<_>>().join(".")
+        );
+
+        let mut index = 0;
+        for component in components {
+            let (key, source, kind) = match component {
+                Component::Translate { key, fallback } => {
+                    let source = fallback.unwrap_or_else(|| key.clone());
+                    (key, source, "translate")
+                }
+                Component::Text(text) => {
+                    index += 1;
+                    (format!("{}.{}", prefix, index), text, "text")
+                }
+            };
+            if seen.insert(key.clone()) {
+                entries.push(TranslatableEntry {
+                    key,
+                    placeholders: placeholders::extract(&source),
+                    source,
+                    source_path: file.source_path.clone(),
+                    kind: kind.to_string(),
+                    line: None,
+                });
+            }
+        }
+    }
+
+    entries
+}
+
+enum Component {
+    Translate { key: String, fallback: Option<String> },
+    Text(String),
+}
+
+// 递归查找文本组件；进度 display 中的 title/description 也可以直接是字符串
+fn collect_components(value: &Value, out: &mut Vec<Component>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(key) = object.get("translate").and_then(|v| v.as_str()) {
+                let fallback = object.get("fallback").and_then(|v| v.as_str()).map(str::to_string);
+                out.push(Component::Translate { key: key.to_string(), fallback });
+            } else if let Some(text) = object.get("text").and_then(|v| v.as_str()) {
+                if !text.trim().is_empty() {
+                    out.push(Component::Text(text.to_string()));
+                }
+            }
+            for (field, child) in object {
+                match child {
+                    Value::String(text) if is_display_text(object, field) && !text.trim().is_empty() => {
+                        out.push(Component::Text(text.clone()));
+                    }
+                    _ => collect_components(child, out),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_components(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_display_text(object: &serde_json::Map<String, Value>, field: &str) -> bool {
+    matches!(field, "title" | "description") && object.contains_key("icon")
+}
+
+// 命令中的 JSON 文本组件（tellraw、title、bossbar 等），以及 NBT 中以单引号包裹的组件：
+// 从每个 { 或 [ 开始尝试解析，成功时跳过已解析的部分
+fn collect_command_components(line: &str, out: &mut Vec<Component>) {
+    let mut offset = 0;
+    while let Some(start) = line[offset..].find(['{', '[']).map(|index| offset + index) {
+        let mut stream = serde_json::Deserializer::from_str(&line[start..]).into_iter::<Value>();
+        match stream.next() {
+            Some(Ok(value)) if value.is_object() || value.is_array() => {
+                collect_components(&value, out);
+                offset = start + stream.byte_offset();
+            }
+            _ => offset = start + 1,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::class_strings::scan_hardcoded_strings;
+
+// 硬编码字符串扫描器：解析 MOD 类文件常量池，只在 deep 预设下运行
+pub struct HardcodedStringScanner;
+
+impl ContentScanner for HardcodedStringScanner {
+    fn id(&self) -> &'static str {
+        "hardcoded_strings"
+    }
+
+    fn name(&self) -> &'static str {
+        "hardcoded class strings"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_class_strings"
+    }
+
+    fn weight(&self) -> f64 {
+        4.0
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Deep
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let report = scan_hardcoded_strings(ctx.mod_jar_paths(), ctx.limits, ctx.cache, ctx.archive_diagnostics);
+        output.hardcoded_strings = Some(report);
+    }
+}
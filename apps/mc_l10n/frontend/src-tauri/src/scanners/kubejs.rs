@@ -0,0 +1,28 @@
+use super::resourcepack::scan_assets_lang_files;
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+
+// KubeJS 扫描器：组合包通过 kubejs/assets/<namespace>/lang 为脚本添加的物品、方块等提供文本
+pub struct KubeJsScanner;
+
+impl ContentScanner for KubeJsScanner {
+    fn id(&self) -> &'static str {
+        "kubejs"
+    }
+
+    fn name(&self) -> &'static str {
+        "KubeJS assets"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_kubejs"
+    }
+
+    // 整合包的脚本物品名称只出现在这里，standard 预设即读取
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Standard
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        scan_assets_lang_files(ctx, &ctx.project_path.join("kubejs").join("assets"), "kubejs", output);
+    }
+}
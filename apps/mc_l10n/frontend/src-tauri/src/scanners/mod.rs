@@ -0,0 +1,328 @@
+pub mod betterquesting;
+pub mod bundled_packs;
+pub mod custom_rules;
+pub mod customnpcs;
+pub mod datapacks;
+pub mod hardcoded_strings;
+pub mod kubejs;
+pub mod mod_jars;
+pub mod mod_lang;
+pub mod patchouli;
+pub mod resourcepack;
+pub mod zenscript;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::class_strings::HardcodedStringReport;
+use crate::concurrency::WorkLimits;
+use crate::config::AppConfig;
+use crate::extensions;
+use crate::formatting_codes::KeyFormatting;
+use crate::intern::{Interner, Symbol};
+use crate::jar::{ArchiveDiagnostics, ArchiveIssue};
+use crate::lang_detect::RejectedCandidate;
+use crate::locale;
+use crate::metadata_cache::MetadataCache;
+use crate::mod_filter::ModFilter;
+use crate::mod_metadata::ModJarMetadata;
+use crate::pack_module::PackModuleResource;
+use crate::profiling::ScanProfiler;
+use crate::progress::ProgressReporter;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageResource {
+    pub namespace: Symbol,
+    pub locale: Symbol,
+    pub source_path: String,
+    pub source_type: Symbol,
+    pub key_count: u32,
+    // 文件中出现多次的键数，详情记入扫描警告
+    #[serde(default)]
+    pub duplicate_keys: u32,
+    // 源语言（en_us）文件中含占位符的键及其占位符，供译文的占位符校验使用；其他语言为空
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub placeholders: BTreeMap<String, Vec<String>>,
+    // 含 § 格式代码的键
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formatting: BTreeMap<String, KeyFormatting>,
+    // 按键前缀（item.、block.、gui. 等）分类的键数，见 key_categories
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub key_categories: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub parse_status: ParseStatus,
+    // 无法读取或解析时的原因（如 JSON 语法错误的位置）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<String>,
+    pub priority: u32,
+    // 来自 MOD JAR 内置资源包时记录所属 MOD 和包名
+    #[serde(default)]
+    pub bundled_pack: Option<BundledPackOrigin>,
+}
+
+// 语言文件的解析结果；解析失败的文件仍列出，键数为 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseStatus {
+    #[default]
+    Parsed,
+    Failed,
+}
+
+impl ParseStatus {
+    pub fn from_error(error: &Option<String>) -> Self {
+        if error.is_some() {
+            ParseStatus::Failed
+        } else {
+            ParseStatus::Parsed
+        }
+    }
+}
+
+// 解析失败的语言文件汇总为一条警告，列出前几个文件及原因
+pub fn parse_failure_warning(resources: &[LanguageResource]) -> Option<String> {
+    const LISTED: usize = 5;
+    let failed: Vec<&LanguageResource> =
+        resources.iter().filter(|resource| resource.parse_status == ParseStatus::Failed).collect();
+    if failed.is_empty() {
+        return None;
+    }
+    let listed: Vec<String> = failed
+        .iter()
+        .take(LISTED)
+        .map(|resource| format!("{} ({})", resource.source_path, resource.parse_error.as_deref().unwrap_or("unknown error")))
+        .collect();
+    let mut warning = format!("{} language files failed to parse: {}", failed.len(), listed.join("; "));
+    if failed.len() > LISTED {
+        warning.push_str(&format!(" and {} more", failed.len() - LISTED));
+    }
+    Some(warning)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledPackOrigin {
+    pub mod_id: String,
+    pub jar_path: String,
+    pub pack_name: String,
+}
+
+// 扫描预设：quick 只读取清单和语言文件键数，standard 包含 MOD 内置资源包、KubeJS 语言文件和 Patchouli 手册，
+// deep 额外扫描 Jar-in-Jar 以外的嵌套依赖 JAR、任务/NPC 数据、数据包文本组件、脚本和类文件中的硬编码字符串
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPreset {
+    Quick,
+    #[default]
+    Standard,
+    Deep,
+}
+
+// 扫描器运行时可用的共享资源
+pub struct ScanContext<'a> {
+    pub project_path: &'a Path,
+    pub preset: ScanPreset,
+    pub limits: &'a WorkLimits,
+    pub cache: Option<&'a MetadataCache>,
+    // 项目设置中排除的 MOD
+    pub mod_filter: &'a ModFilter,
+    // 被禁用的 MOD 是否参与语言文件和键数统计
+    pub include_disabled_mods: bool,
+    // mods 目录下递归搜索的子目录层数
+    pub mods_dir_depth: usize,
+    // JSON 语言文件按宽松模式解析（忽略注释和尾随逗号）
+    pub lenient_lang_json: bool,
+    pub progress: &'a ProgressReporter,
+    pub profiler: &'a ScanProfiler,
+    // 本次扫描读取归档时的资源限制警告和读取失败，扫描器通过 JarReader::open_with 记入
+    pub archive_diagnostics: &'a ArchiveDiagnostics,
+    // 参与扫描的 MOD JAR 及其元数据，第一次用到时计算，各扫描器共用
+    pub mod_jars: OnceLock<Vec<IncludedModJar>>,
+}
+
+// 未被排除的 MOD JAR；无法读取元数据时 metadata 为 None
+pub struct IncludedModJar {
+    pub path: PathBuf,
+    pub metadata: Option<ModJarMetadata>,
+}
+
+impl ScanContext<'_> {
+    // 未被排除的 MOD JAR；include_disabled_mods 开启时包含被禁用的 MOD。
+    // 每次扫描只读取一次元数据并划分保留和排除的 MOD
+    pub fn mod_jars(&self) -> &[IncludedModJar] {
+        self.mod_jars.get_or_init(|| {
+            let jar_paths =
+                mod_jars::collect_included_mod_jar_paths(self.project_path, self.mods_dir_depth, self.include_disabled_mods);
+            self.mod_filter
+                .partition_with_metadata(jar_paths, self.limits, self.cache)
+                .0
+                .into_iter()
+                .map(|(path, metadata)| IncludedModJar { path, metadata })
+                .collect()
+        })
+    }
+
+    pub fn mod_jar_paths(&self) -> Vec<PathBuf> {
+        self.mod_jars().iter().map(|jar| jar.path.clone()).collect()
+    }
+
+    // 扫描器运行结束后把读取归档时记录的警告并入输出
+    pub fn collect_archive_diagnostics(&self, output: &mut ScanOutput) {
+        output.warnings.extend(self.archive_diagnostics.take_violations());
+        output.unreadable_archives.extend(self.archive_diagnostics.take_issues());
+    }
+}
+
+// 各扫描器的汇总输出
+#[derive(Default)]
+pub struct ScanOutput {
+    pub mod_jars: Vec<ModJarMetadata>,
+    pub language_resources: Vec<LanguageResource>,
+    pub pack_modules: Vec<PackModuleResource>,
+    pub hardcoded_strings: Option<HardcodedStringReport>,
+    pub warnings: Vec<String>,
+    // 单个文件的读取错误；出错的文件被跳过，扫描继续
+    pub errors: Vec<String>,
+    // 无法打开的归档和归档中读取失败的条目，由 collect_archive_diagnostics 并入
+    pub unreadable_archives: Vec<ArchiveIssue>,
+    // 路径像语言文件、但没有通过结构检查的文件
+    pub rejected_lang_files: Vec<RejectedCandidate>,
+    // 同一次扫描中的命名空间、语言代码和来源类型共享同一份字符串
+    pub interner: Interner,
+}
+
+// 内容扫描器：每种包格式（MOD JAR、资源包、KubeJS、CustomNPCs 等）实现一个
+pub trait ContentScanner: Send + Sync {
+    fn id(&self) -> &str;
+
+    // 展示给用户的名称，也用于进度消息
+    fn name(&self) -> &str;
+
+    // 进度事件中的阶段名
+    fn phase(&self) -> &str;
+
+    // 相对耗时，用于在扫描器之间分配进度区间
+    fn weight(&self) -> f64 {
+        1.0
+    }
+
+    // 包含该扫描器的最低预设
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Standard
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput);
+}
+
+// 扫描器信息，供前端展示和开关
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerInfo {
+    pub id: String,
+    pub name: String,
+    pub phase: String,
+    pub preset: ScanPreset,
+    pub enabled: bool,
+}
+
+// 所有已注册的扫描器，顺序即执行顺序；自定义提取规则和已启用扩展的扫描器排在内置扫描器之后
+pub fn registry(config: &AppConfig) -> Vec<Box<dyn ContentScanner>> {
+    let mut scanners: Vec<Box<dyn ContentScanner>> = vec![
+        Box::new(mod_jars::ModJarScanner),
+        Box::new(mod_lang::ModLangScanner),
+        Box::new(bundled_packs::BundledPackScanner),
+        Box::new(resourcepack::ResourcePackScanner),
+        Box::new(kubejs::KubeJsScanner),
+        Box::new(patchouli::PatchouliScanner),
+        Box::new(customnpcs::CustomNpcsScanner),
+        Box::new(betterquesting::BetterQuestingScanner),
+        Box::new(datapacks::DatapackScanner),
+        Box::new(zenscript::ZenScriptScanner),
+        Box::new(hardcoded_strings::HardcodedStringScanner),
+    ];
+    scanners.extend(custom_rules::scanners(config));
+    scanners.extend(extensions::scanners(config));
+    scanners
+}
+
+pub fn list_scanners(config: &AppConfig) -> Vec<ScannerInfo> {
+    registry(config)
+        .iter()
+        .map(|scanner| ScannerInfo {
+            id: scanner.id().to_string(),
+            name: scanner.name().to_string(),
+            phase: scanner.phase().to_string(),
+            preset: scanner.preset(),
+            enabled: !config.disabled_scanners.iter().any(|id| id == scanner.id()),
+        })
+        .collect()
+}
+
+// 预设包含且未被用户关闭的扫描器
+pub fn enabled_scanners(config: &AppConfig, preset: ScanPreset) -> Vec<Box<dyn ContentScanner>> {
+    registry(config)
+        .into_iter()
+        .filter(|scanner| scanner.preset() <= preset)
+        .filter(|scanner| !config.disabled_scanners.iter().any(|id| id == scanner.id()))
+        .collect()
+}
+
+pub fn is_registered(config: &AppConfig, id: &str) -> bool {
+    registry(config).iter().any(|scanner| scanner.id() == id)
+}
+
+// 依次运行扫描器，按权重把 [start, end] 的进度区间分给各扫描器
+pub fn run_scanners(scanners: &[Box<dyn ContentScanner>], ctx: &ScanContext<'_>, start: f64, end: f64) -> ScanOutput {
+    let total_weight: f64 = scanners.iter().map(|scanner| scanner.weight()).sum();
+    let mut output = ScanOutput::default();
+    let mut phase_start = start;
+
+    for scanner in scanners {
+        // 暂停时在两个扫描器之间等待；取消后不再运行剩余的扫描器
+        if !ctx.progress.checkpoint() {
+            break;
+        }
+        let span = if total_weight > 0.0 {
+            (end - start) * scanner.weight() / total_weight
+        } else {
+            0.0
+        };
+
+        ctx.profiler.phase(scanner.phase());
+        ctx.progress.phase(
+            scanner.phase(),
+            phase_start,
+            phase_start + span,
+            &format!("Scanning {}...", scanner.name()),
+            0,
+        );
+        scanner.scan(ctx, &mut output);
+        phase_start += span;
+    }
+
+    ctx.collect_archive_diagnostics(&mut output);
+    output.warnings.extend(unknown_locale_warnings(&output.language_resources));
+    let io = &ctx.limits.io;
+    if io.limit() < io.max() {
+        output.warnings.push(format!(
+            "Slow disk reads detected (network drive?), IO concurrency was reduced from {} to {}",
+            io.max(),
+            io.limit()
+        ));
+    }
+    output
+}
+
+// 规范化后仍不在 Minecraft 语言列表中的语言代码，每个代码只提示一次
+fn unknown_locale_warnings(resources: &[LanguageResource]) -> Vec<String> {
+    let unknown: BTreeSet<&str> = resources
+        .iter()
+        .map(|resource| &*resource.locale)
+        .filter(|code| locale::normalize_locale(code).unknown)
+        .collect();
+
+    unknown
+        .into_iter()
+        .map(|code| format!("Unknown locale code '{}' is not a Minecraft language", code))
+        .collect()
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::config::AppConfig;
+use crate::hashing;
+use crate::mod_metadata::{extract_mod_metadata, extract_nested_mod_metadata};
+use crate::profiling::{millis, timed, ModTiming};
+
+// 模组 JAR 扫描器：读取 mods 目录（以及单 JAR 项目根目录）中的 JAR 元数据，
+// 以及 JAR 内嵌入的 Jar-in-Jar 子 MOD。被启动器禁用的 MOD 同样列出，enabled 为 false
+pub struct ModJarScanner;
+
+impl ContentScanner for ModJarScanner {
+    fn id(&self) -> &'static str {
+        "mod_jars"
+    }
+
+    fn name(&self) -> &'static str {
+        "mod JAR files"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_mods"
+    }
+
+    fn weight(&self) -> f64 {
+        6.0
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Quick
+    }
+
+    // 每处理完一个 JAR 上报一次（节流后的）进度
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let mut jar_paths = collect_mod_jar_paths_with_depth(ctx.project_path, ctx.mods_dir_depth);
+        jar_paths.extend(collect_disabled_mod_jar_paths(ctx.project_path, ctx.mods_dir_depth));
+        ctx.progress.set_total(jar_paths.len() as u32);
+
+        let mod_jars = ctx.limits.map(jar_paths, |limits, path| {
+            let started = Instant::now();
+            let mut timing = ModTiming {
+                path: path.to_string_lossy().to_string(),
+                ..ModTiming::default()
+            };
+            let mut metadata = extract_mod_metadata(&path, limits, ctx.cache, &mut timing);
+            // 使用缓存时已顺带计算指纹
+            if let Some(metadata) = metadata.as_mut().filter(|metadata| metadata.sha256.is_none()) {
+                let _io = limits.io.acquire();
+                if let Ok(fingerprint) = timed(&mut timing.hash_ms, || hashing::fingerprint_file(&path)) {
+                    metadata.set_fingerprint(fingerprint);
+                }
+            }
+            timing.total_ms = millis(started.elapsed());
+            match &metadata {
+                Some(metadata) => ctx.progress.mod_done(&timing.path, &metadata.display_name),
+                None => ctx.progress.file_done(&timing.path),
+            }
+            let error = timing.error.clone();
+            let mut nested = match (&metadata, &error) {
+                (Some(metadata), None) => extract_nested_mod_metadata(&path, metadata, limits, ctx.archive_diagnostics),
+                _ => Vec::new(),
+            };
+            let subdirectory = mods_subdirectory(ctx.project_path, &path);
+            let enabled = !is_disabled_mod_file(&path);
+            for metadata in metadata.iter_mut().chain(nested.iter_mut()) {
+                metadata.subdirectory = subdirectory.clone();
+                metadata.enabled = enabled;
+            }
+            ctx.profiler.record_mod(timing);
+            (metadata.map(|metadata| (metadata, nested)), error)
+        });
+
+        // 无法读取的 JAR 逐个报告，不中断扫描
+        let (mod_jars, errors): (Vec<_>, Vec<_>) = mod_jars.into_iter().unzip();
+        output.errors.extend(
+            errors
+                .into_iter()
+                .flatten()
+                .map(|e| format!("{} (metadata inferred from file name)", e)),
+        );
+
+        // 被排除的 MOD 同样读取元数据（用于判断 id），但不计入结果；排除父 MOD 时其子 MOD 一并排除。
+        // 子 MOD 紧跟在父 MOD 之后
+        for (metadata, nested) in mod_jars.into_iter().flatten() {
+            if ctx.mod_filter.excludes(&metadata.mod_id) {
+                continue;
+            }
+            output.mod_jars.push(metadata);
+            output
+                .mod_jars
+                .extend(nested.into_iter().filter(|nested| !ctx.mod_filter.excludes(&nested.mod_id)));
+        }
+    }
+}
+
+// mods 目录下默认搜索的子目录层数（如 mods/1.20.1/、mods/client/）
+pub const DEFAULT_MODS_DIR_DEPTH: usize = 2;
+
+// 参与语言文件和键数统计的 MOD JAR，按设置中的 mods_dir_depth 和 include_disabled_mods 收集
+pub fn collect_mod_jar_paths(project_path: &Path, config: &AppConfig) -> Vec<PathBuf> {
+    collect_included_mod_jar_paths(project_path, config.mods_dir_depth, config.include_disabled_mods)
+}
+
+// include_disabled 时包含被禁用的 MOD
+pub fn collect_included_mod_jar_paths(project_path: &Path, depth: usize, include_disabled: bool) -> Vec<PathBuf> {
+    let mut jar_paths = collect_mod_jar_paths_with_depth(project_path, depth);
+    if include_disabled {
+        jar_paths.extend(collect_disabled_mod_jar_paths(project_path, depth));
+    }
+    jar_paths
+}
+
+// depth 为 mods 目录下递归搜索的子目录层数，0 表示只看 mods 目录本身
+pub fn collect_mod_jar_paths_with_depth(project_path: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut jar_paths = Vec::new();
+    
+    // 扫描 mods 目录
+    walk_mods_dir(&project_path.join("mods"), depth, &is_jar_file, &mut jar_paths);
+    
+    // 如果是单个 JAR 文件项目
+    if let Ok(entries) = fs::read_dir(project_path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() && is_jar_file(&path) {
+                jar_paths.push(path);
+            }
+        }
+    }
+    
+    jar_paths
+}
+
+// mods 目录中被启动器禁用的 MOD：重命名为 .jar.disabled 或 .jar.old 的文件
+pub fn collect_disabled_mod_jar_paths(project_path: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut jar_paths = Vec::new();
+    walk_mods_dir(&project_path.join("mods"), depth, &is_disabled_mod_file, &mut jar_paths);
+    jar_paths
+}
+
+// 隐藏目录（如 packwiz 的 .index）不是 MOD 目录，跳过
+fn walk_mods_dir(dir: &Path, depth: usize, matches: &dyn Fn(&Path) -> bool, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() {
+            if matches(&path) {
+                out.push(path);
+            }
+        } else if depth > 0
+            && path.is_dir()
+            && !entry.file_name().to_string_lossy().starts_with('.')
+        {
+            walk_mods_dir(&path, depth - 1, matches, out);
+        }
+    }
+}
+
+// JAR 相对于 mods 目录的子目录（如 "1.20.1"），直接位于 mods 目录或项目根目录时为 None
+pub fn mods_subdirectory(project_path: &Path, jar_path: &Path) -> Option<String> {
+    let relative = jar_path.parent()?.strip_prefix(project_path.join("mods")).ok()?;
+    let subdirectory = relative.to_string_lossy().replace('\\', "/");
+    (!subdirectory.is_empty()).then_some(subdirectory)
+}
+
+fn is_jar_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "jar")
+}
+
+pub fn is_disabled_mod_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".jar.disabled") || name.ends_with(".jar.old"))
+}
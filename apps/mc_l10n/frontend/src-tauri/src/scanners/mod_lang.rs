@@ -0,0 +1,323 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+
+use super::{ContentScanner, LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+use crate::formatting_codes::KeyFormatting;
+use crate::jar::{ArchiveDiagnostics, JarReader};
+use crate::lang_stream;
+use crate::locale;
+use crate::metadata_cache::MetadataCache;
+use crate::profiling::millis;
+
+// MOD 语言文件扫描器：MOD JAR 根目录 assets/<namespace>/lang/ 下的语言文件
+pub struct ModLangScanner;
+
+// 归档（MOD JAR 或 zip 资源包）中的一个语言文件
+#[derive(Serialize, Deserialize)]
+pub struct ArchiveLangFile {
+    pub namespace: String,
+    pub locale: String,
+    pub source_path: String,
+    pub key_count: u32,
+    pub duplicate_keys: u32,
+    pub placeholders: BTreeMap<String, Vec<String>>,
+    pub formatting: BTreeMap<String, KeyFormatting>,
+    pub key_categories: BTreeMap<String, u32>,
+    pub parse_error: Option<String>,
+    // 非标准 JSON、非 UTF-8 编码和重复的键的警告
+    pub warnings: Vec<String>,
+    // 从缓存读出时为 0
+    #[serde(skip)]
+    pub parse_ms: f64,
+}
+
+impl CachedLangFile for ArchiveLangFile {
+    fn labelled_paths(&mut self) -> Vec<&mut String> {
+        std::iter::once(&mut self.source_path).chain(self.warnings.iter_mut()).collect()
+    }
+}
+
+// 可写入语言文件缓存的扫描结果
+pub trait CachedLangFile: Serialize + DeserializeOwned {
+    // 以归档自身路径开头的 source_path 和警告
+    fn labelled_paths(&mut self) -> Vec<&mut String>;
+}
+
+impl ContentScanner for ModLangScanner {
+    fn id(&self) -> &'static str {
+        "mod_lang"
+    }
+
+    fn name(&self) -> &'static str {
+        "mod language files"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_mod_lang"
+    }
+
+    fn weight(&self) -> f64 {
+        6.0
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Quick
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let mod_jars: Vec<_> = ctx.mod_jars().iter().collect();
+        let scope = format!("mod_lang;lenient={}", ctx.lenient_lang_json);
+        ctx.progress.set_total(mod_jars.len() as u32);
+
+        let per_jar = ctx.limits.map(mod_jars, |limits, jar| {
+            let jar_path = jar.path.to_string_lossy().to_string();
+            let sha1 = jar.metadata.as_ref().and_then(|metadata| metadata.sha1.as_deref());
+            let files = cached_lang_files(ctx.cache, sha1, &scope, &jar_path, ctx.archive_diagnostics, |diagnostics| {
+                let _io = limits.io.acquire();
+                scan_archive_lang_files(&jar.path, diagnostics, ctx.lenient_lang_json)
+            });
+            if let Ok(files) = &files {
+                for file in files {
+                    ctx.progress.lang_file_done(&file.namespace, &file.source_path, file.key_count);
+                }
+            }
+            ctx.progress.file_done(&jar_path);
+            files
+        });
+
+        // 无法读取的 JAR 逐个报告，不中断扫描
+        for result in per_jar {
+            let files = match result {
+                Ok(files) => files,
+                Err(e) => {
+                    output.errors.push(e);
+                    continue;
+                }
+            };
+            for file in files {
+                ctx.profiler.record_lang_file(&file.source_path, file.parse_ms, file.key_count);
+                output.warnings.extend(file.warnings);
+                output.language_resources.push(LanguageResource {
+                    namespace: output.interner.intern(&file.namespace),
+                    locale: output.interner.intern(&file.locale),
+                    source_path: file.source_path,
+                    source_type: output.interner.intern("mod_jar"),
+                    key_count: file.key_count,
+                    duplicate_keys: file.duplicate_keys,
+                    placeholders: file.placeholders,
+                    formatting: file.formatting,
+                    key_categories: file.key_categories,
+                    parse_status: ParseStatus::from_error(&file.parse_error),
+                    parse_error: file.parse_error,
+                    // 扫描结束后按优先级规则计算
+                    priority: 0,
+                    bundled_pack: None,
+                });
+            }
+        }
+    }
+}
+
+// 按 JAR 的 SHA-1 读取已缓存的语言文件，未命中时调用 scan 并写入缓存，不必再打开 JAR。
+// 同一 JAR 可能位于不同项目，缓存中的路径不含 label（JAR 自身的路径），读出时补上；
+// 读取中触发了资源限制或读取失败的结果取决于限制设置而不只是 JAR 内容，不写入缓存
+pub fn cached_lang_files<T: CachedLangFile>(
+    cache: Option<&MetadataCache>,
+    sha1: Option<&str>,
+    scope: &str,
+    label: &str,
+    diagnostics: &ArchiveDiagnostics,
+    scan: impl FnOnce(&ArchiveDiagnostics) -> Result<Vec<T>, String>,
+) -> Result<Vec<T>, String> {
+    let (Some(cache), Some(sha1)) = (cache, sha1) else {
+        return scan(diagnostics);
+    };
+    if let Some(mut files) = cache.get_lang_map(sha1, scope) {
+        relabel(&mut files, "", label);
+        return Ok(files);
+    }
+
+    let jar_diagnostics = ArchiveDiagnostics::default();
+    let mut result = scan(&jar_diagnostics);
+    if let (Ok(files), true) = (&mut result, jar_diagnostics.is_empty()) {
+        relabel(files, label, "");
+        cache.put_lang_map(sha1, scope, files);
+        relabel(files, "", label);
+    }
+    jar_diagnostics.drain_into(diagnostics);
+    result
+}
+
+fn relabel<T: CachedLangFile>(files: &mut [T], from: &str, to: &str) {
+    for path in files.iter_mut().flat_map(|file| file.labelled_paths()) {
+        if path.starts_with(from) {
+            path.replace_range(..from.len(), to);
+        }
+    }
+}
+
+// 枚举 JAR 或 zip 根目录下 assets/<namespace>/lang/<locale>.(json|lang) 条目并统计键数
+pub fn scan_archive_lang_files(
+    path: &Path,
+    diagnostics: &ArchiveDiagnostics,
+    lenient: bool,
+) -> Result<Vec<ArchiveLangFile>, String> {
+    let mut jar = JarReader::open_with(path, diagnostics)?;
+    Ok(archive_lang_files(&mut jar, "", &path.to_string_lossy(), lenient))
+}
+
+// prefix 为 assets/ 所在的目录（如 "overrides/kubejs/"），label 为归档本身在 source_path 中的路径；
+// lenient 见 lang::scan_lang
+pub fn archive_lang_files(jar: &mut JarReader, prefix: &str, label: &str, lenient: bool) -> Vec<ArchiveLangFile> {
+    let mut files = Vec::new();
+
+    for name in jar.entry_names() {
+        let Some((namespace, file_name)) = name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix("assets/"))
+            .and_then(|rest| rest.split_once("/lang/"))
+        else {
+            continue;
+        };
+        if namespace.contains('/') || file_name.contains('/') {
+            continue;
+        }
+        let Some((file_locale, extension)) = file_name.rsplit_once('.') else { continue };
+        if !matches!(extension, "json" | "lang") {
+            continue;
+        }
+
+        let started = Instant::now();
+        let source_path = format!("{}!/{}", label, name);
+        let locale = locale::canonical_locale(file_locale);
+        let stats = lang_stream::scan_jar_entry(jar, &name, extension, &source_path, &locale, lenient);
+
+        files.push(ArchiveLangFile {
+            namespace: namespace.to_string(),
+            key_count: stats.key_count,
+            duplicate_keys: stats.duplicate_keys,
+            placeholders: stats.placeholders,
+            formatting: stats.formatting,
+            key_categories: stats.key_categories,
+            parse_error: stats.parse_error,
+            warnings: stats.warnings,
+            locale,
+            source_path,
+            parse_ms: millis(started.elapsed()),
+        });
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    // 临时目录：缓存库和两份内容相同、位置不同的 JAR，测试结束时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("th-suite-mod-lang-{}-{}", name, std::process::id()));
+            fs::create_dir_all(&root).unwrap();
+            TempDir(root)
+        }
+
+        // en_us.json 中 a 出现两次，扫描时产生一条重复键警告
+        fn write_jar(&self, name: &str) -> PathBuf {
+            let path = self.0.join(name);
+            let mut writer = ZipWriter::new(fs::File::create(&path).unwrap());
+            writer.start_file("assets/examplemod/lang/en_us.json", SimpleFileOptions::default()).unwrap();
+            writer.write_all(b"{\n  \"a\": \"one\",\n  \"b\": \"two\",\n  \"a\": \"three\"\n}").unwrap();
+            writer.finish().unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn scan_counted(
+        cache: &MetadataCache,
+        path: &Path,
+        scans: &Cell<u32>,
+        diagnostics: &ArchiveDiagnostics,
+    ) -> Vec<ArchiveLangFile> {
+        let label = path.to_string_lossy();
+        cached_lang_files(Some(cache), Some("0123abcd"), "mod_lang;lenient=false", &label, diagnostics, |diagnostics| {
+            scans.set(scans.get() + 1);
+            scan_archive_lang_files(path, diagnostics, false)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn second_scan_reads_lang_files_from_cache() {
+        let dir = TempDir::new("hit");
+        let cache = MetadataCache::open(&dir.0.join("cache.db")).unwrap();
+        let jar = dir.write_jar("examplemod.jar");
+        let diagnostics = ArchiveDiagnostics::default();
+        let scans = Cell::new(0);
+
+        let first = scan_counted(&cache, &jar, &scans, &diagnostics);
+        let second = scan_counted(&cache, &jar, &scans, &diagnostics);
+        assert_eq!(scans.get(), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].source_path, first[0].source_path);
+        assert_eq!(second[0].warnings, first[0].warnings);
+        assert_eq!((second[0].key_count, second[0].duplicate_keys), (2, 1));
+        assert_eq!(second[0].locale, "en_us");
+    }
+
+    #[test]
+    fn cached_paths_follow_the_jar_location() {
+        let dir = TempDir::new("relabel");
+        let cache = MetadataCache::open(&dir.0.join("cache.db")).unwrap();
+        let original = dir.write_jar("examplemod.jar");
+        let copy = dir.write_jar("examplemod-copy.jar");
+        let diagnostics = ArchiveDiagnostics::default();
+        let scans = Cell::new(0);
+
+        scan_counted(&cache, &original, &scans, &diagnostics);
+        let files = scan_counted(&cache, &copy, &scans, &diagnostics);
+        assert_eq!(scans.get(), 1);
+        let expected = format!("{}!/assets/examplemod/lang/en_us.json", copy.to_string_lossy());
+        assert_eq!(files[0].source_path, expected);
+        assert!(files[0].warnings[0].starts_with(&expected));
+    }
+
+    #[test]
+    fn scope_and_missing_fingerprint_bypass_the_cache() {
+        let dir = TempDir::new("miss");
+        let cache = MetadataCache::open(&dir.0.join("cache.db")).unwrap();
+        let jar = dir.write_jar("examplemod.jar");
+        let label = jar.to_string_lossy();
+        let diagnostics = ArchiveDiagnostics::default();
+        let scans = Cell::new(0);
+        let scan = |sha1: Option<&str>, scope: &str| {
+            cached_lang_files(Some(&cache), sha1, scope, &label, &diagnostics, |diagnostics| {
+                scans.set(scans.get() + 1);
+                scan_archive_lang_files(&jar, diagnostics, false)
+            })
+            .unwrap()
+        };
+
+        scan(Some("0123abcd"), "mod_lang;lenient=false");
+        scan(Some("0123abcd"), "mod_lang;lenient=true");
+        scan(None, "mod_lang;lenient=false");
+        assert_eq!(scans.get(), 3);
+    }
+}
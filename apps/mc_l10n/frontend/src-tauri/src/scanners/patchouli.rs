@@ -0,0 +1,240 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::{ContentScanner, LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+use crate::io_retry;
+use crate::jar::{ArchiveDiagnostics, JarReader};
+use crate::locale;
+use crate::profiling::millis;
+
+const BOOKS_DIR: &str = "patchouli_books/";
+
+// 页面和分类中包含文本的字段，其他字段是 id、图标、配方等
+const TEXT_FIELDS: &[&str] = &["name", "description", "title", "subtitle", "text", "landing_text"];
+
+// Patchouli 手册扫描器：MOD JAR 中 assets|data/<namespace>/patchouli_books/<book>/<locale>/ 下的页面，
+// 以及实例 patchouli_books/、config/patchouli_books/ 中的外部手册。每个页面文件作为一个语言资源，
+// 命名空间为 <namespace>:<book>
+pub struct PatchouliScanner;
+
+// 手册中的一个页面文件
+struct BookPage {
+    namespace: String,
+    locale: String,
+    source_path: String,
+    key_count: u32,
+    parse_ms: f64,
+}
+
+impl ContentScanner for PatchouliScanner {
+    fn id(&self) -> &'static str {
+        "patchouli"
+    }
+
+    fn name(&self) -> &'static str {
+        "Patchouli books"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_patchouli"
+    }
+
+    fn weight(&self) -> f64 {
+        2.0
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Standard
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let jar_paths = ctx.mod_jar_paths();
+        ctx.progress.set_total(jar_paths.len() as u32);
+
+        let per_jar = ctx.limits.map(jar_paths, |limits, path| {
+            let pages = {
+                let _io = limits.io.acquire();
+                scan_jar_books(&path, ctx.archive_diagnostics)
+            };
+            ctx.progress.file_done(&path.to_string_lossy());
+            pages
+        });
+
+        // 无法读取的 JAR 逐个报告，不中断扫描
+        let mut pages = Vec::new();
+        for result in per_jar {
+            match result {
+                Ok(found) => pages.extend(found),
+                Err(e) => output.errors.push(e),
+            }
+        }
+        for root in [ctx.project_path.join("patchouli_books"), ctx.project_path.join("config").join("patchouli_books")] {
+            let _io = ctx.limits.io.acquire();
+            scan_external_books(&root, &mut pages);
+        }
+
+        for page in pages {
+            ctx.profiler.record_lang_file(&page.source_path, page.parse_ms, page.key_count);
+            ctx.progress.lang_file_done(&page.namespace, &page.source_path, page.key_count);
+            output.language_resources.push(LanguageResource {
+                namespace: output.interner.intern(&page.namespace),
+                locale: output.interner.intern(&page.locale),
+                source_path: page.source_path,
+                source_type: output.interner.intern("patchouli"),
+                key_count: page.key_count,
+                duplicate_keys: 0,
+                placeholders: BTreeMap::new(),
+                formatting: BTreeMap::new(),
+                key_categories: BTreeMap::new(),
+                parse_status: ParseStatus::Parsed,
+                parse_error: None,
+                // 扫描结束后按优先级规则计算
+                priority: 0,
+                bundled_pack: None,
+            });
+        }
+    }
+}
+
+fn scan_jar_books(path: &Path, diagnostics: &ArchiveDiagnostics) -> Result<Vec<BookPage>, String> {
+    let mut jar = JarReader::open_with(path, diagnostics)?;
+    let label = path.to_string_lossy().to_string();
+    let mut pages = Vec::new();
+
+    for name in jar.entry_names() {
+        if !name.ends_with(".json") || !is_book_path(&name) {
+            continue;
+        }
+        let started = Instant::now();
+        let source_path = format!("{}!/{}", label, name);
+        let Some(entries) = jar.read_entry_string(&name).and_then(|content| page_entries(&source_path, &content)) else {
+            continue;
+        };
+        if let Some(page) = book_page(source_path, entries.len() as u32, started) {
+            pages.push(page);
+        }
+    }
+
+    Ok(pages)
+}
+
+// 外部手册目录：<root>/<book>/<locale>/**.json
+fn scan_external_books(root: &Path, pages: &mut Vec<BookPage>) {
+    let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let started = Instant::now();
+            let source_path = path.to_string_lossy().to_string();
+            let Some(entries) = io_retry::read_to_string(&path)
+                .ok()
+                .and_then(|content| page_entries(&source_path, &content))
+            else {
+                continue;
+            };
+            if let Some(page) = book_page(source_path, entries.len() as u32, started) {
+                pages.push(page);
+            }
+        }
+    }
+}
+
+// 全部使用语言键的页面没有需要翻译的文本，不列出
+fn book_page(source_path: String, key_count: u32, started: Instant) -> Option<BookPage> {
+    if key_count == 0 {
+        return None;
+    }
+    let location = BookLocation::parse(&source_path)?;
+    Some(BookPage {
+        namespace: format!("{}:{}", location.namespace, location.book),
+        locale: locale::canonical_locale(&location.locale),
+        key_count,
+        parse_ms: millis(started.elapsed()),
+        source_path,
+    })
+}
+
+// 手册页面在路径中的位置；page 为相对于语言目录、不含扩展名的页面路径（如 entries/basics/intro）
+struct BookLocation {
+    namespace: String,
+    book: String,
+    locale: String,
+    page: String,
+}
+
+impl BookLocation {
+    // 路径形如 .../assets/<namespace>/patchouli_books/<book>/<locale>/<page>.json，
+    // 外部手册没有所属命名空间，记为 patchouli。template 目录只有布局，不含文本
+    fn parse(path: &str) -> Option<Self> {
+        let path = path.replace('\\', "/");
+        let (before, rest) = path.rsplit_once(BOOKS_DIR)?;
+        let mut parts = rest.splitn(3, '/');
+        let book = parts.next()?;
+        let locale = parts.next()?;
+        let page = parts.next()?.strip_suffix(".json")?;
+        if book.is_empty() || page.is_empty() || page.starts_with("templates/") {
+            return None;
+        }
+
+        let mut owner = before.trim_end_matches('/').rsplit(['/', '!']);
+        let namespace = match (owner.next(), owner.next()) {
+            (Some(namespace), Some("assets" | "data")) if !namespace.is_empty() => namespace,
+            _ => "patchouli",
+        };
+        Some(Self {
+            namespace: namespace.to_string(),
+            book: book.to_string(),
+            locale: locale.to_string(),
+            page: page.to_string(),
+        })
+    }
+}
+
+pub fn is_book_path(path: &str) -> bool {
+    BookLocation::parse(path).is_some()
+}
+
+// 页面中的文本，键为 <page>.<字段>，pages 数组中的字段为 <page>.pages.<序号>.<字段>。
+// 使用 i18n 的手册中字段值是语言键，文本在语言文件中，不重复计入
+pub fn page_entries(source_path: &str, content: &str) -> Option<HashMap<String, String>> {
+    let location = BookLocation::parse(source_path)?;
+    let json: Value = serde_json::from_str(content.trim_start_matches('\u{FEFF}')).ok()?;
+    let mut entries = HashMap::new();
+    collect_text(&json, &location.page, &mut entries);
+    Some(entries)
+}
+
+fn collect_text(value: &Value, prefix: &str, entries: &mut HashMap<String, String>) {
+    let Some(object) = value.as_object() else { return };
+    for (field, value) in object {
+        match value {
+            Value::String(text) if TEXT_FIELDS.contains(&field.as_str()) && !is_translation_key(text) => {
+                entries.insert(format!("{}.{}", prefix, field), text.clone());
+            }
+            Value::Array(pages) if field == "pages" => {
+                for (index, page) in pages.iter().enumerate() {
+                    collect_text(page, &format!("{}.pages.{}", prefix, index), entries);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_translation_key(text: &str) -> bool {
+    text.contains('.')
+        && !text.ends_with('.')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | ':'))
+}
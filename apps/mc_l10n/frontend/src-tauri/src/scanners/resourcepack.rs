@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use super::mod_lang::scan_archive_lang_files;
+use super::{ContentScanner, LanguageResource, ParseStatus, ScanContext, ScanOutput, ScanPreset};
+use crate::intern::Symbol;
+use crate::lang_detect::{self, Detection};
+use crate::lang_stream;
+use crate::locale;
+use crate::profiling::millis;
+
+// 资源包扫描器：读取项目 assets/<namespace>/lang 下的语言文件，以及 resourcepacks/ 中 zip 资源包内的语言文件
+pub struct ResourcePackScanner;
+
+impl ContentScanner for ResourcePackScanner {
+    fn id(&self) -> &'static str {
+        "resourcepack"
+    }
+
+    fn name(&self) -> &'static str {
+        "language resources"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_language_resources"
+    }
+
+    fn weight(&self) -> f64 {
+        3.0
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Quick
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        scan_assets_lang_files(ctx, &ctx.project_path.join("assets"), "resourcepack", output);
+        scan_zipped_resource_packs(ctx, output);
+    }
+}
+
+// 直接读取 resourcepacks/*.zip，不需要解压；无法打开的 zip 逐个报告，不中断扫描
+fn scan_zipped_resource_packs(ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+    let Ok(entries) = fs::read_dir(ctx.project_path.join("resourcepacks")) else { return };
+    let mut zip_paths: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")))
+        .collect();
+    zip_paths.sort();
+
+    for zip_path in zip_paths {
+        let files = {
+            let _io = ctx.limits.io.acquire();
+            scan_archive_lang_files(&zip_path, ctx.archive_diagnostics, ctx.lenient_lang_json)
+        };
+        let files = match files {
+            Ok(files) => files,
+            Err(e) => {
+                output.errors.push(e);
+                continue;
+            }
+        };
+
+        for file in files {
+            ctx.profiler.record_lang_file(&file.source_path, file.parse_ms, file.key_count);
+            ctx.progress.lang_file_done(&file.namespace, &file.source_path, file.key_count);
+            output.warnings.extend(file.warnings);
+            output.language_resources.push(LanguageResource {
+                namespace: output.interner.intern(&file.namespace),
+                locale: output.interner.intern(&file.locale),
+                source_path: file.source_path,
+                source_type: output.interner.intern("resourcepack_zip"),
+                key_count: file.key_count,
+                duplicate_keys: file.duplicate_keys,
+                placeholders: file.placeholders,
+                formatting: file.formatting,
+                key_categories: file.key_categories,
+                parse_status: ParseStatus::from_error(&file.parse_error),
+                parse_error: file.parse_error,
+                // 扫描结束后按优先级规则计算
+                priority: 0,
+                bundled_pack: None,
+            });
+        }
+    }
+}
+
+// 扫描 <assets_dir>/<namespace>/lang 下的语言文件
+pub fn scan_assets_lang_files(ctx: &ScanContext<'_>, assets_dir: &Path, source_type: &str, output: &mut ScanOutput) {
+    // 扫描 assets 目录结构
+    if !assets_dir.exists() {
+        return;
+    }
+    
+    // 遍历 namespace 目录
+    if let Ok(namespace_entries) = fs::read_dir(assets_dir) {
+        for namespace_entry in namespace_entries.flatten() {
+            if !namespace_entry.path().is_dir() {
+                continue;
+            }
+            
+            let namespace = output.interner.intern(&namespace_entry.file_name().to_string_lossy());
+            let lang_dir = namespace_entry.path().join("lang");
+            
+            if lang_dir.exists() {
+                if let Ok(lang_entries) = fs::read_dir(&lang_dir) {
+                    for lang_entry in lang_entries.flatten() {
+                        let lang_path = lang_entry.path();
+                        if !lang_path.is_file() {
+                            continue;
+                        }
+                        match lang_detect::detect(&lang_path) {
+                            Detection::Language => {
+                                let started = Instant::now();
+                                if let Some(lang_resource) = create_language_resource(ctx, &lang_path, &namespace, source_type, output) {
+                                    ctx.profiler.record_lang_file(&lang_resource.source_path, millis(started.elapsed()), lang_resource.key_count);
+                                    ctx.progress.lang_file_done(&namespace, &lang_resource.source_path, lang_resource.key_count);
+                                    output.language_resources.push(lang_resource);
+                                }
+                            }
+                            Detection::Rejected(candidate) => output.rejected_lang_files.push(candidate),
+                            Detection::NotCandidate => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 创建语言资源对象，解析警告并入 output
+fn create_language_resource(
+    ctx: &ScanContext<'_>,
+    lang_path: &Path,
+    namespace: &Symbol,
+    source_type: &str,
+    output: &mut ScanOutput,
+) -> Option<LanguageResource> {
+    let file_name = lang_path.file_stem()?.to_str()?;
+    let canonical = locale::canonical_locale(file_name);
+
+    // 统计语言文件中的键数量和重复的键数，源语言文件另记占位符
+    let stats = lang_stream::scan_file(lang_path, &canonical, ctx.lenient_lang_json);
+    output.warnings.extend(stats.warnings);
+
+    Some(LanguageResource {
+        namespace: namespace.clone(),
+        locale: output.interner.intern(&canonical),
+        source_path: lang_path.to_string_lossy().to_string(),
+        source_type: output.interner.intern(source_type),
+        key_count: stats.key_count,
+        duplicate_keys: stats.duplicate_keys,
+        placeholders: stats.placeholders,
+        formatting: stats.formatting,
+        key_categories: stats.key_categories,
+        parse_status: ParseStatus::from_error(&stats.parse_error),
+        parse_error: stats.parse_error,
+        // 扫描结束后按优先级规则计算
+        priority: 0,
+        bundled_pack: None,
+    })
+}
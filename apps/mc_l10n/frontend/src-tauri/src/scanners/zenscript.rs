@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ContentScanner, ScanContext, ScanOutput, ScanPreset};
+use crate::io_retry;
+use crate::pack_module::{key_segment, PackModuleResource, TranslatableEntry};
+use crate::placeholders;
+use crate::validation::profiles::SOURCE_LOCALE;
+
+pub const MODULE_TYPE: &str = "scripts";
+
+// 参数是显示文本的方法和属性（物品提示、显示名称、聊天消息）
+const TEXT_METHODS: &[&str] = &[
+    "addTooltip",
+    "addShiftTooltip",
+    "addAdvancedTooltip",
+    "setDisplayName",
+    "displayName",
+    "sendMessage",
+    "sendChat",
+    "sendStatusMessage",
+];
+
+// CraftTweaker 扫描器：scripts/ 下 .zs 脚本中 game.setLocalization 设置的语言键，
+// 物品提示、显示名称等调用中的文本，以及其他看起来像正文的字符串字面量
+pub struct ZenScriptScanner;
+
+impl ContentScanner for ZenScriptScanner {
+    fn id(&self) -> &'static str {
+        MODULE_TYPE
+    }
+
+    fn name(&self) -> &'static str {
+        "CraftTweaker scripts"
+    }
+
+    fn phase(&self) -> &'static str {
+        "scanning_pack_modules"
+    }
+
+    fn preset(&self) -> ScanPreset {
+        ScanPreset::Deep
+    }
+
+    fn scan(&self, ctx: &ScanContext<'_>, output: &mut ScanOutput) {
+        let root = ctx.project_path.join("scripts");
+        let mut files = Vec::new();
+        {
+            let _io = ctx.limits.io.acquire();
+            collect_scripts(&root, &mut files);
+        }
+        files.sort();
+
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        for path in files {
+            let content = {
+                let _io = ctx.limits.io.acquire();
+                io_retry::read_to_string(&path)
+            };
+            let content = match content {
+                Ok(content) => content,
+                Err(e) => {
+                    output.warnings.push(format!("Failed to read {}: {}", path.display(), e));
+                    continue;
+                }
+            };
+            let relative = path.strip_prefix(&root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            for entry in extract_entries(&relative, &path.to_string_lossy(), &content) {
+                if seen.insert(entry.key.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if !entries.is_empty() {
+            output.pack_modules.push(PackModuleResource {
+                module_type: MODULE_TYPE.to_string(),
+                root_path: root.to_string_lossy().to_string(),
+                entries,
+            });
+        }
+    }
+}
+
+fn collect_scripts(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_scripts(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "zs") {
+            files.push(path);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+// setLocalization 的键直接使用脚本给出的语言键，其他文本的键为 scripts.<文件路径>.<序号>
+fn extract_entries(relative: &str, source_path: &str, content: &str) -> Vec<TranslatableEntry> {
+    let path = relative.strip_suffix(".zs").unwrap_or(relative);
+    let prefix = format!("scripts.{}", path.split('/').map(key_segment).collect::<Vec<_>>().join("."));
+    let tokens = tokenize(content);
+    let mut entries = Vec::new();
+    let mut index = 0;
+    let mut push = |key: String, source: String, line: u32, kind: &str| {
+        entries.push(TranslatableEntry {
+            key,
+            placeholders: placeholders::extract(&source),
+            source,
+            source_path: source_path.to_string(),
+            kind: kind.to_string(),
+            line: Some(line),
+        });
+    };
+
+    // 每层括号是否处于文本方法的参数中；format.red("...") 等嵌套调用继承外层
+    let mut text_depth: Vec<bool> = Vec::new();
+    let mut position = 0;
+    while position < tokens.len() {
+        let (line, token) = &tokens[position];
+        match token {
+            Token::Ident(name) if name == "setLocalization" => {
+                let (args, next) = string_arguments(&tokens, position + 1);
+                let localization = match args.as_slice() {
+                    [key, value] => Some((key, value)),
+                    [locale, key, value] if locale.eq_ignore_ascii_case(SOURCE_LOCALE) => Some((key, value)),
+                    _ => None,
+                };
+                if let Some((key, value)) = localization {
+                    push(key.clone(), value.clone(), *line, "localization");
+                }
+                if next > position + 1 {
+                    position = next;
+                    continue;
+                }
+            }
+            Token::Punct('(') => {
+                let in_text = text_depth.last().copied().unwrap_or(false)
+                    || matches!(position.checked_sub(1).map(|p| &tokens[p].1), Some(Token::Ident(name)) if TEXT_METHODS.contains(&name.as_str()));
+                text_depth.push(in_text);
+            }
+            Token::Punct(')') => {
+                text_depth.pop();
+            }
+            Token::Str(text) if !text.trim().is_empty() => {
+                let assigned_text = matches!(
+                    (position.checked_sub(2).map(|p| &tokens[p].1), position.checked_sub(1).map(|p| &tokens[p].1)),
+                    (Some(Token::Ident(name)), Some(Token::Punct('='))) if TEXT_METHODS.contains(&name.as_str())
+                );
+                let kind = if text_depth.last().copied().unwrap_or(false) || assigned_text {
+                    Some("text")
+                } else if looks_like_text(text) {
+                    Some("literal")
+                } else {
+                    None
+                };
+                if let Some(kind) = kind {
+                    index += 1;
+                    push(format!("{}.{}", prefix, index), text.clone(), *line, kind);
+                }
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+
+    entries
+}
+
+// 从 start 处的 ( 开始读取全部为字符串字面量的参数，返回参数和 ) 之后的位置；
+// 参数中有其他表达式时返回空列表和 start
+fn string_arguments(tokens: &[(u32, Token)], start: usize) -> (Vec<String>, usize) {
+    if tokens.get(start).map(|(_, token)| token) != Some(&Token::Punct('(')) {
+        return (Vec::new(), start);
+    }
+    let mut args = Vec::new();
+    let mut position = start + 1;
+    loop {
+        match tokens.get(position).map(|(_, token)| token) {
+            Some(Token::Str(text)) => args.push(text.clone()),
+            _ => return (Vec::new(), start),
+        }
+        match tokens.get(position + 1).map(|(_, token)| token) {
+            Some(Token::Punct(',')) => position += 2,
+            Some(Token::Punct(')')) => return (args, position + 2),
+            _ => return (Vec::new(), start),
+        }
+    }
+}
+
+// 不在文本调用中的字面量只收录像正文的：含空白和字母，且不像资源位置、ore 名称或配方名
+fn looks_like_text(text: &str) -> bool {
+    text.chars().any(char::is_whitespace)
+        && text.chars().any(char::is_alphabetic)
+        && !text.split_whitespace().all(|word| word.contains(':') || word.contains('_'))
+}
+
+// 词法分析：跳过 //、# 和 /* */ 注释，字符串支持 "..." 和 '...' 及其中的转义；每个记号带起始行号
+fn tokenize(content: &str) -> Vec<(u32, Token)> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        match (c, chars.get(index + 1)) {
+            ('\n', _) => {
+                line += 1;
+                index += 1;
+            }
+            ('/', Some('/')) | ('#', _) => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                index += 2;
+                while index < chars.len() && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/')) {
+                    line += u32::from(chars[index] == '\n');
+                    index += 1;
+                }
+                index += 2;
+            }
+            ('"' | '\'', _) => {
+                let start_line = line;
+                let mut text = String::new();
+                index += 1;
+                while index < chars.len() && chars[index] != c {
+                    if chars[index] == '\\' && index + 1 < chars.len() {
+                        index += 1;
+                        match chars[index] {
+                            'n' => text.push('\n'),
+                            't' => text.push('\t'),
+                            'u' => {
+                                let hex: String = chars[index + 1..].iter().take(4).collect();
+                                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                                    Some(decoded) if hex.len() == 4 => {
+                                        text.push(decoded);
+                                        index += 4;
+                                    }
+                                    _ => text.push('u'),
+                                }
+                            }
+                            other => text.push(other),
+                        }
+                    } else {
+                        line += u32::from(chars[index] == '\n');
+                        text.push(chars[index]);
+                    }
+                    index += 1;
+                }
+                index += 1;
+                tokens.push((start_line, Token::Str(text)));
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let start = index;
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                    index += 1;
+                }
+                tokens.push((line, Token::Ident(chars[start..index].iter().collect())));
+            }
+            _ if c.is_whitespace() => index += 1,
+            _ => {
+                tokens.push((line, Token::Punct(c)));
+                index += 1;
+            }
+        }
+    }
+
+    tokens
+}
@@ -0,0 +1,130 @@
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::project_settings;
+
+// 提取原文后、写入本地库之前调用
+pub const ON_ENTRY_EXTRACTED: &str = "on_entry_extracted";
+
+// 导出译文、写入资源包之前调用
+pub const ON_BEFORE_EXPORT: &str = "on_before_export";
+
+// 单次钩子调用允许的最大操作数，防止脚本死循环卡住扫描和导出
+const MAX_OPERATIONS: u64 = 100_000;
+
+// 钩子返回的字符串最大长度
+const MAX_STRING_SIZE: usize = 64 * 1024;
+
+// 项目设置中配置的 Rhai 脚本。钩子函数签名为 fn hook(key, value, ctx)，ctx 包含 namespace、locale、source；
+// 返回 () 保持不变，返回字符串替换值，返回 #{ key: .., value: .. } 替换键和值，返回 false 丢弃该条目
+pub struct ScriptHooks {
+    engine: Engine,
+    ast: AST,
+    path: PathBuf,
+}
+
+impl ScriptHooks {
+    pub fn load(script_path: &Path) -> Result<Self, String> {
+        let script = fs::read_to_string(script_path)
+            .map_err(|e| format!("Failed to read script {}: {}", script_path.display(), e))?;
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_string_size(MAX_STRING_SIZE);
+        let ast = engine
+            .compile(&script)
+            .map_err(|e| format!("Failed to compile script {}: {}", script_path.display(), e))?;
+
+        Ok(Self {
+            engine,
+            ast,
+            path: script_path.to_path_buf(),
+        })
+    }
+
+    // 项目设置中的脚本路径可以是绝对路径，也可以相对于项目目录；未配置时返回 None
+    pub fn for_project(project_path: &str) -> Result<Option<Self>, String> {
+        let settings = project_settings::load_project_settings(project_path);
+        settings
+            .script_hooks
+            .map(|script| Self::load(&resolve_script_path(project_path, &script)))
+            .transpose()
+    }
+
+    pub fn has_hook(&self, hook: &str) -> bool {
+        self.ast.iter_functions().any(|function| function.name == hook)
+    }
+
+    // 对每个条目调用钩子；脚本出错时返回错误，不写入部分转换的结果
+    pub fn apply(
+        &self,
+        hook: &str,
+        entries: &mut HashMap<String, String>,
+        namespace: &str,
+        locale: &str,
+        source: &str,
+    ) -> Result<(), String> {
+        if !self.has_hook(hook) {
+            return Ok(());
+        }
+
+        let mut ctx = Map::new();
+        ctx.insert("namespace".into(), Dynamic::from(namespace.to_string()));
+        ctx.insert("locale".into(), Dynamic::from(locale.to_string()));
+        ctx.insert("source".into(), Dynamic::from(source.to_string()));
+
+        let mut transformed = HashMap::with_capacity(entries.len());
+        for (key, value) in entries.drain() {
+            let result: Dynamic = self
+                .engine
+                .call_fn(&mut Scope::new(), &self.ast, hook, (key.clone(), value.clone(), ctx.clone()))
+                .map_err(|e| format!("{} in {} failed for key {}: {}", hook, self.path.display(), key, e))?;
+
+            if let Some((key, value)) = interpret(result, key, value)
+                .map_err(|e| format!("{} in {} returned {} for a key", hook, self.path.display(), e))?
+            {
+                transformed.insert(key, value);
+            }
+        }
+        *entries = transformed;
+        Ok(())
+    }
+}
+
+fn interpret(result: Dynamic, key: String, value: String) -> Result<Option<(String, String)>, String> {
+    if result.is_unit() {
+        return Ok(Some((key, value)));
+    }
+    if let Ok(keep) = result.as_bool() {
+        return Ok(keep.then_some((key, value)));
+    }
+
+    let type_name = result.type_name();
+    if let Some(map) = result.clone().try_cast::<Map>() {
+        let field = |name: &str, default: String| match map.get(name) {
+            Some(field) => field.clone().into_string().map_err(|_| format!("a non-string {}", name)),
+            None => Ok(default),
+        };
+        return Ok(Some((field("key", key)?, field("value", value)?)));
+    }
+    match result.into_string() {
+        Ok(value) => Ok(Some((key, value))),
+        Err(_) => Err(format!("an unsupported {}", type_name)),
+    }
+}
+
+fn resolve_script_path(project_path: &str, script: &str) -> PathBuf {
+    let path = Path::new(script);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        Path::new(project_path).join(path)
+    }
+}
+
+// 保存设置前校验脚本可以编译
+pub fn validate_script(project_path: &str, script: &str) -> Result<(), String> {
+    ScriptHooks::load(&resolve_script_path(project_path, script)).map(|_| ())
+}
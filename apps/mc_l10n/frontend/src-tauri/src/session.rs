@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::config::AppConfig;
+use crate::scanners::ScanPreset;
+
+// 当前会话的进行中状态，每次变化后写入 session.json；正常退出时删除该文件
+static CURRENT: Mutex<Option<SessionState>> = Mutex::new(None);
+
+// 进行中的扫描；扫描完成或失败后移除
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub scan_id: String,
+    pub project_path: String,
+    pub preset: ScanPreset,
+    pub started_at: String,
+    // 最近一次进入的扫描阶段和该阶段开始时的总进度
+    pub phase: Option<String>,
+    pub progress: f64,
+    // 暂停中的扫描及暂停时的位置：当前阶段已处理的条目（JAR、目录等）数和最近处理的条目
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub item_index: u32,
+    #[serde(default)]
+    pub current_item: Option<String>,
+}
+
+// 前端交给后端保存的未保存编辑内容，按编辑器 id 索引
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorBuffer {
+    pub id: String,
+    pub content: String,
+    // 前端恢复编辑器所需的信息，如项目、文件和光标位置
+    pub context: Option<Value>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    pub started_at: String,
+    pub scans: Vec<ScanCheckpoint>,
+    // 尚未发送到服务器的队列项，由前端整体替换
+    pub unsent_queue: Vec<Value>,
+    pub editor_buffers: BTreeMap<String, EditorBuffer>,
+}
+
+impl SessionState {
+    fn is_empty(&self) -> bool {
+        self.scans.is_empty() && self.unsent_queue.is_empty() && self.editor_buffers.is_empty()
+    }
+}
+
+fn session_path() -> Result<PathBuf, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(config.get_data_dir().join("session.json"))
+}
+
+// 上次异常退出时留下的会话，用户恢复或放弃后删除
+fn recovered_path() -> Result<PathBuf, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    Ok(config.get_data_dir().join("session_recovered.json"))
+}
+
+// 启动时调用：session.json 仍然存在说明上次没有正常退出，其中有未完成的内容时转存为待恢复的会话
+pub fn begin() -> Result<(), String> {
+    let path = session_path()?;
+    let previous = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<SessionState>(&content).ok());
+    if let Some(previous) = previous.filter(|state| !state.is_empty()) {
+        write_atomic(&recovered_path()?, &previous)?;
+    }
+
+    let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    let state = SessionState {
+        started_at: chrono::Utc::now().to_rfc3339(),
+        ..SessionState::default()
+    };
+    write_atomic(&path, &state)?;
+    *current = Some(state);
+    Ok(())
+}
+
+// 正常退出时删除当前会话文件
+pub fn end() {
+    let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    *current = None;
+    if let Ok(path) = session_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// 修改当前会话并立即写入磁盘；会话未开始时忽略
+fn update(change: impl FnOnce(&mut SessionState)) -> Result<(), String> {
+    let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(state) = current.as_mut() else {
+        return Ok(());
+    };
+    change(state);
+    write_atomic(&session_path()?, state)
+}
+
+// 先写临时文件再改名，崩溃时不会留下写了一半的会话文件
+fn write_atomic(path: &Path, state: &SessionState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let content = serde_json::to_string(state).map_err(|e| e.to_string())?;
+    fs::write(&partial, content).map_err(|e| format!("Failed to save session: {}", e))?;
+    fs::rename(&partial, path).map_err(|e| format!("Failed to save session: {}", e))
+}
+
+pub fn start_scan(scan_id: &str, project_path: &str, preset: ScanPreset) {
+    let checkpoint = ScanCheckpoint {
+        scan_id: scan_id.to_string(),
+        project_path: project_path.to_string(),
+        preset,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        phase: None,
+        progress: 0.0,
+        paused: false,
+        item_index: 0,
+        current_item: None,
+    };
+    log_error(update(|state| state.scans.push(checkpoint)));
+}
+
+// 扫描进入新阶段时更新检查点；不在会话中的扫描（如从网址扫描）忽略
+pub fn checkpoint_scan(scan_id: &str, phase: &str, progress: f64) {
+    update_checkpoint(scan_id, |checkpoint| {
+        checkpoint.phase = Some(phase.to_string());
+        checkpoint.progress = progress;
+        checkpoint.item_index = 0;
+        checkpoint.current_item = None;
+    });
+}
+
+// 扫描暂停时记录位置，恢复时清除暂停标记
+pub fn pause_scan(scan_id: &str, paused: bool, item_index: u32, current_item: Option<String>) {
+    update_checkpoint(scan_id, |checkpoint| {
+        checkpoint.paused = paused;
+        checkpoint.item_index = item_index;
+        checkpoint.current_item = current_item;
+    });
+}
+
+fn update_checkpoint(scan_id: &str, change: impl FnOnce(&mut ScanCheckpoint)) {
+    let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(state) = current.as_mut() else {
+        return;
+    };
+    let Some(checkpoint) = state.scans.iter_mut().find(|checkpoint| checkpoint.scan_id == scan_id) else {
+        return;
+    };
+    change(checkpoint);
+    log_error(session_path().and_then(|path| write_atomic(&path, state)));
+}
+
+pub fn finish_scan(scan_id: &str) {
+    log_error(update(|state| state.scans.retain(|checkpoint| checkpoint.scan_id != scan_id)));
+}
+
+pub fn set_unsent_queue(items: Vec<Value>) -> Result<(), String> {
+    update(|state| state.unsent_queue = items)
+}
+
+pub fn unsent_queue() -> Vec<Value> {
+    let current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    current.as_ref().map(|state| state.unsent_queue.clone()).unwrap_or_default()
+}
+
+// 移除已发送的队列项；发送期间前端可能已替换队列，按内容而不是位置移除
+pub fn remove_unsent(sent: &[Value]) -> Result<(), String> {
+    update(|state| state.unsent_queue.retain(|item| !sent.contains(item)))
+}
+
+pub fn save_editor_buffer(id: &str, content: String, context: Option<Value>) -> Result<(), String> {
+    let buffer = EditorBuffer {
+        id: id.to_string(),
+        content,
+        context,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    update(|state| {
+        state.editor_buffers.insert(id.to_string(), buffer);
+    })
+}
+
+pub fn discard_editor_buffer(id: &str) -> Result<(), String> {
+    update(|state| {
+        state.editor_buffers.remove(id);
+    })
+}
+
+// 上次异常退出时未完成的扫描、未发送的队列和未保存的编辑内容
+pub fn recoverable() -> Result<Option<SessionState>, String> {
+    let Ok(content) = fs::read_to_string(recovered_path()?) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to read recovered session: {}", e))
+}
+
+// 前端恢复或放弃后调用。恢复的队列和编辑内容由前端重新交给当前会话
+pub fn discard_recoverable() -> Result<(), String> {
+    match fs::remove_file(recovered_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+// 会话文件写入失败不影响扫描本身
+fn log_error(result: Result<(), String>) {
+    if let Err(e) = result {
+        eprintln!("{}", e);
+    }
+}
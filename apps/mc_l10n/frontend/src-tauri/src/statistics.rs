@@ -0,0 +1,188 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::database;
+use crate::lang;
+use crate::lang_detect;
+use crate::locale;
+use crate::validation::profiles::{self, SOURCE_LOCALE};
+use crate::validation::Severity;
+
+// 项目级统计，供仪表盘直接展示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectStatistics {
+    pub project_path: String,
+    pub source_locale: String,
+    pub namespace_count: u32,
+    pub total_keys: u32,
+    pub locales: Vec<LocaleStatistics>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocaleStatistics {
+    pub locale: String,
+    pub translated_keys: u32,
+    pub missing_keys: u32,
+    // 已翻译键占原文键的比例，0~1
+    pub coverage: f64,
+    pub error_count: u32,
+    pub warning_count: u32,
+    // 本地数据库中没有该项目的条目时为空
+    pub origin: Option<TranslationOrigin>,
+}
+
+// 本地数据库中译文的来源分布
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranslationOrigin {
+    // 机器翻译且未审校
+    pub machine: u32,
+    // 已审校或锁定
+    pub human: u32,
+    // 新建、冲突或被驳回
+    pub other: u32,
+    // 机器翻译占（机器 + 人工）的比例
+    pub machine_ratio: f64,
+}
+
+// 统计项目中每个语言的覆盖率、校验问题数以及机器/人工翻译比例；
+// excluded_namespaces 为被排除的 MOD 提供的命名空间，不计入统计
+pub fn project_statistics(
+    project_path: &str,
+    database_path: &Path,
+    excluded_namespaces: &HashSet<String>,
+) -> Result<ProjectStatistics, String> {
+    let path = Path::new(project_path);
+    let mut source = lang::load_locale_entries(path, SOURCE_LOCALE);
+    source.retain(|namespace, _| !excluded_namespaces.contains(namespace));
+    if source.is_empty() {
+        return Err(format!("No {} language files found in {}", SOURCE_LOCALE, project_path));
+    }
+    let total_keys: u32 = source.values().map(|entries| entries.len() as u32).sum();
+
+    // 本地数据库不存在或尚未建表时不统计来源分布
+    let store = database::open_read_only(database_path).ok();
+
+    let mut locales = Vec::new();
+    for locale in find_project_locales(path) {
+        let translations = lang::load_locale_entries(path, &locale);
+        let translated_keys = count_translated(&source, &translations);
+        let report = profiles::run_validation(project_path, None, &locale)?;
+        let count_issues = |severity: Severity| {
+            report
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == severity && !excluded_namespaces.contains(&issue.namespace))
+                .count() as u32
+        };
+        let origin = store
+            .as_ref()
+            .and_then(|conn| load_translation_origin(conn, &locale, source.keys()).ok())
+            .filter(|origin| origin.machine + origin.human + origin.other > 0);
+
+        locales.push(LocaleStatistics {
+            translated_keys,
+            missing_keys: total_keys - translated_keys,
+            coverage: if total_keys > 0 { translated_keys as f64 / total_keys as f64 } else { 0.0 },
+            error_count: count_issues(Severity::Error),
+            warning_count: count_issues(Severity::Warning),
+            origin,
+            locale,
+        });
+    }
+
+    Ok(ProjectStatistics {
+        project_path: project_path.to_string(),
+        source_locale: SOURCE_LOCALE.to_string(),
+        namespace_count: source.len() as u32,
+        total_keys,
+        locales,
+    })
+}
+
+// 项目 assets/<namespace>/lang 下出现过的所有目标语言
+fn find_project_locales(project_path: &Path) -> BTreeSet<String> {
+    let mut locales = BTreeSet::new();
+    let Ok(namespace_entries) = fs::read_dir(project_path.join("assets")) else {
+        return locales;
+    };
+
+    for namespace_entry in namespace_entries.flatten() {
+        let Ok(lang_entries) = fs::read_dir(namespace_entry.path().join("lang")) else {
+            continue;
+        };
+        for lang_entry in lang_entries.flatten() {
+            let lang_path = lang_entry.path();
+            if !lang_detect::is_language_file(&lang_path) {
+                continue;
+            }
+            if let Some(stem) = lang_path.file_stem() {
+                let locale = locale::canonical_locale(&stem.to_string_lossy());
+                if locale != SOURCE_LOCALE {
+                    locales.insert(locale);
+                }
+            }
+        }
+    }
+
+    locales
+}
+
+// 原文中存在且译文非空的键数量
+fn count_translated(
+    source: &HashMap<String, HashMap<String, String>>,
+    translations: &HashMap<String, HashMap<String, String>>,
+) -> u32 {
+    source
+        .iter()
+        .map(|(namespace, source_entries)| {
+            let Some(target_entries) = translations.get(namespace) else {
+                return 0;
+            };
+            source_entries
+                .keys()
+                .filter(|key| target_entries.get(*key).is_some_and(|value| !value.trim().is_empty()))
+                .count() as u32
+        })
+        .sum()
+}
+
+// 按项目的命名空间汇总本地数据库中译文条目的状态
+fn load_translation_origin<'a>(
+    conn: &Connection,
+    locale: &str,
+    namespaces: impl Iterator<Item = &'a String>,
+) -> Result<TranslationOrigin, rusqlite::Error> {
+    let mut statement = conn.prepare(
+        "SELECT e.status, COUNT(*)
+         FROM core_translation_entries e
+         JOIN core_language_files f ON f.uid = e.language_file_uid
+         WHERE lower(f.locale) = lower(?1) AND f.rel_path LIKE ?2
+         GROUP BY e.status",
+    )?;
+
+    let mut origin = TranslationOrigin::default();
+    for namespace in namespaces {
+        let pattern = format!("%assets/{}/lang/%", namespace);
+        let rows = statement.query_map(params![locale, pattern], |row| {
+            Ok((row.get::<_, Option<String>>(0)?, row.get::<_, u32>(1)?))
+        })?;
+
+        for row in rows {
+            let (status, count) = row?;
+            match status.as_deref().unwrap_or_default() {
+                "mt" => origin.machine += count,
+                "reviewed" | "locked" => origin.human += count,
+                _ => origin.other += count,
+            }
+        }
+    }
+
+    let labelled = origin.machine + origin.human;
+    if labelled > 0 {
+        origin.machine_ratio = origin.machine as f64 / labelled as f64;
+    }
+    Ok(origin)
+}
@@ -0,0 +1,393 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+use crate::config::AppConfig;
+use crate::events::{self, AppEvent};
+use crate::metrics;
+use crate::remote_sftp::{self, RemoteServerStore, RemoteSyncReport};
+use crate::session;
+use crate::worker_pool::WorkerPool;
+
+// 定时同步检查间隔
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+// 同步计划和限速设置，保存在数据目录下的 sync_schedule.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSchedule {
+    // 定时同步的远程服务器 id
+    #[serde(default)]
+    pub server_ids: Vec<String>,
+    // 自动同步间隔（分钟），0 表示只手动同步
+    #[serde(default)]
+    pub interval_minutes: u32,
+    // 下载和上传限速（KB/s），0 表示不限速
+    #[serde(default)]
+    pub max_download_kbps: u32,
+    #[serde(default)]
+    pub max_upload_kbps: u32,
+    // 免打扰时段内不自动同步
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    // 按流量计费的网络下不自动同步
+    #[serde(default = "default_true")]
+    pub pause_on_metered: bool,
+    // 接收未发送队列的地址，未配置时不发送队列
+    #[serde(default)]
+    pub outbound_endpoint: Option<String>,
+    // 每个请求发送的队列项数量
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u32,
+    #[serde(default)]
+    pub last_sync_at: Option<String>,
+    // 最近一次同步失败的原因，成功后清空
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl Default for SyncSchedule {
+    fn default() -> Self {
+        Self {
+            server_ids: Vec::new(),
+            interval_minutes: 0,
+            max_download_kbps: 0,
+            max_upload_kbps: 0,
+            quiet_hours: None,
+            pause_on_metered: true,
+            outbound_endpoint: None,
+            batch_size: default_batch_size(),
+            last_sync_at: None,
+            last_error: None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_batch_size() -> u32 {
+    50
+}
+
+// 本地时间的时段，如 22:00 至 07:00；结束早于开始时跨越午夜
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+impl QuietHours {
+    fn contains(&self, time: chrono::NaiveTime) -> bool {
+        let parse = |value: &str| chrono::NaiveTime::parse_from_str(value, "%H:%M").ok();
+        let (Some(start), Some(end)) = (parse(&self.start), parse(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+impl SyncSchedule {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let schedule_path = Self::get_schedule_path()?;
+
+        if schedule_path.exists() {
+            let content = fs::read_to_string(&schedule_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let schedule_path = Self::get_schedule_path()?;
+
+        if let Some(parent) = schedule_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&schedule_path, content)?;
+        Ok(())
+    }
+
+    fn get_schedule_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("sync_schedule.json"))
+    }
+
+    pub fn download_limit(&self) -> Option<u64> {
+        (self.max_download_kbps > 0).then(|| self.max_download_kbps as u64 * 1024)
+    }
+
+    fn upload_limit(&self) -> Option<u64> {
+        (self.max_upload_kbps > 0).then(|| self.max_upload_kbps as u64 * 1024)
+    }
+
+    // 开启了定时同步且距上次同步已超过间隔
+    fn is_due(&self) -> bool {
+        if self.interval_minutes == 0 {
+            return false;
+        }
+        let Some(last) = self.last_sync_at.as_deref().and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok()) else {
+            return true;
+        };
+        chrono::Utc::now().signed_duration_since(last) >= chrono::Duration::minutes(self.interval_minutes as i64)
+    }
+
+    // 不能自动同步的原因：quiet_hours 或 metered
+    fn blocked_reason(&self, metered: bool) -> Option<&'static str> {
+        let now = chrono::Local::now().time();
+        if self.quiet_hours.as_ref().is_some_and(|quiet| quiet.contains(now)) {
+            Some("quiet_hours")
+        } else if self.pause_on_metered && metered {
+            Some("metered")
+        } else {
+            None
+        }
+    }
+}
+
+// 调度器状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub due: bool,
+    pub metered: bool,
+    pub blocked_reason: Option<String>,
+    pub pending_queue_items: u32,
+    pub last_sync_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+pub fn status() -> Result<SyncStatus, String> {
+    let schedule = SyncSchedule::load().map_err(|e| e.to_string())?;
+    let metered = is_metered_connection();
+    Ok(SyncStatus {
+        due: schedule.is_due(),
+        metered,
+        blocked_reason: schedule.blocked_reason(metered).map(str::to_string),
+        pending_queue_items: session::unsent_queue().len() as u32,
+        last_sync_at: schedule.last_sync_at.clone(),
+        last_error: schedule.last_error.clone(),
+    })
+}
+
+// 一次同步的结果，同时作为 "sync-completed" 事件的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRunReport {
+    pub servers: Vec<RemoteSyncReport>,
+    pub queue_sent: u32,
+    pub queue_remaining: u32,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub errors: Vec<String>,
+}
+
+// 按速率限制读取；每读取一块后等待到平均速率不超过限制
+pub struct ThrottledReader<R> {
+    inner: R,
+    throttle: Throttle,
+}
+
+impl<R: Read> ThrottledReader<R> {
+    pub fn new(inner: R, bytes_per_sec: u64) -> Self {
+        Self {
+            inner,
+            throttle: Throttle::new(bytes_per_sec),
+        }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // 每次最多读取约 1/10 秒的数据量，避免一次读取大块后长时间停顿
+        let chunk = buf.len().min((self.throttle.bytes_per_sec / 10).max(1024) as usize);
+        let read = self.inner.read(&mut buf[..chunk])?;
+        self.throttle.consume(read as u64);
+        Ok(read)
+    }
+}
+
+struct Throttle {
+    bytes_per_sec: u64,
+    started: Instant,
+    bytes: u64,
+}
+
+impl Throttle {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: bytes_per_sec.max(1),
+            started: Instant::now(),
+            bytes: 0,
+        }
+    }
+
+    // 已传输的数据按限速需要的时间超过实际耗时时返回需要等待的时间
+    fn delay(&mut self, bytes: u64) -> Duration {
+        self.bytes += bytes;
+        let expected = Duration::from_secs_f64(self.bytes as f64 / self.bytes_per_sec as f64);
+        expected.saturating_sub(self.started.elapsed())
+    }
+
+    fn consume(&mut self, bytes: u64) {
+        let delay = self.delay(bytes);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+// 同步计划中的服务器，然后分批发送未发送的队列；force 为 false 时遵守免打扰时段和计费网络设置
+pub async fn sync_now(app: &tauri::AppHandle, force: bool) -> Result<SyncRunReport, String> {
+    let mut schedule = SyncSchedule::load().map_err(|e| e.to_string())?;
+    if !force {
+        if let Some(reason) = schedule.blocked_reason(is_metered_connection()) {
+            return Err(format!("Sync skipped: {}", reason));
+        }
+    }
+
+    let started = Instant::now();
+    let mut report = SyncRunReport {
+        servers: Vec::new(),
+        queue_sent: 0,
+        queue_remaining: 0,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        duration_ms: 0,
+        errors: Vec::new(),
+    };
+
+    let store = RemoteServerStore::load().map_err(|e| e.to_string())?;
+    let workers = app.state::<WorkerPool>();
+    for server_id in &schedule.server_ids {
+        let server = match store.get(server_id) {
+            Ok(server) => server.clone(),
+            Err(e) => {
+                report.errors.push(e);
+                continue;
+            }
+        };
+        let limit = schedule.download_limit();
+        let result = workers.submit(move || remote_sftp::sync_server(&server, limit))?;
+        match result.await.map_err(|_| "Remote sync task failed".to_string())? {
+            Ok((server_report, _)) => {
+                metrics::record_sync(Some(&server_report));
+                report.servers.push(server_report);
+            }
+            Err(e) => {
+                metrics::record_sync(None);
+                report.errors.push(format!("{}: {}", server_id, e));
+            }
+        }
+    }
+
+    if let Some(endpoint) = schedule.outbound_endpoint.clone() {
+        if let Err(e) = flush_queue(&endpoint, &schedule, &mut report).await {
+            report.errors.push(e);
+        }
+    }
+    report.queue_remaining = session::unsent_queue().len() as u32;
+    report.duration_ms = started.elapsed().as_millis() as u64;
+
+    schedule.last_sync_at = Some(report.started_at.clone());
+    schedule.last_error = (!report.errors.is_empty()).then(|| report.errors.join("; "));
+    schedule.save().map_err(|e| e.to_string())?;
+
+    events::emit(app, AppEvent::SyncCompleted(&report));
+    Ok(report)
+}
+
+// 每批作为 {"items": [...]} 发送，成功后从会话的未发送队列中移除；按上传限速在批次之间等待
+async fn flush_queue(endpoint: &str, schedule: &SyncSchedule, report: &mut SyncRunReport) -> Result<(), String> {
+    let items = session::unsent_queue();
+    let client = reqwest::Client::new();
+    let mut throttle = schedule.upload_limit().map(Throttle::new);
+
+    for batch in items.chunks(schedule.batch_size.max(1) as usize) {
+        let body = serde_json::to_vec(&serde_json::json!({ "items": batch })).map_err(|e| e.to_string())?;
+        let size = body.len() as u64;
+        let response = client
+            .post(endpoint)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send queue: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Queue endpoint returned {}", response.status()));
+        }
+
+        session::remove_unsent(batch)?;
+        report.queue_sent += batch.len() as u32;
+        if let Some(throttle) = throttle.as_mut() {
+            tokio::time::sleep(throttle.delay(size)).await;
+        }
+    }
+    Ok(())
+}
+
+// 定时同步：到期且不在免打扰时段、不在计费网络时执行，失败原因记录在同步计划中
+pub fn start_scheduler(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+            if !SyncSchedule::load().is_ok_and(|schedule| schedule.is_due()) {
+                continue;
+            }
+            match sync_now(&app, false).await {
+                Ok(_) => {}
+                // 被免打扰时段或计费网络推迟时不记录为失败，下次检查时重试
+                Err(e) if e.starts_with("Sync skipped") => {}
+                Err(e) => eprintln!("Scheduled sync failed: {}", e),
+            }
+        }
+    });
+}
+
+// 当前网络是否按流量计费；无法判断时视为不计费
+#[cfg(target_os = "windows")]
+pub fn is_metered_connection() -> bool {
+    use std::os::windows::process::CommandExt;
+    // 不为 powershell.exe 弹出控制台窗口
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    // NetworkCostType 为 Unrestricted 以外的值（Fixed、Variable）表示计费网络
+    let script = "[Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime] | Out-Null; \
+                  [Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile().GetConnectionCost().NetworkCostType";
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .is_ok_and(|output| {
+            let cost = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            matches!(cost.as_str(), "Fixed" | "Variable")
+        })
+}
+
+// macOS 没有可供命令行查询的计费网络设置
+#[cfg(target_os = "macos")]
+pub fn is_metered_connection() -> bool {
+    false
+}
+
+// NetworkManager 对各设备连接的判断，值为 yes 或 yes (guessed) 时视为计费网络
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn is_metered_connection() -> bool {
+    Command::new("nmcli")
+        .args(["-t", "-f", "GENERAL.METERED", "device", "show"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .any(|(_, metered)| metered.trim().starts_with("yes"))
+        })
+}
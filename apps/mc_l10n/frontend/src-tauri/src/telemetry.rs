@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::AppConfig;
+
+// 两次上报之间的最短间隔
+const REPORT_INTERVAL_HOURS: i64 = 24;
+
+// 后台检查是否需要上报的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+// 统计文件的读改写在多个扫描线程之间串行
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+// 本地累计的匿名统计，只在用户开启后记录，保存在数据目录下的 telemetry.json。
+// 不记录路径、MOD 名称、语言内容或任何标识信息，只记录分桶后的计数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelemetryStats {
+    pub period_started_at: Option<String>,
+    #[serde(default)]
+    pub scan_duration_buckets: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub pack_size_buckets: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub feature_usage: BTreeMap<String, u32>,
+}
+
+// 实际上报的内容，preview_telemetry 返回的就是这份数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryReport {
+    pub app_version: String,
+    pub os: String,
+    pub period_started_at: Option<String>,
+    pub scan_duration_buckets: BTreeMap<String, u32>,
+    pub pack_size_buckets: BTreeMap<String, u32>,
+    pub feature_usage: BTreeMap<String, u32>,
+}
+
+impl TelemetryStats {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let stats_path = Self::get_stats_path()?;
+
+        if stats_path.exists() {
+            let content = fs::read_to_string(&stats_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let stats_path = Self::get_stats_path()?;
+
+        if let Some(parent) = stats_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&stats_path, content)?;
+        Ok(())
+    }
+
+    // 删除本地累计的全部统计
+    pub fn clear() -> Result<(), Box<dyn std::error::Error>> {
+        let stats_path = Self::get_stats_path()?;
+        if stats_path.exists() {
+            fs::remove_file(stats_path)?;
+        }
+        Ok(())
+    }
+
+    fn get_stats_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("telemetry.json"))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.scan_duration_buckets.is_empty() && self.pack_size_buckets.is_empty() && self.feature_usage.is_empty()
+    }
+
+    pub fn report(&self) -> TelemetryReport {
+        TelemetryReport {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            period_started_at: self.period_started_at.clone(),
+            scan_duration_buckets: self.scan_duration_buckets.clone(),
+            pack_size_buckets: self.pack_size_buckets.clone(),
+            feature_usage: self.feature_usage.clone(),
+        }
+    }
+}
+
+// 记录一次完成的扫描（耗时和 MOD 数量都只记录所在区间）
+pub fn record_scan(duration_ms: u64, total_mods: u32) {
+    update(|stats| {
+        *stats.scan_duration_buckets.entry(duration_bucket(duration_ms).to_string()).or_default() += 1;
+        *stats.pack_size_buckets.entry(pack_size_bucket(total_mods).to_string()).or_default() += 1;
+    });
+}
+
+// 记录一次功能使用，name 为固定的功能名
+pub fn record_feature(name: &str) {
+    update(|stats| *stats.feature_usage.entry(name.to_string()).or_default() += 1);
+}
+
+// 未开启统计时什么都不写
+fn update(apply: impl FnOnce(&mut TelemetryStats)) {
+    if !AppConfig::load().is_ok_and(|config| config.telemetry_enabled) {
+        return;
+    }
+
+    let _lock = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut stats = TelemetryStats::load().unwrap_or_default();
+    if stats.period_started_at.is_none() {
+        stats.period_started_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    apply(&mut stats);
+    if let Err(e) = stats.save() {
+        eprintln!("Failed to save usage statistics: {}", e);
+    }
+}
+
+fn duration_bucket(duration_ms: u64) -> &'static str {
+    match duration_ms {
+        0..=9_999 => "<10s",
+        10_000..=29_999 => "10-30s",
+        30_000..=59_999 => "30-60s",
+        60_000..=299_999 => "1-5m",
+        _ => ">5m",
+    }
+}
+
+fn pack_size_bucket(total_mods: u32) -> &'static str {
+    match total_mods {
+        0 => "0",
+        1..=50 => "1-50",
+        51..=150 => "51-150",
+        151..=300 => "151-300",
+        _ => ">300",
+    }
+}
+
+// 开启统计且配置了上报地址时，每天最多上报一次，成功后清空本地统计
+pub async fn send_if_due() -> Result<bool, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let Some(endpoint) = config.telemetry_endpoint.filter(|_| config.telemetry_enabled) else {
+        return Ok(false);
+    };
+
+    let stats = TelemetryStats::load().map_err(|e| e.to_string())?;
+    let started = stats
+        .period_started_at
+        .as_deref()
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok());
+    let due = started.is_some_and(|started| {
+        chrono::Utc::now().signed_duration_since(started) >= chrono::Duration::hours(REPORT_INTERVAL_HOURS)
+    });
+    if stats.is_empty() || !due {
+        return Ok(false);
+    }
+
+    let response = reqwest::Client::new()
+        .post(&endpoint)
+        .json(&stats.report())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send usage statistics: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", endpoint, response.status()));
+    }
+
+    let _lock = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    TelemetryStats::clear().map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+pub fn start_reporter() {
+    tauri::async_runtime::spawn(async {
+        loop {
+            if let Err(e) = send_if_due().await {
+                eprintln!("{}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Theme};
+
+use crate::config::AppConfig;
+use crate::events::{self, AppEvent};
+
+// AppConfig.theme 的可选值；system 表示跟随系统
+pub const THEME_LIGHT: &str = "light";
+pub const THEME_DARK: &str = "dark";
+pub const THEME_SYSTEM: &str = "system";
+
+// 最近一次发送的主题状态，状态不变时不重复发送事件
+static LAST_STATE: Mutex<Option<ThemeState>> = Mutex::new(None);
+
+// 主题事件 "theme-changed" 的内容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemeState {
+    // 配置中的值：light、dark 或 system
+    pub theme: String,
+    // 实际使用的主题：light 或 dark
+    pub effective: String,
+    pub system_theme: String,
+    pub high_contrast: bool,
+}
+
+pub fn is_valid_theme(theme: &str) -> bool {
+    matches!(theme, THEME_LIGHT | THEME_DARK | THEME_SYSTEM)
+}
+
+// 读取系统主题和高对比度设置，并按配置计算实际主题；system_theme 为窗口上报的主题，未提供时从主窗口读取
+pub fn current_state(app: &AppHandle, system_theme: Option<Theme>) -> ThemeState {
+    let system_theme = system_theme
+        .or_else(|| app.get_webview_window("main").and_then(|window| window.theme().ok()))
+        .map_or(THEME_LIGHT, |theme| if theme == Theme::Dark { THEME_DARK } else { THEME_LIGHT });
+    let theme = AppConfig::load()
+        .map(|config| config.theme)
+        .ok()
+        .filter(|theme| is_valid_theme(theme))
+        .unwrap_or_else(|| THEME_LIGHT.to_string());
+    let effective = if theme == THEME_SYSTEM { system_theme } else { theme.as_str() }.to_string();
+
+    ThemeState {
+        effective,
+        theme,
+        system_theme: system_theme.to_string(),
+        high_contrast: high_contrast_enabled(),
+    }
+}
+
+// 在启动、系统主题变化、窗口重新获得焦点和修改配置后调用；状态变化时发送主题事件
+pub fn refresh(app: &AppHandle, system_theme: Option<Theme>) -> ThemeState {
+    let state = current_state(app, system_theme);
+    let mut last = LAST_STATE.lock().unwrap_or_else(|e| e.into_inner());
+    if last.as_ref() != Some(&state) {
+        events::emit(app, AppEvent::ThemeChanged(&state));
+        *last = Some(state.clone());
+    }
+    state
+}
+
+// 系统的高对比度设置；无法读取时视为关闭
+#[cfg(target_os = "windows")]
+fn high_contrast_enabled() -> bool {
+    use std::os::windows::process::CommandExt;
+    // 不为 reg.exe 弹出控制台窗口
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    // HighContrast 的 Flags 第 0 位为 HCF_HIGHCONTRASTON
+    Command::new("reg")
+        .args(["query", r"HKCU\Control Panel\Accessibility\HighContrast", "/v", "Flags"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()
+        .and_then(|output| {
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let flags = stdout.split_whitespace().last()?.to_string();
+            flags.parse::<u32>().ok()
+        })
+        .is_some_and(|flags| flags & 1 == 1)
+}
+
+#[cfg(target_os = "macos")]
+fn high_contrast_enabled() -> bool {
+    Command::new("defaults")
+        .args(["read", "com.apple.universalaccess", "increaseContrast"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn high_contrast_enabled() -> bool {
+    Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.a11y.interface", "high-contrast"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
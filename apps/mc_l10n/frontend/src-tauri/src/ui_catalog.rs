@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::AppConfig;
+use crate::locale::same_locale;
+
+// 缺少的键回退到该语言，同时作为校验的基准
+pub const REFERENCE_LOCALE: &str = "en";
+
+// 随应用发布的界面文本：(语言, i18next 命名空间, 相对前端 src 目录的路径, 内容)
+const BUILTIN_CATALOGS: &[(&str, &str, &str, &str)] = &[
+    ("en", "mcStudio", "locales/en/mc-studio.json", include_str!("../../src/locales/en/mc-studio.json")),
+    ("zh-CN", "mcStudio", "locales/zh-CN/mc-studio.json", include_str!("../../src/locales/zh-CN/mc-studio.json")),
+    ("en", "minecraft", "locales/minecraft/en.json", include_str!("../../src/locales/minecraft/en.json")),
+    ("zh-CN", "minecraft", "locales/minecraft/zh-CN.json", include_str!("../../src/locales/minecraft/zh-CN.json")),
+];
+
+// 某个语言的全部界面文本及其与基准语言的差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiCatalog {
+    pub locale: String,
+    // 命名空间 -> 嵌套的 i18next 资源
+    pub namespaces: BTreeMap<String, Value>,
+    // 以 命名空间:a.b.c 表示的键
+    pub missing_keys: Vec<String>,
+    pub extra_keys: Vec<String>,
+    // {{占位符}} 与基准语言不一致的键
+    pub placeholder_mismatches: Vec<String>,
+    // 无法解析或包含非字符串值的文件
+    pub errors: Vec<String>,
+}
+
+// 用户提供的界面翻译放在 data_dir/ui_locales/<语言>/<命名空间>.json，可以新增语言或覆盖内置文本
+fn override_dir(config: &AppConfig) -> PathBuf {
+    config.get_data_dir().join("ui_locales")
+}
+
+// 开发模式下直接读取前端源码中的文件，修改后无需重新编译
+#[cfg(debug_assertions)]
+fn builtin_source(relative_path: &str, embedded: &'static str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../src").join(relative_path);
+    fs::read_to_string(path).unwrap_or_else(|_| embedded.to_string())
+}
+
+#[cfg(not(debug_assertions))]
+fn builtin_source(_relative_path: &str, embedded: &'static str) -> String {
+    embedded.to_string()
+}
+
+// 开发模式下监视的内置文件
+#[cfg(debug_assertions)]
+pub fn builtin_source_paths() -> Vec<PathBuf> {
+    BUILTIN_CATALOGS
+        .iter()
+        .map(|(_, _, relative_path, _)| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../src").join(relative_path))
+        .collect()
+}
+
+// 内置和用户提供的语言
+pub fn list_locales(config: &AppConfig) -> Vec<String> {
+    let mut locales: BTreeSet<String> = BUILTIN_CATALOGS.iter().map(|(locale, ..)| locale.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(override_dir(config)) {
+        locales.extend(
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string()),
+        );
+    }
+    locales.into_iter().collect()
+}
+
+// 读取语言的各命名空间；语言代码按规范形式匹配（zh_cn 与 zh-CN 相同）
+fn load_namespaces(config: &AppConfig, locale: &str, errors: &mut Vec<String>) -> BTreeMap<String, Value> {
+    let mut namespaces = BTreeMap::new();
+
+    for (builtin_locale, namespace, relative_path, embedded) in BUILTIN_CATALOGS {
+        if !same_locale(builtin_locale, locale) {
+            continue;
+        }
+        match serde_json::from_str(&builtin_source(relative_path, embedded)) {
+            Ok(value) => {
+                namespaces.insert(namespace.to_string(), value);
+            }
+            Err(e) => errors.push(format!("{}: {}", relative_path, e)),
+        }
+    }
+
+    let Ok(locale_dirs) = fs::read_dir(override_dir(config)) else {
+        return namespaces;
+    };
+    for locale_dir in locale_dirs.flatten() {
+        if !same_locale(&locale_dir.file_name().to_string_lossy(), locale) {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(locale_dir.path()) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if !path.extension().is_some_and(|ext| ext == "json") {
+                continue;
+            }
+            let Some(namespace) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let parsed = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str::<Value>(&content).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(value) => merge(namespaces.entry(namespace).or_insert_with(|| Value::Object(Map::new())), value),
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    namespaces
+}
+
+// 返回语言的界面文本，并与基准语言比对缺失、多余的键和占位符
+pub fn get_catalog(config: &AppConfig, locale: &str) -> Result<UiCatalog, String> {
+    let mut errors = Vec::new();
+    let namespaces = load_namespaces(config, locale, &mut errors);
+    if namespaces.is_empty() && errors.is_empty() {
+        return Err(format!("No UI catalog for locale {}", locale));
+    }
+    let reference = load_namespaces(config, REFERENCE_LOCALE, &mut Vec::new());
+
+    let flatten_all = |namespaces: &BTreeMap<String, Value>, errors: &mut Vec<String>| {
+        let mut flat = BTreeMap::new();
+        for (namespace, value) in namespaces {
+            flatten(&format!("{}:", namespace), value, &mut flat, errors);
+        }
+        flat
+    };
+    let strings = flatten_all(&namespaces, &mut errors);
+    let reference_strings = flatten_all(&reference, &mut Vec::new());
+
+    let missing_keys = reference_strings.keys().filter(|key| !strings.contains_key(*key)).cloned().collect();
+    let extra_keys = strings.keys().filter(|key| !reference_strings.contains_key(*key)).cloned().collect();
+    let placeholder_mismatches = strings
+        .iter()
+        .filter(|(key, value)| {
+            reference_strings
+                .get(*key)
+                .is_some_and(|reference| placeholders(reference) != placeholders(value))
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    Ok(UiCatalog {
+        locale: locale.to_string(),
+        namespaces,
+        missing_keys,
+        extra_keys,
+        placeholder_mismatches,
+        errors,
+    })
+}
+
+// 用户文件中的对象与内置文本逐层合并，同名字符串以用户文件为准
+fn merge(target: &mut Value, source: Value) {
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, source) => *target = source,
+    }
+}
+
+// 把嵌套对象展开为 a.b.c 形式的键；非字符串的叶子记为错误
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, String>, errors: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object {
+                let separator = if prefix.ends_with(':') { "" } else { "." };
+                flatten(&format!("{}{}{}", prefix, separator, key), child, out, errors);
+            }
+        }
+        Value::String(text) => {
+            out.insert(prefix.to_string(), text.clone());
+        }
+        _ => errors.push(format!("{} is not a string", prefix)),
+    }
+}
+
+// i18next 的 {{name}} 占位符
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    let mut found = BTreeSet::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        found.insert(rest[start + 2..start + 2 + end].trim());
+        rest = &rest[start + 2 + end + 2..];
+    }
+    found
+}
+
+// 开发模式下界面文本文件变化事件 "ui-catalog-changed" 的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiCatalogChanged {
+    pub paths: Vec<String>,
+}
+
+// 开发模式下轮询内置和用户提供的界面文本文件，变化后通知前端重新调用 get_ui_catalog
+#[cfg(debug_assertions)]
+pub fn start_dev_watcher(app: tauri::AppHandle) {
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
+
+    let watched_files = move || -> HashMap<PathBuf, Option<SystemTime>> {
+        let mut paths = builtin_source_paths();
+        if let Ok(config) = AppConfig::load() {
+            if let Ok(locale_dirs) = fs::read_dir(override_dir(&config)) {
+                for locale_dir in locale_dirs.flatten() {
+                    if let Ok(files) = fs::read_dir(locale_dir.path()) {
+                        paths.extend(files.flatten().map(|file| file.path()));
+                    }
+                }
+            }
+        }
+        paths
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    };
+
+    std::thread::spawn(move || {
+        let mut known = watched_files();
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            let current = watched_files();
+            let mut changed: Vec<String> = current
+                .iter()
+                .filter(|(path, modified)| known.get(*path) != Some(*modified))
+                .chain(known.iter().filter(|(path, _)| !current.contains_key(*path)))
+                .map(|(path, _)| path.to_string_lossy().to_string())
+                .collect();
+            if !changed.is_empty() {
+                changed.sort();
+                crate::events::emit(&app, crate::events::AppEvent::UiCatalogChanged(&UiCatalogChanged { paths: changed }));
+            }
+            known = current;
+        }
+    });
+}
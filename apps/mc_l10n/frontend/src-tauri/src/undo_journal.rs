@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::config::AppConfig;
+
+// 最多保留的可撤销操作数，更早的操作连同备份一起删除
+const MAX_OPERATIONS: usize = 20;
+
+// 日志的读改写在多个命令之间串行
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Delete,
+    Overwrite,
+    Patch,
+}
+
+// 一次破坏性操作；撤销时按备份恢复 paths 中的每一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalOperation {
+    pub id: String,
+    pub kind: OperationKind,
+    pub description: String,
+    pub paths: Vec<JournaledPath>,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaledPath {
+    pub path: String,
+    // 操作前路径不存在时为 None，撤销时删除操作产生的文件
+    pub backup: Option<String>,
+}
+
+// 操作日志，保存在数据目录下的 undo/journal.json，备份在 undo/<操作 id>/ 中
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UndoJournal {
+    pub operations: Vec<JournalOperation>,
+}
+
+impl UndoJournal {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let journal_path = Self::get_journal_path()?;
+
+        if journal_path.exists() {
+            let content = fs::read_to_string(&journal_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let journal_path = Self::get_journal_path()?;
+
+        if let Some(parent) = journal_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&journal_path, content)?;
+        Ok(())
+    }
+
+    fn get_journal_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(undo_dir()?.join("journal.json"))
+    }
+}
+
+fn undo_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let config = AppConfig::load()?;
+    Ok(config.get_data_dir().join("undo"))
+}
+
+// 在删除、覆盖或修改之前调用：备份 paths 的当前内容并写入日志
+pub fn record(kind: OperationKind, description: &str, paths: &[&Path]) -> Result<(), String> {
+    let _lock = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut journal = UndoJournal::load().map_err(|e| e.to_string())?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let backup_dir = undo_dir().map_err(|e| e.to_string())?.join(&id);
+    let mut journaled = Vec::new();
+    for (index, path) in paths.iter().enumerate() {
+        let backup = if path.exists() {
+            let backup_path = backup_dir.join(index.to_string());
+            if let Err(e) = copy_recursive(path, &backup_path) {
+                let _ = fs::remove_dir_all(&backup_dir);
+                return Err(format!("Failed to back up {}: {}", path.display(), e));
+            }
+            Some(backup_path.to_string_lossy().to_string())
+        } else {
+            None
+        };
+        journaled.push(JournaledPath {
+            path: path.to_string_lossy().to_string(),
+            backup,
+        });
+    }
+
+    journal.operations.push(JournalOperation {
+        id,
+        kind,
+        description: description.to_string(),
+        paths: journaled,
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+    });
+
+    if journal.operations.len() > MAX_OPERATIONS {
+        let expired = journal.operations.len() - MAX_OPERATIONS;
+        for operation in journal.operations.drain(..expired) {
+            remove_backups(&operation);
+        }
+    }
+
+    journal.save().map_err(|e| e.to_string())
+}
+
+// 可撤销的操作，最近的在前
+pub fn list_operations() -> Result<Vec<JournalOperation>, String> {
+    let journal = UndoJournal::load().map_err(|e| e.to_string())?;
+    Ok(journal.operations.into_iter().rev().collect())
+}
+
+// 撤销最近一次操作：删除当前内容，再从备份恢复；没有可撤销的操作时返回 None
+pub fn undo_last() -> Result<Option<JournalOperation>, String> {
+    let _lock = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut journal = UndoJournal::load().map_err(|e| e.to_string())?;
+    let Some(operation) = journal.operations.last().cloned() else {
+        return Ok(None);
+    };
+
+    for item in &operation.paths {
+        let path = Path::new(&item.path);
+        remove_path(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        if let Some(backup) = &item.backup {
+            copy_recursive(Path::new(backup), path).map_err(|e| format!("Failed to restore {}: {}", path.display(), e))?;
+        }
+    }
+
+    // 全部恢复成功后才从日志中移除，失败时可以重试
+    journal.operations.pop();
+    journal.save().map_err(|e| e.to_string())?;
+    remove_backups(&operation);
+    Ok(Some(operation))
+}
+
+fn remove_backups(operation: &JournalOperation) {
+    if let Ok(dir) = undo_dir() {
+        let _ = fs::remove_dir_all(dir.join(&operation.id));
+    }
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn copy_recursive(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &target.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
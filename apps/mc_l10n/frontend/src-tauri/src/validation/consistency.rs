@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::glossary::Glossary;
+use crate::lang;
+use crate::undo_journal::{self, OperationKind};
+
+// 同一原文的一种译法及使用它的键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationVariant {
+    pub translation: String,
+    pub keys: Vec<String>,
+}
+
+// 同一原文在多个命名空间中出现不一致译法
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsistencyGroup {
+    pub source: String,
+    pub namespaces: Vec<String>,
+    pub variants: Vec<TranslationVariant>,
+    pub suggested_translation: String,
+    pub glossary_translation: Option<String>,
+}
+
+// 跨模组一致性检查报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    pub source_locale: String,
+    pub target_locale: String,
+    pub checked_sources: u32,
+    pub groups: Vec<ConsistencyGroup>,
+}
+
+// 统一译法的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarmonizeResult {
+    pub source: String,
+    pub translation: String,
+    pub updated_keys: u32,
+    pub updated_files: Vec<String>,
+}
+
+// 查找同一英文原文在不同命名空间中被译成不同文本的情况
+pub fn check_consistency(
+    pack_path: &Path,
+    source_locale: &str,
+    target_locale: &str,
+) -> Result<ConsistencyReport, String> {
+    let sources = lang::load_locale_entries(pack_path, source_locale);
+    let targets = lang::load_locale_entries(pack_path, target_locale);
+
+    if targets.is_empty() {
+        return Err(format!("No {} language files found in {}", target_locale, pack_path.display()));
+    }
+
+    let glossary = Glossary::load().unwrap_or_default();
+
+    // 原文 -> 译文 -> 键列表
+    let mut by_source: BTreeMap<String, BTreeMap<String, Vec<(String, String)>>> = BTreeMap::new();
+
+    for (namespace, target_entries) in &targets {
+        let Some(source_entries) = sources.get(namespace) else {
+            continue;
+        };
+
+        for (key, translation) in target_entries {
+            let Some(source) = source_entries.get(key) else {
+                continue;
+            };
+
+            let source = source.trim();
+            let translation = translation.trim();
+
+            // 未翻译的条目不参与比较
+            if source.is_empty() || translation.is_empty() || translation == source {
+                continue;
+            }
+
+            by_source
+                .entry(source.to_string())
+                .or_default()
+                .entry(translation.to_string())
+                .or_default()
+                .push((namespace.clone(), key.clone()));
+        }
+    }
+
+    let checked_sources = by_source.len() as u32;
+    let mut groups = Vec::new();
+
+    for (source, translations) in by_source {
+        if translations.len() < 2 {
+            continue;
+        }
+
+        let namespaces: BTreeSet<String> = translations
+            .values()
+            .flatten()
+            .map(|(namespace, _)| namespace.clone())
+            .collect();
+
+        if namespaces.len() < 2 {
+            continue;
+        }
+
+        let mut variants: Vec<TranslationVariant> = translations
+            .into_iter()
+            .map(|(translation, mut keys)| {
+                keys.sort();
+                TranslationVariant {
+                    translation,
+                    keys: keys.into_iter().map(|(ns, key)| format!("{}:{}", ns, key)).collect(),
+                }
+            })
+            .collect();
+
+        variants.sort_by(|a, b| b.keys.len().cmp(&a.keys.len()).then(a.translation.cmp(&b.translation)));
+
+        let glossary_translation = glossary
+            .entries
+            .iter()
+            .find(|e| e.source == source && e.locale == target_locale)
+            .map(|e| e.target.clone());
+
+        let suggested_translation = glossary_translation
+            .clone()
+            .unwrap_or_else(|| variants[0].translation.clone());
+
+        groups.push(ConsistencyGroup {
+            source,
+            namespaces: namespaces.into_iter().collect(),
+            variants,
+            suggested_translation,
+            glossary_translation,
+        });
+    }
+
+    Ok(ConsistencyReport {
+        source_locale: source_locale.to_string(),
+        target_locale: target_locale.to_string(),
+        checked_sources,
+        groups,
+    })
+}
+
+// 将一组键统一为同一译法，并把结果写入术语表
+pub fn harmonize_translations(
+    pack_path: &Path,
+    target_locale: &str,
+    source: &str,
+    translation: &str,
+    keys: &[String],
+) -> Result<HarmonizeResult, String> {
+    let updates_by_namespace = updates_by_namespace(translation, keys)?;
+    let lang_files: HashMap<String, _> = lang::find_locale_files(pack_path, target_locale).into_iter().collect();
+    let touched: Vec<&Path> = updates_by_namespace
+        .keys()
+        .filter_map(|namespace| lang_files.get(*namespace).map(|path| path.as_path()))
+        .collect();
+    undo_journal::record(OperationKind::Patch, &format!("Harmonize {} keys to \"{}\"", keys.len(), translation), &touched)?;
+
+    let mut updated_keys = 0;
+    let mut updated_files = Vec::new();
+
+    for (namespace, updates) in &updates_by_namespace {
+        let lang_path = lang_files
+            .get(*namespace)
+            .ok_or_else(|| format!("No {} language file for namespace {}", target_locale, namespace))?;
+
+        let updated = lang::update_lang_entries(lang_path, updates)?;
+        if updated > 0 {
+            updated_keys += updated;
+            updated_files.push(lang_path.to_string_lossy().to_string());
+        }
+    }
+
+    let mut glossary = Glossary::load().map_err(|e| e.to_string())?;
+    glossary.upsert(source, translation, target_locale, Some("harmonized by consistency check".to_string()));
+    glossary.save().map_err(|e| e.to_string())?;
+
+    updated_files.sort();
+
+    Ok(HarmonizeResult {
+        source: source.to_string(),
+        translation: translation.to_string(),
+        updated_keys,
+        updated_files,
+    })
+}
+
+// 试运行：返回统一译法时实际会修改的语言文件及修改后的大小
+pub fn plan_harmonize(
+    pack_path: &Path,
+    target_locale: &str,
+    translation: &str,
+    keys: &[String],
+) -> Result<Vec<(PathBuf, u64)>, String> {
+    let lang_files: HashMap<String, _> = lang::find_locale_files(pack_path, target_locale).into_iter().collect();
+    let mut planned = Vec::new();
+
+    for (namespace, updates) in &updates_by_namespace(translation, keys)? {
+        let lang_path = lang_files
+            .get(*namespace)
+            .ok_or_else(|| format!("No {} language file for namespace {}", target_locale, namespace))?;
+        let (content, updated) = lang::updated_lang_content(lang_path, updates)?;
+        if updated > 0 {
+            planned.push((lang_path.clone(), content.len() as u64));
+        }
+    }
+
+    planned.sort();
+    Ok(planned)
+}
+
+// 按命名空间分组 namespace:key 形式的键
+fn updates_by_namespace<'a>(
+    translation: &str,
+    keys: &'a [String],
+) -> Result<HashMap<&'a str, HashMap<String, String>>, String> {
+    let mut updates_by_namespace: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    for qualified_key in keys {
+        let (namespace, key) = qualified_key
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid key '{}', expected namespace:key", qualified_key))?;
+        updates_by_namespace
+            .entry(namespace)
+            .or_default()
+            .insert(key.to_string(), translation.to_string());
+    }
+    Ok(updates_by_namespace)
+}
@@ -0,0 +1,569 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::lang;
+
+// 每个缺失字形最多记录的示例键数量
+const MAX_SAMPLE_KEYS: usize = 5;
+
+// 默认字体之外无法显示的字符
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingGlyph {
+    pub codepoint: String,
+    pub character: String,
+    pub occurrences: u32,
+    pub sample_keys: Vec<String>,
+}
+
+// 字形覆盖检查报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlyphCoverageReport {
+    pub locale: String,
+    pub checked_characters: u32,
+    pub covered_by_pack: u32,
+    pub font_providers: Vec<String>,
+    pub unverified_providers: Vec<String>,
+    pub missing_glyphs: Vec<MissingGlyph>,
+}
+
+// 资源包 minecraft:default 字体中声明的字形来源
+#[derive(Default)]
+struct PackFontCoverage {
+    chars: HashSet<char>,
+    ttf_fonts: Vec<TtfFont>,
+    providers: Vec<String>,
+    unverified: Vec<String>,
+}
+
+impl PackFontCoverage {
+    fn contains(&self, c: char) -> bool {
+        if self.chars.contains(&c) {
+            return true;
+        }
+
+        self.ttf_fonts
+            .iter()
+            .any(|font| !font.skip.contains(&c) && font.cmap.has_glyph(&font.data, c as u32))
+    }
+}
+
+struct TtfFont {
+    data: Vec<u8>,
+    cmap: CmapTable,
+    skip: HashSet<char>,
+}
+
+pub fn is_cjk_locale(locale: &str) -> bool {
+    let language = locale.split(['_', '-']).next().unwrap_or("").to_lowercase();
+    matches!(language.as_str(), "zh" | "ja" | "ko" | "lzh")
+}
+
+// 检查目标语言译文中使用的字符是否被默认字体或资源包字体覆盖
+pub fn check_glyph_coverage(pack_path: &Path, locale: &str) -> Result<GlyphCoverageReport, String> {
+    if !is_cjk_locale(locale) {
+        return Err(format!("Glyph coverage check only applies to zh/ja/ko locales, got {}", locale));
+    }
+
+    let translations = lang::load_locale_entries(pack_path, locale);
+    if translations.is_empty() {
+        return Err(format!("No {} language files found in {}", locale, pack_path.display()));
+    }
+
+    let pack_fonts = load_pack_font_coverage(pack_path);
+
+    // 统计每个字符的出现次数与示例键
+    let mut usage: BTreeMap<char, (u32, Vec<String>)> = BTreeMap::new();
+    for (namespace, entries) in &translations {
+        for (key, value) in entries {
+            for c in visible_chars(value) {
+                let (count, samples) = usage.entry(c).or_insert_with(|| (0, Vec::new()));
+                *count += 1;
+                if samples.len() < MAX_SAMPLE_KEYS {
+                    samples.push(format!("{}:{}", namespace, key));
+                }
+            }
+        }
+    }
+
+    let checked_characters = usage.len() as u32;
+    let mut covered_by_pack = 0;
+    let mut missing_glyphs = Vec::new();
+
+    for (c, (occurrences, mut sample_keys)) in usage {
+        if pack_fonts.contains(c) {
+            if !is_covered_by_default_font(c) {
+                covered_by_pack += 1;
+            }
+            continue;
+        }
+
+        if is_covered_by_default_font(c) {
+            continue;
+        }
+
+        sample_keys.sort();
+        missing_glyphs.push(MissingGlyph {
+            codepoint: format!("U+{:04X}", c as u32),
+            character: c.to_string(),
+            occurrences,
+            sample_keys,
+        });
+    }
+
+    missing_glyphs.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then(a.codepoint.cmp(&b.codepoint)));
+
+    Ok(GlyphCoverageReport {
+        locale: locale.to_string(),
+        checked_characters,
+        covered_by_pack,
+        font_providers: pack_fonts.providers,
+        unverified_providers: pack_fonts.unverified,
+        missing_glyphs,
+    })
+}
+
+// 过滤掉 § 格式代码与控制字符后的可见字符
+fn visible_chars(value: &str) -> Vec<char> {
+    let mut chars = Vec::new();
+    let mut iter = value.chars();
+
+    while let Some(c) = iter.next() {
+        if c == '§' {
+            iter.next();
+            continue;
+        }
+        if c.is_control() {
+            continue;
+        }
+        chars.push(c);
+    }
+
+    chars
+}
+
+// 原版默认字体（ascii、accented、nonlatin_european 位图及回退的 Unifont）包含字形的区段。
+// 私用区、特殊区、未列出的区段和辅助平面字符（如 CJK 扩展 B 区）会显示为方块
+const DEFAULT_FONT_RANGES: &[(u32, u32)] = &[
+    // 拉丁、希腊、西里尔字母及组合附加符号
+    (0x0020, 0x007E),
+    (0x00A0, 0x052F),
+    // 亚美尼亚、希伯来、阿拉伯、印度诸文字、泰文、格鲁吉亚文
+    (0x0530, 0x10FF),
+    // 谚文字母
+    (0x1100, 0x11FF),
+    // 拉丁扩展附加、希腊扩展
+    (0x1E00, 0x1FFF),
+    // 标点、上下标、货币、字母式符号、数字形式、箭头、数学符号、技术符号、框线与几何图形、杂项符号
+    (0x2000, 0x2BFF),
+    // CJK 部首、康熙部首、汉字结构描述符
+    (0x2E80, 0x2FDF),
+    (0x2FF0, 0x2FFF),
+    // CJK 符号和标点、平假名、片假名、注音、谚文兼容字母、CJK 笔画、带圈字符、CJK 兼容字符
+    (0x3000, 0x33FF),
+    // CJK 扩展 A 区、易经卦符、CJK 统一汉字
+    (0x3400, 0x9FFF),
+    // 彝文
+    (0xA000, 0xA4CF),
+    // 谚文音节
+    (0xAC00, 0xD7A3),
+    // CJK 兼容汉字
+    (0xF900, 0xFAFF),
+    // 竖排标点、CJK 兼容形式、小写变体
+    (0xFE10, 0xFE1F),
+    (0xFE30, 0xFE6F),
+    // 半角及全角字符
+    (0xFF00, 0xFFEF),
+];
+
+fn is_covered_by_default_font(c: char) -> bool {
+    let code = c as u32;
+    DEFAULT_FONT_RANGES.iter().any(|&(start, end)| (start..=end).contains(&code))
+}
+
+// 读取资源包中 minecraft:default 字体的提供者
+fn load_pack_font_coverage(pack_path: &Path) -> PackFontCoverage {
+    let mut coverage = PackFontCoverage::default();
+    let mut visited = HashSet::new();
+    collect_font_providers(pack_path, "minecraft:default", &mut coverage, &mut visited);
+    coverage
+}
+
+fn collect_font_providers(
+    pack_path: &Path,
+    font_id: &str,
+    coverage: &mut PackFontCoverage,
+    visited: &mut HashSet<String>,
+) {
+    if !visited.insert(font_id.to_string()) {
+        return;
+    }
+
+    let font_path = resolve_font_resource(pack_path, &format!("{}.json", font_id));
+    let Some(json) = fs::read_to_string(&font_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return;
+    };
+
+    let Some(providers) = json.get("providers").and_then(|p| p.as_array()) else {
+        return;
+    };
+
+    for provider in providers {
+        let provider_type = provider.get("type").and_then(|t| t.as_str()).unwrap_or("unknown");
+        let file = provider.get("file").and_then(|f| f.as_str()).unwrap_or("");
+
+        match provider_type {
+            "bitmap" => {
+                let rows = provider.get("chars").and_then(|c| c.as_array());
+                for row in rows.into_iter().flatten().filter_map(|r| r.as_str()) {
+                    coverage.chars.extend(row.chars().filter(|c| *c != '\0'));
+                }
+                coverage.providers.push(format!("bitmap {}", file));
+            }
+            "space" => {
+                if let Some(advances) = provider.get("advances").and_then(|a| a.as_object()) {
+                    coverage.chars.extend(advances.keys().filter_map(|k| k.chars().next()));
+                }
+                coverage.providers.push("space".to_string());
+            }
+            "ttf" => {
+                let ttf_path = resolve_font_resource(pack_path, file);
+                match fs::read(&ttf_path).ok().and_then(|data| CmapTable::parse(&data).map(|cmap| (data, cmap))) {
+                    Some((data, cmap)) => {
+                        let skip = match provider.get("skip") {
+                            Some(serde_json::Value::String(s)) => s.chars().collect(),
+                            Some(serde_json::Value::Array(rows)) => {
+                                rows.iter().filter_map(|r| r.as_str()).flat_map(str::chars).collect()
+                            }
+                            _ => HashSet::new(),
+                        };
+                        coverage.ttf_fonts.push(TtfFont { data, cmap, skip });
+                        coverage.providers.push(format!("ttf {}", file));
+                    }
+                    None => coverage.unverified.push(format!("ttf {} (unreadable font file)", file)),
+                }
+            }
+            "reference" => {
+                if let Some(id) = provider.get("id").and_then(|i| i.as_str()) {
+                    coverage.providers.push(format!("reference {}", id));
+                    collect_font_providers(pack_path, id, coverage, visited);
+                }
+            }
+            other => coverage.unverified.push(format!("{} {}", other, file).trim_end().to_string()),
+        }
+    }
+}
+
+// 将 "namespace:path" 解析为 assets/<namespace>/font/<path>
+fn resolve_font_resource(pack_path: &Path, location: &str) -> PathBuf {
+    let (namespace, path) = location.split_once(':').unwrap_or(("minecraft", location));
+    pack_path.join("assets").join(namespace).join("font").join(path)
+}
+
+// TrueType/OpenType cmap 子表（仅支持格式 4 和 12）
+struct CmapTable {
+    offset: usize,
+    format: u16,
+}
+
+impl CmapTable {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let num_tables = read_u16(data, 4)? as usize;
+        let cmap_offset = (0..num_tables)
+            .map(|i| 12 + i * 16)
+            .find(|record| data.get(*record..*record + 4) == Some(b"cmap".as_slice()))
+            .and_then(|record| read_u32(data, record + 8))? as usize;
+
+        let num_subtables = read_u16(data, cmap_offset + 2)? as usize;
+        let mut best: Option<Self> = None;
+
+        for i in 0..num_subtables {
+            let record = cmap_offset + 4 + i * 8;
+            let platform_id = read_u16(data, record)?;
+            let encoding_id = read_u16(data, record + 2)?;
+            let offset = cmap_offset + read_u32(data, record + 4)? as usize;
+            let format = read_u16(data, offset)?;
+
+            let unicode = matches!((platform_id, encoding_id), (0, _) | (3, 1) | (3, 10));
+            if !unicode || !matches!(format, 4 | 12) {
+                continue;
+            }
+
+            // 格式 12 覆盖辅助平面，优先使用
+            if best.as_ref().map_or(true, |b| b.format < format) {
+                best = Some(Self { offset, format });
+            }
+        }
+
+        best
+    }
+
+    fn has_glyph(&self, data: &[u8], code: u32) -> bool {
+        match self.format {
+            4 => self.format4_glyph(data, code).is_some_and(|glyph| glyph != 0),
+            12 => self.format12_glyph(data, code).is_some_and(|glyph| glyph != 0),
+            _ => false,
+        }
+    }
+
+    fn format4_glyph(&self, data: &[u8], code: u32) -> Option<u16> {
+        if code > 0xFFFF {
+            return None;
+        }
+
+        let seg_count = read_u16(data, self.offset + 6)? as usize / 2;
+        let end_codes = self.offset + 14;
+        let start_codes = end_codes + seg_count * 2 + 2;
+        let id_deltas = start_codes + seg_count * 2;
+        let id_range_offsets = id_deltas + seg_count * 2;
+
+        for segment in 0..seg_count {
+            let end = read_u16(data, end_codes + segment * 2)? as u32;
+            if code > end {
+                continue;
+            }
+
+            let start = read_u16(data, start_codes + segment * 2)? as u32;
+            if code < start {
+                return None;
+            }
+
+            let delta = read_u16(data, id_deltas + segment * 2)?;
+            let range_offset_pos = id_range_offsets + segment * 2;
+            let range_offset = read_u16(data, range_offset_pos)? as usize;
+
+            if range_offset == 0 {
+                return Some((code as u16).wrapping_add(delta));
+            }
+
+            let glyph_pos = range_offset_pos + range_offset + (code - start) as usize * 2;
+            let glyph = read_u16(data, glyph_pos)?;
+            return Some(if glyph == 0 { 0 } else { glyph.wrapping_add(delta) });
+        }
+
+        None
+    }
+
+    fn format12_glyph(&self, data: &[u8], code: u32) -> Option<u32> {
+        let num_groups = read_u32(data, self.offset + 12)? as usize;
+
+        for group in 0..num_groups {
+            let record = self.offset + 16 + group * 12;
+            let start = read_u32(data, record)?;
+            let end = read_u32(data, record + 4)?;
+            if (start..=end).contains(&code) {
+                // 起始字形号来自字体文件，溢出的映射视为未覆盖
+                return read_u32(data, record + 8)?.checked_add(code - start);
+            }
+        }
+
+        None
+    }
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    data.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    data.get(pos..pos + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_u16(out: &mut Vec<u8>, value: u16) {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn push_u32(out: &mut Vec<u8>, value: u32) {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+
+    // 只含 cmap 表的字体文件；subtables 为 (platform_id, encoding_id, 子表内容)
+    fn font(subtables: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        push_u32(&mut data, 0x0001_0000);
+        push_u16(&mut data, 1);
+        data.extend_from_slice(&[0; 6]);
+        data.extend_from_slice(b"cmap");
+        push_u32(&mut data, 0);
+        push_u32(&mut data, 28);
+        push_u32(&mut data, 0);
+
+        let mut cmap = Vec::new();
+        push_u16(&mut cmap, 0);
+        push_u16(&mut cmap, subtables.len() as u16);
+        let mut offset = 4 + subtables.len() * 8;
+        for (platform_id, encoding_id, subtable) in subtables {
+            push_u16(&mut cmap, *platform_id);
+            push_u16(&mut cmap, *encoding_id);
+            push_u32(&mut cmap, offset as u32);
+            offset += subtable.len();
+        }
+        for (_, _, subtable) in subtables {
+            cmap.extend_from_slice(subtable);
+        }
+        data.extend(cmap);
+        data
+    }
+
+    // segments 为 (起始码位, 结束码位, idDelta, 字形数组)；字形数组为空时按 idDelta 直接映射
+    fn format4(segments: &[(u16, u16, u16, &[u16])]) -> Vec<u8> {
+        let seg_count = segments.len();
+        let mut table = Vec::new();
+        push_u16(&mut table, 4);
+        push_u16(&mut table, 0);
+        push_u16(&mut table, 0);
+        push_u16(&mut table, (seg_count * 2) as u16);
+        table.extend_from_slice(&[0; 6]);
+        for (_, end, _, _) in segments {
+            push_u16(&mut table, *end);
+        }
+        push_u16(&mut table, 0);
+        for (start, _, _, _) in segments {
+            push_u16(&mut table, *start);
+        }
+        for (_, _, delta, _) in segments {
+            push_u16(&mut table, *delta);
+        }
+        let mut glyph_ids = Vec::new();
+        for (segment, (_, _, _, glyphs)) in segments.iter().enumerate() {
+            let range_offset = if glyphs.is_empty() { 0 } else { (seg_count - segment + glyph_ids.len()) * 2 };
+            push_u16(&mut table, range_offset as u16);
+            glyph_ids.extend_from_slice(glyphs);
+        }
+        for glyph in glyph_ids {
+            push_u16(&mut table, glyph);
+        }
+        table
+    }
+
+    // groups 为 (起始码位, 结束码位, 起始字形号)
+    fn format12(groups: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut table = Vec::new();
+        push_u16(&mut table, 12);
+        push_u16(&mut table, 0);
+        push_u32(&mut table, (16 + groups.len() * 12) as u32);
+        push_u32(&mut table, 0);
+        push_u32(&mut table, groups.len() as u32);
+        for (start, end, glyph) in groups {
+            push_u32(&mut table, *start);
+            push_u32(&mut table, *end);
+            push_u32(&mut table, *glyph);
+        }
+        table
+    }
+
+    fn sample_format4() -> Vec<u8> {
+        format4(&[
+            // 一、丁 映射到字形 1、2
+            (0x4E00, 0x4E01, 1u16.wrapping_sub(0x4E00), &[]),
+            // 中 映射到字形 5，丮 在字形数组中为 0（未覆盖）
+            (0x4E2D, 0x4E2E, 0, &[5, 0]),
+            (0xFFFF, 0xFFFF, 1, &[]),
+        ])
+    }
+
+    #[test]
+    fn format4_maps_delta_and_glyph_array_segments() {
+        let data = font(&[(3, 1, sample_format4())]);
+        let cmap = CmapTable::parse(&data).expect("format 4 subtable");
+        assert_eq!(cmap.format, 4);
+
+        assert!(cmap.has_glyph(&data, 0x4E00));
+        assert!(cmap.has_glyph(&data, 0x4E01));
+        assert!(cmap.has_glyph(&data, 0x4E2D));
+        assert!(!cmap.has_glyph(&data, 0x4E2E));
+        // 段之间的空隙、0xFFFF 结束段和辅助平面都不覆盖
+        assert!(!cmap.has_glyph(&data, 0x4E02));
+        assert!(!cmap.has_glyph(&data, 0xFFFF));
+        assert!(!cmap.has_glyph(&data, 0x20000));
+    }
+
+    #[test]
+    fn format12_covers_supplementary_plane() {
+        let data = font(&[(3, 10, format12(&[(0x4E00, 0x4E00, 3), (0x20000, 0x20001, 10), (0x30000, 0x30001, u32::MAX)]))]);
+        let cmap = CmapTable::parse(&data).expect("format 12 subtable");
+        assert_eq!(cmap.format, 12);
+
+        assert!(cmap.has_glyph(&data, 0x4E00));
+        assert!(cmap.has_glyph(&data, 0x20000));
+        assert!(cmap.has_glyph(&data, 0x20001));
+        assert!(!cmap.has_glyph(&data, 0x20002));
+        assert!(!cmap.has_glyph(&data, 0x4E01));
+        // 起始字形号溢出的映射视为未覆盖
+        assert!(cmap.has_glyph(&data, 0x30000));
+        assert!(!cmap.has_glyph(&data, 0x30001));
+    }
+
+    #[test]
+    fn parse_prefers_format12_and_skips_non_unicode_subtables() {
+        let data = font(&[(3, 1, sample_format4()), (3, 10, format12(&[(0x20000, 0x20000, 1)]))]);
+        let cmap = CmapTable::parse(&data).unwrap();
+        assert_eq!(cmap.format, 12);
+        assert!(cmap.has_glyph(&data, 0x20000));
+
+        // Macintosh 平台的子表和不支持的格式不使用
+        let data = font(&[(1, 0, sample_format4()), (3, 1, vec![0, 6, 0, 0])]);
+        assert!(CmapTable::parse(&data).is_none());
+    }
+
+    #[test]
+    fn truncated_or_malformed_fonts_do_not_panic() {
+        let data = font(&[(3, 1, sample_format4()), (0, 4, format12(&[(0x20000, 0x20001, 10)]))]);
+        let codes = [0x4E00, 0x4E2D, 0x4E2E, 0x20000, 0x20001];
+
+        for len in 0..data.len() {
+            let truncated = &data[..len];
+            if let Some(cmap) = CmapTable::parse(truncated) {
+                for code in codes {
+                    cmap.has_glyph(truncated, code);
+                }
+            }
+        }
+
+        // 完整解析后再读截断的数据
+        let cmap = CmapTable::parse(&data).unwrap();
+        for len in 0..data.len() {
+            for code in codes {
+                cmap.has_glyph(&data[..len], code);
+            }
+        }
+
+        // 表目录、子表偏移和段数、组数指向文件以外
+        let mut huge_tables = data.clone();
+        huge_tables[4..6].copy_from_slice(&u16::MAX.to_be_bytes());
+        huge_tables[12..16].copy_from_slice(b"glyf");
+        assert!(CmapTable::parse(&huge_tables).is_none());
+
+        let mut bad_offset = data.clone();
+        bad_offset[20..24].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(CmapTable::parse(&bad_offset).is_none());
+
+        let mut bad_subtable = data.clone();
+        bad_subtable[28 + 8..28 + 12].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(CmapTable::parse(&bad_subtable).is_none());
+
+        let format4_only = font(&[(3, 1, sample_format4())]);
+        let mut bad_segments = format4_only.clone();
+        bad_segments[28 + 12 + 6..28 + 12 + 8].copy_from_slice(&u16::MAX.to_be_bytes());
+        let cmap = CmapTable::parse(&bad_segments).unwrap();
+        for code in codes {
+            cmap.has_glyph(&bad_segments, code);
+        }
+
+        let format12_only = font(&[(3, 10, format12(&[(0x20000, 0x20001, 10)]))]);
+        let mut bad_groups = format12_only.clone();
+        bad_groups[28 + 12 + 12..28 + 12 + 16].copy_from_slice(&u32::MAX.to_be_bytes());
+        let cmap = CmapTable::parse(&bad_groups).unwrap();
+        for code in codes {
+            cmap.has_glyph(&bad_groups, code);
+        }
+    }
+}
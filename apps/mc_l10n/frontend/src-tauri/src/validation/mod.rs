@@ -0,0 +1,68 @@
+pub mod consistency;
+pub mod glyphs;
+pub mod profiles;
+pub mod whitespace;
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::intern::{Interner, Symbol};
+use crate::lang;
+
+// 校验问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Ignore,
+}
+
+// 单条校验问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub rule: String,
+    pub severity: Severity,
+    pub namespace: String,
+    pub key: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+// 原文与译文的一组对应条目
+#[derive(Debug, Clone)]
+pub struct TranslationPair {
+    pub namespace: Symbol,
+    pub key: Symbol,
+    pub source: String,
+    pub translation: String,
+}
+
+// 按命名空间和键配对原文与译文，只返回两边都存在的条目
+pub fn load_translation_pairs(pack_path: &Path, source_locale: &str, target_locale: &str) -> Vec<TranslationPair> {
+    let sources = lang::load_locale_entries(pack_path, source_locale);
+    let targets = lang::load_locale_entries(pack_path, target_locale);
+    let mut interner = Interner::new();
+    let mut pairs = Vec::new();
+
+    for (namespace, target_entries) in targets {
+        let Some(source_entries) = sources.get(&namespace) else {
+            continue;
+        };
+        let namespace = interner.intern(&namespace);
+
+        for (key, translation) in target_entries {
+            if let Some(source) = source_entries.get(&key) {
+                pairs.push(TranslationPair {
+                    namespace: namespace.clone(),
+                    key: interner.intern(&key),
+                    source: source.clone(),
+                    translation,
+                });
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| a.namespace.cmp(&b.namespace).then(a.key.cmp(&b.key)));
+    pairs
+}
@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{consistency, glyphs, whitespace, Severity, ValidationIssue};
+use crate::project_settings::{self, ProjectSettings};
+
+pub const SOURCE_LOCALE: &str = "en_us";
+
+// 所有校验规则，顺序即报告中的展示顺序
+pub const RULES: &[&str] = &[
+    "missing_glyph",
+    "inconsistent_translation",
+    "leading_whitespace",
+    "trailing_whitespace",
+    "double_space",
+    "missing_line_break",
+    "invisible_character",
+    "control_character",
+];
+
+// 校验配置档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationProfile {
+    pub name: String,
+    pub description: String,
+    pub rules: BTreeMap<String, Severity>,
+}
+
+// 校验报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub project_path: String,
+    pub profile: String,
+    pub source_locale: String,
+    pub target_locale: String,
+    pub rules: BTreeMap<String, Severity>,
+    pub error_count: u32,
+    pub warning_count: u32,
+    pub issues: Vec<ValidationIssue>,
+}
+
+// 内置的 strict / release / lenient 配置档
+pub fn builtin_profiles() -> Vec<ValidationProfile> {
+    use Severity::{Error, Ignore, Warning};
+
+    let profile = |name: &str, description: &str, severities: [Severity; 8]| ValidationProfile {
+        name: name.to_string(),
+        description: description.to_string(),
+        rules: RULES
+            .iter()
+            .zip(severities)
+            .map(|(rule, severity)| (rule.to_string(), severity))
+            .collect(),
+    };
+
+    vec![
+        profile(
+            "strict",
+            "All checks are errors",
+            [Error, Error, Error, Error, Error, Error, Error, Error],
+        ),
+        profile(
+            "release",
+            "Block rendering and formatting breakage, warn about cosmetic issues",
+            [Error, Warning, Warning, Warning, Warning, Error, Error, Error],
+        ),
+        profile(
+            "lenient",
+            "Only report problems visible in game",
+            [Warning, Ignore, Ignore, Ignore, Ignore, Warning, Warning, Warning],
+        ),
+    ]
+}
+
+pub fn find_profile(name: &str) -> Result<ValidationProfile, String> {
+    builtin_profiles()
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown validation profile: {}", name))
+}
+
+// 按配置档运行全部校验；未指定配置档时使用项目设置中的配置档
+pub fn run_validation(
+    project_path: &str,
+    profile: Option<&str>,
+    target_locale: &str,
+) -> Result<ValidationReport, String> {
+    let settings = project_settings::load_project_settings(project_path);
+    run_validation_with_settings(project_path, profile, &settings, target_locale)
+}
+
+fn run_validation_with_settings(
+    project_path: &str,
+    profile: Option<&str>,
+    settings: &ProjectSettings,
+    target_locale: &str,
+) -> Result<ValidationReport, String> {
+    let profile_name = profile.unwrap_or(settings.validation_profile.as_str());
+    let mut rules = find_profile(profile_name)?.rules;
+
+    // 项目级覆盖优先于配置档
+    for (rule, severity) in &settings.severity_overrides {
+        if rules.contains_key(rule) {
+            rules.insert(rule.clone(), *severity);
+        }
+    }
+
+    let enabled = |rule: &str| rules.get(rule).is_some_and(|s| *s != Severity::Ignore);
+    let path = Path::new(project_path);
+    let mut issues = Vec::new();
+
+    if enabled("missing_glyph") && glyphs::is_cjk_locale(target_locale) {
+        let report = glyphs::check_glyph_coverage(path, target_locale)?;
+        for glyph in report.missing_glyphs {
+            let (namespace, key) = glyph
+                .sample_keys
+                .first()
+                .and_then(|k| k.split_once(':'))
+                .map(|(ns, key)| (ns.to_string(), key.to_string()))
+                .unwrap_or_default();
+
+            issues.push(ValidationIssue {
+                rule: "missing_glyph".to_string(),
+                severity: Severity::Error,
+                namespace,
+                key,
+                message: format!(
+                    "Character {} ({}) is not covered by any font and appears {} times",
+                    glyph.character, glyph.codepoint, glyph.occurrences
+                ),
+                suggestion: None,
+            });
+        }
+    }
+
+    if enabled("inconsistent_translation") {
+        let report = consistency::check_consistency(path, SOURCE_LOCALE, target_locale)?;
+        for group in report.groups {
+            for variant in group.variants.iter().filter(|v| v.translation != group.suggested_translation) {
+                for qualified_key in &variant.keys {
+                    let (namespace, key) = qualified_key.split_once(':').unwrap_or(("", qualified_key));
+                    issues.push(ValidationIssue {
+                        rule: "inconsistent_translation".to_string(),
+                        severity: Severity::Warning,
+                        namespace: namespace.to_string(),
+                        key: key.to_string(),
+                        message: format!(
+                            "\"{}\" is translated as \"{}\" here but \"{}\" elsewhere",
+                            group.source, variant.translation, group.suggested_translation
+                        ),
+                        suggestion: Some(group.suggested_translation.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    issues.extend(whitespace::check_whitespace(path, SOURCE_LOCALE, target_locale));
+
+    // 应用配置档中的严重程度并过滤被忽略的规则
+    let mut issues: Vec<ValidationIssue> = issues
+        .into_iter()
+        .filter_map(|mut issue| {
+            let severity = *rules.get(&issue.rule)?;
+            if severity == Severity::Ignore {
+                return None;
+            }
+            issue.severity = severity;
+            Some(issue)
+        })
+        .collect();
+
+    issues.sort_by_key(|issue| {
+        (
+            issue.severity != Severity::Error,
+            RULES.iter().position(|r| *r == issue.rule),
+            issue.namespace.clone(),
+            issue.key.clone(),
+        )
+    });
+
+    let error_count = issues.iter().filter(|i| i.severity == Severity::Error).count() as u32;
+    let warning_count = issues.iter().filter(|i| i.severity == Severity::Warning).count() as u32;
+
+    Ok(ValidationReport {
+        project_path: project_path.to_string(),
+        profile: profile_name.to_string(),
+        source_locale: SOURCE_LOCALE.to_string(),
+        target_locale: target_locale.to_string(),
+        rules,
+        error_count,
+        warning_count,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // 临时项目目录：assets/examplemod/lang 下的 en_us.json 和 de_de.json，测试结束时删除
+    struct TempProject(PathBuf);
+
+    impl TempProject {
+        fn new(name: &str, source: &str, target: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("th-suite-profiles-{}-{}", name, std::process::id()));
+            let lang_dir = root.join("assets").join("examplemod").join("lang");
+            fs::create_dir_all(&lang_dir).unwrap();
+            fs::write(lang_dir.join("en_us.json"), source).unwrap();
+            fs::write(lang_dir.join("de_de.json"), target).unwrap();
+            TempProject(root)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempProject {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // 译文缺少原文的尾随空格（release 中为警告），并丢失了换行（release 中为错误）
+    fn sample_project(name: &str) -> TempProject {
+        TempProject::new(
+            name,
+            r#"{"gui.examplemod.level": "Level: ", "gui.examplemod.help": "Line one\\nLine two"}"#,
+            r#"{"gui.examplemod.level": "Stufe:", "gui.examplemod.help": "Zeile eins"}"#,
+        )
+    }
+
+    fn severity_of(report: &ValidationReport, rule: &str) -> Option<Severity> {
+        report.issues.iter().find(|issue| issue.rule == rule).map(|issue| issue.severity)
+    }
+
+    fn settings_with(overrides: &[(&str, Severity)]) -> ProjectSettings {
+        let mut settings = ProjectSettings::default();
+        for (rule, severity) in overrides {
+            settings.severity_overrides.insert(rule.to_string(), *severity);
+        }
+        settings
+    }
+
+    #[test]
+    fn profile_defaults_apply_without_overrides() {
+        let project = sample_project("defaults");
+        let report = run_validation_with_settings(project.path(), None, &settings_with(&[]), "de_de").unwrap();
+        assert_eq!(report.profile, "release");
+        assert_eq!(severity_of(&report, "trailing_whitespace"), Some(Severity::Warning));
+        assert_eq!(severity_of(&report, "missing_line_break"), Some(Severity::Error));
+        assert_eq!((report.error_count, report.warning_count), (1, 1));
+        // 错误排在警告之前
+        assert_eq!(report.issues[0].rule, "missing_line_break");
+    }
+
+    #[test]
+    fn override_raises_severity() {
+        let project = sample_project("raise");
+        let settings = settings_with(&[("trailing_whitespace", Severity::Error)]);
+        let report = run_validation_with_settings(project.path(), None, &settings, "de_de").unwrap();
+        assert_eq!(report.rules["trailing_whitespace"], Severity::Error);
+        assert_eq!(severity_of(&report, "trailing_whitespace"), Some(Severity::Error));
+        assert_eq!((report.error_count, report.warning_count), (2, 0));
+    }
+
+    #[test]
+    fn override_lowers_severity_or_ignores_rule() {
+        let project = sample_project("lower");
+        let settings = settings_with(&[("missing_line_break", Severity::Warning), ("trailing_whitespace", Severity::Ignore)]);
+        let report = run_validation_with_settings(project.path(), None, &settings, "de_de").unwrap();
+        assert_eq!(severity_of(&report, "missing_line_break"), Some(Severity::Warning));
+        assert_eq!(severity_of(&report, "trailing_whitespace"), None);
+        assert_eq!((report.error_count, report.warning_count), (0, 1));
+    }
+
+    #[test]
+    fn override_applies_on_top_of_explicit_profile() {
+        let project = sample_project("explicit");
+        let settings = settings_with(&[("trailing_whitespace", Severity::Warning)]);
+        let report = run_validation_with_settings(project.path(), Some("strict"), &settings, "de_de").unwrap();
+        assert_eq!(report.profile, "strict");
+        assert_eq!(severity_of(&report, "trailing_whitespace"), Some(Severity::Warning));
+        assert_eq!(severity_of(&report, "missing_line_break"), Some(Severity::Error));
+    }
+
+    #[test]
+    fn unknown_rule_overrides_are_ignored() {
+        let project = sample_project("unknown");
+        let settings = settings_with(&[("no_such_rule", Severity::Error), ("Trailing_Whitespace", Severity::Error)]);
+        let report = run_validation_with_settings(project.path(), None, &settings, "de_de").unwrap();
+        assert!(!report.rules.contains_key("no_such_rule"));
+        assert!(!report.rules.contains_key("Trailing_Whitespace"));
+        assert_eq!(report.rules.len(), RULES.len());
+        assert_eq!(severity_of(&report, "trailing_whitespace"), Some(Severity::Warning));
+        assert_eq!((report.error_count, report.warning_count), (1, 1));
+    }
+
+    #[test]
+    fn unknown_profile_is_an_error() {
+        let project = sample_project("bad-profile");
+        assert!(run_validation_with_settings(project.path(), Some("paranoid"), &settings_with(&[]), "de_de").is_err());
+    }
+}
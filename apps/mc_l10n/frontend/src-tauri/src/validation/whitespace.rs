@@ -0,0 +1,236 @@
+use std::path::Path;
+
+use super::{load_translation_pairs, Severity, TranslationPair, ValidationIssue};
+
+// 复制粘贴时常被带入的不可见字符
+const INVISIBLE_CHARS: &[char] = &[
+    '\u{00AD}', // 软连字符
+    '\u{200B}', // 零宽空格
+    '\u{200C}', // 零宽不连字
+    '\u{200D}', // 零宽连字
+    '\u{200E}', // 从左到右标记
+    '\u{200F}', // 从右到左标记
+    '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', // 双向嵌入控制
+    '\u{2060}', // 词连接符
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}', // 双向隔离控制
+    '\u{FEFF}', // BOM / 零宽不换行空格
+];
+
+// 检查项目中所有译文的空白与控制字符问题
+pub fn check_whitespace(pack_path: &Path, source_locale: &str, target_locale: &str) -> Vec<ValidationIssue> {
+    load_translation_pairs(pack_path, source_locale, target_locale)
+        .iter()
+        .flat_map(check_pair)
+        .collect()
+}
+
+// 检查单条译文，每个问题都附带统一的自动修复建议
+pub fn check_pair(pair: &TranslationPair) -> Vec<ValidationIssue> {
+    let source = pair.source.as_str();
+    let translation = pair.translation.as_str();
+    let mut problems: Vec<(&str, Severity, String)> = Vec::new();
+
+    if translation.is_empty() {
+        return Vec::new();
+    }
+
+    let source_leading = leading_whitespace(source);
+    let translation_leading = leading_whitespace(translation);
+    if source_leading != translation_leading {
+        problems.push((
+            "leading_whitespace",
+            Severity::Warning,
+            format!("Leading whitespace {:?} does not match source {:?}", translation_leading, source_leading),
+        ));
+    }
+
+    let source_trailing = trailing_whitespace(source);
+    let translation_trailing = trailing_whitespace(translation);
+    if source_trailing != translation_trailing {
+        problems.push((
+            "trailing_whitespace",
+            Severity::Warning,
+            format!("Trailing whitespace {:?} does not match source {:?}", translation_trailing, source_trailing),
+        ));
+    }
+
+    if trim_edges(translation).contains("  ") && !source.contains("  ") {
+        problems.push(("double_space", Severity::Warning, "Translation contains consecutive spaces".to_string()));
+    }
+
+    let source_breaks = count_line_breaks(source);
+    let translation_breaks = count_line_breaks(translation);
+    if translation_breaks < source_breaks {
+        problems.push((
+            "missing_line_break",
+            Severity::Error,
+            format!("Source has {} line breaks but translation has {}", source_breaks, translation_breaks),
+        ));
+    }
+
+    let invisible: Vec<String> = translation
+        .chars()
+        .filter(|c| INVISIBLE_CHARS.contains(c) && !source.contains(*c))
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect();
+    if !invisible.is_empty() {
+        problems.push((
+            "invisible_character",
+            Severity::Error,
+            format!("Translation contains invisible characters: {}", invisible.join(", ")),
+        ));
+    }
+
+    let control: Vec<String> = translation
+        .chars()
+        .filter(|c| c.is_control() && *c != '\n' && !source.contains(*c))
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect();
+    if !control.is_empty() {
+        problems.push((
+            "control_character",
+            Severity::Error,
+            format!("Translation contains control characters: {}", control.join(", ")),
+        ));
+    }
+
+    if problems.is_empty() {
+        return Vec::new();
+    }
+
+    let fixed = suggest_fix(source, translation);
+    let suggestion = (fixed != translation).then_some(fixed);
+
+    problems
+        .into_iter()
+        .map(|(rule, severity, message)| ValidationIssue {
+            rule: rule.to_string(),
+            severity,
+            namespace: pair.namespace.to_string(),
+            key: pair.key.to_string(),
+            message,
+            // 丢失换行无法自动修复
+            suggestion: if rule == "missing_line_break" { None } else { suggestion.clone() },
+        })
+        .collect()
+}
+
+// 按原文的首尾空白重建译文，并去除多余空格和不可见字符
+fn suggest_fix(source: &str, translation: &str) -> String {
+    let cleaned: String = trim_edges(translation)
+        .chars()
+        .filter(|c| !(INVISIBLE_CHARS.contains(c) || (c.is_control() && *c != '\n')) || source.contains(*c))
+        .collect();
+
+    let core = if source.contains("  ") {
+        cleaned
+    } else {
+        let mut collapsed = String::with_capacity(cleaned.len());
+        for c in cleaned.chars() {
+            if c == ' ' && collapsed.ends_with(' ') {
+                continue;
+            }
+            collapsed.push(c);
+        }
+        collapsed
+    };
+
+    format!("{}{}{}", leading_whitespace(source), trim_edges(&core), trailing_whitespace(source))
+}
+
+// 全角空格（U+3000）在中日文译文中用作排版缩进，不算首尾空白
+fn is_edge_whitespace(c: char) -> bool {
+    c.is_whitespace() && c != '\u{3000}'
+}
+
+fn trim_edges(value: &str) -> &str {
+    value.trim_matches(is_edge_whitespace)
+}
+
+fn leading_whitespace(value: &str) -> &str {
+    &value[..value.len() - value.trim_start_matches(is_edge_whitespace).len()]
+}
+
+fn trailing_whitespace(value: &str) -> &str {
+    &value[value.trim_end_matches(is_edge_whitespace).len()..]
+}
+
+// 统计真实换行符和字面量 \n 序列（.lang 文件中保持转义形式）
+fn count_line_breaks(value: &str) -> usize {
+    value.matches('\n').count() + value.matches("\\n").count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(source: &str, translation: &str) -> TranslationPair {
+        TranslationPair {
+            namespace: "examplemod".into(),
+            key: "gui.examplemod.title".into(),
+            source: source.to_string(),
+            translation: translation.to_string(),
+        }
+    }
+
+    fn rules(issues: &[ValidationIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn matching_whitespace_has_no_issues() {
+        assert!(check_pair(&pair(" Level: ", " 等级： ")).is_empty());
+        assert!(check_pair(&pair("Line one\\nLine two", "第一行\\n第二行")).is_empty());
+        // 空译文由缺失翻译检查处理
+        assert!(check_pair(&pair(" Level ", "")).is_empty());
+    }
+
+    #[test]
+    fn leading_and_trailing_whitespace_follow_source() {
+        let issues = check_pair(&pair("Level: ", " 等级："));
+        assert_eq!(rules(&issues), vec!["leading_whitespace", "trailing_whitespace"]);
+        assert!(issues.iter().all(|issue| issue.severity == Severity::Warning));
+        assert_eq!(issues[0].suggestion.as_deref(), Some("等级： "));
+    }
+
+    #[test]
+    fn ideographic_space_is_not_edge_whitespace() {
+        assert!(check_pair(&pair("Chapter 1", "\u{3000}\u{3000}第一章")).is_empty());
+        assert!(check_pair(&pair("Done", "完成\u{3000}")).is_empty());
+
+        // 修复其他问题时保留全角空格缩进
+        let issues = check_pair(&pair("Chapter 1 begins", "\u{3000}第一章  开始 "));
+        assert_eq!(rules(&issues), vec!["trailing_whitespace", "double_space"]);
+        assert_eq!(issues[0].suggestion.as_deref(), Some("\u{3000}第一章 开始"));
+    }
+
+    #[test]
+    fn double_space_is_collapsed_unless_source_has_one() {
+        let issues = check_pair(&pair("Iron Sword", "铁  剑"));
+        assert_eq!(rules(&issues), vec!["double_space"]);
+        assert_eq!(issues[0].suggestion.as_deref(), Some("铁 剑"));
+
+        assert!(check_pair(&pair("A  B", "甲  乙")).is_empty());
+    }
+
+    #[test]
+    fn missing_line_break_is_an_error_without_suggestion() {
+        let issues = check_pair(&pair("First\\nSecond\nThird", "第一 第二\n第三"));
+        assert_eq!(rules(&issues), vec!["missing_line_break"]);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(issues[0].suggestion.is_none());
+        assert!(issues[0].message.contains("2 line breaks"));
+    }
+
+    #[test]
+    fn invisible_and_control_characters_are_reported_and_removed() {
+        let issues = check_pair(&pair("Stone", "石\u{200B}头\u{0007}"));
+        assert_eq!(rules(&issues), vec!["invisible_character", "control_character"]);
+        assert!(issues[0].message.contains("U+200B"));
+        assert!(issues[1].message.contains("U+0007"));
+        assert!(issues.iter().all(|issue| issue.suggestion.as_deref() == Some("石头")));
+
+        // 原文中也有的字符不报告
+        assert!(check_pair(&pair("a\u{00AD}b\tc", "甲\u{00AD}乙\t丙")).is_empty());
+    }
+}
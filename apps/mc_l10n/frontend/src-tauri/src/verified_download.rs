@@ -0,0 +1,163 @@
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+
+// 下载的文件应有的大小和摘要；未知的项为 None，已知的项全部需要一致
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedFile {
+    pub size: Option<u64>,
+    pub sha1: Option<String>,
+    pub sha256: Option<String>,
+    pub sha512: Option<String>,
+}
+
+impl ExpectedFile {
+    // 按十六进制长度识别 SHA-1、SHA-256 或 SHA-512
+    pub fn from_checksum(checksum: &str) -> Result<Self, String> {
+        let checksum = checksum.trim().to_ascii_lowercase();
+        if !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid checksum: {}", checksum));
+        }
+        let mut expected = Self::default();
+        match checksum.len() {
+            40 => expected.sha1 = Some(checksum),
+            64 => expected.sha256 = Some(checksum),
+            128 => expected.sha512 = Some(checksum),
+            _ => return Err(format!("Checksum must be a SHA-1, SHA-256 or SHA-512 hex digest: {}", checksum)),
+        }
+        Ok(expected)
+    }
+}
+
+// 校验通过、仍在待定目录中的文件；调用方用 promote 移到最终位置
+#[derive(Debug)]
+pub struct VerifiedFile {
+    pub path: PathBuf,
+    // 重定向后的最终地址
+    pub url: reqwest::Url,
+    pub size: u64,
+    pub sha1: String,
+}
+
+impl VerifiedFile {
+    // 移动到 target；跨磁盘时复制后删除
+    pub fn promote(self, target: &Path) -> Result<(), String> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        if fs::rename(&self.path, target).is_err() {
+            let copied = fs::copy(&self.path, target);
+            let _ = fs::remove_file(&self.path);
+            copied.map_err(|e| format!("Failed to move download to {}: {}", target.display(), e))?;
+        }
+        Ok(())
+    }
+
+    pub fn discard(self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// 未完成校验的下载都放在这里，不会被扫描或导入
+pub fn pending_dir(config: &AppConfig) -> PathBuf {
+    config.get_data_dir().join("downloads").join("pending")
+}
+
+// 流式下载到待定目录并同时计算摘要；大小或任一摘要不一致时删除文件并返回错误。
+// on_progress 在每个数据块后以 (已下载字节数, 总字节数) 调用
+pub async fn download_verified(
+    config: &AppConfig,
+    url: &str,
+    expected: &ExpectedFile,
+    max_bytes: u64,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<VerifiedFile, String> {
+    let mut response = reqwest::get(url).await.map_err(|e| format!("{}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", url, response.status()));
+    }
+
+    let content_length = response.content_length();
+    if content_length.is_some_and(|length| length > max_bytes) {
+        return Err(format!("{} is larger than the {} MB download limit", url, max_bytes / (1024 * 1024)));
+    }
+    if let (Some(length), Some(size)) = (content_length, expected.size) {
+        if length != size {
+            return Err(format!("Rejected {}: expected {} bytes, server reports {}", url, size, length));
+        }
+    }
+    let final_url = response.url().clone();
+
+    let pending = pending_dir(config);
+    fs::create_dir_all(&pending).map_err(|e| format!("Failed to create {}: {}", pending.display(), e))?;
+    let path = pending.join(format!("{}.pending", uuid::Uuid::new_v4()));
+
+    let result = async {
+        let mut file = File::create(&path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        let mut sha1 = Sha1::new();
+        let mut sha256 = expected.sha256.as_ref().map(|_| Sha256::new());
+        let mut sha512 = expected.sha512.as_ref().map(|_| Sha512::new());
+        let mut size = 0u64;
+        on_progress(0, content_length);
+
+        while let Some(chunk) = response.chunk().await.map_err(|e| format!("{}: {}", url, e))? {
+            size += chunk.len() as u64;
+            if size > max_bytes {
+                return Err(format!("{} is larger than the {} MB download limit", url, max_bytes / (1024 * 1024)));
+            }
+            sha1.update(&chunk);
+            if let Some(hasher) = sha256.as_mut() {
+                hasher.update(&chunk);
+            }
+            if let Some(hasher) = sha512.as_mut() {
+                hasher.update(&chunk);
+            }
+            file.write_all(&chunk).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            on_progress(size, content_length);
+        }
+        file.sync_all().map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        // 服务器声明了长度但连接提前结束时同样拒绝
+        if let Some(expected_size) = expected.size.or(content_length) {
+            if size != expected_size {
+                return Err(format!("Rejected {}: expected {} bytes, received {}", url, expected_size, size));
+            }
+        }
+        let sha1 = format!("{:x}", sha1.finalize());
+        check_digest(url, "SHA-1", expected.sha1.as_deref(), Some(&sha1))?;
+        check_digest(url, "SHA-256", expected.sha256.as_deref(), sha256.map(|h| format!("{:x}", h.finalize())).as_deref())?;
+        check_digest(url, "SHA-512", expected.sha512.as_deref(), sha512.map(|h| format!("{:x}", h.finalize())).as_deref())?;
+        Ok((size, sha1))
+    }
+    .await;
+
+    match result {
+        Ok((size, sha1)) => Ok(VerifiedFile {
+            path,
+            url: final_url,
+            size,
+            sha1,
+        }),
+        Err(e) => {
+            let _ = fs::remove_file(&path);
+            Err(e)
+        }
+    }
+}
+
+fn check_digest(url: &str, algorithm: &str, expected: Option<&str>, actual: Option<&str>) -> Result<(), String> {
+    match (expected, actual) {
+        (Some(expected), Some(actual)) if !expected.trim().eq_ignore_ascii_case(actual) => Err(format!(
+            "Rejected {}: {} mismatch, expected {}, got {}",
+            url,
+            algorithm,
+            expected.trim(),
+            actual
+        )),
+        _ => Ok(()),
+    }
+}
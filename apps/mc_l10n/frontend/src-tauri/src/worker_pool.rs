@@ -0,0 +1,75 @@
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tokio::sync::oneshot;
+
+// 同时执行的命令任务数量（统计、导出、预览等）；扫描不在这里执行，见 run_scan
+const COMMAND_WORKERS: usize = 4;
+
+// 等待执行的任务上限，超出时直接拒绝而不是阻塞调用方
+const COMMAND_QUEUE_CAPACITY: usize = 16;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// 独立于 Tauri/tokio 运行时的工作线程池，哈希和解析等重任务不会占用处理 IPC 的线程
+pub struct WorkerPool {
+    sender: SyncSender<Job>,
+}
+
+impl WorkerPool {
+    pub fn new(name: &str, workers: usize, queue_capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue_capacity);
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for index in 0..workers.max(1) {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("{}-{}", name, index))
+                // release 构建使用 panic = "abort"，任务中的 panic 会直接结束进程，错误需要作为结果返回
+                .spawn(move || loop {
+                    let job = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Failed to spawn worker thread");
+        }
+
+        Self { sender }
+    }
+
+    pub fn for_commands() -> Self {
+        Self::new("command-worker", COMMAND_WORKERS, COMMAND_QUEUE_CAPACITY)
+    }
+
+    // 提交任务，返回可在异步代码中等待的结果；队列已满时立即返回错误
+    pub fn submit<F, R>(&self, work: F) -> Result<oneshot::Receiver<R>, String>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (result_sender, result_receiver) = oneshot::channel();
+        let job: Job = Box::new(move || {
+            let _ = result_sender.send(work());
+        });
+
+        match self.sender.try_send(job) {
+            Ok(()) => Ok(result_receiver),
+            Err(TrySendError::Full(_)) => Err("Too many tasks are queued, please try again later".to_string()),
+            Err(TrySendError::Disconnected(_)) => Err("Worker pool has shut down".to_string()),
+        }
+    }
+}
+
+// 扫描在各自的阻塞线程上运行，长时间的扫描不会占满执行其他命令的线程池；
+// 同时运行的扫描数由扫描队列控制
+pub async fn run_scan<F, R>(work: F) -> Result<R, String>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|_| "Scan task failed".to_string())
+}
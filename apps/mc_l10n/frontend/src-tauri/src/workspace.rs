@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::config::AppConfig;
+use crate::events::{self, AppEvent};
+
+// 用户批准的项目根目录；命令按 id 引用项目，同时作为文件系统插件的放行列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRoot {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    // modpack、mod_jar、resourcepack、directory 或 file
+    pub project_type: String,
+    #[serde(default)]
+    pub last_scan_id: Option<String>,
+    #[serde(default)]
+    pub last_scanned_at: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    pub added_at: String,
+}
+
+// 工作区变化事件的负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceEvent {
+    // added、updated、removed 或 scanned
+    pub action: String,
+    pub root: WorkspaceRoot,
+}
+
+// 所有项目根目录，保存在数据目录下的 workspaces.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceStore {
+    pub roots: Vec<WorkspaceRoot>,
+}
+
+impl WorkspaceStore {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if store_path.exists() {
+            let content = fs::read_to_string(&store_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let store_path = Self::get_store_path()?;
+
+        if let Some(parent) = store_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&store_path, content)?;
+        Ok(())
+    }
+
+    // 置顶的排在前面，其余按名称排序
+    pub fn list(&self) -> Vec<WorkspaceRoot> {
+        let mut roots = self.roots.clone();
+        roots.sort_by(|a, b| b.pinned.cmp(&a.pinned).then_with(|| a.name.cmp(&b.name)));
+        roots
+    }
+
+    pub fn get(&self, id: &str) -> Result<&WorkspaceRoot, String> {
+        self.roots
+            .iter()
+            .find(|root| root.id == id)
+            .ok_or_else(|| format!("Workspace not found: {}", id))
+    }
+
+    // 路径规范化后加入；同一路径只能加入一次
+    pub fn add(&mut self, name: &str, path: &str) -> Result<WorkspaceRoot, String> {
+        let path = fs::canonicalize(path).map_err(|e| format!("Invalid project path {}: {}", path, e))?;
+        let path_string = path.to_string_lossy().to_string();
+        if let Some(existing) = self.roots.iter().find(|root| root.path == path_string) {
+            return Err(format!("{} is already in workspace {}", path_string, existing.name));
+        }
+
+        let name = name.trim();
+        let root = WorkspaceRoot {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: if name.is_empty() {
+                path.file_name().unwrap_or_default().to_string_lossy().to_string()
+            } else {
+                name.to_string()
+            },
+            project_type: detect_project_type(&path).to_string(),
+            path: path_string,
+            last_scan_id: None,
+            last_scanned_at: None,
+            pinned: false,
+            added_at: chrono::Utc::now().to_rfc3339(),
+        };
+        self.roots.push(root.clone());
+        Ok(root)
+    }
+
+    pub fn update(&mut self, id: &str, name: Option<String>, pinned: Option<bool>) -> Result<WorkspaceRoot, String> {
+        let root = self
+            .roots
+            .iter_mut()
+            .find(|root| root.id == id)
+            .ok_or_else(|| format!("Workspace not found: {}", id))?;
+        if let Some(name) = name.filter(|name| !name.trim().is_empty()) {
+            root.name = name.trim().to_string();
+        }
+        if let Some(pinned) = pinned {
+            root.pinned = pinned;
+        }
+        Ok(root.clone())
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<WorkspaceRoot, String> {
+        let index = self
+            .roots
+            .iter()
+            .position(|root| root.id == id)
+            .ok_or_else(|| format!("Workspace not found: {}", id))?;
+        Ok(self.roots.remove(index))
+    }
+
+    // 记录某个路径最近一次扫描；路径不在工作区中时返回 None
+    pub fn record_scan(&mut self, project_path: &str, scan_id: &str) -> Option<WorkspaceRoot> {
+        let canonical = fs::canonicalize(project_path).ok()?;
+        let root = self
+            .roots
+            .iter_mut()
+            .find(|root| Path::new(&root.path) == canonical)?;
+        root.last_scan_id = Some(scan_id.to_string());
+        root.last_scanned_at = Some(chrono::Utc::now().to_rfc3339());
+        Some(root.clone())
+    }
+
+    fn get_store_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config = AppConfig::load()?;
+        Ok(config.get_data_dir().join("workspaces.json"))
+    }
+}
+
+// 按目录内容粗略判断项目类型
+pub fn detect_project_type(path: &Path) -> &'static str {
+    if path.is_file() {
+        return if path.extension().is_some_and(|ext| ext == "jar") { "mod_jar" } else { "file" };
+    }
+    if crate::detect_modpack(&path.to_path_buf()) || path.join("mods").is_dir() {
+        "modpack"
+    } else if path.join("pack.mcmeta").is_file() {
+        "resourcepack"
+    } else {
+        "directory"
+    }
+}
+
+// 通知前端工作区变化
+pub fn notify(app: &AppHandle, action: &str, root: &WorkspaceRoot) {
+    let event = WorkspaceEvent {
+        action: action.to_string(),
+        root: root.clone(),
+    };
+    events::emit(app, AppEvent::WorkspaceChanged(&event));
+}
+
+// 把项目根目录加入文件系统插件的放行范围；移除的目录在下次启动后不再放行
+pub fn allow_in_fs_scope(app: &AppHandle, root: &WorkspaceRoot) {
+    let path = Path::new(&root.path);
+    let allowed = if path.is_dir() {
+        app.fs_scope().allow_directory(path, true)
+    } else {
+        app.fs_scope().allow_file(path)
+    };
+    if allowed.is_err() {
+        eprintln!("Failed to allow workspace {} in fs scope", root.path);
+    }
+}
@@ -21,6 +21,7 @@ import {
   RefreshCw,
 } from 'lucide-react'
 import { invoke } from '@tauri-apps/api/core'
+import { decodeIpcPayload } from '@/utils/compression'
 import { listen } from '@tauri-apps/api/event'
 import { open } from '@tauri-apps/plugin-dialog'
 import { useTranslation } from 'react-i18next'
@@ -342,7 +343,8 @@ const ProjectScan: React.FC<ProjectScanProps> = ({ onScanComplete, onCreateProje
 
   const handleGetScanResult = async (scanId: string) => {
     try {
-      const result = await invoke<ScanResult>('get_scan_result', { scan_id: scanId })
+      const payload = await invoke<ArrayBuffer>('get_scan_result', { scan_id: scanId })
+      const result = await decodeIpcPayload<ScanResult>(payload)
       setScanResult(result)
       setIsScanning(false)
 
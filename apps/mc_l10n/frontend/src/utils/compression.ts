@@ -81,6 +81,22 @@ export async function decompress(data: string): Promise<string> {
   return simpleDecompress(compressed)
 }
 
+/**
+ * 解码 Tauri 后端返回的二进制结果
+ * 后端对较大的结果使用 gzip 压缩，通过 gzip 魔数 (1f 8b) 区分压缩与未压缩的 JSON
+ */
+export async function decodeIpcPayload<T>(payload: ArrayBuffer): Promise<T> {
+  const bytes = new Uint8Array(payload)
+  const isGzip = bytes.length > 2 && bytes[0] === 0x1f && bytes[1] === 0x8b
+
+  if (!isGzip) {
+    return JSON.parse(new TextDecoder().decode(bytes)) as T
+  }
+
+  const stream = new Blob([bytes]).stream().pipeThrough(new DecompressionStream('gzip'))
+  return JSON.parse(await new Response(stream).text()) as T
+}
+
 // 简单的游程编码压缩
 function simpleCompress(str: string): string {
   let compressed = ''